@@ -704,8 +704,13 @@ export async function createBasicWrapTokensInstruction(
     const tokenDecimals = decimals ?? 9;
     console.log(`⏱️ [WRAP-IX] +${Date.now() - ixStart}ms: Decimals=${tokenDecimals}`);
 
-    // Create instruction data buffer: discriminator (8) + amount (8) + min_rift_out (8) = 24 bytes
-    const instructionData = Buffer.alloc(24);
+    // Detect V1 rift (legacy program: no deadline/create_rift_ata args, SPL Token mint)
+    const isV1Rift = riftPubkey.toBase58() === 'CuyPWoNgoLQ8DHkZFK6A8CW65p1ecLqMWfE7AvphNmWL';
+
+    // Create instruction data buffer:
+    // V1: discriminator (8) + amount (8) + min_rift_out (8) = 24 bytes
+    // V2: ... + deadline_unix (8) + create_rift_ata (1) = 33 bytes
+    const instructionData = Buffer.alloc(isV1Rift ? 24 : 33);
     let offset = 0;
 
     // Create wrap_tokens instruction discriminator
@@ -743,6 +748,14 @@ export async function createBasicWrapTokensInstruction(
     dataView.setBigUint64(offset, amountLamports, true);
     offset += 8;
     dataView.setBigUint64(offset, minRiftOut, true);
+    offset += 8;
+    if (!isV1Rift) {
+      // deadline_unix = 0 (no deadline); min_rift_out already bounds the execution price
+      dataView.setBigInt64(offset, BigInt(0), true);
+      offset += 8;
+      // create_rift_ata = false: the caller creates the user's RIFT ATA up front
+      dataView.setUint8(offset, 0);
+    }
     console.log(`⏱️ [WRAP-IX] +${Date.now() - ixStart}ms: Instruction data built`);
 
     // Import SPL Token utilities
@@ -780,8 +793,7 @@ export async function createBasicWrapTokensInstruction(
     );
     console.log(`⏱️ [WRAP-IX] +${Date.now() - ixStart}ms: Got user underlying ATA`);
 
-    // Detect V1 rift and use correct token program
-    const isV1Rift = riftPubkey.toBase58() === 'CuyPWoNgoLQ8DHkZFK6A8CW65p1ecLqMWfE7AvphNmWL';
+    // Use correct token program for the rift mint
     const riftTokenProgram = isV1Rift ? TOKEN_PROGRAM_ID : TOKEN_2022_PROGRAM_ID;
 
     console.log(`⏱️ [WRAP-IX] +${Date.now() - ixStart}ms: Getting user rift ATA...`);
@@ -804,6 +816,20 @@ export async function createBasicWrapTokensInstruction(
       [Buffer.from('vault_auth'), riftPubkey.toBuffer()],
       programId
     );
+
+    // V2-only PDAs: wrap fee destination, per-user activity, protocol config
+    const [wrapFeesVaultPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from('wrap_fees_vault'), riftPubkey.toBuffer()],
+      programId
+    );
+    const [userActivityPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from('activity'), riftPubkey.toBuffer(), user.toBuffer()],
+      programId
+    );
+    const [globalConfigPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from('global_config')],
+      programId
+    );
     console.log(`⏱️ [WRAP-IX] +${Date.now() - ixStart}ms: All PDAs done`);
 
     // Create the instruction with ALL wrap_tokens accounts
@@ -823,16 +849,26 @@ export async function createBasicWrapTokensInstruction(
         { pubkey: underlyingTokenProgram, isSigner: false, isWritable: false }
       );
     } else {
-      // V2: Has underlying_mint, fees_vault, vault_authority, TWO token programs, system_program
+      // V2: account order must match WrapTokens in the IDL (18 accounts)
+      // Omitted optional accounts are passed as the program ID (Anchor's None)
       accountKeys.push(
         { pubkey: new PublicKey(riftData.underlyingMint), isSigner: false, isWritable: false },
         { pubkey: new PublicKey(riftData.riftMint), isSigner: false, isWritable: true },
         { pubkey: riftMintAuthPDA, isSigner: false, isWritable: false },
         { pubkey: feesVaultPDA, isSigner: false, isWritable: true },
         { pubkey: vaultAuthorityPDA, isSigner: false, isWritable: false },
+        // wrap_fees_vault: always passed, only used once the creator configured it
+        { pubkey: wrapFeesVaultPDA, isSigner: false, isWritable: true },
         { pubkey: underlyingTokenProgram, isSigner: false, isWritable: false },
         { pubkey: riftTokenProgram, isSigner: false, isWritable: false },
-        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false }
+        // user_activity: init_if_needed on the first wrap (rent paid by the user)
+        { pubkey: userActivityPDA, isSigner: false, isWritable: true },
+        // associated_token_program: None (create_rift_ata = false)
+        { pubkey: programId, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        // fee_exemption: None (normal fees)
+        { pubkey: programId, isSigner: false, isWritable: false },
+        { pubkey: globalConfigPDA, isSigner: false, isWritable: false }
       );
     }
 
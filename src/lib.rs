@@ -26,21 +26,16 @@ use anchor_spl::token_2022::spl_token_2022::{
 };
 
 use anchor_lang::solana_program::program::{invoke, invoke_signed};
-use anchor_lang::solana_program::program_option::COption;
 use anchor_lang::solana_program::program_pack::Pack; // For SPL Token Mint::unpack
 use anchor_lang::solana_program::system_instruction;
 use anchor_lang::solana_program::sysvar::rent::Rent;
-use std::str::FromStr;
-
-// Token-2022 Metadata Extension
-use spl_token_2022::extension::metadata_pointer;
-use spl_token_2022::extension::ExtensionType as Token2022ExtensionType;
-use spl_token_2022::instruction::initialize_mint2;
 
 // Oracle SDKs (safer than manual byte parsing)
 use switchboard_on_demand::on_demand::accounts::pull_feed::PullFeedAccountData;
 
 // Internal modules
+#[cfg(test)]
+mod tests;
 // mod jupiter; // Removed - Jupiter integration implemented inline (lines 1851-1918)
 
 // Mainnet program id
@@ -60,17 +55,136 @@ const DEFAULT_TREASURY_WALLET: &str = "5NrHu6zpWqYT6LH74WmTNFHGcxZEmRMVK4hR7sHjS
 
 /// Borsh-serialized size of `Rift` struct data (excluding the 8-byte Anchor discriminator).
 /// Computed as the sum of all fixed-size fields in the Rift struct.
-pub const RIFT_STRUCT_SIZE: usize = 774;
+pub const RIFT_STRUCT_SIZE: usize = 1399;
 
 /// Total account size for Rift PDA: 8 bytes discriminator + struct payload.
-pub const RIFT_ACCOUNT_SIZE: usize = 8 + RIFT_STRUCT_SIZE; // = 782 bytes
+pub const RIFT_ACCOUNT_SIZE: usize = 8 + RIFT_STRUCT_SIZE; // = 1407 bytes
+
+/// Rift account size at launch (8 + 774); migrate_rift_account grows these to RIFT_ACCOUNT_SIZE
+pub const LEGACY_RIFT_ACCOUNT_SIZE: usize = 782;
+
+/// GlobalConfig size before the authority/policy/skim fields; grown by migrate_global_config
+pub const LEGACY_GLOBAL_CONFIG_SIZE: usize = 8 + 32 + 2 + 1;
 
 // **FIX ISSUE #7**: Reentrancy guard auto-timeout after ~2 days
+// Default for rift.reentrancy_timeout_slots; operators may tune it within the bounds below
 const REENTRANCY_TIMEOUT_SLOTS: u64 = 432000; // ~2 days at 400ms/slot
+const MIN_REENTRANCY_TIMEOUT_SLOTS: u64 = 150; // ~1 minute
+const MAX_REENTRANCY_TIMEOUT_SLOTS: u64 = 432000; // ~2 days
 
 // **FIX ISSUE #5**: Oracle change delay (24 hours)
 const ORACLE_CHANGE_DELAY: i64 = 86400; // 24 hours in seconds
 
+// Allowed RIFT transfer fee band at rift creation
+const MIN_LAUNCH_TRANSFER_FEE_BPS: u16 = 70; // 0.7%
+const MAX_LAUNCH_TRANSFER_FEE_BPS: u16 = 100; // 1%
+// Default Token-2022 maximum fee per RIFT transfer when the creator doesn't cap it (uncapped)
+const DEFAULT_MAX_TRANSFER_FEE: u64 = u64::MAX;
+
+// Minimum interval between oracle change proposals (prevents timelock reset spam)
+const ORACLE_PROPOSAL_COOLDOWN: i64 = 3600; // 1 hour in seconds
+
+// Minimum interval between keeper-triggered daily snapshots
+const DAILY_SNAPSHOT_INTERVAL: i64 = 86400; // 24 hours in seconds
+
+// Oracle trust age: beyond this, oracle prices are ignored by rebalance logic
+const DEFAULT_MAX_ORACLE_TRUST_AGE: i64 = 604800; // 7 days in seconds
+const MIN_ORACLE_TRUST_AGE: i64 = 3600; // 1 hour
+const MAX_ORACLE_TRUST_AGE: i64 = 2592000; // 30 days
+
+/// Layout version of `RiftSummary` returned by `get_rift_state` (bump on any field change)
+pub const RIFT_SUMMARY_VERSION: u8 = 3;
+
+// Combined RIFT + underlying transfer fee cap at creation (default = both 100 bps caps, i.e. no extra limit)
+const DEFAULT_MAX_TOTAL_LEAKAGE_BPS: u16 = 200;
+
+// Oracle freshness heartbeat for wrap/unwrap: default and allowed range for max_oracle_age
+const DEFAULT_ORACLE_FRESHNESS_AGE: i64 = 3600; // 1 hour
+const MIN_ORACLE_FRESHNESS_AGE: i64 = 60; // 1 minute
+const MAX_ORACLE_FRESHNESS_AGE: i64 = 86400; // 24 hours
+
+// Oracle cadence bounds (validated at creation and by set_oracle_update_interval)
+const MIN_ORACLE_UPDATE_INTERVAL: i64 = 300; // 5 minutes
+const MAX_ORACLE_UPDATE_INTERVAL: i64 = 86400; // 24 hours
+const MIN_REBALANCE_INTERVAL: i64 = 3600; // 1 hour
+const MAX_REBALANCE_INTERVAL: i64 = 604800; // 7 days
+const MIN_ARBITRAGE_THRESHOLD_BPS: u16 = 10; // 0.1%
+const MAX_ARBITRAGE_THRESHOLD_BPS: u16 = 5000; // 50%
+
+// Rolling 24h volume window: 24 hourly buckets
+const VOLUME_BUCKET_SECONDS: i64 = 3600; // 1 hour
+
+// Keeper reward per rebalance: at most this share of the fees_vault balance not owed as skim
+const MAX_REBALANCE_REWARD_BPS: u64 = 100; // 1%
+
+// Max leftover base units per underlying vault that close_rift treats as empty (forwarded to creator)
+const CLOSE_DUST_TOLERANCE: u64 = 5;
+
+// Minimum time between begin_close and finalize_close, so holders see the close coming
+const CLOSE_DELAY_SECONDS: i64 = 86400; // 24 hours
+
+/// `can_close` return bits (u16 LE) - each set bit is a close_rift check that would fail
+pub const CLOSE_BLOCKED_RIFT_SUPPLY: u16 = 1 << 0; // total_rift_minted != 0
+pub const CLOSE_BLOCKED_UNDERLYING_WRAPPED: u16 = 1 << 1; // total_underlying_wrapped != 0
+pub const CLOSE_BLOCKED_FEES_UNDISTRIBUTED: u16 = 1 << 2; // total_fees_collected != 0
+pub const CLOSE_BLOCKED_VAULT: u16 = 1 << 3; // backing vault above dust tolerance
+pub const CLOSE_BLOCKED_FEES_VAULT: u16 = 1 << 4; // fees_vault above dust tolerance
+pub const CLOSE_BLOCKED_WITHHELD_VAULT: u16 = 1 << 5; // withheld_vault not empty
+pub const CLOSE_BLOCKED_WRAP_FEES_VAULT: u16 = 1 << 6; // wrap_fees_vault not empty
+pub const CLOSE_BLOCKED_CLOSE_DELAY: u16 = 1 << 7; // finalize_close only: no begin_close / delay
+
+/// `inspect_underlying` blocked bits - each set bit is a reason rift creation would reject the mint
+pub const UNDERLYING_BLOCKED_NON_TRANSFERABLE: u16 = 1 << 0;
+pub const UNDERLYING_BLOCKED_PERMANENT_DELEGATE: u16 = 1 << 1;
+pub const UNDERLYING_BLOCKED_MINT_CLOSE_AUTHORITY: u16 = 1 << 2;
+pub const UNDERLYING_BLOCKED_TRANSFER_HOOK: u16 = 1 << 3;
+pub const UNDERLYING_BLOCKED_MEMO_TRANSFER: u16 = 1 << 4;
+pub const UNDERLYING_BLOCKED_DEFAULT_ACCOUNT_STATE: u16 = 1 << 5;
+pub const UNDERLYING_BLOCKED_CONFIDENTIAL_TRANSFER: u16 = 1 << 6; // enforced, or not opted in
+pub const UNDERLYING_BLOCKED_CONFIDENTIAL_TRANSFER_FEE: u16 = 1 << 7;
+pub const UNDERLYING_BLOCKED_TRANSFER_FEE: u16 = 1 << 8; // underlying transfer fee above 100 bps
+pub const UNDERLYING_BLOCKED_MINT_POLICY: u16 = 1 << 9; // denied by the protocol mint policy
+pub const UNDERLYING_BLOCKED_NESTED_RIFT: u16 = 1 << 10; // RIFT mint (creatable with allow_nested)
+
+// Maximum length of a creator-set metadata URI
+const MAX_METADATA_URI_LEN: usize = 200;
+
+// Maximum explicit RIFT symbol length in bytes (before the optional r/m prefix)
+const MAX_SYMBOL_LEN: u8 = 16;
+
+// Maximum protocol cut of fee distributions (GlobalConfig.distribution_fee_bps)
+const MAX_DISTRIBUTION_FEE_BPS: u16 = 1000; // 10%
+
+// Switchboard confidence floor (rift.min_confidence_bps); must stay below the 5% ceiling
+const DEFAULT_MIN_CONFIDENCE_BPS: u16 = 1;
+const MAX_MIN_CONFIDENCE_BPS: u16 = 500;
+
+// Bounds on the sample count of an OracleHistory companion account
+const MIN_ORACLE_HISTORY_LEN: u16 = 2; // TWAP needs two samples
+const MAX_ORACLE_HISTORY_LEN: u16 = 32;
+
+// Longest zero-fee launch window a creator can request at creation
+const MAX_FEE_FREE_LAUNCH_SECONDS: u32 = 259200; // 72 hours
+// Maximum protocol skim of wrap/unwrap volume (GlobalConfig.protocol_fee_bps)
+const MAX_PROTOCOL_FEE_BPS: u16 = 30; // 0.3%
+
+// Backing ratio circuit breaker: default and allowed range for min_backing_ratio_bps
+const DEFAULT_MIN_BACKING_RATIO_BPS: u16 = 9500; // 95%
+const MIN_BACKING_RATIO_FLOOR_BPS: u16 = 5000; // 50%
+const MIN_BACKING_RATIO_CEILING_BPS: u16 = 10000; // 100%
+
+// Minimum wrap/unwrap fee in underlying base units: default and creator-settable cap
+// Keeps bps fees on low-decimal underlyings (e.g. 0-decimal gaming tokens) from rounding to zero
+const DEFAULT_MIN_FEE_UNITS: u64 = 1;
+const MAX_MIN_FEE_UNITS: u64 = 1_000_000;
+
+// Default cap on vanity rifts per creator (CreatorRiftCount.max_rifts), adjustable by program authority
+const DEFAULT_MAX_VANITY_RIFTS_PER_CREATOR: u32 = 100;
+
+// GlobalConfig.mint_policy_mode: how MintPolicy PDAs gate rift creation
+const MINT_POLICY_DENYLIST: u8 = 0; // Default: any underlying unless explicitly denied
+const MINT_POLICY_ALLOWLIST: u8 = 1; // Curated: only explicitly allowed underlyings
+
 #[program]
 // ================================================================
 // Rifts Protocol V2 - Core Safety Invariants (non-governance)
@@ -115,6 +229,7 @@ pub mod rifts_protocol {
     /// This generates the mint PDA deterministically from vanity seed
     /// **MEMORY OPTIMIZATION**: Use fixed-size array instead of Vec to avoid heap allocation
     /// **FIX HIGH #4**: Removed user-provided mint_bump parameter to prevent PDA bump grinding
+    #[allow(clippy::too_many_arguments)]
     pub fn create_rift_with_vanity_pda(
         ctx: Context<CreateRiftWithVanityPDA>,
         vanity_seed: [u8; 32], // Fixed-size array - no heap allocation!
@@ -124,22 +239,78 @@ pub mod rifts_protocol {
         name_len: u8,          // Actual length of name to use (0-32)
         transfer_fee_bps: u16, // Token-2022 transfer fee (70-100 = 0.7%-1%)
         prefix_type: u8,       // 0 = 'r' (Rift), 1 = 'm' (Monorift)
+        max_total_leakage_bps: Option<u16>, // Cap on RIFT + underlying transfer fee (None = 200 bps)
+        max_transfer_fee: Option<u64>, // Token-2022 max fee per RIFT transfer, raw units (None = uncapped)
+        allow_confidential: bool, // Opt-in: accept an underlying with non-enforced confidential transfers
+        symbol: [u8; 16],         // Explicit metadata symbol (UTF-8)
+        symbol_len: u8,           // Bytes of `symbol` to use (0 = derive from the prefixed name)
+        prefix_symbol: bool,      // Prepend the r/m prefix to the explicit symbol
+        fee_free_seconds: u32,    // Zero-fee wrap/unwrap window after launch (max 72h, 0 = none)
+        allow_nested: bool,       // Opt-in: accept an underlying that is itself a RIFT mint
     ) -> Result<()> {
         msg!("DEBUG: Inside create_rift_with_vanity_pda function!");
         msg!("DEBUG: seed_len={}, name_len={}, transfer_fee_bps={}", seed_len, name_len, transfer_fee_bps);
+
+        // **IDEMPOTENT CREATE**: rift is init_if_needed so a retried submission reaches the handler;
+        // an already-initialized rift for this creator/underlying is reported as RiftAlreadyExists
+        if ctx.accounts.rift.creator != Pubkey::default() {
+            require!(
+                ctx.accounts.rift.creator == ctx.accounts.creator.key()
+                    && ctx.accounts.rift.underlying_mint == ctx.accounts.underlying_mint.key(),
+                ErrorCode::InvalidSeedComponent
+            );
+            msg!("⚠️ Rift {} already exists (duplicate create submission)", ctx.accounts.rift.key());
+            return Err(ErrorCode::RiftAlreadyExists.into());
+        }
+
+        // **MINT POLICY**: Curated deployments can allow/deny specific underlyings
+        GlobalConfig::check_mint_policy(
+            &ctx.accounts.global_config.to_account_info(),
+            &ctx.accounts.mint_policy.to_account_info(),
+        )?;
+
+        // **NESTED RIFTS**: Wrapping another rift's RIFT mint requires an explicit opt-in
+        require!(
+            allow_nested
+                || !RiftMintRegistry::is_registered(
+                    &ctx.accounts.underlying_registry.to_account_info()
+                ),
+            ErrorCode::NestedRiftNotAllowed
+        );
+
+        // **SPAM CAP**: Vanity seeds allow unlimited rifts per creator/underlying, so count them
+        let creator_rift_count = &mut ctx.accounts.creator_rift_count;
+        if creator_rift_count.creator == Pubkey::default() {
+            creator_rift_count.creator = ctx.accounts.creator.key();
+            creator_rift_count.max_rifts = DEFAULT_MAX_VANITY_RIFTS_PER_CREATOR;
+            creator_rift_count.bump = ctx.bumps.creator_rift_count;
+        }
+        require!(
+            creator_rift_count.rift_count < creator_rift_count.max_rifts,
+            ErrorCode::CreatorRiftLimitReached
+        );
+        creator_rift_count.rift_count = creator_rift_count
+            .rift_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
         let rift = &mut ctx.accounts.rift;
 
         // Validate fees and seed length
         require!(seed_len <= 32, ErrorCode::InvalidVanitySeed);
         // **TOKEN-2022**: Validate transfer fee is between 0.7% and 1% (70-100 basis points)
         require!(
-            transfer_fee_bps >= 70 && transfer_fee_bps <= 100,
+            (MIN_LAUNCH_TRANSFER_FEE_BPS..=MAX_LAUNCH_TRANSFER_FEE_BPS).contains(&transfer_fee_bps),
             ErrorCode::InvalidTransferFee
         );
+        // **MAX FEE CAP**: Whale-friendly cap on the proportional transfer fee
+        let max_transfer_fee = max_transfer_fee.unwrap_or(DEFAULT_MAX_TRANSFER_FEE);
+        require!(max_transfer_fee > 0, ErrorCode::InvalidMaxTransferFee);
 
         // **FIX HIGH #29**: Validate underlying mint has no freeze authority to prevent fund lockup
         // **FIX HIGH #30**: Validate underlying mint has no mint authority to prevent supply inflation
         // **FIX CRITICAL #31**: Validate Token-2022 extensions to prevent DoS and vault drain
+        let mut underlying_interest_bearing = false;
         {
             let mint_info = ctx.accounts.underlying_mint.to_account_info();
             let mint_data = mint_info.try_borrow_data()?;
@@ -212,6 +383,13 @@ pub mod rifts_protocol {
                                 u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
                             require!(fee_bps <= 100, ErrorCode::ExcessiveTransferFee);
                             msg!("✅ Underlying transfer fee: {} bps (acceptable)", fee_bps);
+
+                            // **LEAKAGE CAP**: Bound worst-case combined fee on a round trip
+                            check_combined_leakage(
+                                transfer_fee_bps,
+                                fee_bps,
+                                max_total_leakage_bps,
+                            )?;
                         }
                         ExtensionType::MintCloseAuthority => {
                             // HIGH: Mint can be closed, freezing all token accounts
@@ -246,8 +424,19 @@ pub mod rifts_protocol {
                             // **FIX MEDIUM #6 (Audit)**: BLOCK ConfidentialTransferMint extension
                             // Confidential transfers require special handling not implemented in wrap/unwrap
                             // Would cause transfer failures or incorrect balance tracking
-                            msg!("❌ Underlying mint has ConfidentialTransferMint - not supported!");
-                            return Err(ErrorCode::UnsafeUnderlyingMint.into());
+                            // **CONFIDENTIAL OPT-IN**: Creators may accept a mint whose confidential
+                            // transfers are optional (auto-approve off); the rift only ever moves
+                            // public balances. Auto-approved (enforced) configs stay blocked.
+                            use spl_token_2022::extension::confidential_transfer::ConfidentialTransferMint;
+                            let ct_config = mint_state
+                                .get_extension::<ConfidentialTransferMint>()
+                                .map_err(|_| ErrorCode::InvalidMint)?;
+                            let auto_approve = bool::from(ct_config.auto_approve_new_accounts);
+                            if !allow_confidential || auto_approve {
+                                msg!("❌ Underlying mint has ConfidentialTransferMint - not supported!");
+                                return Err(ErrorCode::UnsafeUnderlyingMint.into());
+                            }
+                            msg!("⚠️ ConfidentialTransferMint present but not enforced - public transfers only");
                         }
                         ExtensionType::ConfidentialTransferFeeConfig => {
                             // **FIX MEDIUM #6 (Audit)**: BLOCK ConfidentialTransferFeeConfig extension
@@ -255,6 +444,18 @@ pub mod rifts_protocol {
                             msg!("❌ Underlying mint has ConfidentialTransferFeeConfig - not supported!");
                             return Err(ErrorCode::UnsafeUnderlyingMint.into());
                         }
+                        ExtensionType::InterestBearingConfig => {
+                            // **INTEREST-BEARING**: Allowed - interest only changes the UI amount,
+                            // raw balances never grow, so 1:1 raw redemption passes it through
+                            underlying_interest_bearing = true;
+                            msg!("✅ Underlying mint is interest-bearing - RIFT redeems raw amounts 1:1");
+                        }
+                        ExtensionType::ScaledUiAmount => {
+                            // **SCALED UI AMOUNT**: Allowed - display-only multiplier, raw balances
+                            // and transfers are unaffected and need no vault account extension.
+                            // RIFT mirrors raw amounts, so wallets show it unscaled vs the underlying
+                            msg!("⚠️ Underlying mint has ScaledUiAmount - RIFT mirrors raw (unscaled) amounts");
+                        }
                         _ => {
                             // Other extensions (ImmutableOwner, CpiGuard) are handled
                             // CpiGuard: Account extensions added during vault init
@@ -287,8 +488,10 @@ pub mod rifts_protocol {
 
         // **FEE SPLIT**: If no partner provided, creator is the partner (50/50 split with treasury)
         rift.partner_wallet = Some(partner_wallet.unwrap_or(ctx.accounts.creator.key()));
-        rift.partner_fee_bps = 5000; // Always 50% (5000 bps) - stored for backwards compatibility
-        let default_treasury = Pubkey::from_str_const(DEFAULT_TREASURY_WALLET);
+        rift.partner_fee_bps = 5000; // Default 50% (5000 bps) - adjustable via set_partner_fee_bps
+        let default_treasury =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .default_treasury;
         rift.treasury_wallet = Some(default_treasury);
         // **MEDIUM FIX #11**: Initialize configurable wrap/unwrap fees (default 0.3%)
         rift.wrap_fee_bps = 30; // Default 0.3% wrap fee
@@ -302,18 +505,10 @@ pub mod rifts_protocol {
 
         // Set rift name (fixed-size array - no heap allocation!)
         require!(name_len <= 32, ErrorCode::NameTooLong);
-        if name_len > 0 {
-            // **FIX MEDIUM #22**: Validate UTF-8 encoding before accepting name
-            let name_slice = &rift_name[..name_len as usize];
-            require!(
-                core::str::from_utf8(name_slice).is_ok(),
-                ErrorCode::InvalidRiftName
-            );
-            rift.name[..name_len as usize].copy_from_slice(name_slice);
-        } else {
-            // Default: empty name (all zeros)
-            rift.name = [0u8; 32];
-        }
+        // **FIX MEDIUM #22**: Validate UTF-8 encoding (and a usable display name) before accepting
+        validate_rift_name(&rift_name, name_len)?;
+        rift.name = [0u8; 32];
+        rift.name[..name_len as usize].copy_from_slice(&rift_name[..name_len as usize]);
 
         // **SECURITY FIX**: Initialize hybrid oracle system with valid initial state
         let current_time = Clock::get()?.unix_timestamp;
@@ -332,19 +527,21 @@ pub mod rifts_protocol {
         // **SECURITY FIX**: Set reasonable bounds for oracle intervals to prevent DoS
         rift.oracle_update_interval = 30 * 60; // 30 minutes (min 5 min, max 24 hours)
         require!(
-            rift.oracle_update_interval >= 300 && rift.oracle_update_interval <= 86400,
+            (MIN_ORACLE_UPDATE_INTERVAL..=MAX_ORACLE_UPDATE_INTERVAL)
+                .contains(&rift.oracle_update_interval),
             ErrorCode::InvalidOracleParameters
         );
 
         rift.max_rebalance_interval = 24 * 60 * 60; // 24 hours (min 1 hour, max 7 days)
         require!(
-            rift.max_rebalance_interval >= 3600 && rift.max_rebalance_interval <= 604800,
+            (MIN_REBALANCE_INTERVAL..=MAX_REBALANCE_INTERVAL).contains(&rift.max_rebalance_interval),
             ErrorCode::InvalidOracleParameters
         );
 
         rift.arbitrage_threshold_bps = 200; // 2% (min 0.1%, max 50%)
         require!(
-            rift.arbitrage_threshold_bps >= 10 && rift.arbitrage_threshold_bps <= 5000,
+            (MIN_ARBITRAGE_THRESHOLD_BPS..=MAX_ARBITRAGE_THRESHOLD_BPS)
+                .contains(&rift.arbitrage_threshold_bps),
             ErrorCode::InvalidOracleParameters
         );
 
@@ -367,21 +564,49 @@ pub mod rifts_protocol {
         // **HIGH FIX #3**: Initialize manual oracle rate limiting
         rift.last_manual_oracle_update = 0;
 
+        // Defaults for all post-launch configuration (shared with migrate_rift_account)
+        rift.apply_default_config();
+
+        // Record launch transfer fee (immutable) alongside the live fee
+        rift.initial_transfer_fee_bps = transfer_fee_bps;
+        rift.transfer_fee_bps = transfer_fee_bps;
+
+        // Interest-bearing underlying disclosure (redemption stays raw 1:1)
+        rift.interest_bearing = underlying_interest_bearing;
+
+        // Confidential transfer opt-in (validated against the underlying mint above)
+        rift.allow_confidential = allow_confidential;
+
+        rift.fee_free_until = launch_fee_free_until(fee_free_seconds)?;
+        rift.allow_nested = allow_nested;
+
+        // **NESTED RIFTS**: Mark this RIFT mint so later rifts can't wrap it without opting in
+        let rift_mint_registry = &mut ctx.accounts.rift_mint_registry;
+        rift_mint_registry.rift = rift.key();
+        rift_mint_registry.bump = ctx.bumps.rift_mint_registry;
+
         // **TOKEN-2022 TRANSFER FEE**: Manual initialization with 0.7% transfer fee on DEX trades
         // This fee applies ONLY to transfers (DEX trading), NOT to mint/burn (wrap/unwrap)
         use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
-        use spl_token_2022::extension::{ExtensionType, StateWithExtensionsMut};
+        use spl_token_2022::extension::ExtensionType;
 
         // 1. Calculate metadata strings FIRST (needed for space calculation)
         let rift_name_str =
             core::str::from_utf8(&rift_name[..name_len as usize]).unwrap_or("Rift Token");
-        // Use prefixed name for both display name and symbol; symbol still capped at 10 chars
         // prefix_type: 0 = 'r' (Rift), 1 = 'm' (Monorift)
         let prefix = if prefix_type == 1 { "m" } else { "r" };
         let display_name = format!("{}{}", prefix, rift_name_str);
-        let symbol = display_name[..display_name.len().min(10)].to_string();
+        let symbol = rift_symbol(prefix, &display_name, &symbol, symbol_len, prefix_symbol)?;
+
+        // **DISPLAY METADATA**: Advertise what the RIFT wraps and at what precision
+        // (RIFT mirrors the underlying decimals) so wallets/explorers render amounts correctly
+        let additional_metadata = rift_display_metadata(
+            &ctx.accounts.underlying_mint.key(),
+            ctx.accounts.underlying_mint.decimals,
+        );
 
         // 2. Calculate TOKEN METADATA space (uses variable-length TLV encoding)
+        // Includes the additional_metadata pairs so the mint is funded for them up front
         use spl_token_metadata_interface::state::TokenMetadata;
         use spl_pod::optional_keys::OptionalNonZeroPubkey;
         let metadata = TokenMetadata {
@@ -390,7 +615,7 @@ pub mod rifts_protocol {
             uri: "".to_string(),
             update_authority: OptionalNonZeroPubkey::default(),
             mint: Pubkey::default(), // placeholder
-            additional_metadata: vec![],
+            additional_metadata: additional_metadata.clone(),
         };
         let metadata_space = metadata.tlv_size_of().map_err(|_| ErrorCode::InvalidMint)?;
 
@@ -412,6 +637,13 @@ pub mod rifts_protocol {
         let final_mint_len = base_mint_space + metadata_space + METADATA_TLV_BUFFER;
         let mint_rent = Rent::get()?.minimum_balance(final_mint_len);
 
+        // **RENT PREFLIGHT**: Fail before any create_account if mint + 3 vaults can't be funded
+        ensure_create_rift_rent(
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.underlying_mint.to_account_info(),
+            mint_rent,
+        )?;
+
         msg!("🔍 DEBUG: base_mint_space (Mint+Extensions) = {}", base_mint_space);
         msg!("🔍 DEBUG: metadata_space (TLV) = {}", metadata_space);
         msg!("🔍 DEBUG: METADATA_TLV_BUFFER = {}", METADATA_TLV_BUFFER);
@@ -450,22 +682,26 @@ pub mod rifts_protocol {
         // This fee is ONLY charged on transfers (DEX trades), NOT on mint/burn!
         use spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config;
 
-        // **CRITICAL FIX #5**: Use PROGRAM_AUTHORITY for fee authorities, not creator
+        // **CRITICAL FIX #5**: Use the program authority for fee authorities, not creator
         // This prevents creators from manipulating fees on their rifts
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
 
         // **PER-RIFT TREASURY FIX**: Use default treasury (will be set in rift.treasury_wallet)
         // This ensures withdraw_withheld_authority matches the per-rift treasury
-        let default_treasury = Pubkey::from_str_const(DEFAULT_TREASURY_WALLET);
+        let default_treasury =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .default_treasury;
 
         invoke_signed(
             &initialize_transfer_fee_config(
                 &spl_token_2022::ID,
                 ctx.accounts.rift_mint.key,
-                Some(&program_authority), // transfer_fee_config_authority = PROGRAM_AUTHORITY
+                Some(&program_authority), // transfer_fee_config_authority = program authority
                 Some(&default_treasury),   // withdraw_withheld_authority = rift.treasury_wallet ✅
                 transfer_fee_bps,         // Configurable fee (70-100 bps = 0.7%-1%)
-                u64::MAX,                 // no maximum fee cap
+                max_transfer_fee,         // Maximum fee per transfer (u64::MAX = uncapped)
             )
             .map_err(|_| ErrorCode::InvalidMint)?,
             &[ctx.accounts.rift_mint.to_account_info()],
@@ -511,11 +747,16 @@ pub mod rifts_protocol {
             use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
             let fee_config = mint_state.get_extension::<TransferFeeConfig>()?;
             let actual_fee_bps = u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
+            let actual_max_fee = u64::from(fee_config.newer_transfer_fee.maximum_fee);
 
             require!(
                 actual_fee_bps == transfer_fee_bps,
                 ErrorCode::TransferFeeConfigMismatch
             );
+            require!(
+                actual_max_fee == max_transfer_fee,
+                ErrorCode::TransferFeeConfigMismatch
+            );
 
             drop(rift_mint_data);
             msg!(
@@ -567,6 +808,25 @@ pub mod rifts_protocol {
             signer_seeds,
         )?;
 
+        // **DISPLAY METADATA**: Write underlying_mint/decimals into additional_metadata
+        for (key, value) in additional_metadata {
+            let field_ix = spl_token_metadata_interface::instruction::update_field(
+                &spl_token_2022::ID,
+                &ctx.accounts.rift_mint.key(),
+                &ctx.accounts.rift_mint_authority.key(),
+                spl_token_metadata_interface::state::Field::Key(key),
+                value,
+            );
+            invoke_signed(
+                &field_ix,
+                &[
+                    ctx.accounts.rift_mint.to_account_info(),
+                    ctx.accounts.rift_mint_authority.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
+
         msg!("✅ Token-2022 mint created with metadata");
         msg!("Name: {}, Symbol: {}", display_name, symbol);
 
@@ -583,44 +843,10 @@ pub mod rifts_protocol {
         // 1. INITIALIZE VAULT (backing vault for underlying tokens)
         msg!("Initializing vault...");
 
-        let vault_space = if *underlying_token_program == spl_token_2022::ID {
-            // Calculate space based on underlying mint's Token-2022 extensions
-            let underlying_mint_info = ctx.accounts.underlying_mint.to_account_info();
-            let mint_data = underlying_mint_info.try_borrow_data()?;
-            let mint_account = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
-
-            let mint_extensions = mint_account.get_extension_types()?;
-            let mut account_extensions = Vec::new();
-
-            for ext_type in mint_extensions.iter() {
-                match ext_type {
-                    ExtensionType::TransferFeeConfig => {
-                        account_extensions.push(ExtensionType::TransferFeeAmount);
-                    }
-                    ExtensionType::MemoTransfer => {
-                        account_extensions.push(ExtensionType::MemoTransfer);
-                    }
-                    ExtensionType::NonTransferable => {
-                        account_extensions.push(ExtensionType::NonTransferable);
-                    }
-                    ExtensionType::ImmutableOwner => {
-                        account_extensions.push(ExtensionType::ImmutableOwner);
-                    }
-                    ExtensionType::CpiGuard => {
-                        account_extensions.push(ExtensionType::CpiGuard);
-                    }
-                    _ => {}
-                }
-            }
-
-            drop(mint_data);
-
-            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
-                &account_extensions,
-            ).map_err(|_| ErrorCode::InvalidMint)?
-        } else {
-            165 // Standard SPL Token size
-        };
+        let vault_space = compute_vault_account_space(
+            &ctx.accounts.underlying_mint.to_account_info(),
+            underlying_token_program,
+        )?;
 
         let vault_rent = Rent::get()?.minimum_balance(vault_space);
         let vault_seeds = &[b"vault", rift_key.as_ref(), &[ctx.bumps.vault]];
@@ -675,50 +901,10 @@ pub mod rifts_protocol {
 
         // **FIX MEDIUM-HIGH #26**: Calculate proper space by reading underlying mint's actual extensions
         // The underlying mint may be Token-2022 with multiple extensions
-        let fees_vault_space = if *underlying_token_program == spl_token_2022::ID {
-            // Read underlying mint to determine what extensions it has
-            let mint_info = ctx.accounts.underlying_mint.to_account_info();
-            let mint_data = mint_info.try_borrow_data()?;
-            let mint_account =
-                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
-
-            // Get list of extensions this mint has
-            let mint_extensions = mint_account.get_extension_types()?;
-
-            // Build list of required ACCOUNT extensions based on MINT extensions
-            let mut account_extensions = Vec::new();
-
-            for ext_type in mint_extensions.iter() {
-                match ext_type {
-                    ExtensionType::TransferFeeConfig => {
-                        account_extensions.push(ExtensionType::TransferFeeAmount);
-                    }
-                    ExtensionType::MemoTransfer => {
-                        account_extensions.push(ExtensionType::MemoTransfer);
-                    }
-                    ExtensionType::NonTransferable => {
-                        account_extensions.push(ExtensionType::NonTransferable);
-                    }
-                    ExtensionType::ImmutableOwner => {
-                        account_extensions.push(ExtensionType::ImmutableOwner);
-                    }
-                    ExtensionType::CpiGuard => {
-                        account_extensions.push(ExtensionType::CpiGuard);
-                    }
-                    _ => {}
-                }
-            }
-
-            drop(mint_data); // Release borrow before CPI
-
-            // Calculate space with ALL required extensions
-            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
-                &account_extensions,
-            )
-            .map_err(|_| ErrorCode::InvalidMint)?
-        } else {
-            165 // Standard SPL Token size
-        };
+        let fees_vault_space = compute_vault_account_space(
+            &ctx.accounts.underlying_mint.to_account_info(),
+            underlying_token_program,
+        )?;
         let fees_vault_rent = Rent::get()?.minimum_balance(fees_vault_space);
 
         invoke_signed(
@@ -774,45 +960,10 @@ pub mod rifts_protocol {
 
         // **FIX MEDIUM-HIGH #26**: Calculate proper space by reading RIFT mint's actual extensions
         // RIFT mint is always Token-2022, but may have additional extensions beyond TransferFeeConfig
-        let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
-        let mint_data_rift = rift_mint_info.try_borrow_data()?;
-        let mint_account_rift =
-            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data_rift)?;
-
-        // Get list of extensions this mint has
-        let mint_extensions_rift = mint_account_rift.get_extension_types()?;
-
-        // Build list of required ACCOUNT extensions based on MINT extensions
-        let mut account_extensions_rift = Vec::new();
-
-        for ext_type in mint_extensions_rift.iter() {
-            match ext_type {
-                ExtensionType::TransferFeeConfig => {
-                    account_extensions_rift.push(ExtensionType::TransferFeeAmount);
-                }
-                ExtensionType::MemoTransfer => {
-                    account_extensions_rift.push(ExtensionType::MemoTransfer);
-                }
-                ExtensionType::NonTransferable => {
-                    account_extensions_rift.push(ExtensionType::NonTransferable);
-                }
-                ExtensionType::ImmutableOwner => {
-                    account_extensions_rift.push(ExtensionType::ImmutableOwner);
-                }
-                ExtensionType::CpiGuard => {
-                    account_extensions_rift.push(ExtensionType::CpiGuard);
-                }
-                _ => {}
-            }
-        }
-
-        drop(mint_data_rift); // Release borrow before CPI
-
-        // Calculate space with ALL required extensions
-        let withheld_vault_space = ExtensionType::try_calculate_account_len::<
-            spl_token_2022::state::Account,
-        >(&account_extensions_rift)
-        .map_err(|_| ErrorCode::InvalidMint)?;
+        let withheld_vault_space = compute_vault_account_space(
+            &ctx.accounts.rift_mint.to_account_info(),
+            &spl_token_2022::ID,
+        )?;
         let withheld_vault_rent = Rent::get()?.minimum_balance(withheld_vault_space);
 
         invoke_signed(
@@ -855,6 +1006,7 @@ pub mod rifts_protocol {
     }
 
     /// Initialize a new Rift (wrapped token vault) - STACK OPTIMIZED (Original PDA version)
+    #[allow(clippy::too_many_arguments)]
     pub fn create_rift(
         ctx: Context<CreateRift>,
         partner_wallet: Option<Pubkey>,
@@ -862,21 +1014,60 @@ pub mod rifts_protocol {
         name_len: u8,
         transfer_fee_bps: u16, // Token-2022 transfer fee (70-100 = 0.7%-1%)
         prefix_type: u8,       // 0 = 'r' (Rift), 1 = 'm' (Monorift)
+        max_total_leakage_bps: Option<u16>, // Cap on RIFT + underlying transfer fee (None = 200 bps)
+        max_transfer_fee: Option<u64>, // Token-2022 max fee per RIFT transfer, raw units (None = uncapped)
+        allow_confidential: bool, // Opt-in: accept an underlying with non-enforced confidential transfers
+        symbol: [u8; 16],         // Explicit metadata symbol (UTF-8)
+        symbol_len: u8,           // Bytes of `symbol` to use (0 = derive from the prefixed name)
+        prefix_symbol: bool,      // Prepend the r/m prefix to the explicit symbol
+        fee_free_seconds: u32,    // Zero-fee wrap/unwrap window after launch (max 72h, 0 = none)
+        allow_nested: bool,       // Opt-in: accept an underlying that is itself a RIFT mint
     ) -> Result<()> {
+        // **IDEMPOTENT CREATE**: rift is init_if_needed so a retried submission reaches the handler;
+        // an already-initialized rift for this creator/underlying is reported as RiftAlreadyExists
+        if ctx.accounts.rift.creator != Pubkey::default() {
+            require!(
+                ctx.accounts.rift.creator == ctx.accounts.creator.key()
+                    && ctx.accounts.rift.underlying_mint == ctx.accounts.underlying_mint.key(),
+                ErrorCode::InvalidSeedComponent
+            );
+            msg!("⚠️ Rift {} already exists (duplicate create submission)", ctx.accounts.rift.key());
+            return Err(ErrorCode::RiftAlreadyExists.into());
+        }
+
+        // **MINT POLICY**: Curated deployments can allow/deny specific underlyings
+        GlobalConfig::check_mint_policy(
+            &ctx.accounts.global_config.to_account_info(),
+            &ctx.accounts.mint_policy.to_account_info(),
+        )?;
+
+        // **NESTED RIFTS**: Wrapping another rift's RIFT mint requires an explicit opt-in
+        require!(
+            allow_nested
+                || !RiftMintRegistry::is_registered(
+                    &ctx.accounts.underlying_registry.to_account_info()
+                ),
+            ErrorCode::NestedRiftNotAllowed
+        );
+
         let rift = &mut ctx.accounts.rift;
 
         // **MEDIUM FIX #7**: Validate and set rift name (fixed-size array - no heap allocation!)
         require!(name_len <= 32, ErrorCode::NameTooLong);
         // **TOKEN-2022**: Validate transfer fee is between 0.7% and 1% (70-100 basis points)
         require!(
-            transfer_fee_bps >= 70 && transfer_fee_bps <= 100,
+            (MIN_LAUNCH_TRANSFER_FEE_BPS..=MAX_LAUNCH_TRANSFER_FEE_BPS).contains(&transfer_fee_bps),
             ErrorCode::InvalidTransferFee
         );
+        // **MAX FEE CAP**: Whale-friendly cap on the proportional transfer fee
+        let max_transfer_fee = max_transfer_fee.unwrap_or(DEFAULT_MAX_TRANSFER_FEE);
+        require!(max_transfer_fee > 0, ErrorCode::InvalidMaxTransferFee);
 
         // **FIX HIGH #33**: Mirror underlying mint validation from create_rift_with_vanity_pda
         // **FIX HIGH #29**: Validate underlying mint has no freeze authority to prevent fund lockup
         // **FIX HIGH #30**: Validate underlying mint has no mint authority to prevent supply inflation
         // **FIX CRITICAL #31**: Validate Token-2022 extensions to prevent DoS and vault drain
+        let mut underlying_interest_bearing = false;
         {
             let mint_info = ctx.accounts.underlying_mint.to_account_info();
             let mint_data = mint_info.try_borrow_data()?;
@@ -949,6 +1140,13 @@ pub mod rifts_protocol {
                                 u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
                             require!(fee_bps <= 100, ErrorCode::ExcessiveTransferFee);
                             msg!("✅ Underlying transfer fee: {} bps (acceptable)", fee_bps);
+
+                            // **LEAKAGE CAP**: Bound worst-case combined fee on a round trip
+                            check_combined_leakage(
+                                transfer_fee_bps,
+                                fee_bps,
+                                max_total_leakage_bps,
+                            )?;
                         }
                         ExtensionType::MintCloseAuthority => {
                             // HIGH: Mint can be closed, freezing all token accounts
@@ -983,8 +1181,19 @@ pub mod rifts_protocol {
                             // **FIX MEDIUM #6 (Audit)**: BLOCK ConfidentialTransferMint extension
                             // Confidential transfers require special handling not implemented in wrap/unwrap
                             // Would cause transfer failures or incorrect balance tracking
-                            msg!("❌ Underlying mint has ConfidentialTransferMint - not supported!");
-                            return Err(ErrorCode::UnsafeUnderlyingMint.into());
+                            // **CONFIDENTIAL OPT-IN**: Creators may accept a mint whose confidential
+                            // transfers are optional (auto-approve off); the rift only ever moves
+                            // public balances. Auto-approved (enforced) configs stay blocked.
+                            use spl_token_2022::extension::confidential_transfer::ConfidentialTransferMint;
+                            let ct_config = mint_state
+                                .get_extension::<ConfidentialTransferMint>()
+                                .map_err(|_| ErrorCode::InvalidMint)?;
+                            let auto_approve = bool::from(ct_config.auto_approve_new_accounts);
+                            if !allow_confidential || auto_approve {
+                                msg!("❌ Underlying mint has ConfidentialTransferMint - not supported!");
+                                return Err(ErrorCode::UnsafeUnderlyingMint.into());
+                            }
+                            msg!("⚠️ ConfidentialTransferMint present but not enforced - public transfers only");
                         }
                         ExtensionType::ConfidentialTransferFeeConfig => {
                             // **FIX MEDIUM #6 (Audit)**: BLOCK ConfidentialTransferFeeConfig extension
@@ -992,6 +1201,18 @@ pub mod rifts_protocol {
                             msg!("❌ Underlying mint has ConfidentialTransferFeeConfig - not supported!");
                             return Err(ErrorCode::UnsafeUnderlyingMint.into());
                         }
+                        ExtensionType::InterestBearingConfig => {
+                            // **INTEREST-BEARING**: Allowed - interest only changes the UI amount,
+                            // raw balances never grow, so 1:1 raw redemption passes it through
+                            underlying_interest_bearing = true;
+                            msg!("✅ Underlying mint is interest-bearing - RIFT redeems raw amounts 1:1");
+                        }
+                        ExtensionType::ScaledUiAmount => {
+                            // **SCALED UI AMOUNT**: Allowed - display-only multiplier, raw balances
+                            // and transfers are unaffected and need no vault account extension.
+                            // RIFT mirrors raw amounts, so wallets show it unscaled vs the underlying
+                            msg!("⚠️ Underlying mint has ScaledUiAmount - RIFT mirrors raw (unscaled) amounts");
+                        }
                         _ => {
                             // Other extensions (ImmutableOwner, CpiGuard) are handled
                             // CpiGuard: Account extensions added during vault init
@@ -1007,26 +1228,16 @@ pub mod rifts_protocol {
             drop(mint_data); // Release borrow
         }
 
-        if name_len > 0 {
-            // **MEDIUM FIX #7**: Validate name is valid UTF-8 to prevent off-chain parser issues
-            let name_slice = &rift_name[..name_len as usize];
-            require!(
-                core::str::from_utf8(name_slice).is_ok(),
-                ErrorCode::InvalidRiftName
-            );
-            rift.name[..name_len as usize].copy_from_slice(name_slice);
-        } else {
-            // **MEMORY OPTIMIZATION**: Use empty name (all zeros)
-            rift.name = [0u8; 32];
-        }
+        // **MEDIUM FIX #7**: Validate name is valid UTF-8 to prevent off-chain parser issues
+        validate_rift_name(&rift_name, name_len)?;
+        rift.name = [0u8; 32];
+        rift.name[..name_len as usize].copy_from_slice(&rift_name[..name_len as usize]);
 
         rift.creator = ctx.accounts.creator.key();
         rift.underlying_mint = ctx.accounts.underlying_mint.key();
         rift.rift_mint = ctx.accounts.rift_mint.key();
         // **ATOMIC INIT**: Initialize all 3 vaults during create_rift (Option A implementation)
         // This ensures clean fee accounting and better UX (single transaction setup)
-        let rift_key = rift.key();
-
         // Will be set to actual initialized addresses below
         // Temporarily set to system program (will update after CPI)
         rift.vault = anchor_lang::solana_program::system_program::ID;
@@ -1035,8 +1246,10 @@ pub mod rifts_protocol {
 
         // **FEE SPLIT**: If no partner provided, creator is the partner (50/50 split with treasury)
         rift.partner_wallet = Some(partner_wallet.unwrap_or(ctx.accounts.creator.key()));
-        rift.partner_fee_bps = 5000; // Always 50% (5000 bps) - stored for backwards compatibility
-        let default_treasury = Pubkey::from_str_const(DEFAULT_TREASURY_WALLET);
+        rift.partner_fee_bps = 5000; // Default 50% (5000 bps) - adjustable via set_partner_fee_bps
+        let default_treasury =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .default_treasury;
         rift.treasury_wallet = Some(default_treasury);
         // **CRITICAL FIX #1**: Initialize configurable wrap/unwrap fees (default 0.3%)
         rift.wrap_fee_bps = 30; // Default 0.3% wrap fee
@@ -1090,21 +1303,49 @@ pub mod rifts_protocol {
         rift.pending_switchboard_account = None;
         rift.oracle_change_timestamp = 0;
 
+        // Defaults for all post-launch configuration (shared with migrate_rift_account)
+        rift.apply_default_config();
+
+        // Record launch transfer fee (immutable) alongside the live fee
+        rift.initial_transfer_fee_bps = transfer_fee_bps;
+        rift.transfer_fee_bps = transfer_fee_bps;
+
+        // Interest-bearing underlying disclosure (redemption stays raw 1:1)
+        rift.interest_bearing = underlying_interest_bearing;
+
+        // Confidential transfer opt-in (validated against the underlying mint above)
+        rift.allow_confidential = allow_confidential;
+
+        rift.fee_free_until = launch_fee_free_until(fee_free_seconds)?;
+        rift.allow_nested = allow_nested;
+
+        // **NESTED RIFTS**: Mark this RIFT mint so later rifts can't wrap it without opting in
+        let rift_mint_registry = &mut ctx.accounts.rift_mint_registry;
+        rift_mint_registry.rift = rift.key();
+        rift_mint_registry.bump = ctx.bumps.rift_mint_registry;
+
         // **TOKEN-2022**: Initialize Token-2022 mint with transfer fee extension
         // This fee applies ONLY to transfers (DEX trading), NOT to mint/burn (wrap/unwrap)
         use anchor_lang::solana_program::{program::invoke_signed, system_instruction};
-        use spl_token_2022::extension::{ExtensionType, StateWithExtensionsMut};
+        use spl_token_2022::extension::ExtensionType;
 
         // 1. Calculate metadata strings FIRST (needed for space calculation)
         let rift_name_str =
             core::str::from_utf8(&rift_name[..name_len as usize]).unwrap_or("Rift Token");
-        // Use prefixed name for both display name and symbol; symbol still capped at 10 chars
         // prefix_type: 0 = 'r' (Rift), 1 = 'm' (Monorift)
         let prefix = if prefix_type == 1 { "m" } else { "r" };
         let display_name = format!("{}{}", prefix, rift_name_str);
-        let symbol = display_name[..display_name.len().min(10)].to_string();
+        let symbol = rift_symbol(prefix, &display_name, &symbol, symbol_len, prefix_symbol)?;
+
+        // **DISPLAY METADATA**: Advertise what the RIFT wraps and at what precision
+        // (RIFT mirrors the underlying decimals) so wallets/explorers render amounts correctly
+        let additional_metadata = rift_display_metadata(
+            &ctx.accounts.underlying_mint.key(),
+            ctx.accounts.underlying_mint.decimals,
+        );
 
         // 2. Calculate TOKEN METADATA space (uses variable-length TLV encoding)
+        // Includes the additional_metadata pairs so the mint is funded for them up front
         use spl_token_metadata_interface::state::TokenMetadata;
         use spl_pod::optional_keys::OptionalNonZeroPubkey;
         let metadata = TokenMetadata {
@@ -1113,7 +1354,7 @@ pub mod rifts_protocol {
             uri: "".to_string(),
             update_authority: OptionalNonZeroPubkey::default(),
             mint: Pubkey::default(), // placeholder
-            additional_metadata: vec![],
+            additional_metadata: additional_metadata.clone(),
         };
         let metadata_space = metadata.tlv_size_of().map_err(|_| ErrorCode::InvalidMint)?;
 
@@ -1135,6 +1376,13 @@ pub mod rifts_protocol {
         let final_mint_len = base_mint_space + metadata_space + METADATA_TLV_BUFFER;
         let mint_rent = Rent::get()?.minimum_balance(final_mint_len);
 
+        // **RENT PREFLIGHT**: Fail before any create_account if mint + 3 vaults can't be funded
+        ensure_create_rift_rent(
+            &ctx.accounts.creator.to_account_info(),
+            &ctx.accounts.underlying_mint.to_account_info(),
+            mint_rent,
+        )?;
+
         msg!("🔍 DEBUG: base_mint_space (Mint+Extensions) = {}", base_mint_space);
         msg!("🔍 DEBUG: metadata_space (TLV) = {}", metadata_space);
         msg!("🔍 DEBUG: METADATA_TLV_BUFFER = {}", METADATA_TLV_BUFFER);
@@ -1170,20 +1418,24 @@ pub mod rifts_protocol {
         // This fee is ONLY charged on transfers (DEX trades), NOT on mint/burn!
         use spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config;
 
-        // Use PROGRAM_AUTHORITY for fee authorities (prevents creators from manipulating fees)
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        // Use the program authority for fee authorities (prevents creators from manipulating fees)
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         // **PER-RIFT TREASURY FIX**: Use default treasury (will be set in rift.treasury_wallet)
         // This ensures withdraw_withheld_authority matches the per-rift treasury
-        let default_treasury = Pubkey::from_str_const(DEFAULT_TREASURY_WALLET);
+        let default_treasury =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .default_treasury;
 
         invoke_signed(
             &initialize_transfer_fee_config(
                 &spl_token_2022::ID,
                 ctx.accounts.rift_mint.key,
-                Some(&program_authority), // transfer_fee_config_authority = PROGRAM_AUTHORITY
+                Some(&program_authority), // transfer_fee_config_authority = program authority
                 Some(&default_treasury),   // withdraw_withheld_authority = rift.treasury_wallet ✅
                 transfer_fee_bps,         // Configurable fee (70-100 bps = 0.7%-1%)
-                u64::MAX,                 // no maximum fee cap
+                max_transfer_fee,         // Maximum fee per transfer (u64::MAX = uncapped)
             )
             .map_err(|_| ErrorCode::InvalidMint)?,
             &[ctx.accounts.rift_mint.to_account_info()],
@@ -1229,11 +1481,16 @@ pub mod rifts_protocol {
             use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
             let fee_config = mint_state.get_extension::<TransferFeeConfig>()?;
             let actual_fee_bps = u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
+            let actual_max_fee = u64::from(fee_config.newer_transfer_fee.maximum_fee);
 
             require!(
                 actual_fee_bps == transfer_fee_bps,
                 ErrorCode::TransferFeeConfigMismatch
             );
+            require!(
+                actual_max_fee == max_transfer_fee,
+                ErrorCode::TransferFeeConfigMismatch
+            );
 
             drop(rift_mint_data);
             msg!(
@@ -1277,6 +1534,25 @@ pub mod rifts_protocol {
             signer_seeds,
         )?;
 
+        // **DISPLAY METADATA**: Write underlying_mint/decimals into additional_metadata
+        for (key, value) in additional_metadata {
+            let field_ix = spl_token_metadata_interface::instruction::update_field(
+                &spl_token_2022::ID,
+                &ctx.accounts.rift_mint.key(),
+                &ctx.accounts.rift_mint_authority.key(),
+                spl_token_metadata_interface::state::Field::Key(key),
+                value,
+            );
+            invoke_signed(
+                &field_ix,
+                &[
+                    ctx.accounts.rift_mint.to_account_info(),
+                    ctx.accounts.rift_mint_authority.to_account_info(),
+                ],
+                signer_seeds,
+            )?;
+        }
+
         msg!("✅ Token-2022 mint created with metadata");
         msg!("Name: {}, Symbol: {}", display_name, symbol);
 
@@ -1292,44 +1568,10 @@ pub mod rifts_protocol {
         // 1. INITIALIZE VAULT (backing vault for underlying tokens)
         msg!("Initializing vault...");
 
-        let vault_space = if *underlying_token_program == spl_token_2022::ID {
-            // Calculate space based on underlying mint's Token-2022 extensions
-            let underlying_mint_info = ctx.accounts.underlying_mint.to_account_info();
-            let mint_data = underlying_mint_info.try_borrow_data()?;
-            let mint_account = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
-
-            let mint_extensions = mint_account.get_extension_types()?;
-            let mut account_extensions = Vec::new();
-
-            for ext_type in mint_extensions.iter() {
-                match ext_type {
-                    ExtensionType::TransferFeeConfig => {
-                        account_extensions.push(ExtensionType::TransferFeeAmount);
-                    }
-                    ExtensionType::MemoTransfer => {
-                        account_extensions.push(ExtensionType::MemoTransfer);
-                    }
-                    ExtensionType::NonTransferable => {
-                        account_extensions.push(ExtensionType::NonTransferable);
-                    }
-                    ExtensionType::ImmutableOwner => {
-                        account_extensions.push(ExtensionType::ImmutableOwner);
-                    }
-                    ExtensionType::CpiGuard => {
-                        account_extensions.push(ExtensionType::CpiGuard);
-                    }
-                    _ => {}
-                }
-            }
-
-            drop(mint_data);
-
-            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
-                &account_extensions,
-            ).map_err(|_| ErrorCode::InvalidMint)?
-        } else {
-            165 // Standard SPL Token size
-        };
+        let vault_space = compute_vault_account_space(
+            &ctx.accounts.underlying_mint.to_account_info(),
+            underlying_token_program,
+        )?;
 
         let vault_rent = Rent::get()?.minimum_balance(vault_space);
         let (vault_key, vault_bump) = Pubkey::find_program_address(
@@ -1465,39 +1707,10 @@ pub mod rifts_protocol {
         msg!("Initializing withheld_vault...");
 
         // Calculate space based on RIFT mint's extensions (always Token-2022)
-        let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
-        let rift_mint_data = rift_mint_info.try_borrow_data()?;
-        let rift_mint_account = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)?;
-
-        let rift_mint_extensions = rift_mint_account.get_extension_types()?;
-        let mut withheld_account_extensions = Vec::new();
-
-        for ext_type in rift_mint_extensions.iter() {
-            match ext_type {
-                ExtensionType::TransferFeeConfig => {
-                    withheld_account_extensions.push(ExtensionType::TransferFeeAmount);
-                }
-                ExtensionType::MemoTransfer => {
-                    withheld_account_extensions.push(ExtensionType::MemoTransfer);
-                }
-                ExtensionType::NonTransferable => {
-                    withheld_account_extensions.push(ExtensionType::NonTransferable);
-                }
-                ExtensionType::ImmutableOwner => {
-                    withheld_account_extensions.push(ExtensionType::ImmutableOwner);
-                }
-                ExtensionType::CpiGuard => {
-                    withheld_account_extensions.push(ExtensionType::CpiGuard);
-                }
-                _ => {}
-            }
-        }
-
-        drop(rift_mint_data);
-
-        let withheld_vault_space = ExtensionType::try_calculate_account_len::<
-            spl_token_2022::state::Account
-        >(&withheld_account_extensions).map_err(|_| ErrorCode::InvalidMint)?;
+        let withheld_vault_space = compute_vault_account_space(
+            &ctx.accounts.rift_mint.to_account_info(),
+            &spl_token_2022::ID,
+        )?;
 
         let withheld_vault_rent = Rent::get()?.minimum_balance(withheld_vault_space);
         let (withheld_vault_key, withheld_vault_bump) = Pubkey::find_program_address(
@@ -1565,91 +1778,242 @@ pub mod rifts_protocol {
         Ok(())
     }
 
-    /// Initialize vault for rift
-    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
-        // Vault is automatically initialized through the constraint
-        Ok(())
-    }
+    /// Initialize a new Rift whose RIFT mint is a classic SPL Token mint (opt-in legacy mode)
+    /// For venues without Token-2022 transfer-fee support: no transfer fee, no metadata,
+    /// no withheld_vault. Mint decimals mirror the underlying; fees_vault is initialized separately.
+    pub fn create_rift_spl(
+        ctx: Context<CreateRiftSpl>,
+        partner_wallet: Option<Pubkey>,
+        rift_name: [u8; 32],
+        name_len: u8,
+        fee_free_seconds: u32, // Zero-fee wrap/unwrap window after launch (max 72h, 0 = none)
+        allow_nested: bool,    // Opt-in: accept an underlying that is itself a RIFT mint
+    ) -> Result<()> {
+        use spl_token_2022::extension::ExtensionType;
 
-    /// Initialize fees vault for collecting wrap/unwrap fees (underlying tokens)
-    /// Must be called after rift creation to enable fee collection
-    /// **FIX CRITICAL #19**: Manual initialization to properly size for Token-2022 extensions
-    pub fn initialize_fees_vault(ctx: Context<InitializeFeesVault>) -> Result<()> {
-        let rift = &mut ctx.accounts.rift;
+        // **IDEMPOTENT CREATE**: Same duplicate-submission handling as create_rift
+        if ctx.accounts.rift.creator != Pubkey::default() {
+            require!(
+                ctx.accounts.rift.creator == ctx.accounts.creator.key()
+                    && ctx.accounts.rift.underlying_mint == ctx.accounts.underlying_mint.key(),
+                ErrorCode::InvalidSeedComponent
+            );
+            msg!("⚠️ Rift {} already exists (duplicate create submission)", ctx.accounts.rift.key());
+            return Err(ErrorCode::RiftAlreadyExists.into());
+        }
 
-        // **FIX CRITICAL #34**: Only creator or program authority can initialize fees vault
-        // Prevents front-running attacks where attacker creates vault with wrong owner/space
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
-        require!(
-            ctx.accounts.user.key() == rift.creator || ctx.accounts.user.key() == program_authority,
-            ErrorCode::Unauthorized
-        );
+        // **MINT POLICY**: Curated deployments can allow/deny specific underlyings
+        GlobalConfig::check_mint_policy(
+            &ctx.accounts.global_config.to_account_info(),
+            &ctx.accounts.mint_policy.to_account_info(),
+        )?;
 
-        // **FIX CRITICAL #34**: Validate token_program matches underlying_mint's owner
-        // Prevents creating vault with foreign program owner that can't be reinitialized
-        let underlying_mint_owner = ctx.accounts.underlying_mint.owner;
+        // **NESTED RIFTS**: Wrapping another rift's RIFT mint requires an explicit opt-in
         require!(
-            ctx.accounts.token_program.key() == *underlying_mint_owner,
-            ErrorCode::InvalidProgramId
+            allow_nested
+                || !RiftMintRegistry::is_registered(
+                    &ctx.accounts.underlying_registry.to_account_info()
+                ),
+            ErrorCode::NestedRiftNotAllowed
         );
 
-        msg!("✅ Authorization validated: user is creator or program authority");
-
-        // **FIX MEDIUM-HIGH #26**: Calculate proper space by reading underlying mint's actual extensions
-        let fees_vault_space = if ctx.accounts.token_program.key() == spl_token_2022::ID {
-            // Read underlying mint to determine what extensions it has
-            let underlying_mint_info = ctx.accounts.underlying_mint.to_account_info();
-            let mint_data = underlying_mint_info.try_borrow_data()?;
-            let mint_account =
-                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
-
-            // Get list of extensions this mint has
-            let mint_extensions = mint_account.get_extension_types()?;
+        require!(name_len <= 32, ErrorCode::NameTooLong);
 
-            // Build list of required ACCOUNT extensions based on MINT extensions
-            let mut account_extensions = Vec::new();
+        // **FIX CRITICAL #31**: Same unsafe-extension screening as create_rift
+        let mut underlying_interest_bearing = false;
+        {
+            let mint_info = ctx.accounts.underlying_mint.to_account_info();
+            if *mint_info.owner == spl_token_2022::ID {
+                let mint_data = mint_info.try_borrow_data()?;
+                let mint_state =
+                    StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)
+                        .map_err(|_| ErrorCode::InvalidMint)?;
+                let extension_types = mint_state
+                    .get_extension_types()
+                    .map_err(|_| ErrorCode::InvalidMint)?;
 
-            for ext_type in mint_extensions.iter() {
-                match ext_type {
-                    ExtensionType::TransferFeeConfig => {
-                        // Mint has transfer fees → account needs TransferFeeAmount
-                        account_extensions.push(ExtensionType::TransferFeeAmount);
-                    }
-                    ExtensionType::MemoTransfer => {
-                        // Mint requires memos → account needs MemoTransfer
-                        account_extensions.push(ExtensionType::MemoTransfer);
-                    }
-                    ExtensionType::NonTransferable => {
-                        // Mint is non-transferable → account needs NonTransferable
-                        account_extensions.push(ExtensionType::NonTransferable);
-                    }
-                    ExtensionType::ImmutableOwner => {
-                        // Mint has immutable owner → account needs ImmutableOwner
-                        account_extensions.push(ExtensionType::ImmutableOwner);
-                    }
-                    ExtensionType::CpiGuard => {
-                        // Mint has CPI guard → account needs CpiGuard
-                        account_extensions.push(ExtensionType::CpiGuard);
-                    }
-                    _ => {
-                        // Other mint extensions (PermanentDelegate, MintCloseAuthority, etc.)
-                        // don't require corresponding account extensions
+                for ext_type in extension_types.iter() {
+                    match ext_type {
+                        ExtensionType::NonTransferable
+                        | ExtensionType::PermanentDelegate
+                        | ExtensionType::MintCloseAuthority
+                        | ExtensionType::TransferHook
+                        | ExtensionType::MemoTransfer
+                        | ExtensionType::DefaultAccountState
+                        | ExtensionType::ConfidentialTransferMint
+                        | ExtensionType::ConfidentialTransferFeeConfig => {
+                            msg!("❌ Underlying mint has unsupported extension {:?}", ext_type);
+                            return Err(ErrorCode::UnsafeUnderlyingMint.into());
+                        }
+                        ExtensionType::TransferFeeConfig => {
+                            let fee_config = mint_state
+                                .get_extension::<TransferFeeConfig>()
+                                .map_err(|_| ErrorCode::InvalidMint)?;
+                            let fee_bps =
+                                u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
+                            require!(fee_bps <= 100, ErrorCode::ExcessiveTransferFee);
+                        }
+                        ExtensionType::InterestBearingConfig => {
+                            underlying_interest_bearing = true;
+                        }
+                        ExtensionType::ScaledUiAmount => {
+                            // Display-only multiplier; raw balances/transfers unaffected
+                            msg!("⚠️ Underlying mint has ScaledUiAmount - RIFT mirrors raw (unscaled) amounts");
+                        }
+                        _ => {}
                     }
                 }
             }
+        }
 
-            drop(mint_data); // Release borrow before CPI
+        let rift = &mut ctx.accounts.rift;
 
-            // Calculate space with ALL required extensions
-            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
-                &account_extensions,
-            )
-            .map_err(|_| ErrorCode::InvalidMint)?
-        } else {
-            165 // Standard SPL Token size
-        };
+        // **MEDIUM FIX #7**: Validate name is valid UTF-8 to prevent off-chain parser issues
+        validate_rift_name(&rift_name, name_len)?;
+        rift.name = [0u8; 32];
+        rift.name[..name_len as usize].copy_from_slice(&rift_name[..name_len as usize]);
 
-        let fees_vault_rent = Rent::get()?.minimum_balance(fees_vault_space);
+        rift.creator = ctx.accounts.creator.key();
+        rift.underlying_mint = ctx.accounts.underlying_mint.key();
+        rift.rift_mint = ctx.accounts.rift_mint.key();
+        // Backing vault is initialized by Anchor; fees_vault via initialize_fees_vault
+        // Legacy mints have no transfer fee, so there is never a withheld_vault
+        rift.vault = ctx.accounts.vault.key();
+        rift.fees_vault = anchor_lang::solana_program::system_program::ID;
+        rift.withheld_vault = anchor_lang::solana_program::system_program::ID;
+
+        // **FEE SPLIT**: If no partner provided, creator is the partner (50/50 split with treasury)
+        rift.partner_wallet = Some(partner_wallet.unwrap_or(ctx.accounts.creator.key()));
+        rift.partner_fee_bps = 5000; // Default 50% (5000 bps) - adjustable via set_partner_fee_bps
+        let default_treasury =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .default_treasury;
+        rift.treasury_wallet = Some(default_treasury);
+        // **CRITICAL FIX #1**: Initialize configurable wrap/unwrap fees (default 0.3%)
+        rift.wrap_fee_bps = 30; // Default 0.3% wrap fee
+        rift.unwrap_fee_bps = 30; // Default 0.3% unwrap fee
+        rift.total_underlying_wrapped = 0;
+        rift.total_rift_minted = 0;
+        rift.total_burned = 0;
+        rift.backing_ratio = 1_000_000; // 100% initially (6 decimals precision) - FIXED from 10000
+        rift.last_rebalance = Clock::get()?.unix_timestamp;
+        rift.created_at = Clock::get()?.unix_timestamp;
+
+        // Initialize hybrid oracle system
+        rift.oracle_prices = [PriceData::default(); 10];
+        rift.price_index = 0;
+        rift.oracle_update_interval = 30 * 60; // 30 minutes
+        rift.max_rebalance_interval = 24 * 60 * 60; // 24 hours
+        rift.arbitrage_threshold_bps = 200; // 2% threshold
+        rift.last_oracle_update = Clock::get()?.unix_timestamp;
+
+        // Initialize advanced metrics
+        rift.total_volume_24h = 0;
+        rift.price_deviation = 0;
+        rift.arbitrage_opportunity_bps = 0;
+        rift.rebalance_count = 0;
+
+        // Initialize RIFTS token distribution tracking
+        rift.total_fees_collected = 0;
+        rift.rifts_tokens_distributed = 0;
+        rift.rifts_tokens_burned = 0;
+
+        // **SECURITY FIX #50**: Initialize oracle accounts as None (must be set explicitly)
+        rift.switchboard_feed_account = None;
+
+        // **HIGH FIX #3**: Initialize manual oracle rate limiting
+        rift.last_manual_oracle_update = 0;
+
+        // **FIX HIGH #2**: Initialize cumulative drift tracking
+        rift.manual_oracle_base_price = 0;
+        rift.manual_oracle_drift_window_start = 0;
+
+        // Initialize reentrancy protection
+        rift.reentrancy_guard = false;
+        rift.reentrancy_guard_slot = 0;
+
+        // Initialize closure state
+        rift.is_closed = false;
+        rift.closed_at_slot = 0;
+
+        // Initialize oracle change timelock
+        rift.oracle_change_pending = false;
+        rift.pending_switchboard_account = None;
+        rift.oracle_change_timestamp = 0;
+
+        // Defaults for all post-launch configuration (shared with migrate_rift_account)
+        rift.apply_default_config();
+
+        // Interest-bearing underlying disclosure (redemption stays raw 1:1)
+        rift.interest_bearing = underlying_interest_bearing;
+
+        // Classic SPL Token RIFT mint (no transfer fee; confidential underlyings stay blocked)
+        rift.legacy_mint = true;
+
+        rift.fee_free_until = launch_fee_free_until(fee_free_seconds)?;
+        rift.allow_nested = allow_nested;
+
+        // **NESTED RIFTS**: Mark this RIFT mint so later rifts can't wrap it without opting in
+        let rift_mint_registry = &mut ctx.accounts.rift_mint_registry;
+        rift_mint_registry.rift = rift.key();
+        rift_mint_registry.bump = ctx.bumps.rift_mint_registry;
+
+        emit!(RiftCreated {
+            rift: rift.key(),
+            creator: rift.creator,
+            underlying_mint: rift.underlying_mint,
+            partner_fee_bps: rift.partner_fee_bps,
+        });
+
+        msg!(
+            "✅ Legacy SPL Token rift created: mint {} ({} decimals), vault {}",
+            ctx.accounts.rift_mint.key(),
+            ctx.accounts.underlying_mint.decimals,
+            ctx.accounts.vault.key()
+        );
+
+        Ok(())
+    }
+
+    /// Initialize vault for rift
+    pub fn initialize_vault(_ctx: Context<InitializeVault>) -> Result<()> {
+        // Vault is automatically initialized through the constraint
+        Ok(())
+    }
+
+    /// Initialize fees vault for collecting wrap/unwrap fees (underlying tokens)
+    /// Must be called after rift creation to enable fee collection
+    /// **FIX CRITICAL #19**: Manual initialization to properly size for Token-2022 extensions
+    pub fn initialize_fees_vault(ctx: Context<InitializeFeesVault>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        // **FIX CRITICAL #34**: Only creator or program authority can initialize fees vault
+        // Prevents front-running attacks where attacker creates vault with wrong owner/space
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.user.key() == rift.creator || ctx.accounts.user.key() == program_authority,
+            ErrorCode::Unauthorized
+        );
+
+        // **FIX CRITICAL #34**: Validate token_program matches underlying_mint's owner
+        // Prevents creating vault with foreign program owner that can't be reinitialized
+        let underlying_mint_owner = ctx.accounts.underlying_mint.owner;
+        require!(
+            ctx.accounts.token_program.key() == *underlying_mint_owner,
+            ErrorCode::InvalidProgramId
+        );
+
+        msg!("✅ Authorization validated: user is creator or program authority");
+
+        // **FIX MEDIUM-HIGH #26**: Calculate proper space by reading underlying mint's actual extensions
+        let fees_vault_space = compute_vault_account_space(
+            &ctx.accounts.underlying_mint.to_account_info(),
+            &ctx.accounts.token_program.key(),
+        )?;
+
+        let fees_vault_rent = Rent::get()?.minimum_balance(fees_vault_space);
 
         // Derive PDA
         let (fees_vault_key, fees_vault_bump) =
@@ -1660,6 +2024,19 @@ pub mod rifts_protocol {
             ErrorCode::InvalidPDA
         );
 
+        // **IDEMPOTENT INIT**: Vault already created (e.g. by the atomic creation path) - repoint only
+        if existing_vault_matches(
+            &ctx.accounts.fees_vault.to_account_info(),
+            &ctx.accounts.token_program.key(),
+            &ctx.accounts.underlying_mint.key(),
+            &ctx.accounts.vault_authority.key(),
+            ErrorCode::InvalidFeesVault,
+        )? {
+            rift.fees_vault = fees_vault_key;
+            msg!("✅ Fees vault already initialized for rift: {}", rift.key());
+            return Ok(());
+        }
+
         // **FIX CRITICAL #24**: Use invoke_signed so PDA can sign account creation
         let rift_key = rift.key();
         let fees_vault_seeds = &[
@@ -1726,6 +2103,116 @@ pub mod rifts_protocol {
         Ok(())
     }
 
+    /// Creator: create a separate vault that receives wrap fees instead of fees_vault
+    /// Unwrap fees keep going to fees_vault; wrap fees are paid out with claim_wrap_fees
+    pub fn initialize_wrap_fees_vault(ctx: Context<InitializeWrapFeesVault>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        rift.wrap_fees_vault = Some(ctx.accounts.wrap_fees_vault.key());
+
+        msg!(
+            "✅ Wrap fees vault {} initialized for rift: {}",
+            ctx.accounts.wrap_fees_vault.key(),
+            rift.key()
+        );
+
+        Ok(())
+    }
+
+    /// Creator: distribute the wrap fees vault with the same split as the fees vault
+    /// Outstanding protocol skim (protocol_fees_owed) is sent to the global treasury first, then the
+    /// protocol cut, partner_fee_bps to the partner and the remainder to the rift treasury
+    pub fn claim_wrap_fees(ctx: Context<ClaimWrapFees>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        let wrap_fees_vault_key = rift.wrap_fees_vault.ok_or(ErrorCode::InvalidFeesVault)?;
+        let wrap_fees_vault = ctx.accounts.wrap_fees_vault.to_account_info();
+        let balance =
+            read_vault_amount(&wrap_fees_vault, wrap_fees_vault_key, ErrorCode::InvalidFeesVault)?;
+        require!(balance > 0, ErrorCode::InsufficientFees);
+
+        let partner_wallet = rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?;
+        let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
+        validate_fee_recipient(
+            &ctx.accounts.treasury_underlying_account,
+            &treasury_wallet,
+            &rift.underlying_mint,
+            ErrorCode::InvalidTreasuryVault,
+        )?;
+        if let Some(partner_account) = &ctx.accounts.partner_underlying_account {
+            validate_fee_recipient(
+                partner_account,
+                &partner_wallet,
+                &rift.underlying_mint,
+                ErrorCode::InvalidPartnerVault,
+            )?;
+        }
+
+        let rift_key = rift.key();
+        let vault_auth_bump = [ctx.bumps.vault_authority];
+        let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
+        let signer = &[vault_auth_seeds];
+        let decimals = ctx.accounts.underlying_mint.decimals;
+
+        let (protocol_skim_amount, protocol_amount, partner_amount, treasury_amount) =
+            distribute_wrap_fees_vault(
+                rift,
+                balance,
+                rift.protocol_fees_owed,
+                wrap_fees_vault,
+                ctx.accounts.underlying_mint.to_account_info(),
+                decimals,
+                ctx.accounts.underlying_token_program.to_account_info(),
+                ctx.accounts.vault_authority.to_account_info(),
+                signer,
+                ctx.accounts
+                    .protocol_treasury_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                ctx.accounts.treasury_underlying_account.to_account_info(),
+                ctx.accounts
+                    .partner_underlying_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                ctx.accounts
+                    .protocol_underlying_account
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                &ctx.accounts.global_config.to_account_info(),
+            )?;
+        let claimed = balance
+            .checked_sub(protocol_skim_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let rift = &mut ctx.accounts.rift;
+        rift.protocol_fees_owed = rift
+            .protocol_fees_owed
+            .checked_sub(protocol_skim_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(WrapFeesClaimed {
+            rift: rift.key(),
+            amount: claimed,
+            protocol_skim_amount,
+            protocol_amount,
+            partner_amount,
+            treasury_amount,
+            claimer: ctx.accounts.creator.key(),
+        });
+
+        Ok(())
+    }
+
     /// Initialize withheld vault for collecting SPL Token-2022 withheld transfer fees (RIFT tokens)
     /// Must be called after rift creation to enable withheld fee collection
     /// **FIX CRITICAL #20**: Manual initialization to properly size for Token-2022 extensions
@@ -1734,12 +2221,17 @@ pub mod rifts_protocol {
 
         // **FIX CRITICAL #35**: Only creator or program authority can initialize withheld vault
         // Prevents front-running attacks where attacker creates vault with wrong owner/space
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
             ctx.accounts.user.key() == rift.creator || ctx.accounts.user.key() == program_authority,
             ErrorCode::Unauthorized
         );
 
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no transfer fee / withheld fees
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
         // **FIX CRITICAL #35**: Validate token_program is Token-2022 (RIFT mint is always Token-2022)
         // Prevents creating vault with foreign program owner that can't be reinitialized
         require!(
@@ -1751,47 +2243,10 @@ pub mod rifts_protocol {
 
         // **FIX MEDIUM-HIGH #26**: Calculate proper space by reading RIFT mint's actual extensions
         // Note: RIFT mint is always Token-2022, but may have additional extensions beyond TransferFeeConfig
-        let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
-        let mint_data = rift_mint_info.try_borrow_data()?;
-        let mint_account = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
-
-        // Get list of extensions this mint has
-        let mint_extensions = mint_account.get_extension_types()?;
-
-        // Build list of required ACCOUNT extensions based on MINT extensions
-        let mut account_extensions = Vec::new();
-
-        for ext_type in mint_extensions.iter() {
-            match ext_type {
-                ExtensionType::TransferFeeConfig => {
-                    // RIFT mint has transfer fees → account needs TransferFeeAmount
-                    account_extensions.push(ExtensionType::TransferFeeAmount);
-                }
-                ExtensionType::MemoTransfer => {
-                    account_extensions.push(ExtensionType::MemoTransfer);
-                }
-                ExtensionType::NonTransferable => {
-                    account_extensions.push(ExtensionType::NonTransferable);
-                }
-                ExtensionType::ImmutableOwner => {
-                    account_extensions.push(ExtensionType::ImmutableOwner);
-                }
-                ExtensionType::CpiGuard => {
-                    account_extensions.push(ExtensionType::CpiGuard);
-                }
-                _ => {
-                    // Other mint extensions don't require corresponding account extensions
-                }
-            }
-        }
-
-        drop(mint_data); // Release borrow before CPI
-
-        // Calculate space with ALL required extensions
-        let withheld_vault_space = ExtensionType::try_calculate_account_len::<
-            spl_token_2022::state::Account,
-        >(&account_extensions)
-        .map_err(|_| ErrorCode::InvalidMint)?;
+        let withheld_vault_space = compute_vault_account_space(
+            &ctx.accounts.rift_mint.to_account_info(),
+            &spl_token_2022::ID,
+        )?;
 
         let withheld_vault_rent = Rent::get()?.minimum_balance(withheld_vault_space);
 
@@ -1804,6 +2259,19 @@ pub mod rifts_protocol {
             ErrorCode::InvalidPDA
         );
 
+        // **IDEMPOTENT INIT**: Vault already created (e.g. by the atomic creation path) - repoint only
+        if existing_vault_matches(
+            &ctx.accounts.withheld_vault.to_account_info(),
+            &spl_token_2022::ID,
+            &ctx.accounts.rift_mint.key(),
+            &ctx.accounts.vault_authority.key(),
+            ErrorCode::InvalidWithheldVault,
+        )? {
+            rift.withheld_vault = withheld_vault_key;
+            msg!("✅ Withheld vault already initialized for rift: {}", rift.key());
+            return Ok(());
+        }
+
         // **FIX CRITICAL #25**: Use invoke_signed so PDA can sign account creation
         let rift_key = rift.key();
         let withheld_vault_seeds = &[
@@ -1862,609 +2330,368 @@ pub mod rifts_protocol {
     }
 
     /// Simple vault-based wrap - deposits underlying tokens and mints RIFT tokens
-    pub fn wrap_tokens(ctx: Context<WrapTokens>, amount: u64, min_rift_out: u64) -> Result<()> {
-        // **CRITICAL FIX #2 + FIX HIGH #1 + FIX ISSUE #7**: Add reentrancy protection with auto-timeout
-        {
-            let rift = &mut ctx.accounts.rift;
-
-            // **FIX ISSUE #7**: Auto-clear stuck guard after timeout
-            if rift.reentrancy_guard {
-                let current_slot = Clock::get()?.slot;
-                if current_slot > rift.reentrancy_guard_slot + REENTRANCY_TIMEOUT_SLOTS {
-                    msg!(
-                        "⚠️ Auto-clearing stuck reentrancy guard (set at slot {}, current {})",
-                        rift.reentrancy_guard_slot,
-                        current_slot
-                    );
-                    rift.reentrancy_guard = false;
-                    rift.reentrancy_guard_slot = 0;
-                } else {
-                    return Err(ErrorCode::ReentrancyDetected.into());
-                }
-            }
-
-            rift.reentrancy_guard = true;
-            rift.reentrancy_guard_slot = Clock::get()?.slot;
-        }
-
-        // Execute the actual function logic
-        let execution_result = (|| -> Result<()> {
-            let rift = &mut ctx.accounts.rift;
-
-            // **FIX ISSUE #8**: Verify rift is not closed
-            require!(!rift.is_closed, ErrorCode::RiftClosed);
-
-            // Basic validation
-            require!(amount > 0, ErrorCode::InvalidAmount);
-
-            // **CRITICAL FIX #3**: Manual token account validation - MUST validate, not skip
-            // **FIX CRITICAL #27**: Validate accounts against their respective token programs
-            {
-                // Validate underlying token account (can be SPL Token or Token-2022)
-                require!(
-                    *ctx.accounts.user_underlying.owner
-                        == ctx.accounts.underlying_token_program.key(),
-                    ErrorCode::InvalidTokenAccount
-                );
-                let underlying_data = ctx.accounts.user_underlying.try_borrow_data()?;
-                require!(underlying_data.len() >= 64, ErrorCode::InvalidTokenAccount);
-                // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
-                let underlying_mint = Pubkey::new_from_array(
-                    underlying_data[0..32]
-                        .try_into()
-                        .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-                );
-                let underlying_owner = Pubkey::new_from_array(
-                    underlying_data[32..64]
-                        .try_into()
-                        .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-                );
-                require!(
-                    underlying_mint == rift.underlying_mint,
-                    ErrorCode::InvalidMint
-                );
-                require!(
-                    underlying_owner == ctx.accounts.user.key(),
-                    ErrorCode::UnauthorizedTokenAccount
-                );
-
-                // Validate rift token account (always Token-2022)
-                require!(
-                    *ctx.accounts.user_rift_tokens.owner == spl_token_2022::ID,
-                    ErrorCode::InvalidTokenAccount
-                );
-                let rift_data = ctx.accounts.user_rift_tokens.try_borrow_data()?;
-                require!(rift_data.len() >= 64, ErrorCode::InvalidTokenAccount);
-                // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
-                let rift_mint_check = Pubkey::new_from_array(
-                    rift_data[0..32]
-                        .try_into()
-                        .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-                );
-                let rift_owner = Pubkey::new_from_array(
-                    rift_data[32..64]
-                        .try_into()
-                        .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-                );
-                require!(rift_mint_check == rift.rift_mint, ErrorCode::InvalidMint);
-                require!(
-                    rift_owner == ctx.accounts.user.key(),
-                    ErrorCode::UnauthorizedTokenAccount
-                );
-            }
+    /// `deadline_unix`: reject if executed after this unix timestamp (0 = no deadline)
+    /// **COMPOSABILITY**: Sets return data to the post-fee net RIFT minted (u64 LE), readable
+    /// by a calling program via `get_return_data` right after the CPI
+    pub fn wrap_tokens(
+        ctx: Context<WrapTokens>,
+        amount: u64,
+        min_rift_out: u64,
+        deadline_unix: i64,
+        create_rift_ata: bool, // Opt-in: create the user's RIFT ATA if it doesn't exist yet
+    ) -> Result<()> {
+        let rift_minted =
+            process_wrap(ctx, amount, min_rift_out, None, deadline_unix, create_rift_ata)?;
+        set_wrap_return_data(rift_minted);
+        Ok(())
+    }
 
-            // **HIGH FIX #5**: Validate amount bounds BEFORE fee calculation to prevent edge case overflows
-            let fee_multiplier = u64::from(rift.wrap_fee_bps);
-            require!(
-                amount <= u64::MAX / fee_multiplier.max(1),
-                ErrorCode::AmountTooLarge
-            );
+    /// Wrap native SOL into a wSOL rift - funds the user's wSOL ATA with `lamports`, syncs it,
+    /// runs the standard wrap flow, then closes the ATA back to SOL if it was created here
+    /// A pre-existing wSOL ATA is left open (its prior balance is untouched)
+    pub fn wrap_sol(
+        ctx: Context<WrapTokens>,
+        lamports: u64,
+        min_rift_out: u64,
+        deadline_unix: i64,
+        create_rift_ata: bool, // Opt-in: create the user's RIFT ATA if it doesn't exist yet
+    ) -> Result<()> {
+        require!(lamports > 0, ErrorCode::InvalidAmount);
+        // Only rifts over the canonical wSOL mint (classic SPL Token) accept native SOL
+        require!(
+            ctx.accounts.rift.underlying_mint == spl_token::native_mint::ID,
+            ErrorCode::NotNativeSolRift
+        );
+        require!(
+            ctx.accounts.underlying_token_program.key() == anchor_spl::token::ID,
+            ErrorCode::InvalidProgramId
+        );
 
-            // **CRITICAL FIX - HIGH ISSUE #2**: Check vault balance BEFORE transfer to detect underlying transfer fees
-            let vault_balance_before = ctx.accounts.vault.amount;
+        let expected_wsol_ata = anchor_spl::associated_token::get_associated_token_address(
+            &ctx.accounts.user.key(),
+            &spl_token::native_mint::ID,
+        );
+        require!(
+            ctx.accounts.user_underlying.key() == expected_wsol_ata,
+            ErrorCode::InvalidTokenAccount
+        );
 
-            // **TOKEN-2022 FIX**: Read underlying mint decimals for transfer_checked
-            let underlying_mint_data = ctx.accounts.underlying_mint.try_borrow_data()?;
-            require!(underlying_mint_data.len() >= 45, ErrorCode::InvalidMint);
-            let underlying_decimals = underlying_mint_data[44]; // decimals at offset 44
-            drop(underlying_mint_data);
+        // Temporary wSOL account: only created (and later closed) when the user has none
+        let created_wsol_ata = ctx.accounts.user_underlying.data_is_empty();
+        if created_wsol_ata {
+            let associated_token_program = ctx
+                .accounts
+                .associated_token_program
+                .as_ref()
+                .ok_or(ErrorCode::MissingAssociatedTokenProgram)?;
 
-            // **FIX CRITICAL #27**: Transfer underlying tokens using underlying_token_program
-            // **TOKEN-2022 FIX**: Use transfer_checked instead of transfer for Token-2022 compatibility
-            let transfer_ctx = CpiContext::new(
-                ctx.accounts.underlying_token_program.to_account_info(),
-                TransferChecked {
-                    from: ctx.accounts.user_underlying.to_account_info(),
-                    to: ctx.accounts.vault.to_account_info(),
+            anchor_spl::associated_token::create(CpiContext::new(
+                associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: ctx.accounts.user.to_account_info(),
+                    associated_token: ctx.accounts.user_underlying.to_account_info(),
                     authority: ctx.accounts.user.to_account_info(),
                     mint: ctx.accounts.underlying_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.underlying_token_program.to_account_info(),
                 },
-            );
-            interface_transfer_checked(transfer_ctx, amount, underlying_decimals)?;
-
-            // **CRITICAL FIX - HIGH ISSUE #2**: Reload vault to get actual amount received (after transfer fees)
-            ctx.accounts.vault.reload()?;
-            let vault_balance_after = ctx.accounts.vault.amount;
-            let actual_received = vault_balance_after
-                .checked_sub(vault_balance_before)
-                .ok_or(ErrorCode::MathOverflow)?;
-
-            msg!(
-                "Requested: {}, Actually received in vault: {}",
-                amount,
-                actual_received
-            );
+            ))?;
+        }
 
-            // **CRITICAL FIX - HIGH ISSUE #2**: Calculate wrap fee based on ACTUAL amount received, not requested
-            let wrap_fee = actual_received
-                .checked_mul(fee_multiplier)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(10000)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let amount_after_fee = actual_received
-                .checked_sub(wrap_fee)
-                .ok_or(ErrorCode::MathOverflow)?;
-
-            // **MEDIUM FIX #3**: Slippage protection - ensure user receives at least minimum expected RIFT
-            // Protects against fee-on-transfer tokens and extreme slippage
-            require!(
-                amount_after_fee >= min_rift_out,
-                ErrorCode::SlippageExceeded
-            );
-            msg!(
-                "✅ Slippage check passed: minting {} >= minimum {}",
-                amount_after_fee,
-                min_rift_out
-            );
-
-            let rift_key = rift.key();
-
-            // **FEE ROUTING**: Transfer wrap fee from vault to fees_vault (only if fees_vault is initialized)
-            // **FIX MEDIUM #5 (Audit)**: Measure actual credited amount for transfer-fee underlyings
-            let actual_fee_credited: u64;
-            if wrap_fee > 0 && rift.fees_vault != anchor_lang::solana_program::system_program::ID {
-                // **FIX MEDIUM #23**: Verify fees_vault is actually a valid token account before transferring
-                let fees_vault_info = ctx.accounts.fees_vault.to_account_info();
-                require!(
-                    fees_vault_info.owner == ctx.accounts.underlying_token_program.key,
-                    ErrorCode::InvalidFeesVault
-                );
-                require!(
-                    fees_vault_info.data_len() >= 165, // Minimum token account size
-                    ErrorCode::InvalidFeesVault
-                );
-
-                // **FIX MEDIUM #5 (Audit)**: Get pre-transfer balance
-                let fees_vault_balance_before = ctx.accounts.fees_vault.amount;
-
-                let vault_auth_bump = [ctx.bumps.vault_authority];
-                let vault_auth_seeds: &[&[u8]] =
-                    &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
-                let vault_auth_signer = &[&vault_auth_seeds[..]];
-
-                let fee_transfer_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.underlying_token_program.to_account_info(),
-                    TransferChecked {
-                        from: ctx.accounts.vault.to_account_info(),
-                        to: ctx.accounts.fees_vault.to_account_info(),
-                        authority: ctx.accounts.vault_authority.to_account_info(),
-                        mint: ctx.accounts.underlying_mint.to_account_info(),
-                    },
-                    vault_auth_signer,
-                );
-                interface_transfer_checked(fee_transfer_ctx, wrap_fee, underlying_decimals)?;
-
-                // **FIX MEDIUM #5 (Audit)**: Measure actual credited amount
-                ctx.accounts.fees_vault.reload()?;
-                let fees_vault_balance_after = ctx.accounts.fees_vault.amount;
-                actual_fee_credited = fees_vault_balance_after
-                    .checked_sub(fees_vault_balance_before)
-                    .ok_or(ErrorCode::MathOverflow)?;
-
-                if actual_fee_credited != wrap_fee {
-                    msg!("⚠️ Transfer fee detected: sent {}, credited {}", wrap_fee, actual_fee_credited);
-                }
-                msg!("Wrap fee {} transferred to fees_vault (credited: {})", wrap_fee, actual_fee_credited);
-            } else if wrap_fee > 0 {
-                actual_fee_credited = wrap_fee; // Fee kept in vault, accounted at full value
-                msg!(
-                    "Wrap fee {} kept in vault (fees_vault not initialized)",
-                    wrap_fee
-                );
-            } else {
-                actual_fee_credited = 0;
-            }
-
-            // Mint RIFT tokens to user
-            let bump_seed = [ctx.bumps.rift_mint_authority];
-            let signer_seeds: &[&[u8]] = &[b"rift_mint_auth", rift_key.as_ref(), &bump_seed];
-            let signer = &[&signer_seeds[..]];
-
-            // **FIX CRITICAL #27**: Mint RIFT tokens using rift_token_program (always Token-2022)
-            let mint_ctx = CpiContext::new_with_signer(
-                ctx.accounts.rift_token_program.to_account_info(),
-                token_interface::MintTo {
-                    mint: ctx.accounts.rift_mint.to_account_info(),
-                    to: ctx.accounts.user_rift_tokens.to_account_info(),
-                    authority: ctx.accounts.rift_mint_authority.to_account_info(),
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: ctx.accounts.user_underlying.to_account_info(),
                 },
-                signer,
-            );
-            interface_mint_to(mint_ctx, amount_after_fee)?;
-
-            // Update rift state
-            rift.total_underlying_wrapped = rift
-                .total_underlying_wrapped
-                .checked_add(amount_after_fee)
-                .ok_or(ErrorCode::MathOverflow)?;
-            rift.total_rift_minted = rift
-                .total_rift_minted
-                .checked_add(amount_after_fee)
-                .ok_or(ErrorCode::MathOverflow)?;
-
-            // **FEE ACCOUNTING FIX**: Track wrap fees in total_fees_collected (same as unwrap)
-            // **FIX MEDIUM #5 (Audit)**: Use actual_fee_credited to account for transfer fees
-            if actual_fee_credited > 0 {
-                rift.total_fees_collected = rift
-                    .total_fees_collected
-                    .checked_add(actual_fee_credited)
-                    .ok_or(ErrorCode::MathOverflow)?;
-            }
-
-            msg!(
-                "✅ Wrapped {} tokens → {} RIFT (fee: {})",
-                amount,
-                amount_after_fee,
-                wrap_fee
-            );
-
-            Ok(())
-        })();
-
-        // **FIX HIGH #1 + FIX ISSUE #7**: Always clear guard and slot, even on error
-        ctx.accounts.rift.reentrancy_guard = false;
-        ctx.accounts.rift.reentrancy_guard_slot = 0;
-
-        execution_result
-    }
-
-    /// Simple vault-based unwrap - burns RIFT and returns underlying from vault
-    pub fn unwrap_from_vault(ctx: Context<UnwrapFromVault>, rift_token_amount: u64, min_underlying_out: u64) -> Result<()> {
-        // **CRITICAL FIX + FIX HIGH #1 + FIX ISSUE #7**: Add reentrancy protection with auto-timeout
-        {
-            let rift = &mut ctx.accounts.rift;
-
-            // **FIX ISSUE #7**: Auto-clear stuck guard after timeout
-            if rift.reentrancy_guard {
-                let current_slot = Clock::get()?.slot;
-                if current_slot > rift.reentrancy_guard_slot + REENTRANCY_TIMEOUT_SLOTS {
-                    msg!(
-                        "⚠️ Auto-clearing stuck reentrancy guard (set at slot {}, current {})",
-                        rift.reentrancy_guard_slot,
-                        current_slot
-                    );
-                    rift.reentrancy_guard = false;
-                    rift.reentrancy_guard_slot = 0;
-                } else {
-                    return Err(ErrorCode::ReentrancyDetected.into());
-                }
-            }
-
-            rift.reentrancy_guard = true;
-            rift.reentrancy_guard_slot = Clock::get()?.slot;
+            ),
+            lamports,
+        )?;
+        token_interface::sync_native(CpiContext::new(
+            ctx.accounts.underlying_token_program.to_account_info(),
+            token_interface::SyncNative {
+                account: ctx.accounts.user_underlying.to_account_info(),
+            },
+        ))?;
+
+        // process_wrap consumes the context; keep what the close needs
+        let user_info = ctx.accounts.user.to_account_info();
+        let wsol_info = ctx.accounts.user_underlying.to_account_info();
+        let token_program_info = ctx.accounts.underlying_token_program.to_account_info();
+
+        let rift_minted =
+            process_wrap(ctx, lamports, min_rift_out, None, deadline_unix, create_rift_ata)?;
+
+        // Any failure above reverts the whole transaction, including the temporary account
+        if created_wsol_ata {
+            token_interface::close_account(CpiContext::new(
+                token_program_info,
+                token_interface::CloseAccount {
+                    account: wsol_info,
+                    destination: user_info.clone(),
+                    authority: user_info,
+                },
+            ))?;
+            msg!("✅ Closed temporary wSOL account {}", expected_wsol_ata);
         }
 
-        // Execute the actual function logic
-        let execution_result = (|| -> Result<()> {
-            let rift = &mut ctx.accounts.rift;
-
-            // **FIX ISSUE #8**: Verify rift is not closed
-            require!(!rift.is_closed, ErrorCode::RiftClosed);
-
-            // Validate amount
-            require!(rift_token_amount > 0, ErrorCode::InvalidAmount);
-
-            // **SECURITY FIX #49**: Manual token account validation (stack optimization)
-            // **FIX CRITICAL #27**: Validate accounts against their respective token programs
-            {
-                // Validate underlying token account (can be SPL Token or Token-2022)
-                require!(
-                    *ctx.accounts.user_underlying.owner
-                        == ctx.accounts.underlying_token_program.key(),
-                    ErrorCode::InvalidTokenAccount
-                );
-                let underlying_data = ctx.accounts.user_underlying.try_borrow_data()?;
-                require!(underlying_data.len() >= 64, ErrorCode::InvalidTokenAccount);
-                // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
-                let underlying_mint = Pubkey::new_from_array(
-                    underlying_data[0..32]
-                        .try_into()
-                        .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-                );
-                let underlying_owner = Pubkey::new_from_array(
-                    underlying_data[32..64]
-                        .try_into()
-                        .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-                );
-                require!(
-                    underlying_mint == rift.underlying_mint,
-                    ErrorCode::InvalidMint
-                );
-                require!(
-                    underlying_owner == ctx.accounts.user.key(),
-                    ErrorCode::UnauthorizedTokenAccount
-                );
-
-                // Validate rift token account (always Token-2022)
-                require!(
-                    *ctx.accounts.user_rift_tokens.owner == spl_token_2022::ID,
-                    ErrorCode::InvalidTokenAccount
-                );
-                let rift_data = ctx.accounts.user_rift_tokens.try_borrow_data()?;
-                require!(rift_data.len() >= 64, ErrorCode::InvalidTokenAccount);
-                // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
-                let rift_mint_check = Pubkey::new_from_array(
-                    rift_data[0..32]
-                        .try_into()
-                        .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-                );
-                let rift_owner = Pubkey::new_from_array(
-                    rift_data[32..64]
-                        .try_into()
-                        .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-                );
-                require!(rift_mint_check == rift.rift_mint, ErrorCode::InvalidMint);
-                require!(
-                    rift_owner == ctx.accounts.user.key(),
-                    ErrorCode::UnauthorizedTokenAccount
-                );
-            }
-
-            // **HIGH FIX #5**: Validate amount bounds BEFORE fee calculation
-            let fee_multiplier = u64::from(rift.unwrap_fee_bps);
-            require!(
-                rift_token_amount <= u64::MAX / fee_multiplier.max(1),
-                ErrorCode::AmountTooLarge
-            );
+        // After the close CPI, which would otherwise clear it
+        set_wrap_return_data(rift_minted);
+        Ok(())
+    }
 
-            // **MEDIUM FIX #11**: Use configurable unwrap fee - safe now due to bounds check above
-            let unwrap_fee = rift_token_amount
-                .checked_mul(fee_multiplier)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(10000)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let amount_after_fee = rift_token_amount
-                .checked_sub(unwrap_fee)
-                .ok_or(ErrorCode::MathOverflow)?;
+    /// Exact-out wrap - deposits up to `max_underlying_in` and mints exactly `rift_out` RIFT
+    /// The required input is back-computed from the wrap fee and the underlying's transfer fee;
+    /// any rounding surplus stays in the vault as backing (nothing is refunded)
+    pub fn wrap_tokens_exact_out(
+        ctx: Context<WrapTokens>,
+        rift_out: u64,
+        max_underlying_in: u64,
+        deadline_unix: i64,
+        create_rift_ata: bool, // Opt-in: create the user's RIFT ATA if it doesn't exist yet
+    ) -> Result<()> {
+        require!(rift_out > 0, ErrorCode::InvalidAmount);
+        let rift_minted = process_wrap(
+            ctx,
+            max_underlying_in,
+            rift_out,
+            Some(rift_out),
+            deadline_unix,
+            create_rift_ata,
+        )?;
+        set_wrap_return_data(rift_minted);
+        Ok(())
+    }
 
-            msg!(
-                "💰 Unwrapping {} RIFT from vault (fee: {}, net: {})",
-                rift_token_amount,
-                unwrap_fee,
-                amount_after_fee
-            );
+    /// Wrap, then `transfer_checked` the freshly minted RIFT from the user's RIFT account to
+    /// `remaining_accounts[0]` (e.g. an AMM pool token account) in the same transaction
+    /// **FEE IMPLICATION**: unlike a direct mint, the RIFT moves user → destination as a normal
+    /// transfer, so the RIFT transfer fee is withheld on the destination - it receives less than
+    /// the amount minted. `min_destination_received` bounds that post-fee amount.
+    /// **COMPOSABILITY**: Return data is the net RIFT received by the destination (u64 LE)
+    pub fn wrap_and_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, WrapTokens<'info>>,
+        amount: u64,
+        min_rift_out: u64,
+        min_destination_received: u64,
+        deadline_unix: i64,
+        create_rift_ata: bool, // Opt-in: create the user's RIFT ATA if it doesn't exist yet
+    ) -> Result<()> {
+        let destination_info = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(ErrorCode::InvalidTokenAccount)?
+            .clone();
 
-            // **HIGH FIX #10**: Verify vault has sufficient balance BEFORE burning user's tokens
-            // This prevents user losing RIFT tokens if vault is drained
-            // **CRITICAL FIX - HIGH ISSUE #3**: Use .amount from InterfaceAccount instead of manual parsing
-            let vault_balance = ctx.accounts.vault.amount;
+        // Destination must be a RIFT account of this rift's mint, other than the user's own
+        require!(
+            destination_info.key() != ctx.accounts.user_rift_tokens.key(),
+            ErrorCode::InvalidTokenAccount
+        );
+        require!(
+            *destination_info.owner == ctx.accounts.rift_token_program.key(),
+            ErrorCode::InvalidTokenAccount
+        );
+        let destination_balance_before = {
+            let destination_data = destination_info.try_borrow_data()?;
+            let destination_account =
+                StateWithExtensions::<spl_token_2022::state::Account>::unpack(&destination_data)
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?;
             require!(
-                vault_balance >= amount_after_fee,
-                ErrorCode::InsufficientFunds
-            );
-
-            // **FIX CRITICAL #27**: Burn RIFT tokens using rift_token_program (always Token-2022)
-            let burn_ctx = CpiContext::new(
-                ctx.accounts.rift_token_program.to_account_info(),
-                anchor_spl::token_interface::Burn {
-                    mint: ctx.accounts.rift_mint.to_account_info(),
-                    from: ctx.accounts.user_rift_tokens.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
-                },
+                destination_account.base.mint == ctx.accounts.rift.rift_mint,
+                ErrorCode::InvalidMint
             );
-            // **TOKEN-2022 MIGRATION**: Burn is FREE - no transfer fee on burns!
-            interface_burn(burn_ctx, rift_token_amount)?;
-
-            msg!("✅ Burned {} RIFT tokens", rift_token_amount);
-
-            // Transfer underlying tokens from vault to user
-            // Use vault_authority (the vault owner) to sign the transfer
-            let rift_key = rift.key();
-            let bump_seed = [ctx.bumps.vault_authority];
-            let signer_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &bump_seed];
-            let signer = &[&signer_seeds[..]];
-
-            // **TOKEN-2022 FIX**: Read underlying mint decimals for transfer_checked
-            let underlying_mint_data = ctx.accounts.underlying_mint.try_borrow_data()?;
-            require!(underlying_mint_data.len() >= 45, ErrorCode::InvalidMint);
-            let underlying_decimals = underlying_mint_data[44]; // decimals at offset 44
-            drop(underlying_mint_data);
-
-            // **FEE ROUTING**: Transfer unwrap fee from vault to fees_vault FIRST (only if fees_vault is initialized)
-            if unwrap_fee > 0 && rift.fees_vault != anchor_lang::solana_program::system_program::ID
-            {
-                // **FIX MEDIUM #23**: Verify fees_vault is actually a valid token account before transferring
-                // **FIX CRITICAL #27**: fees_vault holds underlying tokens, validate against underlying_token_program
-                let fees_vault_info = ctx.accounts.fees_vault.to_account_info();
-                require!(
-                    fees_vault_info.owner == ctx.accounts.underlying_token_program.key,
-                    ErrorCode::InvalidFeesVault
-                );
-                require!(
-                    fees_vault_info.data_len() >= 165, // Minimum token account size
-                    ErrorCode::InvalidFeesVault
-                );
+            destination_account.base.amount
+        };
 
-                let fee_transfer_ctx = CpiContext::new_with_signer(
-                    ctx.accounts.underlying_token_program.to_account_info(),
-                    TransferChecked {
-                        from: ctx.accounts.vault.to_account_info(),
-                        to: ctx.accounts.fees_vault.to_account_info(),
-                        authority: ctx.accounts.vault_authority.to_account_info(),
-                        mint: ctx.accounts.underlying_mint.to_account_info(),
-                    },
-                    signer,
-                );
-                interface_transfer_checked(fee_transfer_ctx, unwrap_fee, underlying_decimals)?;
-                msg!("Unwrap fee {} transferred to fees_vault", unwrap_fee);
-            } else if unwrap_fee > 0 {
-                msg!(
-                    "Unwrap fee {} kept in vault (fees_vault not initialized)",
-                    unwrap_fee
-                );
-            }
+        let rift_mint_decimals = {
+            let rift_mint_data = ctx.accounts.rift_mint.try_borrow_data()?;
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)
+                .map_err(|_| ErrorCode::InvalidMint)?
+                .base
+                .decimals
+        };
 
-            // **CRITICAL FIX - HIGH ISSUE #2**: Check vault balance BEFORE transfer
-            let vault_balance_before = ctx.accounts.vault.amount;
+        // process_wrap consumes the context; keep what the transfer needs
+        let rift_key = ctx.accounts.rift.key();
+        let user_info = ctx.accounts.user.to_account_info();
+        let user_rift_info = ctx.accounts.user_rift_tokens.to_account_info();
+        let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
+        let rift_token_program_info = ctx.accounts.rift_token_program.to_account_info();
 
-            // **FIX CRITICAL #13**: Parse user DESTINATION balance before transfer (manual parsing for UncheckedAccount)
-            let user_data_before = ctx.accounts.user_underlying.try_borrow_data()?;
-            require!(user_data_before.len() >= 72, ErrorCode::InvalidTokenAccount);
-            // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
-            let user_balance_before = u64::from_le_bytes(
-                user_data_before[64..72]
-                    .try_into()
-                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-            );
-            drop(user_data_before); // Release borrow before CPI
-            msg!(
-                "📊 User underlying balance before transfer: {}",
-                user_balance_before
-            );
+        let rift_minted =
+            process_wrap(ctx, amount, min_rift_out, None, deadline_unix, create_rift_ata)?;
 
-            // **FIX CRITICAL #27**: Transfer underlying tokens using underlying_token_program
-            // **TOKEN-2022 FIX**: Use transfer_checked for Token-2022 compatibility
-            let transfer_ctx = CpiContext::new_with_signer(
-                ctx.accounts.underlying_token_program.to_account_info(),
+        interface_transfer_checked(
+            CpiContext::new(
+                rift_token_program_info,
                 TransferChecked {
-                    from: ctx.accounts.vault.to_account_info(),
-                    to: ctx.accounts.user_underlying.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                    mint: ctx.accounts.underlying_mint.to_account_info(),
+                    from: user_rift_info,
+                    to: destination_info.clone(),
+                    authority: user_info.clone(),
+                    mint: rift_mint_info,
                 },
-                signer,
-            );
-            interface_transfer_checked(transfer_ctx, amount_after_fee, underlying_decimals)?;
-
-            // **CRITICAL FIX - HIGH ISSUE #2**: Reload vault to verify actual amount sent (if underlying has transfer fees)
-            ctx.accounts.vault.reload()?;
-            let vault_balance_after = ctx.accounts.vault.amount;
-            let actual_sent = vault_balance_before
-                .checked_sub(vault_balance_after)
-                .ok_or(ErrorCode::MathOverflow)?;
-
-            // **FIX CRITICAL #13**: Parse user DESTINATION balance after transfer to detect destination-side transfer fees
-            let user_data_after = ctx.accounts.user_underlying.try_borrow_data()?;
-            require!(user_data_after.len() >= 72, ErrorCode::InvalidTokenAccount);
-            // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
-            let user_balance_after = u64::from_le_bytes(
-                user_data_after[64..72]
-                    .try_into()
-                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
-            );
-            drop(user_data_after); // Release borrow
-
-            let actual_received = user_balance_after
-                .checked_sub(user_balance_before)
-                .ok_or(ErrorCode::MathOverflow)?;
-
-            msg!("✅ Transferred {} underlying tokens from vault (actually sent: {}, actually received: {})",
-            amount_after_fee, actual_sent, actual_received);
+            ),
+            rift_minted,
+            rift_mint_decimals,
+        )?;
 
-            // **FIX CRITICAL #13**: Detect destination-side transfer fees
-            if actual_received < actual_sent {
-                let destination_fee = actual_sent.saturating_sub(actual_received);
-                let fee_percentage = (destination_fee as f64 / actual_sent as f64) * 100.0;
-                msg!("⚠️ DESTINATION-SIDE TRANSFER FEE DETECTED!");
-                msg!(
-                    "⚠️ Vault sent: {}, User received: {}",
-                    actual_sent,
-                    actual_received
-                );
-                msg!(
-                    "⚠️ Destination fee: {} ({:.4}%)",
-                    destination_fee,
-                    fee_percentage
-                );
+        let destination_received = {
+            let destination_data = destination_info.try_borrow_data()?;
+            StateWithExtensions::<spl_token_2022::state::Account>::unpack(&destination_data)
+                .map_err(|_| ErrorCode::InvalidTokenAccount)?
+                .base
+                .amount
+                .checked_sub(destination_balance_before)
+                .ok_or(ErrorCode::MathOverflow)?
+        };
+        require!(
+            destination_received >= min_destination_received,
+            ErrorCode::SlippageExceeded
+        );
 
-                // NOTE: Transfer fee limit removed - users are informed via UI warnings instead
-                msg!("⚠️ Destination fee accepted: {:.4}%", fee_percentage);
-            }
+        emit!(WrapAndTransferExecuted {
+            rift: rift_key,
+            user: user_info.key(),
+            destination: destination_info.key(),
+            rift_minted,
+            destination_received,
+        });
 
-            // **CRITICAL FIX #2**: Slippage protection - ensure user received at least expected amount
-            // Protects against fee-on-transfer tokens and deflationary tokens
-            require!(actual_sent >= amount_after_fee, ErrorCode::SlippageExceeded);
-            msg!(
-                "✅ Slippage check passed: sent {} >= expected {}",
-                actual_sent,
-                amount_after_fee
-            );
+        msg!(
+            "✅ Wrapped {} RIFT and transferred to {} ({} received after transfer fee)",
+            rift_minted,
+            destination_info.key(),
+            destination_received
+        );
 
-            // User-provided slippage protection on RECEIVED amount
-            require!(
-                actual_received >= min_underlying_out,
-                ErrorCode::SlippageExceeded
-            );
-            msg!(
-                "✅ User slippage check passed: received {} >= min_out {}",
-                actual_received,
-                min_underlying_out
-            );
+        // After the transfer CPI, which would otherwise clear it
+        set_wrap_return_data(destination_received);
+        Ok(())
+    }
 
-            // **CRITICAL FIX - HIGH ISSUE #2**: Update accounting based on ACTUAL amount sent, not requested
-            rift.total_underlying_wrapped = rift
-                .total_underlying_wrapped
-                .checked_sub(actual_sent)
-                .ok_or(ErrorCode::MathOverflow)?;
-            rift.total_rift_minted = rift
-                .total_rift_minted
-                .checked_sub(rift_token_amount)
-                .ok_or(ErrorCode::MathOverflow)?;
-            rift.total_burned = rift
-                .total_burned
-                .checked_add(rift_token_amount)
-                .ok_or(ErrorCode::MathOverflow)?;
-            rift.total_fees_collected = rift
-                .total_fees_collected
-                .checked_add(unwrap_fee)
-                .ok_or(ErrorCode::MathOverflow)?;
+    /// Simple vault-based unwrap - burns RIFT and returns underlying from vault
+    /// `deadline_unix`: reject if executed after this unix timestamp (0 = no deadline)
+    /// `auto_distribute`: once fees_vault reaches rift.auto_distribute_threshold, also split the
+    /// whole fees_vault to protocol/partner/treasury (requires the optional fee recipient accounts)
+    pub fn unwrap_from_vault(
+        ctx: Context<UnwrapFromVault>,
+        rift_token_amount: u64,
+        min_underlying_out: u64,
+        deadline_unix: i64,
+        auto_distribute: bool,
+    ) -> Result<()> {
+        process_unwrap(
+            ctx,
+            rift_token_amount,
+            min_underlying_out,
+            deadline_unix,
+            false,
+            auto_distribute,
+        )
+    }
 
-            // Update volume
-            rift.total_volume_24h = rift
-                .total_volume_24h
-                .checked_add(amount_after_fee)
-                .ok_or(ErrorCode::MathOverflow)?;
+    /// Emergency unwrap for when the oracle is confirmed stale (newest sample older than
+    /// max_rebalance_interval): ignores oracle state, pays 1:1 capped by the live vault balance
+    /// and burns only the RIFT that was paid out, charges the static unwrap_fee_bps.
+    /// Skips the same-slot guard and drain limit, but still respects pause.
+    /// Unusable while the oracle is healthy or has never been sampled.
+    pub fn emergency_unwrap(
+        ctx: Context<UnwrapFromVault>,
+        rift_token_amount: u64,
+        min_underlying_out: u64,
+    ) -> Result<()> {
+        process_unwrap(ctx, rift_token_amount, min_underlying_out, 0, true, false)
+    }
 
-            // NOTE: Fee distribution happens via separate batch process to avoid stack overflow
-            // **FIX MEDIUM #15**: Do NOT update last_oracle_update on unwrap to prevent rebalance DoS
-            // last_oracle_update should only be updated when actual oracle price data is updated,
-            // not on every vault activity. This prevents users from delaying rebalances via unwrap spam.
+    /// Unwrap a wSOL rift straight to native SOL - receives the underlying into the user's wSOL
+    /// ATA (created here if missing), then closes that temporary ATA back to the user as lamports
+    /// A pre-existing wSOL ATA is left open and keeps the unwrapped wSOL
+    pub fn unwrap_sol(
+        ctx: Context<UnwrapFromVault>,
+        rift_token_amount: u64,
+        min_underlying_out: u64,
+        deadline_unix: i64,
+    ) -> Result<()> {
+        // Only rifts over the canonical wSOL mint (classic SPL Token) pay out native SOL
+        require!(
+            ctx.accounts.rift.underlying_mint == spl_token::native_mint::ID,
+            ErrorCode::NotNativeSolRift
+        );
+        require!(
+            ctx.accounts.underlying_token_program.key() == anchor_spl::token::ID,
+            ErrorCode::InvalidProgramId
+        );
+        // **REENTRANCY**: Refuse to start the sequence while another operation holds the guard;
+        // process_unwrap then holds it across burn + payout, and the ATA create/close below
+        // only touch the user's own wSOL account through the SPL programs
+        require!(
+            !ctx.accounts.rift.reentrancy_guard,
+            ErrorCode::ReentrancyDetected
+        );
 
-            emit!(UnwrapExecuted {
-                rift: rift.key(),
-                user: ctx.accounts.user.key(),
-                rift_token_amount,
-                fee_amount: unwrap_fee,
-                underlying_returned: amount_after_fee,
-            });
+        let expected_wsol_ata = anchor_spl::associated_token::get_associated_token_address(
+            &ctx.accounts.user.key(),
+            &spl_token::native_mint::ID,
+        );
+        require!(
+            ctx.accounts.user_underlying.key() == expected_wsol_ata,
+            ErrorCode::InvalidTokenAccount
+        );
 
-            msg!("✅ Unwrap from vault completed");
+        // Temporary wSOL account: only created (and later closed) when the user has none
+        let created_wsol_ata = ctx.accounts.user_underlying.data_is_empty();
+        if created_wsol_ata {
+            let associated_token_program = ctx
+                .accounts
+                .associated_token_program
+                .as_ref()
+                .ok_or(ErrorCode::MissingAssociatedTokenProgram)?;
 
-            Ok(())
-        })();
+            anchor_spl::associated_token::create(CpiContext::new(
+                associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: ctx.accounts.user.to_account_info(),
+                    associated_token: ctx.accounts.user_underlying.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                    mint: ctx.accounts.underlying_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.underlying_token_program.to_account_info(),
+                },
+            ))?;
+        }
 
-        // **FIX HIGH #1 + FIX ISSUE #7**: Always clear guard and slot, even on error
-        ctx.accounts.rift.reentrancy_guard = false;
-        ctx.accounts.rift.reentrancy_guard_slot = 0;
+        // process_unwrap consumes the context; keep what the close needs
+        let user_info = ctx.accounts.user.to_account_info();
+        let wsol_info = ctx.accounts.user_underlying.to_account_info();
+        let token_program_info = ctx.accounts.underlying_token_program.to_account_info();
+
+        process_unwrap(ctx, rift_token_amount, min_underlying_out, deadline_unix, false, false)?;
+
+        // Any failure above reverts the whole transaction, including the temporary account
+        if created_wsol_ata {
+            token_interface::close_account(CpiContext::new(
+                token_program_info,
+                token_interface::CloseAccount {
+                    account: wsol_info,
+                    destination: user_info.clone(),
+                    authority: user_info,
+                },
+            ))?;
+            msg!("✅ Closed temporary wSOL account {} to native SOL", expected_wsol_ata);
+        }
 
-        execution_result
+        Ok(())
     }
 
     /// Admin function: Fix vault ownership conflicts
     /// **SECURITY FIX #4**: Only PROGRAM_AUTHORITY can fix vault conflicts
     pub fn admin_fix_vault_conflict(ctx: Context<AdminFixVaultConflict>) -> Result<()> {
         // **SECURITY FIX #4**: Only PROGRAM_AUTHORITY can use this admin function
-        let admin_pubkey = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
             ctx.accounts.program_authority.key() == admin_pubkey,
             ErrorCode::UnauthorizedAdmin
@@ -2500,104 +2727,302 @@ pub mod rifts_protocol {
         Ok(())
     }
 
-    /// **SECURITY FIX #4**: Update Switchboard oracle using SDK (prevents byte offset errors)
-    /// Uses switchboard-on-demand SDK for validated price parsing
-    pub fn update_switchboard_oracle(ctx: Context<UpdateSwitchboardOracle>) -> Result<()> {
+    /// Creator: move oracle price reads to an OracleHistory PDA holding `capacity` samples
+    /// Copies the newest inline samples over (oldest first). The inline 10-slot buffer keeps
+    /// being written so freshness checks on wrap/unwrap don't need the extra account.
+    pub fn migrate_oracle_history(ctx: Context<MigrateOracleHistory>, capacity: u16) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
-        // **SECURITY FIX #50**: Validate oracle authority (creator or governance)
         require!(
-            ctx.accounts.oracle_authority.key() == rift.creator,
+            ctx.accounts.creator.key() == rift.creator,
             ErrorCode::Unauthorized
         );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            (MIN_ORACLE_HISTORY_LEN..=MAX_ORACLE_HISTORY_LEN).contains(&capacity),
+            ErrorCode::InvalidOracleHistoryLength
+        );
 
-        // **SECURITY FIX #50**: Bind to stored Switchboard account address
-        let expected_switchboard_account = rift
-            .switchboard_feed_account
-            .ok_or(ErrorCode::OracleAccountNotSet)?;
+        // Existing samples oldest → newest (price_index points at the oldest slot)
+        let buffer_len = rift.oracle_prices.len();
+        let mut samples = [PriceData::default(); 10];
+        let mut sample_count = 0usize;
+        for offset in 0..buffer_len {
+            let price_data = rift.oracle_prices[(rift.price_index as usize + offset) % buffer_len];
+            if price_data.timestamp > 0 {
+                samples[sample_count] = price_data;
+                sample_count += 1;
+            }
+        }
+
+        let history = &mut ctx.accounts.oracle_history;
+        history.rift = rift.key();
+        history.prices = vec![PriceData::default(); capacity as usize];
+        history.price_index = 0;
+        history.bump = ctx.bumps.oracle_history;
+        let skipped = sample_count.saturating_sub(capacity as usize);
+        for price_data in &samples[skipped..sample_count] {
+            history.push(*price_data);
+        }
+
+        rift.oracle_history = Some(history.key());
+
+        msg!(
+            "✅ Oracle history migrated: {} samples copied, capacity {}",
+            sample_count - skipped,
+            capacity
+        );
 
+        Ok(())
+    }
+
+    /// Permissionless: grow a rift created with the launch layout (LEGACY_RIFT_ACCOUNT_SIZE) to
+    /// RIFT_ACCOUNT_SIZE. The payer funds the extra rent; new fields take fresh-rift defaults
+    /// and the live transfer fee is read from the RIFT mint.
+    pub fn migrate_rift_account(ctx: Context<MigrateRiftAccount>) -> Result<()> {
+        let rift_info = ctx.accounts.rift.to_account_info();
+        require!(rift_info.owner == &crate::ID, ErrorCode::InvalidRift);
         require!(
-            ctx.accounts.switchboard_feed.key() == expected_switchboard_account,
-            ErrorCode::OracleAccountMismatch
+            rift_info.data_len() == LEGACY_RIFT_ACCOUNT_SIZE,
+            ErrorCode::AccountAlreadyMigrated
         );
+        {
+            let rift_data = rift_info.try_borrow_data()?;
+            require!(
+                rift_data[..8] == *Rift::DISCRIMINATOR,
+                ErrorCode::InvalidAccountData
+            );
+        }
 
-        // **SECURITY FIX #4**: Use Switchboard SDK for validated price parsing
-        // This replaces manual byte slicing with audited SDK that validates:
-        // - Account structure and version
-        // - Oracle responses and consensus
-        // - Staleness and update timestamps
-        // - Min oracle requirements
-
-        let switchboard_program_id =
-            Pubkey::from_str_const("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
-        require!(
-            ctx.accounts.switchboard_feed.owner == &switchboard_program_id,
-            ErrorCode::InvalidOracleOwner
-        );
-
-        // Load and validate feed using Switchboard SDK
-        // Note: switchboard-on-demand v0.11.1 API expects Ref<'_, &mut [u8]> for parse()
-        // PullFeedAccountData::parse() internally validates:
-        // ✅ Account discriminator (first 8 bytes must match aggregator type)
-        // ✅ Account version and structure
-        // ✅ Deserialization of all fields
-        let feed_account_info = ctx.accounts.switchboard_feed.to_account_info();
-        let feed_data = feed_account_info
-            .try_borrow_data()
-            .map_err(|_| ErrorCode::InvalidOracleData)?;
-
-        let feed_account =
-            PullFeedAccountData::parse(feed_data).map_err(|_| ErrorCode::InvalidOracleData)?;
-
-        // Get current time for validation
-        let current_time = Clock::get()?.unix_timestamp;
-        const MAX_AGE_SECONDS: u64 = 300; // 5 minutes
+        let required_lamports = Rent::get()?.minimum_balance(RIFT_ACCOUNT_SIZE);
+        let current_lamports = rift_info.lamports();
+        if required_lamports > current_lamports {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: rift_info.clone(),
+                    },
+                ),
+                required_lamports - current_lamports,
+            )?;
+        }
+        rift_info.resize(RIFT_ACCOUNT_SIZE)?;
+
+        // Appended bytes are zero - a valid encoding for every new field - then apply defaults
+        let mut rift = {
+            let rift_data = rift_info.try_borrow_data()?;
+            Rift::try_deserialize(&mut &rift_data[..])?
+        };
+        rift.apply_default_config();
+
+        // Launch-layout rifts are all Token-2022 with a transfer fee; the launch value is not
+        // recorded anywhere else, so both fields start from the live fee
+        require!(
+            ctx.accounts.rift_mint.key() == rift.rift_mint,
+            ErrorCode::InvalidMint
+        );
+        {
+            let mint_data = ctx.accounts.rift_mint.try_borrow_data()?;
+            let mint_state = StateWithExtensions::<Mint2022State>::unpack(&mint_data)?;
+            let fee_config = mint_state.get_extension::<TransferFeeConfig>()?;
+            let live_fee_bps = u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
+            rift.initial_transfer_fee_bps = live_fee_bps;
+            rift.transfer_fee_bps = live_fee_bps;
+        }
+
+        {
+            let mut rift_data = rift_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut rift_data;
+            rift.try_serialize(&mut writer)?;
+        }
+
+        msg!(
+            "✅ Rift {} migrated to the current layout ({} -> {} bytes)",
+            rift_info.key(),
+            LEGACY_RIFT_ACCOUNT_SIZE,
+            RIFT_ACCOUNT_SIZE
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless: grow a GlobalConfig created with the original layout
+    /// (LEGACY_GLOBAL_CONFIG_SIZE) to GlobalConfig::SIZE. New fields take the same defaults as
+    /// initialize_global_config, so authorities keep resolving to the hardcoded constants.
+    pub fn migrate_global_config(ctx: Context<MigrateGlobalConfig>) -> Result<()> {
+        let config_info = ctx.accounts.global_config.to_account_info();
+        require!(config_info.owner == &crate::ID, ErrorCode::InvalidAccountData);
+        require!(
+            config_info.data_len() == LEGACY_GLOBAL_CONFIG_SIZE,
+            ErrorCode::AccountAlreadyMigrated
+        );
+
+        let required_lamports = Rent::get()?.minimum_balance(GlobalConfig::SIZE);
+        let current_lamports = config_info.lamports();
+        if required_lamports > current_lamports {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: config_info.clone(),
+                    },
+                ),
+                required_lamports - current_lamports,
+            )?;
+        }
+        config_info.resize(GlobalConfig::SIZE)?;
+
+        let mut config = {
+            let config_data = config_info.try_borrow_data()?;
+            GlobalConfig::try_deserialize(&mut &config_data[..])?
+        };
+        config.apply_default_config();
+        {
+            let mut config_data = config_info.try_borrow_mut_data()?;
+            let mut writer: &mut [u8] = &mut config_data;
+            config.try_serialize(&mut writer)?;
+        }
+
+        msg!("✅ Global config migrated to the current layout");
+
+        Ok(())
+    }
+
+    /// Permissionless: register the RIFT mint of a rift created before the mint registry existed
+    /// so it is treated as a RIFT mint by nested-rift checks
+    pub fn register_rift_mint(ctx: Context<RegisterRiftMint>) -> Result<()> {
+        let registry = &mut ctx.accounts.rift_mint_registry;
+        registry.rift = ctx.accounts.rift.key();
+        registry.bump = ctx.bumps.rift_mint_registry;
+
+        msg!(
+            "✅ Registered RIFT mint {} for rift {}",
+            ctx.accounts.rift.rift_mint,
+            ctx.accounts.rift.key()
+        );
+
+        Ok(())
+    }
+
+    /// Admin function: rewrite rift.vault / fees_vault / withheld_vault from their PDAs
+    /// Heals legacy rifts left with stale pointers (e.g. system_program::ID) by a partial init.
+    /// Every derived vault must already be an initialized token account with the expected
+    /// mint and vault_authority owner, otherwise nothing is written.
+    pub fn admin_repair_vault_pointers(ctx: Context<AdminRepairVaultPointers>) -> Result<()> {
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        // Vault and fees vault hold the underlying (its own program); withheld holds rift tokens
+        let underlying_token_program = *ctx.accounts.underlying_mint.owner;
+        let vault_authority = ctx.accounts.vault_authority.key();
+        let checks = [
+            (
+                ctx.accounts.vault.to_account_info(),
+                underlying_token_program,
+                ctx.accounts.underlying_mint.key(),
+                ErrorCode::InvalidVault,
+            ),
+            (
+                ctx.accounts.fees_vault.to_account_info(),
+                underlying_token_program,
+                ctx.accounts.underlying_mint.key(),
+                ErrorCode::InvalidFeesVault,
+            ),
+            (
+                ctx.accounts.withheld_vault.to_account_info(),
+                spl_token_2022::ID,
+                ctx.accounts.rift_mint.key(),
+                ErrorCode::InvalidWithheldVault,
+            ),
+        ];
+        for (vault, token_program, mint, error) in checks.iter() {
+            require!(
+                existing_vault_matches(vault, token_program, mint, &vault_authority, *error)?,
+                ErrorCode::VaultNotInitialized
+            );
+        }
+
+        let rift = &mut ctx.accounts.rift;
+        msg!("🔧 Repairing vault pointers for rift: {}", rift.key());
+        msg!("   vault: {} -> {}", rift.vault, ctx.accounts.vault.key());
+        msg!("   fees_vault: {} -> {}", rift.fees_vault, ctx.accounts.fees_vault.key());
+        msg!(
+            "   withheld_vault: {} -> {}",
+            rift.withheld_vault,
+            ctx.accounts.withheld_vault.key()
+        );
+
+        rift.vault = ctx.accounts.vault.key();
+        rift.fees_vault = ctx.accounts.fees_vault.key();
+        rift.withheld_vault = ctx.accounts.withheld_vault.key();
+
+        Ok(())
+    }
+
+    /// **SECURITY FIX #4**: Update Switchboard oracle using SDK (prevents byte offset errors)
+    /// Uses switchboard-on-demand SDK for validated price parsing
+    pub fn update_switchboard_oracle(ctx: Context<UpdateSwitchboardOracle>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        // **SECURITY FIX #50**: Validate oracle authority (creator or governance)
+        require!(
+            ctx.accounts.oracle_authority.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
 
-        // Get validated price from feed
-        // SDK automatically checks:
-        // ✅ Oracle consensus (min responses met)
-        // ✅ Account structure and version
-        // ✅ Staleness based on update timestamp
-        let price_result = feed_account
-            .value(MAX_AGE_SECONDS)
-            .map_err(|_| ErrorCode::OraclePriceStale)?;
+        // **REENTRANCY**: Oracle updates must not run nested inside a guarded rebalance/wrap/unwrap
+        // No CPIs happen below, so checking (not holding) the guard is sufficient
+        rift.ensure_not_reentrant(Clock::get()?.slot)?;
 
-        // Switchboard returns Decimal type - convert to f64
-        let price_f64 =
-            (price_result.mantissa() as f64) / 10f64.powi(price_result.scale() as i32);
+        // **SECURITY FIX #50**: Bind to stored Switchboard account address
+        let expected_switchboard_account = rift
+            .switchboard_feed_account
+            .ok_or(ErrorCode::OracleAccountNotSet)?;
 
-        // **FIX CRITICAL**: Validate finiteness and bounds before cast to prevent overflow
-        // Check for NaN, infinity, and that scaled price fits in u64 range
-        // Must validate BEFORE cast since invalid f64 can overflow to arbitrary u64 values
         require!(
-            price_f64.is_finite() && price_f64 > 0.0,
-            ErrorCode::InvalidOraclePrice
+            ctx.accounts.switchboard_feed.key() == expected_switchboard_account,
+            ErrorCode::OracleAccountMismatch
         );
 
-        // **FIX MEDIUM #44**: Validate price won't exceed u64::MAX after scaling
-        // Also check against protocol max (1e12) to prevent later protocol brick
-        let scaled_price_f64 = price_f64 * 1_000_000.0;
+        // **ORACLE SOURCE**: Only rifts that selected Switchboard accept feed updates
         require!(
-            scaled_price_f64 > 0.0 && scaled_price_f64 <= 1_000_000_000_000.0,
-            ErrorCode::OraclePriceTooLarge
+            rift.oracle_source == OracleType::Switchboard,
+            ErrorCode::OracleSourceMismatch
         );
 
-        // Convert f64 to u64 (Switchboard returns decimal values)
-        // Assuming price is in USD with 6 decimals precision
-        // Safe cast: validated finiteness and bounds above
-        let price = scaled_price_f64 as u64;
+        // **SECURITY FIX #4**: Use Switchboard SDK for validated price parsing
+        // Shared with the manual-oracle reference check (see read_switchboard_price)
+        let current_time = Clock::get()?.unix_timestamp;
+        const MAX_AGE_SECONDS: u64 = 300; // 5 minutes
+        let (price, std_dev) = read_switchboard_price_with_std_dev(
+            &ctx.accounts.switchboard_feed.to_account_info(),
+            MAX_AGE_SECONDS,
+        )?;
 
         msg!("✅ Switchboard SDK validation passed");
-        msg!("   Price: {} USD", price_f64);
+        msg!("   Price: {} (6 decimals)", price);
         msg!("   Last update: within {} seconds", MAX_AGE_SECONDS);
 
-        // For Switchboard, we use a default confidence of 1% of price
-        // SDK provides std_deviation which could be used for more accurate confidence
-        let confidence = price
-            .checked_mul(1)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100)
-            .ok_or(ErrorCode::MathOverflow)?;
+        // Confidence is the feed's std deviation across oracle responses when reported,
+        // otherwise a default of 1% of price
+        let confidence = match std_dev {
+            Some(std_dev) => std_dev,
+            None => price
+                .checked_mul(1)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(100)
+                .ok_or(ErrorCode::MathOverflow)?,
+        };
+        let confidence_source = if std_dev.is_some() { "std dev" } else { "default" };
+        msg!("   Confidence: {} ({})", confidence, confidence_source);
 
         // **SECURITY FIX #50**: Validate confidence (confidence should be <= 5% of price)
         let max_confidence = price
@@ -2610,10 +3035,28 @@ pub mod rifts_protocol {
             ErrorCode::OracleConfidenceTooLow
         );
 
+        // **CONFIDENCE FLOOR**: Suspiciously perfect agreement can mask a single manipulated report
+        let min_confidence = u128::from(price)
+            .checked_mul(u128::from(rift.min_confidence_bps))
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            u128::from(confidence) >= min_confidence,
+            ErrorCode::OracleConfidenceTooTight
+        );
+
         // Note: Price bounds already validated before cast (finiteness + 0 < price <= 1e12)
 
         // Update rift oracle with validated price
         rift.add_price_data(price, confidence, current_time)?;
+        record_oracle_history(
+            rift,
+            &mut ctx.accounts.oracle_history,
+            price,
+            confidence,
+            current_time,
+        )?;
 
         emit!(OraclePriceUpdated {
             rift: rift.key(),
@@ -2643,6 +3086,16 @@ pub mod rifts_protocol {
             ErrorCode::Unauthorized
         );
 
+        // **ORACLE SOURCE**: A rift that selected a live feed cannot also take manual prices
+        require!(
+            rift.oracle_source == OracleType::Manual,
+            ErrorCode::OracleSourceMismatch
+        );
+
+        // **REENTRANCY**: Oracle updates must not run nested inside a guarded rebalance/wrap/unwrap
+        // No CPIs happen below, so checking (not holding) the guard is sufficient
+        rift.ensure_not_reentrant(Clock::get()?.slot)?;
+
         // **HIGH FIX #3**: Rate limit - max 1 update per hour (3600 seconds)
         if rift.last_manual_oracle_update > 0 {
             require!(
@@ -2655,7 +3108,8 @@ pub mod rifts_protocol {
         // **FIX CRITICAL #28 + FIX INFO #1 (Audit)**: Use allow_stale_fallback=true to enable recovery
         // When all oracle prices are stale AND backing_ratio is >24h old, this allows manual oracle
         // updates to proceed using the stale backing_ratio as baseline, preventing permanent deadlock
-        let current_avg_price = rift.get_average_oracle_price_with_options(true)?;
+        let oracle_history = rift.oracle_history_for(&ctx.accounts.oracle_history)?;
+        let current_avg_price = rift.get_average_oracle_price_with_options(oracle_history, true)?;
         if current_avg_price > 0 {
             let price_change = if price > current_avg_price {
                 price
@@ -2680,7 +3134,6 @@ pub mod rifts_protocol {
 
         // **FIX HIGH #2 + #18**: Check cumulative drift over lifetime (no reset)
         // Drift window is initialized once and then enforced cumulatively
-        const DRIFT_WINDOW_SECONDS: i64 = 604800; // 7 days (unused now, kept for reference)
 
         // Initialize drift baseline on first manual oracle update
         if rift.manual_oracle_drift_window_start == 0 {
@@ -2690,6 +3143,11 @@ pub mod rifts_protocol {
                 "📊 Initializing drift baseline at price: {}",
                 current_avg_price
             );
+            emit!(OracleDriftBaselineSet {
+                rift: rift.key(),
+                base_price: current_avg_price,
+                window_start: current_time,
+            });
         } else if rift.manual_oracle_base_price > 0 {
             // Check cumulative drift within 7-day window (max 30% total drift)
             let cumulative_change = if price > rift.manual_oracle_base_price {
@@ -2725,6 +3183,50 @@ pub mod rifts_protocol {
         require!(price > 0, ErrorCode::InvalidOraclePrice);
         require!(price <= 1_000_000_000_000, ErrorCode::OraclePriceTooLarge);
 
+        // **REFERENCE CROSS-CHECK**: With a bound Switchboard feed, the manual price must stay
+        // within arbitrage_threshold_bps of it. The feed account is then mandatory; the check is
+        // only skipped when the feed itself is stale/unreadable (the manual oracle's fallback role)
+        if let Some(expected_feed) = rift.switchboard_feed_account {
+            let reference_feed = ctx
+                .accounts
+                .reference_feed
+                .as_ref()
+                .ok_or(ErrorCode::OracleAccountMismatch)?;
+            require!(
+                reference_feed.key() == expected_feed,
+                ErrorCode::OracleAccountMismatch
+            );
+
+            const REFERENCE_MAX_AGE_SECONDS: u64 = 300; // 5 minutes, same as update_switchboard_oracle
+            match read_switchboard_price(
+                &reference_feed.to_account_info(),
+                REFERENCE_MAX_AGE_SECONDS,
+            ) {
+                Ok(reference_price) => {
+                    let deviation = price.abs_diff(reference_price);
+                    let deviation_bps = u128::from(deviation)
+                        .checked_mul(10000)
+                        .ok_or(ErrorCode::MathOverflow)?
+                        .checked_div(u128::from(reference_price))
+                        .ok_or(ErrorCode::MathOverflow)?;
+                    msg!(
+                        "📊 Manual price {} vs reference {}: {} bps (max {})",
+                        price,
+                        reference_price,
+                        deviation_bps,
+                        rift.arbitrage_threshold_bps
+                    );
+                    require!(
+                        deviation_bps <= u128::from(rift.arbitrage_threshold_bps),
+                        ErrorCode::ManualOracleDeviatesFromReference
+                    );
+                }
+                Err(_) => {
+                    msg!("⚠️ Reference feed unavailable/stale - skipping cross-check");
+                }
+            }
+        }
+
         // Validate confidence is reasonable (max 50% of price)
         let max_confidence = price
             .checked_mul(5)
@@ -2744,6 +3246,13 @@ pub mod rifts_protocol {
 
         // Update rift oracle with validated price
         rift.add_price_data(price, confidence, current_time)?;
+        record_oracle_history(
+            rift,
+            &mut ctx.accounts.oracle_history,
+            price,
+            confidence,
+            current_time,
+        )?;
 
         // **HIGH FIX #3**: Update rate limit timestamp
         rift.last_manual_oracle_update = current_time;
@@ -2768,12 +3277,17 @@ pub mod rifts_protocol {
             // **FIX ISSUE #7**: Auto-clear stuck guard after timeout
             if rift.reentrancy_guard {
                 let current_slot = Clock::get()?.slot;
-                if current_slot > rift.reentrancy_guard_slot + REENTRANCY_TIMEOUT_SLOTS {
+                if current_slot > rift.reentrancy_guard_slot + rift.reentrancy_timeout() {
                     msg!(
                         "⚠️ Auto-clearing stuck reentrancy guard (set at slot {}, current {})",
                         rift.reentrancy_guard_slot,
                         current_slot
                     );
+                    emit!(ReentrancyGuardAutoCleared {
+                        rift: rift.key(),
+                        set_at_slot: rift.reentrancy_guard_slot,
+                        cleared_at_slot: current_slot,
+                    });
                     rift.reentrancy_guard = false;
                     rift.reentrancy_guard_slot = 0;
                 } else {
@@ -2790,13 +3304,33 @@ pub mod rifts_protocol {
             let rift = &mut ctx.accounts.rift;
             let clock = Clock::get()?;
 
-            // Check if manual rebalance is allowed
+            // Check if manual rebalance is allowed (this is also the keeper reward gate)
             require!(
                 rift.can_manual_rebalance(clock.unix_timestamp)?,
                 ErrorCode::RebalanceTooSoon
             );
 
-            rift.trigger_automatic_rebalance(clock.unix_timestamp)?;
+            let rebalance_count_before = rift.rebalance_count;
+            let oracle_history = rift.oracle_history_for(&ctx.accounts.oracle_history)?;
+            rift.trigger_automatic_rebalance(clock.unix_timestamp, oracle_history)?;
+
+            // **KEEPER ATTRIBUTION**: Record which bot triggered the rebalance
+            emit!(RebalanceTriggered {
+                rift: rift.key(),
+                backing_ratio: rift.backing_ratio,
+                keeper: ctx.accounts.user.key(),
+                timestamp: clock.unix_timestamp,
+            });
+
+            // **KEEPER REWARD**: Only when the (can_manual_rebalance-gated) rebalance moved state
+            let rebalanced = rift.rebalance_count > rebalance_count_before;
+            if rift.rebalance_reward > 0 && rebalanced {
+                pay_rebalance_reward(
+                    &mut *ctx.accounts,
+                    ctx.bumps.vault_authority,
+                    clock.unix_timestamp,
+                )?;
+            }
 
             Ok(())
         })();
@@ -2810,6 +3344,8 @@ pub mod rifts_protocol {
 
     /// Close a rift and return rent to creator (for fixing invalid vaults)
     /// **FIX CRITICAL #12**: Now checks ALL vaults are empty before allowing close
+    /// Underlying vaults may hold up to CLOSE_DUST_TOLERANCE dust, which is forwarded to the creator
+    /// Withheld transfer fees on Token-2022 vaults are harvested to their mint before closing
     pub fn close_rift(ctx: Context<CloseRift>) -> Result<()> {
         let rift = &ctx.accounts.rift;
 
@@ -2828,9 +3364,14 @@ pub mod rifts_protocol {
         // Check ACTUAL vault balance if initialized
         let system_program_key = anchor_lang::solana_program::system_program::ID;
 
+        // **CLOSE DUST**: Underlying vaults may hold up to CLOSE_DUST_TOLERANCE base units
+        // (transfer-fee/rounding leftovers); that dust is forwarded to the creator before closing
+        let mut vault_dust = 0u64;
+        let mut fees_vault_dust = 0u64;
+
         if rift.vault != system_program_key {
             // **FIX CRITICAL #27**: Manual balance check for UncheckedAccount
-            // Verify vault is a valid token account and has zero balance
+            // Verify vault is a valid token account and has (effectively) zero balance
             require!(
                 *ctx.accounts.vault.owner == anchor_spl::token::ID
                     || *ctx.accounts.vault.owner == spl_token_2022::ID,
@@ -2845,8 +3386,9 @@ pub mod rifts_protocol {
             let vault_balance = u64::from_le_bytes(vault_data[64..72].try_into().map_err(|_| ErrorCode::InvalidAccountData)?);
             drop(vault_data);
 
-            require!(vault_balance == 0, ErrorCode::VaultNotEmpty);
-            msg!("✅ Backing vault balance verified: 0 tokens");
+            require!(vault_balance <= CLOSE_DUST_TOLERANCE, ErrorCode::VaultNotEmpty);
+            vault_dust = vault_balance;
+            msg!("✅ Backing vault balance verified: {} tokens (dust)", vault_balance);
         } else {
             msg!("⚠️ Vault not initialized (skip check)");
         }
@@ -2874,8 +3416,12 @@ pub mod rifts_protocol {
                 u64::from_le_bytes(fees_vault_data[64..72].try_into().map_err(|_| ErrorCode::InvalidAccountData)?);
             drop(fees_vault_data);
 
-            require!(fees_vault_balance == 0, ErrorCode::FeesVaultNotEmpty);
-            msg!("✅ Fees vault balance verified: 0 tokens");
+            require!(
+                fees_vault_balance <= CLOSE_DUST_TOLERANCE,
+                ErrorCode::FeesVaultNotEmpty
+            );
+            fees_vault_dust = fees_vault_balance;
+            msg!("✅ Fees vault balance verified: {} tokens (dust)", fees_vault_balance);
         } else {
             msg!("⚠️ Fees vault not initialized (skip check)");
         }
@@ -2911,81 +3457,721 @@ pub mod rifts_protocol {
             msg!("⚠️ Withheld vault not initialized (skip check)");
         }
 
+        // **WRAP FEE VAULT**: Must be fully claimed (claim_wrap_fees) before closing
+        let wrap_fees_vault_info = match rift.wrap_fees_vault {
+            Some(wrap_fees_vault_key) => {
+                let wrap_fees_vault = ctx
+                    .accounts
+                    .wrap_fees_vault
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidFeesVault)?
+                    .to_account_info();
+                let wrap_fees_vault_balance = read_vault_amount(
+                    &wrap_fees_vault,
+                    wrap_fees_vault_key,
+                    ErrorCode::InvalidFeesVault,
+                )?;
+                require!(wrap_fees_vault_balance == 0, ErrorCode::FeesVaultNotEmpty);
+                msg!("✅ Wrap fees vault balance verified: 0 tokens");
+                wrap_fees_vault
+            }
+            // Placeholder only - skipped below since the stored address is system_program
+            None => ctx.accounts.vault.to_account_info(),
+        };
+
         msg!("✅ All vaults empty - safe to close rift");
 
+        // **RENT RECLAIM**: Close every initialized (verified-empty) vault, rent goes to creator
+        // Rift account rent is included in the total (closed by Anchor via `close = creator`)
+        let rift_key = rift.key();
+        let vault_auth_bump = [ctx.bumps.vault_authority];
+        let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
+        let vault_auth_signer = &[vault_auth_seeds];
+
+        let mut reclaimed_lamports = rift.to_account_info().lamports();
+        let underlying_mint_info =
+            ctx.accounts.underlying_mint.as_ref().map(|mint| mint.to_account_info());
+        let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
+        for (vault_info, stored_vault, dust, mint_info) in [
+            (
+                ctx.accounts.vault.to_account_info(),
+                rift.vault,
+                vault_dust,
+                underlying_mint_info.clone(),
+            ),
+            (
+                ctx.accounts.fees_vault.to_account_info(),
+                rift.fees_vault,
+                fees_vault_dust,
+                underlying_mint_info.clone(),
+            ),
+            (
+                ctx.accounts.withheld_vault.to_account_info(),
+                rift.withheld_vault,
+                0,
+                Some(rift_mint_info.clone()),
+            ),
+            (
+                wrap_fees_vault_info,
+                rift.wrap_fees_vault.unwrap_or(system_program_key),
+                0,
+                underlying_mint_info,
+            ),
+        ] {
+            if stored_vault == system_program_key {
+                continue;
+            }
+
+            // **WITHHELD FEES**: Token-2022 won't close an account with withheld transfer fees;
+            // harvest them to the mint first (permissionless), where the withdraw authority
+            // can still collect them
+            if token_account_withheld_amount(&vault_info)? > 0 {
+                use spl_token_2022::extension::transfer_fee::instruction as transfer_fee_ix;
+
+                let mint_info = mint_info.ok_or(ErrorCode::CloseMintAccountRequired)?;
+                invoke(
+                    &transfer_fee_ix::harvest_withheld_tokens_to_mint(
+                        &spl_token_2022::ID,
+                        mint_info.key,
+                        &[vault_info.key],
+                    )?,
+                    &[
+                        mint_info.clone(),
+                        vault_info.clone(),
+                        ctx.accounts.token_2022_program.to_account_info(),
+                    ],
+                )?;
+                msg!("Harvested withheld fees from vault {} to its mint", vault_info.key);
+            }
+
+            // Vault owner was validated above (SPL Token or Token-2022)
+            let token_program_info = if *vault_info.owner == spl_token_2022::ID {
+                ctx.accounts.token_2022_program.to_account_info()
+            } else {
+                ctx.accounts.token_program.to_account_info()
+            };
+            let vault_lamports = vault_info.lamports();
+
+            // **CLOSE DUST**: Token programs only close empty accounts - forward dust to the creator
+            if dust > 0 {
+                let (Some(creator_underlying), Some(underlying_mint)) = (
+                    ctx.accounts.creator_underlying.as_ref(),
+                    ctx.accounts.underlying_mint.as_ref(),
+                ) else {
+                    return Err(ErrorCode::CloseDustAccountRequired.into());
+                };
+                invoke_signed(
+                    &spl_token_2022::instruction::transfer_checked(
+                        vault_info.owner,
+                        vault_info.key,
+                        &underlying_mint.key(),
+                        &creator_underlying.key(),
+                        ctx.accounts.vault_authority.key,
+                        &[],
+                        dust,
+                        underlying_mint.decimals,
+                    )?,
+                    &[
+                        vault_info.clone(),
+                        underlying_mint.to_account_info(),
+                        creator_underlying.to_account_info(),
+                        ctx.accounts.vault_authority.to_account_info(),
+                        token_program_info.clone(),
+                    ],
+                    vault_auth_signer,
+                )?;
+                msg!("Forwarded {} dust from vault {} to creator", dust, vault_info.key);
+            }
+
+            invoke_signed(
+                &spl_token_2022::instruction::close_account(
+                    vault_info.owner,
+                    vault_info.key,
+                    ctx.accounts.creator.key,         // Rent destination
+                    ctx.accounts.vault_authority.key, // Vault owner PDA
+                    &[],
+                )?,
+                &[
+                    vault_info.clone(),
+                    ctx.accounts.creator.to_account_info(),
+                    ctx.accounts.vault_authority.to_account_info(),
+                    token_program_info,
+                ],
+                vault_auth_signer,
+            )?;
+
+            reclaimed_lamports = reclaimed_lamports
+                .checked_add(vault_lamports)
+                .ok_or(ErrorCode::MathOverflow)?;
+            msg!("✅ Closed vault {} ({} lamports)", vault_info.key, vault_lamports);
+        }
+
         emit!(RiftClosed {
             rift: rift.key(),
             creator: rift.creator,
+            reclaimed_lamports,
         });
 
         Ok(())
     }
 
-    /// Admin function: Close any rift regardless of creator (program authority only)
-    pub fn admin_close_rift(ctx: Context<AdminCloseRift>) -> Result<()> {
+    /// Admin function: Pause wraps/unwraps on a rift (program authority or the rift's guardian)
+    pub fn admin_pause_rift(ctx: Context<AdminPauseRift>) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
-        // Only program authority can use this function
-        let admin_pubkey = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        // **GUARDIAN**: Pause-only role for automated incident response
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        let signer = ctx.accounts.program_authority.key();
         require!(
-            ctx.accounts.program_authority.key() == admin_pubkey,
+            signer == admin_pubkey || rift.guardian == Some(signer),
             ErrorCode::UnauthorizedAdmin
         );
 
-        // **FIX ISSUE #1**: Actually mark the rift as closed
-        rift.is_closed = true;
-        rift.closed_at_slot = Clock::get()?.slot;
+        rift.paused = true;
+        msg!("⚠️ Rift paused: {}", rift.key());
 
-        // **FIX ISSUE #1**: Reset reentrancy guard to prevent stuck state
-        rift.reentrancy_guard = false;
-        rift.reentrancy_guard_slot = 0;
+        emit!(RiftPauseUpdated {
+            rift: rift.key(),
+            paused: true,
+            authority: ctx.accounts.program_authority.key(),
+        });
 
-        // Log the admin close action
-        msg!(
-            "Admin closing rift: {} (original creator: {}) at slot {}",
-            rift.key(),
-            rift.creator,
-            rift.closed_at_slot
+        Ok(())
+    }
+
+    /// Admin function: Resume wraps/unwraps on a paused rift (program authority only)
+    pub fn admin_unpause_rift(ctx: Context<AdminPauseRift>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
         );
 
-        emit!(RiftAdminClosed {
+        // A migrating rift stays paused so its vault only pays out through redemptions
+        require!(rift.migration_destination.is_none(), ErrorCode::RiftAlreadyMigrating);
+        rift.paused = false;
+        msg!("✅ Rift unpaused: {}", rift.key());
+
+        emit!(RiftPauseUpdated {
             rift: rift.key(),
-            original_creator: rift.creator,
-            admin: ctx.accounts.program_authority.key(),
+            paused: false,
+            authority: ctx.accounts.program_authority.key(),
         });
 
         Ok(())
     }
 
-    /// Emergency admin function to withdraw tokens from vault
-    /// **CRITICAL SECURITY**: Requires BOTH admin authorities to prevent single-point-of-failure
-    /// Only use in case of critical issues like closed rifts with locked funds
-    ///
-    /// **ACKNOWLEDGED SECURITY TRADE-OFF (High Issue #3):**
-    /// This function does NOT verify:
-    /// 1. That the rift is actually closed
-    /// 2. That the vault belongs to the specified rift
-    /// This is intentional to allow emergency recovery of funds in edge cases where:
-    /// - Rift state is corrupted but vault is valid
-    /// - Need to recover from program bugs or attacks
-    /// - Need manual intervention for stuck funds
-    ///
-    /// MITIGATION: Requires BOTH independent admin signatures (2-of-2 multisig)
-    /// - PROGRAM_AUTHORITY: 9KiFDT1jPtATAJktQxQ5nErmmFXbya6kXb6hFasN5pz4
-    /// - ADMIN_AUTHORITY_2: CPr8qxu9LKx4tU5LWj53z669fzydGwFyJzw6xWarZ3zB
-    ///
-    /// Both keys must explicitly approve any emergency withdrawal, providing accountability.
-    pub fn admin_emergency_withdraw_vault(
-        ctx: Context<AdminEmergencyWithdrawVault>,
-        amount: u64,
-        closed_rift_pubkey: Pubkey,
-    ) -> Result<()> {
-        // **SECURITY FIX #3**: Require BOTH admin authorities
-        let admin_1 = Pubkey::from_str_const(PROGRAM_AUTHORITY);
-        let admin_2 = Pubkey::from_str_const(ADMIN_AUTHORITY_2);
-
+    /// Admin function: Open a paused rift for migration into another rift of the same underlying
+    /// No funds move here. Each source holder later calls `redeem_migrated_position` to burn their
+    /// own source RIFT and receive destination RIFT for their share of the source vault.
+    pub fn migrate_rift(ctx: Context<MigrateRift>) -> Result<()> {
+        // Only program authority can migrate a rift
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
-            ctx.accounts.admin_authority_1.key() == admin_1,
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        let destination_rift = &ctx.accounts.destination_rift;
+        let destination_key = destination_rift.key();
+        let source_rift = &mut ctx.accounts.source_rift;
+        require!(source_rift.key() != destination_key, ErrorCode::InvalidRift);
+        require!(
+            source_rift.underlying_mint == destination_rift.underlying_mint,
+            ErrorCode::InvalidMint
+        );
+        require!(!source_rift.is_closed, ErrorCode::RiftClosed);
+        require!(source_rift.paused, ErrorCode::RiftNotPaused);
+        require!(
+            source_rift.migration_destination.is_none(),
+            ErrorCode::RiftAlreadyMigrating
+        );
+        require!(!destination_rift.is_closed, ErrorCode::RiftClosed);
+        require!(!destination_rift.closing, ErrorCode::RiftClosing);
+        require!(!destination_rift.paused, ErrorCode::RiftPaused);
+        require!(!destination_rift.wind_down, ErrorCode::RiftWindingDown);
+        require!(
+            destination_rift.migration_destination.is_none(),
+            ErrorCode::RiftAlreadyMigrating
+        );
+
+        source_rift.migration_destination = Some(destination_key);
+
+        msg!(
+            "✅ Rift {} migrating to {} - {} source RIFT redeemable by holders",
+            source_rift.key(),
+            destination_key,
+            source_rift.total_rift_minted
+        );
+
+        emit!(RiftMigrated {
+            source_rift: source_rift.key(),
+            destination_rift: destination_key,
+            source_rift_supply: source_rift.total_rift_minted,
+            source_vault_balance: ctx.accounts.source_vault.amount,
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Redeem source RIFT of a migrating rift for destination RIFT (holder-initiated)
+    /// Burns `amount` of the caller's source RIFT, moves min(amount, pro-rata vault share) of
+    /// underlying vault-to-vault, and mints destination RIFT 1:1 for what the destination received.
+    pub fn redeem_migrated_position(
+        ctx: Context<RedeemMigratedPosition>,
+        amount: u64,
+    ) -> Result<()> {
+        let source_key = ctx.accounts.source_rift.key();
+        let destination_key = ctx.accounts.destination_rift.key();
+        {
+            let source_rift = &ctx.accounts.source_rift;
+            let destination_rift = &ctx.accounts.destination_rift;
+            require!(
+                source_rift.migration_destination == Some(destination_key),
+                ErrorCode::RiftNotMigrating
+            );
+            require!(!destination_rift.is_closed, ErrorCode::RiftClosed);
+            require!(!destination_rift.closing, ErrorCode::RiftClosing);
+            require!(!destination_rift.paused, ErrorCode::RiftPaused);
+            require!(
+                !source_rift.reentrancy_guard && !destination_rift.reentrancy_guard,
+                ErrorCode::ReentrancyDetected
+            );
+            require!(amount > 0, ErrorCode::InvalidAmount);
+            require!(
+                amount <= source_rift.total_rift_minted,
+                ErrorCode::InsufficientFunds
+            );
+        }
+
+        // Under-backed sources pay out pro-rata; surplus backing never inflates destination supply
+        let source_vault_balance = ctx.accounts.source_vault.amount;
+        let pro_rata = u64::try_from(
+            (amount as u128)
+                .checked_mul(source_vault_balance as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                / ctx.accounts.source_rift.total_rift_minted as u128,
+        )
+        .map_err(|_| ErrorCode::MathOverflow)?;
+        let share = amount.min(pro_rata);
+        require!(share > 0, ErrorCode::InvalidAmount);
+
+        // Burn the holder's own source RIFT (holder signs as token account owner)
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.source_rift_token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.source_rift_mint.to_account_info(),
+                from: ctx.accounts.user_source_rift_tokens.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        interface_burn(burn_ctx, amount)?;
+
+        let destination_vault_before = ctx.accounts.destination_vault.amount;
+        let vault_auth_bump = [ctx.bumps.source_vault_authority];
+        let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", source_key.as_ref(), &vault_auth_bump];
+        let vault_auth_signer = &[vault_auth_seeds];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.underlying_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.source_vault.to_account_info(),
+                to: ctx.accounts.destination_vault.to_account_info(),
+                authority: ctx.accounts.source_vault_authority.to_account_info(),
+                mint: ctx.accounts.underlying_mint.to_account_info(),
+            },
+            vault_auth_signer,
+        );
+        interface_transfer_checked(transfer_ctx, share, ctx.accounts.underlying_mint.decimals)?;
+
+        // **TOKEN-2022**: Measure what actually arrived (underlying may charge a transfer fee)
+        ctx.accounts.source_vault.reload()?;
+        ctx.accounts.destination_vault.reload()?;
+        let actual_sent = source_vault_balance
+            .checked_sub(ctx.accounts.source_vault.amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let actual_received = ctx
+            .accounts
+            .destination_vault
+            .amount
+            .checked_sub(destination_vault_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(actual_received > 0, ErrorCode::InvalidAmount);
+
+        // Mint destination RIFT 1:1 for the backing received, to the holder's own account
+        let mint_auth_bump = [ctx.bumps.destination_mint_authority];
+        let mint_auth_seeds: &[&[u8]] =
+            &[b"rift_mint_auth", destination_key.as_ref(), &mint_auth_bump];
+        let mint_auth_signer = &[mint_auth_seeds];
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.destination_rift_token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.destination_rift_mint.to_account_info(),
+                to: ctx.accounts.user_destination_rift_tokens.to_account_info(),
+                authority: ctx.accounts.destination_mint_authority.to_account_info(),
+            },
+            mint_auth_signer,
+        );
+        interface_mint_to(mint_ctx, actual_received)?;
+
+        let source_rift = &mut ctx.accounts.source_rift;
+        source_rift.total_underlying_wrapped = source_rift
+            .total_underlying_wrapped
+            .checked_sub(actual_sent)
+            .ok_or(ErrorCode::MathOverflow)?;
+        source_rift.total_rift_minted = source_rift
+            .total_rift_minted
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        source_rift.total_burned = source_rift.total_burned.saturating_add(amount);
+
+        let destination_rift = &mut ctx.accounts.destination_rift;
+        destination_rift.total_underlying_wrapped = destination_rift
+            .total_underlying_wrapped
+            .checked_add(actual_received)
+            .ok_or(ErrorCode::MathOverflow)?;
+        destination_rift.total_rift_minted = destination_rift
+            .total_rift_minted
+            .checked_add(actual_received)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "✅ Redeemed {} source RIFT for {} destination RIFT ({} -> {})",
+            amount,
+            actual_received,
+            source_key,
+            destination_key
+        );
+
+        emit!(MigratedPositionRedeemed {
+            source_rift: source_key,
+            destination_rift: destination_key,
+            user: ctx.accounts.user.key(),
+            source_rift_burned: amount,
+            underlying_moved: actual_sent,
+            destination_rift_minted: actual_received,
+        });
+
+        Ok(())
+    }
+
+    /// Move a position between two rifts of the same underlying in one atomic step
+    /// Burns `amount` source RIFT, moves the underlying vault-to-vault (no hop through the user),
+    /// and mints destination RIFT to the user. The source side runs the unwrap_from_vault checks
+    /// and fees, the destination side the wrap_tokens ones (cooldown, limits, fee routing).
+    pub fn migrate_position(
+        ctx: Context<MigratePosition>,
+        amount: u64,
+        min_destination_out: u64,
+    ) -> Result<()> {
+        {
+            let source_rift = &ctx.accounts.source_rift;
+            let destination_rift = &ctx.accounts.destination_rift;
+            require!(
+                source_rift.key() != destination_rift.key(),
+                ErrorCode::InvalidRift
+            );
+            require!(
+                source_rift.underlying_mint == destination_rift.underlying_mint,
+                ErrorCode::InvalidMint
+            );
+        }
+
+        // **REENTRANCY**: Guard both rifts for the duration of the migration
+        let current_slot = Clock::get()?.slot;
+        ctx.accounts.source_rift.ensure_not_reentrant(current_slot)?;
+        ctx.accounts.destination_rift.ensure_not_reentrant(current_slot)?;
+        ctx.accounts.source_rift.reentrancy_guard = true;
+        ctx.accounts.source_rift.reentrancy_guard_slot = current_slot;
+        ctx.accounts.destination_rift.reentrancy_guard = true;
+        ctx.accounts.destination_rift.reentrancy_guard_slot = current_slot;
+
+        let execution_result = (|| -> Result<()> {
+            let source_key = ctx.accounts.source_rift.key();
+            let destination_key = ctx.accounts.destination_rift.key();
+            let underlying_decimals = ctx.accounts.underlying_mint.decimals;
+            let now = Clock::get()?.unix_timestamp;
+
+            // ===== Preflight: unwrap checks on the source, wrap checks on the destination =====
+            // **SAME-SLOT GUARD**: Leaving the source rift counts as an unwrap
+            ctx.accounts.source_rift.ensure_not_same_slot_wrap(current_slot)?;
+            let plan = ctx.accounts.source_rift.plan_unwrap(
+                amount,
+                ctx.accounts.source_vault.amount,
+                ctx.accounts.source_fee_exemption.is_some(),
+                false,
+                now,
+            )?;
+
+            let destination_rift = &ctx.accounts.destination_rift;
+            destination_rift.ensure_wrap_open(
+                ctx.accounts.destination_user_activity.last_wrap_slot,
+                current_slot,
+                now,
+            )?;
+            // **WRAP LIMITS**: The deposit into the destination is the source payout
+            require!(
+                destination_rift.max_wrap_amount == 0
+                    || plan.net_underlying <= destination_rift.max_wrap_amount,
+                ErrorCode::WrapAmountTooLarge
+            );
+            let wrap_multiplier = destination_rift
+                .wrap_fee_bps(ctx.accounts.destination_fee_exemption.is_some(), now);
+
+            // **BACKING CIRCUIT BREAKER**: Same rule as wrap_tokens on the destination
+            let destination_vault_before = ctx.accounts.destination_vault.amount;
+            let live_backing_bps =
+                destination_rift.get_live_backing_ratio_bps(destination_vault_before)?;
+            require!(
+                live_backing_bps >= u64::from(destination_rift.min_backing_ratio_bps),
+                ErrorCode::BackingBelowThreshold
+            );
+
+            // ===== Source side =====
+            // Burn source RIFT (burns carry no transfer fee)
+            let burn_ctx = CpiContext::new(
+                ctx.accounts.source_rift_token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.source_rift_mint.to_account_info(),
+                    from: ctx.accounts.user_source_rift_tokens.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            );
+            interface_burn(burn_ctx, plan.rift_burned)?;
+
+            let source_auth_bump = [ctx.bumps.source_vault_authority];
+            let source_auth_seeds: &[&[u8]] =
+                &[b"vault_auth", source_key.as_ref(), &source_auth_bump];
+            let source_auth_signer = &[source_auth_seeds];
+
+            // **FEE ROUTING**: Source unwrap fee to source fees_vault (kept in vault if not initialized)
+            route_unwrap_fee(
+                &ctx.accounts.source_rift,
+                plan.fee,
+                ctx.accounts.source_vault.to_account_info(),
+                ctx.accounts.source_fees_vault.to_account_info(),
+                ctx.accounts.underlying_mint.to_account_info(),
+                underlying_decimals,
+                ctx.accounts.underlying_token_program.to_account_info(),
+                ctx.accounts.source_vault_authority.to_account_info(),
+                source_auth_signer,
+            )?;
+
+            // ===== Vault to vault =====
+            ctx.accounts.source_vault.reload()?;
+            let source_vault_before = ctx.accounts.source_vault.amount;
+
+            let transfer_ctx = CpiContext::new_with_signer(
+                ctx.accounts.underlying_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.source_vault.to_account_info(),
+                    to: ctx.accounts.destination_vault.to_account_info(),
+                    authority: ctx.accounts.source_vault_authority.to_account_info(),
+                    mint: ctx.accounts.underlying_mint.to_account_info(),
+                },
+                source_auth_signer,
+            );
+            interface_transfer_checked(transfer_ctx, plan.net_underlying, underlying_decimals)?;
+
+            // **TOKEN-2022**: Destination credit may be lower than the debit (underlying transfer fee)
+            ctx.accounts.source_vault.reload()?;
+            let actual_sent = source_vault_before
+                .checked_sub(ctx.accounts.source_vault.amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+            ctx.accounts.destination_vault.reload()?;
+            let actual_received = ctx
+                .accounts
+                .destination_vault
+                .amount
+                .checked_sub(destination_vault_before)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            // ===== Destination side =====
+            let (wrap_fee, destination_minted) = ctx
+                .accounts
+                .destination_rift
+                .plan_wrap(actual_received, wrap_multiplier)?;
+            require!(
+                destination_minted >= min_destination_out,
+                ErrorCode::SlippageExceeded
+            );
+
+            let destination_auth_bump = [ctx.bumps.destination_vault_authority];
+            let destination_auth_seeds: &[&[u8]] =
+                &[b"vault_auth", destination_key.as_ref(), &destination_auth_bump];
+
+            // **FEE ROUTING**: Same routing as wrap_tokens
+            let (destination_fee_credited, destination_fee_routed) = route_wrap_fee(
+                &ctx.accounts.destination_rift,
+                wrap_fee,
+                ctx.accounts.destination_vault.to_account_info(),
+                ctx.accounts.destination_fees_vault.to_account_info(),
+                ctx.accounts
+                    .destination_wrap_fees_vault
+                    .as_ref()
+                    .map(|account| account.to_account_info()),
+                ctx.accounts.underlying_mint.to_account_info(),
+                underlying_decimals,
+                ctx.accounts.underlying_token_program.to_account_info(),
+                ctx.accounts.destination_vault_authority.to_account_info(),
+                &[destination_auth_seeds],
+            )?;
+
+            // **SUPPLY CEILING**: Fail clearly instead of inside the token program
+            ensure_mint_headroom(
+                &ctx.accounts.destination_rift_mint.to_account_info(),
+                destination_minted,
+            )?;
+
+            // Mint destination RIFT to the user
+            let mint_auth_bump = [ctx.bumps.destination_mint_authority];
+            let mint_auth_seeds: &[&[u8]] =
+                &[b"rift_mint_auth", destination_key.as_ref(), &mint_auth_bump];
+            let mint_auth_signer = &[mint_auth_seeds];
+            let mint_ctx = CpiContext::new_with_signer(
+                ctx.accounts.destination_rift_token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.destination_rift_mint.to_account_info(),
+                    to: ctx.accounts.user_destination_rift_tokens.to_account_info(),
+                    authority: ctx.accounts.destination_mint_authority.to_account_info(),
+                },
+                mint_auth_signer,
+            );
+            interface_mint_to(mint_ctx, destination_minted)?;
+
+            // ===== Accounting =====
+            let global_config_info = ctx.accounts.global_config.to_account_info();
+            ctx.accounts
+                .source_rift
+                .record_unwrap(&plan, actual_sent, &global_config_info, now)?;
+            ctx.accounts.destination_rift.record_wrap(
+                destination_minted,
+                actual_received,
+                destination_fee_credited,
+                destination_fee_routed,
+                &global_config_info,
+                now,
+            )?;
+
+            // **SAME-SLOT GUARD**: Leaving the source is an unwrap, entering the destination a wrap
+            ctx.accounts.source_rift.record_action_slot(current_slot, false);
+            ctx.accounts.destination_rift.record_action_slot(current_slot, true);
+
+            // Entering the destination counts as a wrap by this user (wrap cooldown)
+            let destination_user_activity = &mut ctx.accounts.destination_user_activity;
+            destination_user_activity.rift = destination_key;
+            destination_user_activity.user = ctx.accounts.user.key();
+            destination_user_activity.last_wrap_slot = current_slot;
+            destination_user_activity.bump = ctx.bumps.destination_user_activity;
+
+            msg!(
+                "✅ Migrated position: {} source RIFT → {} underlying → {} destination RIFT",
+                plan.rift_burned,
+                actual_received,
+                destination_minted
+            );
+
+            emit!(PositionMigrated {
+                source_rift: source_key,
+                destination_rift: destination_key,
+                user: ctx.accounts.user.key(),
+                source_rift_burned: plan.rift_burned,
+                unwrap_fee: plan.fee,
+                underlying_received: actual_received,
+                wrap_fee,
+                destination_rift_minted: destination_minted,
+            });
+
+            Ok(())
+        })();
+
+        // Always clear both guards, even on error
+        ctx.accounts.source_rift.reentrancy_guard = false;
+        ctx.accounts.source_rift.reentrancy_guard_slot = 0;
+        ctx.accounts.destination_rift.reentrancy_guard = false;
+        ctx.accounts.destination_rift.reentrancy_guard_slot = 0;
+
+        execution_result
+    }
+
+    /// Admin function: Close any rift regardless of creator (program authority only)
+    pub fn admin_close_rift(ctx: Context<AdminCloseRift>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        // Only program authority can use this function
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        // **FIX ISSUE #1**: Actually mark the rift as closed
+        rift.is_closed = true;
+        rift.closed_at_slot = Clock::get()?.slot;
+
+        // **FIX ISSUE #1**: Reset reentrancy guard to prevent stuck state
+        rift.reentrancy_guard = false;
+        rift.reentrancy_guard_slot = 0;
+
+        // Log the admin close action
+        msg!(
+            "Admin closing rift: {} (original creator: {}) at slot {}",
+            rift.key(),
+            rift.creator,
+            rift.closed_at_slot
+        );
+
+        emit!(RiftAdminClosed {
+            rift: rift.key(),
+            original_creator: rift.creator,
+            admin: ctx.accounts.program_authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Emergency admin function to withdraw tokens from vault
+    /// **CRITICAL SECURITY**: Requires BOTH admin authorities to prevent single-point-of-failure
+    /// Only use in case of critical issues like closed rifts with locked funds
+    ///
+    /// **ACKNOWLEDGED SECURITY TRADE-OFF (High Issue #3):**
+    /// This function does NOT verify:
+    /// 1. That the rift is actually closed
+    /// 2. That the vault belongs to the specified rift
+    /// This is intentional to allow emergency recovery of funds in edge cases where:
+    /// - Rift state is corrupted but vault is valid
+    /// - Need to recover from program bugs or attacks
+    /// - Need manual intervention for stuck funds
+    ///
+    /// MITIGATION: Requires BOTH independent admin signatures (2-of-2 multisig)
+    /// - GlobalConfig.program_authority (default PROGRAM_AUTHORITY: 9KiFDT1jPtATAJktQxQ5nErmmFXbya6kXb6hFasN5pz4)
+    /// - GlobalConfig.admin_authority_2 (default ADMIN_AUTHORITY_2: CPr8qxu9LKx4tU5LWj53z669fzydGwFyJzw6xWarZ3zB)
+    ///
+    /// Both keys must explicitly approve any emergency withdrawal, providing accountability.
+    pub fn admin_emergency_withdraw_vault(
+        ctx: Context<AdminEmergencyWithdrawVault>,
+        amount: u64,
+        closed_rift_pubkey: Pubkey,
+    ) -> Result<()> {
+        // **SECURITY FIX #3**: Require BOTH admin authorities
+        // **GLOBAL CONFIG**: Rotatable authorities (falls back to constants until initialized)
+        let authorities =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?;
+        let admin_1 = authorities.program_authority;
+        let admin_2 = authorities.admin_authority_2;
+
+        require!(
+            ctx.accounts.admin_authority_1.key() == admin_1,
             ErrorCode::UnauthorizedAdmin
         );
         require!(
@@ -3075,11 +4261,14 @@ pub mod rifts_protocol {
         uri: String,
     ) -> Result<()> {
         // Only program authority can use this function
-        let admin_pubkey = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
             ctx.accounts.admin.key() == admin_pubkey,
             ErrorCode::UnauthorizedAdmin
         );
+        require!(!ctx.accounts.rift.config_frozen, ErrorCode::ConfigFrozen);
 
         msg!(
             "Admin updating metadata for rift mint: {}",
@@ -3162,29 +4351,238 @@ pub mod rifts_protocol {
         Ok(())
     }
 
-    /// Clean up stuck accounts from failed rift creation attempts
-    /// **SECURITY FIX**: Only allow creator to clean up their own stuck accounts
-    pub fn cleanup_stuck_accounts(ctx: Context<CleanupStuckAccounts>) -> Result<()> {
-        // **SECURITY FIX**: Require creator signature to prevent griefing
-        // Only the original creator can clean up their stuck accounts
+    /// Creator: update only the RIFT token's metadata URI (e.g. logo/JSON)
+    /// Name and symbol stay locked to the program authority to prevent impersonation
+    pub fn creator_update_metadata_uri(
+        ctx: Context<CreatorUpdateMetadataUri>,
+        uri: String,
+    ) -> Result<()> {
+        let rift = &ctx.accounts.rift;
 
-        msg!(
-            "Cleaning up stuck accounts for creator: {}",
-            ctx.accounts.creator.key()
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
         );
-        msg!("Stuck mint account: {}", ctx.accounts.stuck_rift_mint.key());
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(!rift.config_frozen, ErrorCode::ConfigFrozen);
+        require!(uri.len() <= MAX_METADATA_URI_LEN, ErrorCode::MetadataUriTooLong);
 
-        // Verify this is actually a stuck mint from a failed rift creation
-        // Check that the mint has proper seeds and belongs to this creator
-        // **FIX CRITICAL #14**: Derive PDA using correct seeds matching create_rift
-        let expected_rift_pda = Pubkey::create_program_address(
+        // Token-2022 reallocs the mint for a longer URI but does not fund it - top up rent first
+        let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
+        let required_lamports = {
+            use spl_token_metadata_interface::state::TokenMetadata;
+            let mint_data = rift_mint_info.try_borrow_data()?;
+            let mint_state =
+                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+            let current_metadata = mint_state.get_variable_len_extension::<TokenMetadata>()?;
+            let new_len = mint_data
+                .len()
+                .saturating_sub(current_metadata.uri.len())
+                .checked_add(uri.len())
+                .ok_or(ErrorCode::MathOverflow)?;
+            Rent::get()?.minimum_balance(new_len)
+        };
+        let current_lamports = rift_mint_info.lamports();
+        if required_lamports > current_lamports {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: rift_mint_info.clone(),
+                    },
+                ),
+                required_lamports - current_lamports,
+            )?;
+        }
+
+        let rift_key = rift.key();
+        let mint_auth_seeds = &[
+            b"rift_mint_auth",
+            rift_key.as_ref(),
+            &[ctx.bumps.rift_mint_authority],
+        ];
+        let signer_seeds = &[&mint_auth_seeds[..]];
+
+        use anchor_lang::solana_program::program::invoke_signed;
+        use spl_token_metadata_interface::instruction::update_field;
+        use spl_token_metadata_interface::state::Field;
+
+        let update_uri_ix = update_field(
+            &spl_token_2022::ID,
+            &ctx.accounts.rift_mint.key(),
+            &ctx.accounts.rift_mint_authority.key(),
+            Field::Uri,
+            uri.clone(),
+        );
+
+        invoke_signed(
+            &update_uri_ix,
             &[
-                b"rift",
-                ctx.accounts.underlying_mint.key().as_ref(),
-                ctx.accounts.creator.key().as_ref(),
-                &[ctx.bumps.expected_rift],
+                rift_mint_info,
+                ctx.accounts.rift_mint_authority.to_account_info(),
             ],
-            ctx.program_id,
+            signer_seeds,
+        )?;
+
+        msg!("✅ Metadata URI updated by creator: {}", uri);
+        Ok(())
+    }
+
+    /// Creator or program authority: return mint lamports above the rent-exempt minimum
+    /// (e.g. the unused METADATA_TLV_BUFFER funding) to the creator
+    /// Uses Token-2022 WithdrawExcessLamports signed by the rift mint authority PDA
+    pub fn reclaim_mint_rent_surplus(ctx: Context<ReclaimMintRentSurplus>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.authority.key() == rift.creator
+                || ctx.accounts.authority.key() == program_authority,
+            ErrorCode::Unauthorized
+        );
+
+        // Surplus is measured against the mint's ACTUAL size - never dips below rent-exempt
+        let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(rift_mint_info.data_len());
+        let surplus = rift_mint_info.lamports().saturating_sub(rent_exempt_minimum);
+        require!(surplus > 0, ErrorCode::NoMintRentSurplus);
+
+        let rift_key = rift.key();
+        let mint_auth_seeds = &[
+            b"rift_mint_auth",
+            rift_key.as_ref(),
+            &[ctx.bumps.rift_mint_authority],
+        ];
+        let signer_seeds = &[&mint_auth_seeds[..]];
+
+        invoke_signed(
+            &spl_token_2022::instruction::withdraw_excess_lamports(
+                &spl_token_2022::ID,
+                &rift_mint_info.key(),
+                &ctx.accounts.creator.key(),
+                &ctx.accounts.rift_mint_authority.key(),
+                &[],
+            )?,
+            &[
+                rift_mint_info.clone(),
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.rift_mint_authority.to_account_info(),
+                ctx.accounts.token_2022_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        emit!(MintRentReclaimed {
+            rift: rift_key,
+            rift_mint: rift_mint_info.key(),
+            amount: surplus,
+            destination: ctx.accounts.creator.key(),
+            authority: ctx.accounts.authority.key(),
+        });
+
+        msg!("✅ Reclaimed {} surplus lamports from RIFT mint to creator", surplus);
+        Ok(())
+    }
+
+    /// Admin function: Sweep stray lamports (above rent-exempt) from vault, fees_vault or
+    /// withheld_vault to the protocol treasury (program authority only)
+    /// Uses Token-2022 WithdrawExcessLamports, so the token balance and rent reserve are untouched;
+    /// classic SPL Token vaults have no such instruction and are rejected
+    pub fn sweep_vault_lamports(ctx: Context<SweepVaultLamports>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        let authorities =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?;
+        require!(
+            ctx.accounts.program_authority.key() == authorities.program_authority,
+            ErrorCode::UnauthorizedAdmin
+        );
+        require!(
+            ctx.accounts.treasury.key() == authorities.treasury_wallet,
+            ErrorCode::InvalidTreasuryVault
+        );
+
+        let source_info = ctx.accounts.source_vault.to_account_info();
+        let source_key = source_info.key();
+        require!(
+            source_key != anchor_lang::solana_program::system_program::ID
+                && (source_key == rift.vault
+                    || source_key == rift.fees_vault
+                    || source_key == rift.withheld_vault),
+            ErrorCode::InvalidVault
+        );
+        require!(
+            source_info.owner == &spl_token_2022::ID,
+            ErrorCode::LamportSweepUnsupported
+        );
+
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(source_info.data_len());
+        let surplus = source_info.lamports().saturating_sub(rent_exempt_minimum);
+        require!(surplus > 0, ErrorCode::NoLamportSurplus);
+
+        let rift_key = rift.key();
+        let vault_auth_seeds = &[
+            b"vault_auth",
+            rift_key.as_ref(),
+            &[ctx.bumps.vault_authority],
+        ];
+        let signer_seeds = &[&vault_auth_seeds[..]];
+
+        invoke_signed(
+            &spl_token_2022::instruction::withdraw_excess_lamports(
+                &spl_token_2022::ID,
+                &source_key,
+                &ctx.accounts.treasury.key(),
+                &ctx.accounts.vault_authority.key(),
+                &[],
+            )?,
+            &[
+                source_info.clone(),
+                ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.vault_authority.to_account_info(),
+                ctx.accounts.token_2022_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        emit!(LamportsSwept {
+            rift: rift_key,
+            source: source_key,
+            amount: surplus,
+            destination: ctx.accounts.treasury.key(),
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        msg!("✅ Swept {} stray lamports from {} to treasury", surplus, source_key);
+        Ok(())
+    }
+
+    /// Clean up stuck accounts from failed rift creation attempts
+    /// **SECURITY FIX**: Only allow creator to clean up their own stuck accounts
+    pub fn cleanup_stuck_accounts(ctx: Context<CleanupStuckAccounts>) -> Result<()> {
+        // **SECURITY FIX**: Require creator signature to prevent griefing
+        // Only the original creator can clean up their stuck accounts
+
+        msg!(
+            "Cleaning up stuck accounts for creator: {}",
+            ctx.accounts.creator.key()
+        );
+        msg!("Stuck mint account: {}", ctx.accounts.stuck_rift_mint.key());
+
+        // Verify this is actually a stuck mint from a failed rift creation
+        // Check that the mint has proper seeds and belongs to this creator
+        // **FIX CRITICAL #14**: Derive PDA using correct seeds matching create_rift
+        Pubkey::create_program_address(
+            &[
+                b"rift",
+                ctx.accounts.underlying_mint.key().as_ref(),
+                ctx.accounts.creator.key().as_ref(),
+                &[ctx.bumps.expected_rift],
+            ],
+            ctx.program_id,
         )
         .map_err(|_| ErrorCode::InvalidStuckAccount)?;
 
@@ -3269,6 +4667,114 @@ pub mod rifts_protocol {
         Ok(())
     }
 
+    /// Close an orphaned vault / fees_vault / withheld_vault left behind by a failed rift creation
+    /// The derived rift account must not exist and the vault must hold no tokens; rent goes back
+    /// to the creator. `seed_len = 0` derives the standard rift PDA, otherwise the vanity PDA.
+    pub fn cleanup_stuck_vault(
+        ctx: Context<CleanupStuckVault>,
+        vanity_seed: [u8; 32],
+        seed_len: u8,
+    ) -> Result<()> {
+        require!(seed_len <= 32, ErrorCode::InvalidVanitySeed);
+
+        // Same derivation as create_rift / create_rift_with_vanity_pda (empty seed = standard)
+        let (expected_rift_pda, _) = Pubkey::find_program_address(
+            &[
+                b"rift",
+                ctx.accounts.underlying_mint.key().as_ref(),
+                ctx.accounts.creator.key().as_ref(),
+                &vanity_seed[..seed_len as usize],
+            ],
+            ctx.program_id,
+        );
+        require!(
+            ctx.accounts.expected_rift.key() == expected_rift_pda,
+            ErrorCode::InvalidStuckAccount
+        );
+        // Check that no actual rift account exists (it's truly stuck)
+        require!(
+            ctx.accounts.expected_rift.data_is_empty(),
+            ErrorCode::RiftAlreadyExists
+        );
+
+        // The stuck vault must be one of the rift's vault PDAs
+        let stuck_vault_key = ctx.accounts.stuck_vault.key();
+        let is_rift_vault = [&b"vault"[..], b"fees_vault", b"withheld_vault"]
+            .iter()
+            .any(|prefix| {
+                let (vault_pda, _) = Pubkey::find_program_address(
+                    &[prefix, expected_rift_pda.as_ref()],
+                    ctx.program_id,
+                );
+                vault_pda == stuck_vault_key
+            });
+        require!(is_rift_vault, ErrorCode::InvalidStuckAccount);
+
+        let (vault_authority, vault_authority_bump) = Pubkey::find_program_address(
+            &[b"vault_auth", expected_rift_pda.as_ref()],
+            ctx.program_id,
+        );
+        require!(
+            ctx.accounts.vault_authority.key() == vault_authority,
+            ErrorCode::InvalidVaultAuthority
+        );
+
+        // Token account owned by the program passed in, with a zero token balance
+        require!(
+            ctx.accounts.stuck_vault.owner == ctx.accounts.token_program.key,
+            ErrorCode::InvalidTokenAccount
+        );
+        {
+            let vault_data = ctx.accounts.stuck_vault.try_borrow_data()?;
+            require!(vault_data.len() >= 72, ErrorCode::InvalidTokenAccount);
+            let vault_amount = u64::from_le_bytes(
+                vault_data[64..72]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            require!(vault_amount == 0, ErrorCode::VaultNotEmpty);
+        }
+
+        let rent_to_return = ctx.accounts.stuck_vault.lamports();
+        let vault_auth_seeds = &[
+            b"vault_auth",
+            expected_rift_pda.as_ref(),
+            &[vault_authority_bump],
+        ];
+
+        // SPL Token and Token-2022 share the CloseAccount layout
+        invoke_signed(
+            &spl_token_2022::instruction::close_account(
+                ctx.accounts.token_program.key,
+                &stuck_vault_key,
+                ctx.accounts.creator.key,         // Rent destination
+                ctx.accounts.vault_authority.key, // Authority
+                &[],                              // No multisig
+            )?,
+            &[
+                ctx.accounts.stuck_vault.to_account_info(),
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.vault_authority.to_account_info(),
+            ],
+            &[&vault_auth_seeds[..]],
+        )?;
+
+        msg!(
+            "✅ Closed stuck vault {}, returned {} lamports to creator",
+            stuck_vault_key,
+            rent_to_return
+        );
+
+        emit!(StuckVaultCleaned {
+            creator: ctx.accounts.creator.key(),
+            stuck_vault: stuck_vault_key,
+            expected_rift: expected_rift_pda,
+            rent_returned: rent_to_return,
+        });
+
+        Ok(())
+    }
+
     /// **FIX CRITICAL #10**: Cleanup stuck VANITY rift accounts
     /// This instruction handles vanity rifts that failed during creation
     /// Vanity rifts use different PDA seeds than regular rifts, so they need a separate cleanup function
@@ -3375,7 +4881,9 @@ pub mod rifts_protocol {
         let rift = &mut ctx.accounts.rift;
 
         // Only PROGRAM_AUTHORITY can reset the guard
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
             ctx.accounts.program_authority.key() == program_authority,
             ErrorCode::UnauthorizedAdmin
@@ -3396,78 +4904,363 @@ pub mod rifts_protocol {
         Ok(())
     }
 
-    /// **SECURITY FIX #50**: Set oracle account addresses (creator only)
-    /// This binds specific Switchboard accounts to the rift for validation
-    pub fn set_oracle_accounts(
-        ctx: Context<SetOracleAccounts>,
-        switchboard_account: Option<Pubkey>,
-    ) -> Result<()> {
-        let rift = &mut ctx.accounts.rift;
-
-        // Only creator can set oracle accounts
+    /// Admin function: Permanently lock the RIFT mint's configuration (program authority only)
+    /// Renounces the metadata update authority and the transfer_fee_config_authority; the
+    /// rift_mint_authority PDA keeps minting rights so wraps continue to work
+    pub fn renounce_mint_authorities(ctx: Context<RenounceMintAuthorities>) -> Result<()> {
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
-            ctx.accounts.creator.key() == rift.creator,
-            ErrorCode::Unauthorized
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
         );
 
-        // Validate accounts are not system program
-        if let Some(switchboard) = switchboard_account {
-            require!(
-                switchboard != anchor_lang::solana_program::system_program::ID,
-                ErrorCode::InvalidOracleAccount
-            );
-        }
+        let rift = &mut ctx.accounts.rift;
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no metadata / transfer fee config
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+        require!(!rift.config_frozen, ErrorCode::ConfigFrozen);
 
-        // Set oracle accounts
-        rift.switchboard_feed_account = switchboard_account;
+        let rift_key = rift.key();
+        let mint_auth_seeds = &[
+            b"rift_mint_auth",
+            rift_key.as_ref(),
+            &[ctx.bumps.rift_mint_authority],
+        ];
+        let signer_seeds = &[&mint_auth_seeds[..]];
 
-        msg!(
-            "Oracle accounts set - Switchboard: {:?}",
-            switchboard_account
-        );
+        // 1. Metadata: update authority (rift_mint_authority PDA) → None
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::update_authority(
+                &spl_token_2022::ID,
+                &ctx.accounts.rift_mint.key(),
+                &ctx.accounts.rift_mint_authority.key(),
+                spl_pod::optional_keys::OptionalNonZeroPubkey::default(),
+            ),
+            &[
+                ctx.accounts.rift_mint.to_account_info(),
+                ctx.accounts.rift_mint_authority.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        // 2. Transfer fee config authority (program authority key) → None
+        invoke(
+            &spl_token_2022::instruction::set_authority(
+                &spl_token_2022::ID,
+                &ctx.accounts.rift_mint.key(),
+                None,
+                spl_token_2022::instruction::AuthorityType::TransferFeeConfig,
+                &ctx.accounts.program_authority.key(),
+                &[],
+            )?,
+            &[
+                ctx.accounts.rift_mint.to_account_info(),
+                ctx.accounts.program_authority.to_account_info(),
+            ],
+        )?;
+
+        rift.config_frozen = true;
+
+        emit!(MintAuthoritiesRenounced {
+            rift: rift_key,
+            rift_mint: ctx.accounts.rift_mint.key(),
+            authority: ctx.accounts.program_authority.key(),
+        });
 
+        msg!("✅ RIFT mint config frozen: metadata + transfer fee authorities renounced");
         Ok(())
     }
 
-    /// **FIX ISSUE #5**: Propose oracle account change with 24h timelock
-    /// Step 1: Creator proposes new oracle accounts
-    pub fn propose_oracle_change(
-        ctx: Context<ProposeOracleChange>,
-        switchboard_account: Option<Pubkey>,
-    ) -> Result<()> {
+    /// Admin function: Assign or clear the rift's pause-only guardian (program authority only)
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
-        let current_time = Clock::get()?.unix_timestamp;
 
-        // Only creator can propose
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
-            ctx.accounts.creator.key() == rift.creator,
-            ErrorCode::Unauthorized
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
         );
 
-        // Validate accounts are not system program
-        if let Some(switchboard) = switchboard_account {
-            require!(
-                switchboard != anchor_lang::solana_program::system_program::ID,
-                ErrorCode::InvalidOracleAccount
-            );
-        }
-
-        // Set pending change with timestamp
-        rift.oracle_change_pending = true;
-        rift.pending_switchboard_account = switchboard_account;
-        rift.oracle_change_timestamp = current_time;
-
-        let effective_time = current_time + ORACLE_CHANGE_DELAY;
-        msg!(
-            "Oracle change proposed - effective after {} (24h from now)",
-            effective_time
-        );
-        msg!("Pending Switchboard: {:?}", switchboard_account);
+        let old_guardian = rift.guardian;
+        rift.guardian = guardian;
 
-        emit!(OracleChangeProposed {
+        emit!(GuardianUpdated {
             rift: rift.key(),
-            switchboard_account,
-            effective_time,
+            old_guardian,
+            new_guardian: guardian,
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        match guardian {
+            Some(guardian) => msg!("✅ Guardian set: {}", guardian),
+            None => msg!("✅ Guardian cleared"),
+        }
+
+        Ok(())
+    }
+
+    /// Admin function: Tune how long a stuck reentrancy guard blocks the rift (program authority only)
+    /// Bounded to 150 (~1 min) - 432000 (~2 days) slots
+    pub fn set_reentrancy_timeout(
+        ctx: Context<SetReentrancyTimeout>,
+        reentrancy_timeout_slots: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
+        );
+        require!(
+            (MIN_REENTRANCY_TIMEOUT_SLOTS..=MAX_REENTRANCY_TIMEOUT_SLOTS)
+                .contains(&reentrancy_timeout_slots),
+            ErrorCode::InvalidReentrancyTimeout
+        );
+
+        let old_timeout_slots = rift.reentrancy_timeout();
+        rift.reentrancy_timeout_slots = reentrancy_timeout_slots;
+
+        emit!(ReentrancyTimeoutUpdated {
+            rift: rift.key(),
+            old_timeout_slots,
+            new_timeout_slots: reentrancy_timeout_slots,
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        msg!(
+            "✅ Reentrancy timeout: {} → {} slots",
+            old_timeout_slots,
+            reentrancy_timeout_slots
+        );
+
+        Ok(())
+    }
+
+    /// Admin: set how many vanity rifts a creator may create (initializes the counter if needed)
+    pub fn admin_set_creator_rift_limit(
+        ctx: Context<AdminSetCreatorRiftLimit>,
+        max_rifts: u32,
+    ) -> Result<()> {
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        let creator_rift_count = &mut ctx.accounts.creator_rift_count;
+        if creator_rift_count.creator == Pubkey::default() {
+            creator_rift_count.creator = ctx.accounts.creator.key();
+            creator_rift_count.bump = ctx.bumps.creator_rift_count;
+        }
+        let old_max_rifts = creator_rift_count.max_rifts;
+        creator_rift_count.max_rifts = max_rifts;
+
+        emit!(CreatorRiftLimitUpdated {
+            creator: ctx.accounts.creator.key(),
+            old_max_rifts,
+            new_max_rifts: max_rifts,
+            rift_count: creator_rift_count.rift_count,
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        msg!(
+            "✅ Creator {} vanity rift limit: {} → {} ({} created)",
+            ctx.accounts.creator.key(),
+            old_max_rifts,
+            max_rifts,
+            creator_rift_count.rift_count
+        );
+
+        Ok(())
+    }
+
+    /// **SECURITY FIX #50**: Set oracle account addresses (creator only)
+    /// This binds specific Switchboard accounts to the rift for validation
+    pub fn set_oracle_accounts(
+        ctx: Context<SetOracleAccounts>,
+        switchboard_account: Option<Pubkey>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        // Only creator can set oracle accounts
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+
+        // Validate accounts are not system program
+        if let Some(switchboard) = switchboard_account {
+            require!(
+                switchboard != anchor_lang::solana_program::system_program::ID,
+                ErrorCode::InvalidOracleAccount
+            );
+        }
+
+        // Set oracle accounts
+        rift.switchboard_feed_account = switchboard_account;
+
+        msg!(
+            "Oracle accounts set - Switchboard: {:?}",
+            switchboard_account
+        );
+
+        Ok(())
+    }
+
+    /// Creator: select which oracle update instruction the rift accepts
+    /// Switchboard requires a bound feed; Manual stays cross-checked against a bound feed
+    pub fn set_oracle_source(ctx: Context<SetOracleSource>, oracle_source: OracleType) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        match oracle_source {
+            OracleType::Switchboard => require!(
+                rift.switchboard_feed_account.is_some(),
+                ErrorCode::OracleAccountNotSet
+            ),
+            OracleType::Manual => {}
+            OracleType::Pyth => return Err(ErrorCode::OracleSourceUnsupported.into()),
+        }
+
+        let old_source = rift.oracle_source;
+        rift.oracle_source = oracle_source;
+
+        emit!(OracleSourceUpdated {
+            rift: rift.key(),
+            old_source,
+            new_source: oracle_source,
+            creator: ctx.accounts.creator.key(),
+        });
+
+        msg!("✅ Oracle source: {:?} → {:?}", old_source, oracle_source);
+
+        Ok(())
+    }
+
+    /// Creator: step 1 of handing the rift to another key (e.g. a DAO or multisig)
+    /// Nothing changes until `new_creator` signs accept_creator_transfer; proposing again replaces
+    /// the pending key. PDAs are seeded by the rift address, not the creator, so none move.
+    pub fn propose_creator_transfer(
+        ctx: Context<ProposeCreatorTransfer>,
+        new_creator: Pubkey,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            new_creator != Pubkey::default() && new_creator != rift.creator,
+            ErrorCode::InvalidNewCreator
+        );
+
+        rift.pending_creator = Some(new_creator);
+
+        emit!(CreatorTransferProposed {
+            rift: rift.key(),
+            current_creator: rift.creator,
+            pending_creator: new_creator,
+        });
+
+        msg!("✅ Creator transfer proposed: {} → {}", rift.creator, new_creator);
+
+        Ok(())
+    }
+
+    /// Pending creator: step 2 - sign to take over rift.creator
+    pub fn accept_creator_transfer(ctx: Context<AcceptCreatorTransfer>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        let pending_creator = rift
+            .pending_creator
+            .ok_or(ErrorCode::NoCreatorTransferPending)?;
+        require!(
+            ctx.accounts.new_creator.key() == pending_creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        let old_creator = rift.creator;
+        rift.creator = pending_creator;
+        rift.pending_creator = None;
+
+        emit!(CreatorTransferAccepted {
+            rift: rift.key(),
+            old_creator,
+            new_creator: pending_creator,
+        });
+
+        msg!("✅ Creator transferred: {} → {}", old_creator, pending_creator);
+
+        Ok(())
+    }
+
+    /// **FIX ISSUE #5**: Propose oracle account change with 24h timelock
+    /// Step 1: Creator proposes new oracle accounts
+    pub fn propose_oracle_change(
+        ctx: Context<ProposeOracleChange>,
+        switchboard_account: Option<Pubkey>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // Only creator can propose
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+
+        // Validate accounts are not system program
+        if let Some(switchboard) = switchboard_account {
+            require!(
+                switchboard != anchor_lang::solana_program::system_program::ID,
+                ErrorCode::InvalidOracleAccount
+            );
+        }
+
+        // Rate-limit proposals so the 24h timer can't be reset indefinitely
+        // oracle_change_timestamp is left untouched by cancel, so cancel + re-propose is limited too
+        if rift.oracle_change_timestamp > 0 {
+            let next_allowed = rift
+                .oracle_change_timestamp
+                .checked_add(ORACLE_PROPOSAL_COOLDOWN)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(
+                current_time >= next_allowed,
+                ErrorCode::OracleProposalTooFrequent
+            );
+        }
+
+        // Set pending change with timestamp
+        rift.oracle_change_pending = true;
+        rift.pending_switchboard_account = switchboard_account;
+        rift.oracle_change_timestamp = current_time;
+
+        let effective_time = current_time + ORACLE_CHANGE_DELAY;
+        msg!(
+            "Oracle change proposed - effective after {} (24h from now)",
+            effective_time
+        );
+        msg!("Pending Switchboard: {:?}", switchboard_account);
+
+        emit!(OracleChangeProposed {
+            rift: rift.key(),
+            switchboard_account,
+            effective_time,
         });
 
         Ok(())
@@ -3550,119 +5343,27 @@ pub mod rifts_protocol {
         ctx: Context<DistributeFeesFromVault>,
         amount: u64,
     ) -> Result<()> {
-        let rift = &mut ctx.accounts.rift;
+        let rift = &ctx.accounts.rift;
 
         // **MANUAL VALIDATION**: Validate underlying_mint (converted to UncheckedAccount to reduce stack usage)
-        // 1. Verify owner is Token program (SPL Token or Token-2022)
-        require!(
-            ctx.accounts.underlying_mint.owner == &anchor_spl::token::ID
-                || ctx.accounts.underlying_mint.owner == &spl_token_2022::ID,
-            ErrorCode::InvalidProgramId
-        );
-        // 2. Deserialize as Mint to ensure it's a valid mint account
         // **TOKEN-2022 FIX**: Handle both SPL Token and Token-2022 mints
-        let underlying_mint_data = ctx.accounts.underlying_mint.try_borrow_data()?;
-        require!(underlying_mint_data.len() >= 45, ErrorCode::InvalidMint);
-        let underlying_decimals = underlying_mint_data[44]; // decimals at offset 44
-        let is_token_2022 = ctx.accounts.underlying_mint.owner == &spl_token_2022::ID;
-        if is_token_2022 {
-            // Token-2022 mints have extensions, use StateWithExtensions
-            spl_token_2022::extension::StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&underlying_mint_data)
-                .map_err(|_| ErrorCode::InvalidMint)?;
-        } else {
-            // Standard SPL Token mint
-            spl_token::state::Mint::unpack(&underlying_mint_data)
-                .map_err(|_| ErrorCode::InvalidMint)?;
-        }
-        drop(underlying_mint_data); // Release borrow before continuing
-        // 3. Verify key matches expected value from rift
         require!(
             ctx.accounts.underlying_mint.key() == rift.underlying_mint,
             ErrorCode::InvalidMint
         );
-
-        // **MANUAL VALIDATION**: Validate treasury_account
-        // 1. Verify it's owned by token program
-        require!(
-            ctx.accounts.treasury_account.owner == &anchor_spl::token::ID
-                || ctx.accounts.treasury_account.owner == &spl_token_2022::ID,
-            ErrorCode::InvalidProgramId
-        );
-        // 2. Deserialize as TokenAccount and validate owner/mint binding
-        // **TOKEN-2022 FIX**: Handle both SPL Token and Token-2022 accounts
-        // **FIX HIGH #1**: Enforce treasury_account.owner == treasury_wallet AND correct mint
-        let treasury_data = ctx.accounts.treasury_account.try_borrow_data()?;
-        let is_treasury_token_2022 = ctx.accounts.treasury_account.owner == &spl_token_2022::ID;
-        let treasury_token_owner: Pubkey;
-        let treasury_token_mint: Pubkey;
-        if is_treasury_token_2022 {
-            let treasury_token_account = spl_token_2022::extension::StateWithExtensions::<spl_token_2022::state::Account>::unpack(&treasury_data)
-                .map_err(|_| ErrorCode::InvalidTreasuryVault)?;
-            treasury_token_owner = treasury_token_account.base.owner;
-            treasury_token_mint = treasury_token_account.base.mint;
-        } else {
-            let treasury_token_account = spl_token::state::Account::unpack(&treasury_data)
-                .map_err(|_| ErrorCode::InvalidTreasuryVault)?;
-            treasury_token_owner = treasury_token_account.owner;
-            treasury_token_mint = treasury_token_account.mint;
-        }
-        drop(treasury_data);
-
-        // **FIX HIGH #1**: Enforce token account owner matches treasury_wallet
-        require!(
-            treasury_token_owner == rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?,
-            ErrorCode::InvalidTreasuryVault
-        );
-        // **FIX HIGH #1**: Enforce token account mint matches underlying_mint
-        require!(
-            treasury_token_mint == rift.underlying_mint,
-            ErrorCode::InvalidTreasuryVault
-        );
-
-        // **MANUAL VALIDATION**: Validate partner_account if present
-        // **FIX HIGH #1**: Enforce partner_account.owner == partner_wallet AND correct mint
-        if ctx.accounts.partner_account.is_some() {
-            let partner_account = ctx.accounts.partner_account.as_ref().unwrap();
-            // 1. Verify it's owned by token program
-            require!(
-                partner_account.owner == &anchor_spl::token::ID
-                    || partner_account.owner == &spl_token_2022::ID,
-                ErrorCode::InvalidProgramId
-            );
-            // 2. Deserialize as TokenAccount and validate owner/mint binding
-            // **TOKEN-2022 FIX**: Handle both SPL Token and Token-2022 accounts
-            let partner_data = partner_account.try_borrow_data()?;
-            let is_partner_token_2022 = partner_account.owner == &spl_token_2022::ID;
-            let partner_token_owner: Pubkey;
-            let partner_token_mint: Pubkey;
-            if is_partner_token_2022 {
-                let partner_token_account = spl_token_2022::extension::StateWithExtensions::<spl_token_2022::state::Account>::unpack(&partner_data)
-                    .map_err(|_| ErrorCode::InvalidPartnerVault)?;
-                partner_token_owner = partner_token_account.base.owner;
-                partner_token_mint = partner_token_account.base.mint;
-            } else {
-                let partner_token_account = spl_token::state::Account::unpack(&partner_data)
-                    .map_err(|_| ErrorCode::InvalidPartnerVault)?;
-                partner_token_owner = partner_token_account.owner;
-                partner_token_mint = partner_token_account.mint;
-            }
-            drop(partner_data);
-
-            // **FIX HIGH #1**: Enforce token account owner matches partner_wallet
-            require!(
-                partner_token_owner == rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?,
-                ErrorCode::InvalidPartnerVault
-            );
-            // **FIX HIGH #1**: Enforce token account mint matches underlying_mint
-            require!(
-                partner_token_mint == rift.underlying_mint,
-                ErrorCode::InvalidPartnerVault
-            );
-        }
+        let underlying_decimals = {
+            let mint_data = ctx.accounts.underlying_mint.try_borrow_data()?;
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)
+                .map_err(|_| ErrorCode::InvalidMint)?
+                .base
+                .decimals
+        };
 
         // **AUTHORIZATION**: Creator, partner, treasury, or PROGRAM_AUTHORITY can distribute fees
         // **FIX ISSUE #2**: Use ok_or instead of expect to prevent panic on corrupted state
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         let partner_wallet = rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?;
         let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
 
@@ -3674,104 +5375,97 @@ pub mod rifts_protocol {
         require!(is_authorized, ErrorCode::Unauthorized);
 
         require!(amount > 0, ErrorCode::InvalidAmount);
-        require!(rift.treasury_wallet.is_some(), ErrorCode::TreasuryNotSet);
 
-        // Verify treasury_wallet matches
+        // **FIX HIGH #1**: Recipient token accounts must be owned by the rift's wallets and
+        // hold the underlying (same checks as claim_wrap_fees and the auto-distribution)
         require!(
-            ctx.accounts.treasury_wallet.key() == rift.treasury_wallet.unwrap(),
+            ctx.accounts.treasury_wallet.key() == treasury_wallet,
             ErrorCode::InvalidTreasuryVault
         );
+        validate_fee_recipient(
+            &ctx.accounts.treasury_account,
+            &treasury_wallet,
+            &rift.underlying_mint,
+            ErrorCode::InvalidTreasuryVault,
+        )?;
+        if let Some(partner_wallet_account) = &ctx.accounts.partner_wallet {
+            require!(
+                partner_wallet_account.key() == partner_wallet,
+                ErrorCode::InvalidPartnerVault
+            );
+        }
+        if let Some(partner_account) = &ctx.accounts.partner_account {
+            validate_fee_recipient(
+                partner_account,
+                &partner_wallet,
+                &rift.underlying_mint,
+                ErrorCode::InvalidPartnerVault,
+            )?;
+        }
 
         // **FEE ROUTING UPDATE**: Check fees_vault balance instead of backing vault
         let fees_vault_balance = ctx.accounts.fees_vault.amount;
 
         require!(amount <= fees_vault_balance, ErrorCode::InsufficientFees);
 
-        msg!("Distributing {} fees from fees_vault (available: {}) to treasury and partner (50/50 split)",
-            amount, fees_vault_balance);
-
-        // **FEE SPLIT**: Always split 50/50 between partner and treasury
-        // Partner always exists (defaults to creator if not provided at rift creation)
-        require!(
-            ctx.accounts.partner_account.is_some(),
-            ErrorCode::MissingPartnerVault
-        );
-        require!(
-            ctx.accounts.partner_wallet.is_some(),
-            ErrorCode::MissingPartnerVault
-        );
-
-        // Verify partner_wallet matches
-        let partner_wallet_key = ctx.accounts.partner_wallet.as_ref().ok_or(ErrorCode::MissingPartnerVault)?.key();
-        require!(
-            partner_wallet_key == rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?,
-            ErrorCode::InvalidPartnerVault
-        );
+        msg!("Distributing {} fees from fees_vault (available: {}) to treasury and partner (partner share: {} bps)",
+            amount, fees_vault_balance, rift.partner_fee_bps);
 
-        // **FIX CRITICAL #2**: 50/50 split with no truncation loss
-        // For odd amounts, treasury gets the extra 1 token
-        let partner_amount = amount.checked_div(2).ok_or(ErrorCode::MathOverflow)?;
-        let treasury_amount = amount
-            .checked_sub(partner_amount)
+        // **PROTOCOL SKIM**: The skimmed share of wrap/unwrap fees is paid out before anything else
+        let protocol_skim_amount = rift.protocol_fees_owed.min(amount);
+        let cut_base = amount
+            .checked_sub(protocol_skim_amount)
             .ok_or(ErrorCode::MathOverflow)?;
-        msg!("Partner amount: {} (~50%)", partner_amount);
-        msg!("Treasury amount: {} (~50%)", treasury_amount);
-
-        // **FIX MEDIUM #9**: Check balance before transfers to detect transfer fee impacts
-        let fees_vault_balance_before = ctx.accounts.fees_vault.amount;
 
         // Setup vault authority seeds
         let rift_key = rift.key();
-        let vault_auth_seeds = &[
-            b"vault_auth",
-            rift_key.as_ref(),
-            &[ctx.bumps.vault_authority],
-        ];
-        let signer = &[&vault_auth_seeds[..]];
+        let vault_auth_bump = [ctx.bumps.vault_authority];
+        let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
+        let signer = &[vault_auth_seeds];
+
+        transfer_protocol_skim(
+            protocol_skim_amount,
+            ctx.accounts.fees_vault.to_account_info(),
+            ctx.accounts.underlying_mint.to_account_info(),
+            underlying_decimals,
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault_authority.to_account_info(),
+            signer,
+            ctx.accounts
+                .protocol_treasury_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            &ctx.accounts.global_config.to_account_info(),
+        )?;
 
-        // Transfer to partner if applicable
-        if partner_amount > 0 {
-            let partner_account = ctx
-                .accounts
+        // **PROTOCOL FEE**: Protocol cut comes off the top; partner/treasury split the remainder
+        // **FIX CRITICAL #2**: Configurable split with no truncation loss (remainder to treasury)
+        let (protocol_amount, partner_amount, treasury_amount) = transfer_fee_split(
+            rift,
+            cut_base,
+            ctx.accounts.fees_vault.to_account_info(),
+            ctx.accounts.underlying_mint.to_account_info(),
+            underlying_decimals,
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.vault_authority.to_account_info(),
+            signer,
+            ctx.accounts.treasury_account.to_account_info(),
+            ctx.accounts
                 .partner_account
                 .as_ref()
-                .ok_or(ErrorCode::MissingPartnerAccount)?;
+                .map(|account| account.to_account_info()),
+            ctx.accounts
+                .protocol_fee_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            &ctx.accounts.global_config.to_account_info(),
+        )?;
 
-            let partner_transfer_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                TransferChecked {
-                    from: ctx.accounts.fees_vault.to_account_info(),
-                    to: partner_account.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                    mint: ctx.accounts.underlying_mint.to_account_info(),
-                },
-                signer,
-            );
-            // **TOKEN-2022 FIX**: Use transfer_checked for Token-2022 compatibility
-            interface_transfer_checked(partner_transfer_ctx, partner_amount, underlying_decimals)?;
-            msg!("✅ Sent {} to partner from fees_vault", partner_amount);
-        }
-
-        // Transfer to treasury from fees_vault
-        // **TOKEN-2022 FIX**: Use transfer_checked for Token-2022 compatibility
-        let treasury_transfer_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.fees_vault.to_account_info(),
-                to: ctx.accounts.treasury_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-                mint: ctx.accounts.underlying_mint.to_account_info(),
-            },
-            signer,
-        );
-        interface_transfer_checked(treasury_transfer_ctx, treasury_amount, underlying_decimals)?;
-
-        // **FIX MEDIUM #9**: Reload and verify actual sent amount to detect transfer fees
-        ctx.accounts.fees_vault.reload()?;
-        let fees_vault_balance_after = ctx.accounts.fees_vault.amount;
-        let actual_sent = fees_vault_balance_before
-            .checked_sub(fees_vault_balance_after)
-            .ok_or(ErrorCode::MathOverflow)?;
+        // **FIX MEDIUM #9**: Reload and verify actual sent amount to detect transfer fees
+        ctx.accounts.fees_vault.reload()?;
+        let actual_sent = fees_vault_balance
+            .checked_sub(ctx.accounts.fees_vault.amount)
+            .ok_or(ErrorCode::MathOverflow)?;
 
         // **FIX MEDIUM #3 (Audit)**: Tighten fee tolerance to match max underlying fee (1%)
         // Previously 95% - now 98% to allow for max 2% total leakage (two 1% transfers)
@@ -3784,10 +5478,15 @@ pub mod rifts_protocol {
 
         // **FIX MEDIUM #4 (Audit)**: Decrement total_fees_collected after successful distribution
         // Uses actual_sent (post balance diff) to ensure accurate accounting even with transfer fees
+        let rift = &mut ctx.accounts.rift;
         rift.total_fees_collected = rift
             .total_fees_collected
             .checked_sub(actual_sent)
             .ok_or(ErrorCode::MathOverflow)?;
+        rift.protocol_fees_owed = rift
+            .protocol_fees_owed
+            .checked_sub(protocol_skim_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
 
         msg!(
             "✅ Distributed {} fees (treasury: {}, partner: {})",
@@ -3800,23 +5499,34 @@ pub mod rifts_protocol {
             actual_sent
         );
 
+        emit!(FeesDistributed {
+            rift: rift.key(),
+            amount,
+            protocol_skim_amount,
+            protocol_amount,
+            treasury_amount,
+            partner_amount,
+            distributor: ctx.accounts.payer.key(),
+            keeper: ctx.accounts.payer.key(),
+        });
+
         Ok(())
     }
 
-    /// Owner only: Update treasury wallet
-    /// **FIX HIGH #5**: REMOVED update_treasury_wallet function
-    /// Treasury wallet is IMMUTABLE after rift creation because:
-    /// 1. Mint's withdraw_withheld_authority is set to TREASURY_WALLET at creation
-    /// 2. This authority cannot be changed after mint initialization
-    /// 3. Changing rift.treasury_wallet would create mismatch with mint authority
-    /// 4. New treasury could not claim withheld fees (only old hardcoded key could)
-    ///
-    /// SECURITY: Treasury is intentionally immutable to prevent authority confusion
-    /// If treasury compromise is a concern, create new rift with new treasury
-    ///
-    /// Previous function removed to prevent misleading treasury "updates" that don't work
-
-    /// Admin function: Withdraw funds from fee collector vault
+    // Owner only: Update treasury wallet
+    // **FIX HIGH #5**: REMOVED update_treasury_wallet function
+    // Treasury wallet is IMMUTABLE after rift creation because:
+    // 1. Mint's withdraw_withheld_authority is set to TREASURY_WALLET at creation
+    // 2. This authority cannot be changed after mint initialization
+    // 3. Changing rift.treasury_wallet would create mismatch with mint authority
+    // 4. New treasury could not claim withheld fees (only old hardcoded key could)
+    //
+    // SECURITY: Treasury is intentionally immutable to prevent authority confusion
+    // If treasury compromise is a concern, create new rift with new treasury
+    //
+    // Previous function removed to prevent misleading treasury "updates" that don't work
+
+    // Admin function: Withdraw funds from fee collector vault
     // REMOVED: admin_withdraw_fee_collector - obsolete after removing external fee_collector program
     // Now using SPL Token-2022's claim_withheld_fees instead
 
@@ -3828,6 +5538,9 @@ pub mod rifts_protocol {
     pub fn admin_claim_withheld_fees(ctx: Context<AdminClaimWithheldFees>) -> Result<()> {
         let rift = &ctx.accounts.rift;
 
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no transfer fee / withheld fees
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
         // **WITHHELD AUTHORITY FIX**: Use treasury_wallet as authority (matches mint initialization)
         // The mint's withdraw_withheld_authority is set to rift.treasury_wallet during creation
         let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
@@ -3893,6 +5606,95 @@ pub mod rifts_protocol {
             destination: ctx.accounts.withheld_vault.key(), // **FEE ROUTING**: Withheld vault where fees are sent
             source_account: ctx.accounts.source_account.key(),
             claimer: ctx.accounts.treasury_signer.key(),
+            keeper: ctx.accounts.treasury_signer.key(),
+        });
+
+        Ok(())
+    }
+
+    /// **TOKEN-2022**: Harvest withheld fees from one account straight into the treasury's RIFT ATA
+    /// Skips the withheld_vault → distribute round trip. Only for rifts where the whole split
+    /// would go to treasury anyway: partner == treasury (or partner_fee_bps == 0) and no protocol cut
+    pub fn claim_withheld_fees_direct(ctx: Context<ClaimWithheldFeesDirect>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no transfer fee / withheld fees
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
+        // **WITHHELD AUTHORITY FIX**: treasury_wallet is the mint's withdraw_withheld_authority
+        let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
+        require!(
+            ctx.accounts.treasury_signer.key() == treasury_wallet,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        // Direct routing is only lossless when treasury would receive everything
+        let partner_wallet = rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?;
+        require!(
+            partner_wallet == treasury_wallet || rift.partner_fee_bps == 0,
+            ErrorCode::DirectClaimNotAllowed
+        );
+        require!(
+            GlobalConfig::load_distribution_fee_bps(&ctx.accounts.global_config.to_account_info())?
+                == 0,
+            ErrorCode::DirectClaimNotAllowed
+        );
+
+        // Destination must be the treasury's canonical RIFT ATA
+        let expected_treasury_ata =
+            anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                &treasury_wallet,
+                &rift.rift_mint,
+                &spl_token_2022::ID,
+            );
+        require!(
+            ctx.accounts.treasury_account.key() == expected_treasury_ata,
+            ErrorCode::InvalidTreasuryVault
+        );
+
+        use spl_token_2022::extension::transfer_fee::instruction::withdraw_withheld_tokens_from_accounts;
+
+        let source_pubkeys = [&ctx.accounts.source_account.key()];
+        let treasury_balance_before = ctx.accounts.treasury_account.amount;
+
+        invoke(
+            &withdraw_withheld_tokens_from_accounts(
+                &spl_token_2022::ID,
+                &ctx.accounts.rift_mint.key(),
+                &ctx.accounts.treasury_account.key(),
+                &ctx.accounts.treasury_signer.key(),
+                &[], // No multisig
+                &source_pubkeys,
+            )
+            .map_err(|_| ErrorCode::InvalidMint)?,
+            &[
+                ctx.accounts.rift_mint.to_account_info(),
+                ctx.accounts.treasury_account.to_account_info(),
+                ctx.accounts.treasury_signer.to_account_info(),
+                ctx.accounts.source_account.to_account_info(),
+            ],
+        )?;
+
+        ctx.accounts.treasury_account.reload()?;
+        let amount = ctx
+            .accounts
+            .treasury_account
+            .amount
+            .checked_sub(treasury_balance_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "✅ Claimed {} withheld fees from {} directly to treasury",
+            amount,
+            ctx.accounts.source_account.key()
+        );
+
+        emit!(WithheldFeesClaimedDirect {
+            rift: ctx.accounts.rift.key(),
+            source_account: ctx.accounts.source_account.key(),
+            treasury_account: ctx.accounts.treasury_account.key(),
+            amount,
+            claimer: ctx.accounts.treasury_signer.key(),
         });
 
         Ok(())
@@ -3901,19 +5703,28 @@ pub mod rifts_protocol {
     /// **TOKEN-2022**: Admin function to update transfer fee on existing rift
     /// Only PROGRAM_AUTHORITY can modify fees (set as transfer_fee_config_authority)
     /// Maximum fee is capped at 2% (200 bps) for safety
+    /// `max_transfer_fee`: Token-2022 maximum fee per transfer (None = keep the mint's current cap)
     pub fn admin_set_transfer_fee(
         ctx: Context<AdminSetTransferFee>,
         new_fee_bps: u16,
+        max_transfer_fee: Option<u64>,
     ) -> Result<()> {
-        let rift = &ctx.accounts.rift;
+        let rift = &mut ctx.accounts.rift;
 
         // Only PROGRAM_AUTHORITY can modify transfer fees
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
             ctx.accounts.program_authority.key() == program_authority,
             ErrorCode::UnauthorizedAdmin
         );
 
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no transfer fee / withheld fees
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+        // **IMMUTABLE CONFIG**: transfer_fee_config_authority has been renounced
+        require!(!rift.config_frozen, ErrorCode::ConfigFrozen);
+
         // Validate fee is within acceptable range (max 2% = 200 bps)
         const MAX_TRANSFER_FEE_BPS: u16 = 200; // 2%
         require!(
@@ -3921,6 +5732,20 @@ pub mod rifts_protocol {
             ErrorCode::InvalidTransferFee
         );
 
+        // **MAX FEE CAP**: Default to the currently scheduled cap so a bps-only update keeps it
+        let max_transfer_fee = match max_transfer_fee {
+            Some(max_transfer_fee) => max_transfer_fee,
+            None => {
+                let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
+                let rift_mint_data = rift_mint_info.try_borrow_data()?;
+                let mint_state =
+                    StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)?;
+                let fee_config = mint_state.get_extension::<TransferFeeConfig>()?;
+                u64::from(fee_config.newer_transfer_fee.maximum_fee)
+            }
+        };
+        require!(max_transfer_fee > 0, ErrorCode::InvalidMaxTransferFee);
+
         msg!(
             "Setting transfer fee to {} bps ({}%) for rift {}",
             new_fee_bps,
@@ -3939,7 +5764,7 @@ pub mod rifts_protocol {
                 &ctx.accounts.program_authority.key(),
                 &[],
                 new_fee_bps,
-                u64::MAX, // no maximum fee cap
+                max_transfer_fee,
             )
             .map_err(|_| ErrorCode::InvalidMint)?,
             &[
@@ -3948,9 +5773,27 @@ pub mod rifts_protocol {
             ],
         )?;
 
+        // Verify the scheduled (newer) fee matches what was requested
+        {
+            let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
+            let rift_mint_data = rift_mint_info.try_borrow_data()?;
+            let mint_state =
+                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)?;
+            let fee_config = mint_state.get_extension::<TransferFeeConfig>()?;
+            require!(
+                u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points) == new_fee_bps
+                    && u64::from(fee_config.newer_transfer_fee.maximum_fee) == max_transfer_fee,
+                ErrorCode::TransferFeeConfigMismatch
+            );
+        }
+
+        // Track live fee; initial_transfer_fee_bps is never modified
+        rift.transfer_fee_bps = new_fee_bps;
+
         emit!(TransferFeeUpdated {
             rift: rift.key(),
             new_fee_bps,
+            max_transfer_fee,
             authority: ctx.accounts.program_authority.key(),
         });
 
@@ -3963,6 +5806,9 @@ pub mod rifts_protocol {
     pub fn claim_withheld_fees(ctx: Context<ClaimWithheldFees>) -> Result<()> {
         let rift = &ctx.accounts.rift;
 
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no transfer fee / withheld fees
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
         // **PER-RIFT TREASURY FIX**: Use rift.treasury_wallet instead of hardcoded constant
         // This allows each rift to have its own treasury that can claim withheld fees
         let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
@@ -4010,6 +5856,164 @@ pub mod rifts_protocol {
             destination: ctx.accounts.withheld_vault.key(),
             source_account: ctx.accounts.source_account.key(),
             claimer: ctx.accounts.treasury_signer.key(),
+            keeper: ctx.accounts.treasury_signer.key(),
+        });
+
+        Ok(())
+    }
+
+    /// **TOKEN-2022**: Multisig variant of claim_withheld_fees for treasuries held by an SPL
+    /// multisig. `treasury_multisig` must equal rift.treasury_wallet; the multisig's signers are
+    /// passed in remaining_accounts and forwarded to the CPI, which enforces the m-of-n threshold.
+    pub fn claim_withheld_fees_multisig<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimWithheldFeesMultisig<'info>>,
+    ) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
+        let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
+        require!(
+            ctx.accounts.treasury_multisig.key() == treasury_wallet,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        // The withdraw authority must be a Token-2022 multisig with every listed signer present
+        require!(
+            ctx.accounts.treasury_multisig.owner == &spl_token_2022::ID,
+            ErrorCode::InvalidProgramId
+        );
+        {
+            let multisig_data = ctx.accounts.treasury_multisig.try_borrow_data()?;
+            let multisig = spl_token_2022::state::Multisig::unpack(&multisig_data)
+                .map_err(|_| ErrorCode::UnauthorizedAdmin)?;
+            let signer_count = ctx.remaining_accounts.len();
+            require!(
+                signer_count >= usize::from(multisig.m)
+                    && signer_count <= spl_token_2022::instruction::MAX_SIGNERS,
+                ErrorCode::UnauthorizedAdmin
+            );
+            for signer_info in ctx.remaining_accounts.iter() {
+                require!(signer_info.is_signer, ErrorCode::UnauthorizedAdmin);
+                require!(
+                    multisig.signers[..usize::from(multisig.n)].contains(signer_info.key),
+                    ErrorCode::UnauthorizedAdmin
+                );
+            }
+        }
+
+        use anchor_lang::solana_program::program::invoke;
+        use spl_token_2022::extension::transfer_fee::instruction::withdraw_withheld_tokens_from_accounts;
+
+        let source_pubkeys = [&ctx.accounts.source_account.key()];
+        let signer_pubkeys: Vec<&Pubkey> =
+            ctx.remaining_accounts.iter().map(|signer_info| signer_info.key).collect();
+
+        let mut account_infos = vec![
+            ctx.accounts.rift_mint.to_account_info(),
+            ctx.accounts.withheld_vault.to_account_info(),
+            ctx.accounts.treasury_multisig.to_account_info(),
+        ];
+        account_infos.extend(ctx.remaining_accounts.iter().cloned());
+        account_infos.push(ctx.accounts.source_account.to_account_info());
+
+        invoke(
+            &withdraw_withheld_tokens_from_accounts(
+                &spl_token_2022::ID,
+                &ctx.accounts.rift_mint.key(),        // mint
+                &ctx.accounts.withheld_vault.key(),   // destination (token account)
+                &ctx.accounts.treasury_multisig.key(), // authority (multisig)
+                &signer_pubkeys,
+                &source_pubkeys,
+            )
+            .map_err(|_| ErrorCode::InvalidMint)?,
+            &account_infos,
+        )?;
+
+        msg!(
+            "✅ Claimed withheld transfer fees from account {} via treasury multisig",
+            ctx.accounts.source_account.key()
+        );
+
+        emit!(WithheldFeesClaimed {
+            rift: ctx.accounts.rift.key(),
+            destination: ctx.accounts.withheld_vault.key(),
+            source_account: ctx.accounts.source_account.key(),
+            claimer: ctx.accounts.treasury_multisig.key(),
+            keeper: ctx.accounts.payer.key(),
+        });
+
+        Ok(())
+    }
+
+    /// **TOKEN-2022**: Sweep transfer fees withheld at the mint level into withheld_vault
+    /// Fees land on the mint when token accounts are closed or harvested to the mint; the
+    /// account-level claims above never touch them. Only the treasury withdraw authority can call.
+    pub fn harvest_mint_withheld(ctx: Context<HarvestMintWithheld>) -> Result<()> {
+        use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+
+        let rift = &ctx.accounts.rift;
+
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
+        let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
+        require!(
+            ctx.accounts.treasury_signer.key() == treasury_wallet,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        let mint_withheld = {
+            let rift_mint_info = ctx.accounts.rift_mint.to_account_info();
+            let rift_mint_data = rift_mint_info.try_borrow_data()?;
+            let mint_state =
+                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)?;
+            u64::from(mint_state.get_extension::<TransferFeeConfig>()?.withheld_amount)
+        };
+
+        if mint_withheld == 0 {
+            msg!("⚠️ No withheld fees on mint {}", ctx.accounts.rift_mint.key());
+            return Ok(());
+        }
+
+        use anchor_lang::solana_program::program::invoke;
+        use spl_token_2022::extension::transfer_fee::instruction::withdraw_withheld_tokens_from_mint;
+
+        let vault_balance_before = ctx.accounts.withheld_vault.amount;
+
+        // Signature: (program_id, mint, destination, authority, multisig_signers)
+        invoke(
+            &withdraw_withheld_tokens_from_mint(
+                &spl_token_2022::ID,
+                &ctx.accounts.rift_mint.key(),
+                &ctx.accounts.withheld_vault.key(),
+                &ctx.accounts.treasury_signer.key(),
+                &[],
+            )
+            .map_err(|_| ErrorCode::InvalidMint)?,
+            &[
+                ctx.accounts.rift_mint.to_account_info(),
+                ctx.accounts.withheld_vault.to_account_info(),
+                ctx.accounts.treasury_signer.to_account_info(),
+            ],
+        )?;
+
+        // Reconcile: the vault must have received exactly what the mint was holding
+        ctx.accounts.withheld_vault.reload()?;
+        let harvested = ctx
+            .accounts
+            .withheld_vault
+            .amount
+            .checked_sub(vault_balance_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(harvested == mint_withheld, ErrorCode::MintWithheldHarvestMismatch);
+
+        msg!("✅ Harvested {} mint-level withheld fees to withheld_vault", harvested);
+
+        emit!(MintWithheldFeesHarvested {
+            rift: ctx.accounts.rift.key(),
+            destination: ctx.accounts.withheld_vault.key(),
+            amount: harvested,
+            claimer: ctx.accounts.treasury_signer.key(),
         });
 
         Ok(())
@@ -4017,107 +6021,39 @@ pub mod rifts_protocol {
 
     /// **FEE MANAGEMENT**: Distribute withheld fees from withheld_vault
     /// Creator, partner, treasury, or PROGRAM_AUTHORITY can call this
-    /// Splits RIFT tokens from withheld_vault to partner (50%) and treasury (50%)
+    /// Splits RIFT tokens from withheld_vault to partner (partner_fee_bps) and treasury (remainder)
     pub fn distribute_withheld_vault(
         ctx: Context<DistributeWithheldVault>,
         amount: u64,
     ) -> Result<()> {
         let rift = &ctx.accounts.rift;
 
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no transfer fee / withheld fees
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
         // **MANUAL VALIDATION**: Validate rift_mint (converted to UncheckedAccount to reduce stack usage)
-        // 1. Verify owner is Token-2022 program (RIFT tokens use Token-2022)
+        // RIFT tokens with withheld fees always use Token-2022
         require!(
             ctx.accounts.rift_mint.owner == &spl_token_2022::ID,
             ErrorCode::InvalidProgramId
         );
-        // 2. Verify key matches expected value from rift
         require!(
             ctx.accounts.rift_mint.key() == rift.rift_mint,
             ErrorCode::InvalidMint
         );
-
-        // **MANUAL VALIDATION**: Validate treasury_account
-        // **FIX HIGH #2**: Enforce treasury_account.owner == treasury_wallet AND correct mint (rift_mint)
-        // Verify it's owned by token program (Token-2022)
-        require!(
-            ctx.accounts.treasury_account.owner == &anchor_spl::token::ID
-                || ctx.accounts.treasury_account.owner == &spl_token_2022::ID,
-            ErrorCode::InvalidProgramId
-        );
-        // Deserialize and validate owner/mint binding
-        {
-            let treasury_data = ctx.accounts.treasury_account.try_borrow_data()?;
-            let is_treasury_token_2022 = ctx.accounts.treasury_account.owner == &spl_token_2022::ID;
-            let treasury_token_owner: Pubkey;
-            let treasury_token_mint: Pubkey;
-            if is_treasury_token_2022 {
-                let treasury_token_account = spl_token_2022::extension::StateWithExtensions::<spl_token_2022::state::Account>::unpack(&treasury_data)
-                    .map_err(|_| ErrorCode::InvalidTreasuryVault)?;
-                treasury_token_owner = treasury_token_account.base.owner;
-                treasury_token_mint = treasury_token_account.base.mint;
-            } else {
-                let treasury_token_account = spl_token::state::Account::unpack(&treasury_data)
-                    .map_err(|_| ErrorCode::InvalidTreasuryVault)?;
-                treasury_token_owner = treasury_token_account.owner;
-                treasury_token_mint = treasury_token_account.mint;
-            }
-            drop(treasury_data);
-
-            // **FIX HIGH #2**: Enforce token account owner matches treasury_wallet
-            require!(
-                treasury_token_owner == rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?,
-                ErrorCode::InvalidTreasuryVault
-            );
-            // **FIX HIGH #2**: Enforce token account mint matches rift_mint (RIFT tokens)
-            require!(
-                treasury_token_mint == rift.rift_mint,
-                ErrorCode::InvalidTreasuryVault
-            );
-        }
-
-        // **MANUAL VALIDATION**: Validate partner_account if present
-        // **FIX HIGH #2**: Enforce partner_account.owner == partner_wallet AND correct mint
-        if ctx.accounts.partner_account.is_some() {
-            let partner_account = ctx.accounts.partner_account.as_ref().unwrap();
-            // Verify it's owned by token program (Token-2022)
-            require!(
-                partner_account.owner == &anchor_spl::token::ID
-                    || partner_account.owner == &spl_token_2022::ID,
-                ErrorCode::InvalidProgramId
-            );
-            // Deserialize and validate owner/mint binding
-            let partner_data = partner_account.try_borrow_data()?;
-            let is_partner_token_2022 = partner_account.owner == &spl_token_2022::ID;
-            let partner_token_owner: Pubkey;
-            let partner_token_mint: Pubkey;
-            if is_partner_token_2022 {
-                let partner_token_account = spl_token_2022::extension::StateWithExtensions::<spl_token_2022::state::Account>::unpack(&partner_data)
-                    .map_err(|_| ErrorCode::InvalidPartnerVault)?;
-                partner_token_owner = partner_token_account.base.owner;
-                partner_token_mint = partner_token_account.base.mint;
-            } else {
-                let partner_token_account = spl_token::state::Account::unpack(&partner_data)
-                    .map_err(|_| ErrorCode::InvalidPartnerVault)?;
-                partner_token_owner = partner_token_account.owner;
-                partner_token_mint = partner_token_account.mint;
-            }
-            drop(partner_data);
-
-            // **FIX HIGH #2**: Enforce token account owner matches partner_wallet
-            require!(
-                partner_token_owner == rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?,
-                ErrorCode::InvalidPartnerVault
-            );
-            // **FIX HIGH #2**: Enforce token account mint matches rift_mint (RIFT tokens)
-            require!(
-                partner_token_mint == rift.rift_mint,
-                ErrorCode::InvalidPartnerVault
-            );
-        }
+        let mint_decimals = {
+            let rift_mint_data = ctx.accounts.rift_mint.try_borrow_data()?;
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)
+                .map_err(|_| ErrorCode::InvalidMint)?
+                .base
+                .decimals
+        };
 
         // **AUTHORIZATION**: Creator, partner, treasury, or PROGRAM_AUTHORITY can distribute fees
         // **FIX ISSUE #2**: Use ok_or instead of expect to prevent panic on corrupted state
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         let partner_wallet = rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?;
         let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
 
@@ -4129,189 +6065,126 @@ pub mod rifts_protocol {
         require!(is_authorized, ErrorCode::Unauthorized);
 
         require!(amount > 0, ErrorCode::InvalidAmount);
-        require!(rift.treasury_wallet.is_some(), ErrorCode::TreasuryNotSet);
 
-        // Verify treasury_wallet matches
+        // **FIX HIGH #2**: Recipient token accounts must be owned by the rift's wallets and
+        // hold RIFT (same checks as distribute_fees_from_vault)
         require!(
-            ctx.accounts.treasury_wallet.key() == rift.treasury_wallet.unwrap(),
+            ctx.accounts.treasury_wallet.key() == treasury_wallet,
             ErrorCode::InvalidTreasuryVault
         );
+        validate_fee_recipient(
+            &ctx.accounts.treasury_account,
+            &treasury_wallet,
+            &rift.rift_mint,
+            ErrorCode::InvalidTreasuryVault,
+        )?;
+        if let Some(partner_wallet_account) = &ctx.accounts.partner_wallet {
+            require!(
+                partner_wallet_account.key() == partner_wallet,
+                ErrorCode::InvalidPartnerVault
+            );
+        }
+        if let Some(partner_account) = &ctx.accounts.partner_account {
+            validate_fee_recipient(
+                partner_account,
+                &partner_wallet,
+                &rift.rift_mint,
+                ErrorCode::InvalidPartnerVault,
+            )?;
+        }
 
         // Check withheld_vault balance
-        let withheld_vault_balance = ctx.accounts.withheld_vault.amount;
+        let withheld_vault_balance_before = ctx.accounts.withheld_vault.amount;
 
         require!(
-            amount <= withheld_vault_balance,
+            amount <= withheld_vault_balance_before,
             ErrorCode::InsufficientFees
         );
 
-        msg!("Distributing {} withheld fees from withheld_vault (available: {}) to treasury and partner (50/50 split)",
-            amount, withheld_vault_balance);
-
-        // **FEE SPLIT**: Always split 50/50 between partner and treasury
-        // Partner always exists (defaults to creator if not provided at rift creation)
-        require!(
-            ctx.accounts.partner_account.is_some(),
-            ErrorCode::MissingPartnerVault
-        );
-        require!(
-            ctx.accounts.partner_wallet.is_some(),
-            ErrorCode::MissingPartnerVault
-        );
-
-        // Verify partner_wallet matches
-        let partner_wallet_key = ctx.accounts.partner_wallet.as_ref().ok_or(ErrorCode::MissingPartnerVault)?.key();
-        require!(
-            partner_wallet_key == rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?,
-            ErrorCode::InvalidPartnerVault
-        );
-
-        // **FIX CRITICAL #2**: 50/50 split with no truncation loss
-        // For odd amounts, treasury gets the extra 1 token
-        let partner_amount = amount.checked_div(2).ok_or(ErrorCode::MathOverflow)?;
-        let treasury_amount = amount
-            .checked_sub(partner_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
-        msg!("Partner amount: {} (~50%)", partner_amount);
-        msg!("Treasury amount: {} (~50%)", treasury_amount);
+        msg!("Distributing {} withheld fees from withheld_vault (available: {}) to treasury and partner (partner share: {} bps)",
+            amount, withheld_vault_balance_before, rift.partner_fee_bps);
 
-        // **FIX MEDIUM #9**: Check SOURCE balance before transfers
-        let withheld_vault_balance_before = ctx.accounts.withheld_vault.amount;
-
-        // **FIX CRITICAL #11**: Check DESTINATION balances before transfers
-        use spl_token_2022::extension::StateWithExtensions;
-        let partner_balance_before = if partner_amount > 0 {
-            let partner_account = ctx.accounts.partner_account.as_ref().ok_or(ErrorCode::MissingPartnerVault)?;
-            let partner_data = partner_account.try_borrow_data()?;
-            let partner_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&partner_data)
-                .map_err(|_| ErrorCode::InvalidPartnerVault)?;
-            partner_token_account.base.amount
-        } else {
-            0
-        };
-        let treasury_data = ctx.accounts.treasury_account.try_borrow_data()?;
-        let treasury_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&treasury_data)
-            .map_err(|_| ErrorCode::InvalidTreasuryVault)?;
-        let treasury_balance_before = treasury_token_account.base.amount;
-        drop(treasury_data); // Release borrow before transfers
-
-        // **FIX**: Extract mint decimals from rift_mint
-        let rift_mint_data = ctx.accounts.rift_mint.try_borrow_data()?;
-        let rift_mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)
-            .map_err(|_| ErrorCode::InvalidMint)?;
-        let mint_decimals = rift_mint_state.base.decimals;
-        drop(rift_mint_data); // Release borrow before transfers
+        // **FIX CRITICAL #11**: Sum of recipient balances, read before and after the transfers
+        // RIFT has a transfer fee, so recipients are credited less than the vault is debited
+        let recipients: Vec<AccountInfo> = [
+            Some(ctx.accounts.treasury_account.to_account_info()),
+            ctx.accounts
+                .partner_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            ctx.accounts
+                .protocol_fee_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        let recipients_balance = |recipients: &[AccountInfo]| -> Result<u64> {
+            let mut total = 0u64;
+            for (index, recipient) in recipients.iter().enumerate() {
+                // The same wallet may own several recipient accounts (e.g. partner == treasury)
+                if recipients[..index].iter().any(|seen| seen.key == recipient.key) {
+                    continue;
+                }
+                let data = recipient.try_borrow_data()?;
+                require!(data.len() >= 72, ErrorCode::InvalidAccountData);
+                let balance = u64::from_le_bytes(
+                    data[64..72].try_into().map_err(|_| ErrorCode::InvalidAccountData)?,
+                );
+                total = total.checked_add(balance).ok_or(ErrorCode::MathOverflow)?;
+            }
+            Ok(total)
+        };
+        let recipients_balance_before = recipients_balance(&recipients)?;
 
         // Setup vault authority seeds
         let rift_key = rift.key();
-        let vault_auth_seeds = &[
-            b"vault_auth",
-            rift_key.as_ref(),
-            &[ctx.bumps.vault_authority],
-        ];
-        let signer = &[&vault_auth_seeds[..]];
-
-        // Transfer to partner if applicable
-        if partner_amount > 0 {
-            let partner_account = ctx
-                .accounts
-                .partner_account
-                .as_ref()
-                .ok_or(ErrorCode::MissingPartnerAccount)?;
-
-            let partner_transfer_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                anchor_spl::token_2022::TransferChecked {
-                    from: ctx.accounts.withheld_vault.to_account_info(),
-                    to: partner_account.to_account_info(),
-                    authority: ctx.accounts.vault_authority.to_account_info(),
-                    mint: ctx.accounts.rift_mint.to_account_info(),
-                },
-                signer,
-            );
-            anchor_spl::token_2022::transfer_checked(partner_transfer_ctx, partner_amount, mint_decimals)?;
-            msg!(
-                "✅ Sent {} RIFT to partner from withheld_vault",
-                partner_amount
-            );
-        }
-
-        // Transfer to treasury from withheld_vault
-        let treasury_transfer_ctx = CpiContext::new_with_signer(
+        let vault_auth_bump = [ctx.bumps.vault_authority];
+        let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
+        let signer = &[vault_auth_seeds];
+
+        // **PROTOCOL FEE**: Protocol cut comes off the top; partner/treasury split the remainder
+        // **FIX CRITICAL #2**: Configurable split with no truncation loss (remainder to treasury)
+        let (protocol_amount, partner_amount, treasury_amount) = transfer_fee_split(
+            rift,
+            amount,
+            ctx.accounts.withheld_vault.to_account_info(),
+            ctx.accounts.rift_mint.to_account_info(),
+            mint_decimals,
             ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token_2022::TransferChecked {
-                from: ctx.accounts.withheld_vault.to_account_info(),
-                to: ctx.accounts.treasury_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-                mint: ctx.accounts.rift_mint.to_account_info(),
-            },
+            ctx.accounts.vault_authority.to_account_info(),
             signer,
-        );
-        anchor_spl::token_2022::transfer_checked(treasury_transfer_ctx, treasury_amount, mint_decimals)?;
+            ctx.accounts.treasury_account.to_account_info(),
+            ctx.accounts
+                .partner_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            ctx.accounts
+                .protocol_fee_account
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            &ctx.accounts.global_config.to_account_info(),
+        )?;
 
         // **FIX MEDIUM #9**: Reload SOURCE and verify
         ctx.accounts.withheld_vault.reload()?;
-        let withheld_vault_balance_after = ctx.accounts.withheld_vault.amount;
         let actual_sent_from_source = withheld_vault_balance_before
-            .checked_sub(withheld_vault_balance_after)
+            .checked_sub(ctx.accounts.withheld_vault.amount)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        // **FIX CRITICAL #11**: Reload DESTINATIONS and verify actual received amounts
-        let mut partner_received = 0u64;
-        if partner_amount > 0 {
-            if let Some(partner_account) = &ctx.accounts.partner_account {
-                let partner_data = partner_account.try_borrow_data()?;
-                let partner_token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&partner_data)
-                    .map_err(|_| ErrorCode::InvalidPartnerVault)?;
-                partner_received = partner_token_account
-                    .base
-                    .amount
-                    .checked_sub(partner_balance_before)
-                    .ok_or(ErrorCode::MathOverflow)?;
-
-                if partner_received != partner_amount {
-                    let partner_withheld = partner_amount.saturating_sub(partner_received);
-                    msg!(
-                        "⚠️ RIFT transfer fee (partner): sent {}, received {}",
-                        partner_amount,
-                        partner_received
-                    );
-                    msg!(
-                        "⚠️ Partner withheld: {} RIFT ({:.2}%)",
-                        partner_withheld,
-                        (partner_withheld as f64 / partner_amount as f64) * 100.0
-                    );
-                }
-            }
-        }
-
-        let treasury_data_after = ctx.accounts.treasury_account.try_borrow_data()?;
-        let treasury_token_account_after = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&treasury_data_after)
-            .map_err(|_| ErrorCode::InvalidTreasuryVault)?;
-        let treasury_balance_after = treasury_token_account_after.base.amount;
-        let treasury_received = treasury_balance_after
-            .checked_sub(treasury_balance_before)
+        // **FIX CRITICAL #11**: Verify what the recipients actually received
+        let total_received = recipients_balance(&recipients)?
+            .checked_sub(recipients_balance_before)
             .ok_or(ErrorCode::MathOverflow)?;
-
-        if treasury_received != treasury_amount {
-            let treasury_withheld = treasury_amount.saturating_sub(treasury_received);
-            msg!(
-                "⚠️ RIFT transfer fee (treasury): sent {}, received {}",
-                treasury_amount,
-                treasury_received
-            );
+        if total_received != amount {
             msg!(
-                "⚠️ Treasury withheld: {} RIFT ({:.2}%)",
-                treasury_withheld,
-                (treasury_withheld as f64 / treasury_amount as f64) * 100.0
+                "⚠️ RIFT transfer fee withheld on distribution: sent {}, received {}",
+                amount,
+                total_received
             );
         }
 
-        // **FIX CRITICAL #11**: Calculate total withheld at destinations
-        let total_received = partner_received
-            .checked_add(treasury_received)
-            .ok_or(ErrorCode::MathOverflow)?;
-
         // **FIX MEDIUM #3 (Audit)**: Tighten fee tolerance to match max RIFT transfer fee (1%)
         // Previously 95% - now 98% to allow for max 2% total leakage (two 1% transfers)
         // RIFT tokens have transfer fees, so recipients get less than sent
@@ -4328,6 +6201,12 @@ pub mod rifts_protocol {
             ErrorCode::ExcessiveTransferFee
         );
 
+        let rift = &mut ctx.accounts.rift;
+        rift.withheld_fees_distributed = rift
+            .withheld_fees_distributed
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
         msg!(
             "✅ Distributed {} withheld fees (treasury: {}, partner: {})",
             amount,
@@ -4338,9 +6217,11 @@ pub mod rifts_protocol {
         emit!(WithheldFeesDistributed {
             rift: rift.key(),
             amount,
+            protocol_amount,
             treasury_amount,
             partner_amount,
             distributor: ctx.accounts.payer.key(),
+            keeper: ctx.accounts.payer.key(),
         });
 
         Ok(())
@@ -4354,7 +6235,9 @@ pub mod rifts_protocol {
         amount: u64,
     ) -> Result<()> {
         // Only PROGRAM_AUTHORITY can withdraw fees
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
             ctx.accounts.program_authority.key() == program_authority,
             ErrorCode::UnauthorizedAdmin
@@ -4366,7 +6249,7 @@ pub mod rifts_protocol {
         // Derive vault_authority PDA seeds for signing
         let vault_auth_bump = ctx.bumps.vault_authority;
         let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &[vault_auth_bump]];
-        let signer = &[&vault_auth_seeds[..]];
+        let signer = &[vault_auth_seeds];
 
         // **HARDENING**: Ensure vault_authority account matches derived PDA
         let (expected_vault_auth, _) =
@@ -4427,7 +6310,9 @@ pub mod rifts_protocol {
         amount: u64,
     ) -> Result<()> {
         // Only PROGRAM_AUTHORITY can withdraw fees
-        let program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
         require!(
             ctx.accounts.program_authority.key() == program_authority,
             ErrorCode::UnauthorizedAdmin
@@ -4436,10 +6321,13 @@ pub mod rifts_protocol {
         let rift = &ctx.accounts.rift;
         let rift_key = rift.key();
 
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no transfer fee / withheld fees
+        require!(!rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
         // Derive vault_authority PDA seeds for signing
         let vault_auth_bump = ctx.bumps.vault_authority;
         let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &[vault_auth_bump]];
-        let signer = &[&vault_auth_seeds[..]];
+        let signer = &[vault_auth_seeds];
 
         // **HARDENING**: Ensure vault_authority account matches derived PDA
         let (expected_vault_auth, _) =
@@ -4485,674 +6373,6497 @@ pub mod rifts_protocol {
 
         Ok(())
     }
-}
 
-// SIMPLIFIED ACCOUNT STRUCTS TO REDUCE STACK USAGE
+    /// Set the backing ratio circuit breaker threshold (creator only)
+    /// Wraps are rejected while vault backing is below this; unwraps are unaffected
+    pub fn set_min_backing_ratio(
+        ctx: Context<SetMinBackingRatio>,
+        min_backing_ratio_bps: u16,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
 
-#[derive(Accounts)]
-#[instruction(vanity_seed: [u8; 32], seed_len: u8, partner_wallet: Option<Pubkey>, rift_name: [u8; 32], name_len: u8, transfer_fee_bps: u16)]
-pub struct CreateRiftWithVanityPDA<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            (MIN_BACKING_RATIO_FLOOR_BPS..=MIN_BACKING_RATIO_CEILING_BPS)
+                .contains(&min_backing_ratio_bps),
+            ErrorCode::InvalidBackingThreshold
+        );
 
-    /// **CRITICAL SPACE FIX**: Use explicit Borsh size calculation
-    /// Option<Pubkey> = 33 bytes in Borsh (1 discriminant + 32 pubkey), not 32 from std::mem::size_of
-    /// 4 Option<Pubkey> fields in current struct
-    /// Correct size: 8 (discriminator) + 774 (struct) = 782 bytes
-    /// **FIX LOW #1 (Audit)**: Add constraint to prevent panic from invalid seed_len
-    #[account(
-        init,
-        payer = creator,
-        space = 782,
-        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref(), &vanity_seed[..seed_len as usize]],
-        bump,
-        constraint = seed_len <= 32 @ ErrorCode::InvalidVanitySeedLength
-    )]
-    pub rift: Account<'info, Rift>,
+        let previous_bps = rift.min_backing_ratio_bps;
+        rift.min_backing_ratio_bps = min_backing_ratio_bps;
 
-    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
-    pub underlying_mint: InterfaceAccount<'info, Mint>,
+        msg!(
+            "✅ Min backing ratio updated: {} bps → {} bps",
+            previous_bps,
+            min_backing_ratio_bps
+        );
 
-    /// The PDA-derived mint account for vanity address
-    /// **TOKEN-2022**: Manually initialized with transfer fee extension (0.7% on DEX trades)
-    /// **SECURITY NOTE #8**: Using UncheckedAccount because Token-2022 extensions require manual initialization.
-    /// This account is created via invoke_signed with proper validation (lines 189-233).
-    /// RISK: If manual initialization code has bugs, could create invalid/exploitable mints.
-    /// MITIGATION: Thoroughly tested initialization sequence, PDA derivation enforced by seeds.
-    /// CHECK: Manually initialized with Token-2022 transfer fee extension in instruction handler
-    /// **FIX HIGH #4**: Changed from user-provided bump to auto-derived canonical bump
-    /// **FIX LOW #1 (Audit)**: seed_len already validated in rift account constraint
-    #[account(
-        mut,
-        seeds = [b"rift_mint", creator.key().as_ref(), underlying_mint.key().as_ref(), &vanity_seed[..seed_len as usize]],
-        bump,
-    )]
-    pub rift_mint: UncheckedAccount<'info>,
+        Ok(())
+    }
 
-    /// CHECK: PDA for rift mint authority
-    #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
-    )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+    /// Read-only preflight: can the user unwrap their entire RIFT balance right now?
+    /// Runs the same plan_unwrap preflight as unwrap_from_vault against the live vault
+    pub fn can_fully_exit(ctx: Context<CanFullyExit>) -> Result<bool> {
+        let rift = &ctx.accounts.rift;
 
-    /// **ATOMIC INIT**: Vault token account (initialized during create_rift_with_vanity_pda)
-    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
-    #[account(
-        mut,
-        seeds = [b"vault", rift.key().as_ref()],
-        bump
-    )]
-    pub vault: UncheckedAccount<'info>,
+        // Validate user's RIFT token account (Token-2022, or SPL Token for legacy rifts)
+        require!(
+            *ctx.accounts.user_rift_tokens.owner == rift.rift_token_program_id(),
+            ErrorCode::InvalidTokenAccount
+        );
+        let rift_data = ctx.accounts.user_rift_tokens.try_borrow_data()?;
+        require!(rift_data.len() >= 72, ErrorCode::InvalidTokenAccount);
+        let rift_mint_check = Pubkey::new_from_array(
+            rift_data[0..32]
+                .try_into()
+                .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+        );
+        let rift_owner = Pubkey::new_from_array(
+            rift_data[32..64]
+                .try_into()
+                .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+        );
+        let rift_balance = u64::from_le_bytes(
+            rift_data[64..72]
+                .try_into()
+                .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+        );
+        drop(rift_data);
+        require!(rift_mint_check == rift.rift_mint, ErrorCode::InvalidMint);
+        require!(
+            rift_owner == ctx.accounts.user.key(),
+            ErrorCode::UnauthorizedTokenAccount
+        );
 
-    /// **ATOMIC INIT**: Fees vault token account (initialized during create_rift_with_vanity_pda)
-    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
-    #[account(
-        mut,
-        seeds = [b"fees_vault", rift.key().as_ref()],
-        bump
-    )]
-    pub fees_vault: UncheckedAccount<'info>,
+        if rift_balance == 0 {
+            msg!("📊 Exit check: no RIFT to unwrap");
+            return Ok(true);
+        }
 
-    /// **ATOMIC INIT**: Withheld vault token account (initialized during create_rift_with_vanity_pda)
-    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
-    #[account(
-        mut,
-        seeds = [b"withheld_vault", rift.key().as_ref()],
-        bump
-    )]
-    pub withheld_vault: UncheckedAccount<'info>,
+        // Same preflight as unwrap_from_vault (state, oracle, fees, liquidity, drain limit)
+        let vault_balance = ctx.accounts.vault.amount;
+        let can_exit = match rift.plan_unwrap(
+            rift_balance,
+            vault_balance,
+            ctx.accounts.fee_exemption.is_some(),
+            false,
+            Clock::get()?.unix_timestamp,
+        ) {
+            Ok(plan) => {
+                msg!(
+                    "📊 Exit check: balance {} RIFT → {} underlying, vault {}",
+                    rift_balance,
+                    plan.net_underlying,
+                    vault_balance
+                );
+                true
+            }
+            Err(err) => {
+                msg!("❌ Exit check: balance {} RIFT blocked - {}", rift_balance, err);
+                false
+            }
+        };
 
-    /// CHECK: PDA for vault authority (controls all vault transfers)
-    #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
-    )]
-    pub vault_authority: UncheckedAccount<'info>,
+        Ok(can_exit)
+    }
 
-    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
-    #[account(
-        constraint = token_program.key() == anchor_spl::token_2022::ID
-            @ ErrorCode::InvalidProgramId
-    )]
-    pub token_program: Interface<'info, TokenInterface>,
+    /// Read-only: write a small versioned `RiftSummary` to return data
+    /// Gives integrators a stable read path that doesn't depend on the full Rift account layout
+    pub fn get_rift_state(ctx: Context<GetRiftState>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
 
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+        let summary = RiftSummary {
+            version: RIFT_SUMMARY_VERSION,
+            backing_ratio: rift.backing_ratio,
+            total_rift_minted: rift.total_rift_minted,
+            total_underlying_wrapped: rift.total_underlying_wrapped,
+            total_fees_collected: rift.total_fees_collected,
+            wrap_fee_bps: rift.wrap_fee_bps,
+            unwrap_fee_bps: rift.unwrap_fee_bps,
+            is_closed: rift.is_closed,
+            is_paused: rift.paused,
+            wrap_fees_collected: rift.wrap_fees_collected,
+            unwrap_fees_collected: rift.unwrap_fees_collected,
+            withheld_fees_distributed: rift.withheld_fees_distributed,
+            lifetime_wrapped: rift.lifetime_wrapped,
+            lifetime_unwrapped: rift.lifetime_unwrapped,
+        };
 
-    /// CHECK: Validated in handler - must match underlying_mint.owner
-    pub underlying_token_program: UncheckedAccount<'info>,
-}
+        let mut data = Vec::with_capacity(RiftSummary::SIZE);
+        summary.serialize(&mut data)?;
+        anchor_lang::solana_program::program::set_return_data(&data);
 
-#[derive(Accounts)]
-#[instruction(partner_wallet: Option<Pubkey>, rift_name: [u8; 32], name_len: u8, transfer_fee_bps: u16)]
-pub struct CreateRift<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+        Ok(())
+    }
 
-    /// **CRITICAL SPACE FIX**: Use explicit Borsh size calculation
-    /// Option<Pubkey> = 33 bytes in Borsh (1 discriminant + 32 pubkey), not from std::mem::size_of
-    /// Correct size: 8 (discriminator) + 774 (struct) = 782 bytes
-    #[account(
-        init,
-        payer = creator,
-        space = RIFT_ACCOUNT_SIZE,
-        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
-        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
-        bump,
-    )]
-    pub rift: Account<'info, Rift>,
+    /// Read-only: compute the time-weighted average oracle price and emit it
+    pub fn quote_twap(ctx: Context<QuoteTwap>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+        let oracle_history = rift.oracle_history_for(&ctx.accounts.oracle_history)?;
+        let twap_price = rift.get_twap_price(oracle_history)?;
 
-    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
-    pub underlying_mint: InterfaceAccount<'info, Mint>,
+        msg!("📊 TWAP price: {}", twap_price);
+
+        emit!(TwapQuoted {
+            rift: rift.key(),
+            twap_price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Set the partner share of distributed fees (creator or program authority)
+    /// Treasury receives the remainder; applies to both fees_vault and withheld_vault distributions
+    pub fn set_partner_fee_bps(ctx: Context<SetPartnerFeeBps>, partner_fee_bps: u16) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.authority.key() == rift.creator
+                || ctx.accounts.authority.key() == program_authority,
+            ErrorCode::Unauthorized
+        );
+        require!(partner_fee_bps <= 10000, ErrorCode::InvalidPartnerFee);
+
+        let old_partner_fee_bps = rift.partner_fee_bps;
+        rift.partner_fee_bps = partner_fee_bps;
+
+        msg!(
+            "✅ Partner fee share updated: {} bps → {} bps",
+            old_partner_fee_bps,
+            partner_fee_bps
+        );
+
+        emit!(PartnerFeeUpdated {
+            rift: rift.key(),
+            old_partner_fee_bps,
+            new_partner_fee_bps: partner_fee_bps,
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Keeper-callable: emit a consolidated DailySnapshot event (at most once per 24h)
+    /// Only mutates last_snapshot_timestamp
+    pub fn emit_daily_snapshot(ctx: Context<EmitDailySnapshot>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        if rift.last_snapshot_timestamp > 0 {
+            let next_allowed = rift
+                .last_snapshot_timestamp
+                .checked_add(DAILY_SNAPSHOT_INTERVAL)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(current_time >= next_allowed, ErrorCode::SnapshotTooFrequent);
+        }
+
+        // Stale/missing oracle data must not block the snapshot - report 0 instead
+        let oracle_history = rift.oracle_history_for(&ctx.accounts.oracle_history)?;
+        let avg_oracle_price = match rift.get_average_oracle_price(oracle_history) {
+            Ok(price) => price,
+            Err(_) => {
+                msg!("⚠️ Oracle average unavailable - reporting 0 in snapshot");
+                0
+            }
+        };
+
+        rift.last_snapshot_timestamp = current_time;
+
+        emit!(DailySnapshot {
+            rift: rift.key(),
+            total_underlying_wrapped: rift.total_underlying_wrapped,
+            total_rift_minted: rift.total_rift_minted,
+            backing_ratio: rift.backing_ratio,
+            total_fees_collected: rift.total_fees_collected,
+            avg_oracle_price,
+            keeper: ctx.accounts.keeper.key(),
+            timestamp: current_time,
+        });
+
+        msg!("📊 Daily snapshot emitted at {}", current_time);
+
+        Ok(())
+    }
+
+    /// Set how old the oracle may be before rebalance logic ignores it (creator only)
+    pub fn set_max_oracle_trust_age(
+        ctx: Context<SetMaxOracleTrustAge>,
+        max_oracle_trust_age: i64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(
+            (MIN_ORACLE_TRUST_AGE..=MAX_ORACLE_TRUST_AGE).contains(&max_oracle_trust_age),
+            ErrorCode::InvalidOracleParameters
+        );
+
+        rift.max_oracle_trust_age = max_oracle_trust_age;
+
+        msg!("✅ Max oracle trust age set to {}s", max_oracle_trust_age);
+
+        Ok(())
+    }
+
+    /// Idempotently create the treasury and partner ATAs used by fee distributions
+    /// `mint` selects the leg: underlying_mint (fees_vault) or rift_mint (withheld_vault)
+    /// Any payer can call; existing accounts are left untouched
+    pub fn ensure_distribution_atas(ctx: Context<EnsureDistributionAtas>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+        let mint_key = ctx.accounts.mint.key();
+
+        require!(
+            mint_key == rift.underlying_mint || mint_key == rift.rift_mint,
+            ErrorCode::InvalidMint
+        );
+        // Token program must own the mint (SPL Token underlying or Token-2022)
+        require!(
+            *ctx.accounts.mint.owner == ctx.accounts.token_program.key(),
+            ErrorCode::InvalidProgramId
+        );
+
+        let treasury_wallet = rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
+        let partner_wallet = rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?;
+        require!(
+            ctx.accounts.treasury_wallet.key() == treasury_wallet,
+            ErrorCode::InvalidTreasuryVault
+        );
+        require!(
+            ctx.accounts.partner_wallet.key() == partner_wallet,
+            ErrorCode::InvalidPartnerVault
+        );
+
+        // Validate ATA derivation for both legs
+        let token_program_key = ctx.accounts.token_program.key();
+        let expected_treasury_ata =
+            anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                &treasury_wallet,
+                &mint_key,
+                &token_program_key,
+            );
+        require!(
+            ctx.accounts.treasury_ata.key() == expected_treasury_ata,
+            ErrorCode::InvalidTreasuryVault
+        );
+        let expected_partner_ata =
+            anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                &partner_wallet,
+                &mint_key,
+                &token_program_key,
+            );
+        require!(
+            ctx.accounts.partner_ata.key() == expected_partner_ata,
+            ErrorCode::InvalidPartnerVault
+        );
+
+        // create_idempotent is a no-op when the ATA already exists
+        anchor_spl::associated_token::create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            anchor_spl::associated_token::Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: ctx.accounts.treasury_ata.to_account_info(),
+                authority: ctx.accounts.treasury_wallet.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        msg!("✅ Treasury ATA ready: {}", expected_treasury_ata);
+
+        if expected_partner_ata != expected_treasury_ata {
+            anchor_spl::associated_token::create_idempotent(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: ctx.accounts.payer.to_account_info(),
+                    associated_token: ctx.accounts.partner_ata.to_account_info(),
+                    authority: ctx.accounts.partner_wallet.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+            ))?;
+        }
+        msg!("✅ Partner ATA ready: {}", expected_partner_ata);
+
+        Ok(())
+    }
+
+    /// Choose whether wrap fees are extracted to fees_vault (false) or kept as backing (true)
+    /// Creator only
+    pub fn set_fees_to_backing(ctx: Context<SetFeesToBacking>, fees_to_backing: bool) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        rift.fees_to_backing = fees_to_backing;
+
+        msg!(
+            "✅ Wrap fee model: {}",
+            if fees_to_backing { "fee-to-backing" } else { "extract to fees_vault" }
+        );
+
+        Ok(())
+    }
+
+    /// Creator: enable/disable rejecting unwraps in the same slot as a wrap (rift-wide)
+    pub fn set_same_slot_protection(
+        ctx: Context<SetSameSlotProtection>,
+        enabled: bool,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        rift.same_slot_protection = enabled;
+
+        msg!(
+            "✅ Same-slot wrap/unwrap protection: {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+
+        Ok(())
+    }
+
+    /// Creator: exempt a wallet (e.g. treasury or LP bot) from wrap/unwrap fees on this rift
+    pub fn add_fee_exemption(ctx: Context<AddFeeExemption>, wallet: Pubkey) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        let exemption = &mut ctx.accounts.fee_exemption;
+        exemption.rift = rift.key();
+        exemption.wallet = wallet;
+        exemption.bump = ctx.bumps.fee_exemption;
+
+        emit!(FeeExemptionAdded {
+            rift: rift.key(),
+            wallet,
+            creator: ctx.accounts.creator.key(),
+        });
+
+        msg!("✅ Fee exemption added for {}", wallet);
+
+        Ok(())
+    }
+
+    /// Creator: remove a wallet's fee exemption (closes the PDA, rent back to the creator)
+    pub fn remove_fee_exemption(ctx: Context<RemoveFeeExemption>) -> Result<()> {
+        require!(
+            ctx.accounts.creator.key() == ctx.accounts.rift.creator,
+            ErrorCode::Unauthorized
+        );
+
+        let wallet = ctx.accounts.fee_exemption.wallet;
+
+        emit!(FeeExemptionRemoved {
+            rift: ctx.accounts.rift.key(),
+            wallet,
+            creator: ctx.accounts.creator.key(),
+        });
+
+        msg!("✅ Fee exemption removed for {}", wallet);
+
+        Ok(())
+    }
+
+    /// Read-only: expose the protocol's active authorities for client transparency
+    /// Reads the config PDA when initialized, otherwise the hardcoded constants
+    pub fn get_protocol_authorities(
+        ctx: Context<GetProtocolAuthorities>,
+    ) -> Result<ProtocolAuthorities> {
+        GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())
+    }
+
+    /// Read-only: expose the deployed program's key constants so clients don't hardcode them
+    /// Returned via return data; no accounts required. The reentrancy timeout actually in force is
+    /// per rift (rift.reentrancy_timeout_slots); these are its default and allowed bounds
+    pub fn get_program_constants(_ctx: Context<GetProgramConstants>) -> Result<ProgramConstants> {
+        Ok(ProgramConstants {
+            rift_account_size: RIFT_ACCOUNT_SIZE as u64,
+            default_reentrancy_timeout_slots: REENTRANCY_TIMEOUT_SLOTS,
+            min_reentrancy_timeout_slots: MIN_REENTRANCY_TIMEOUT_SLOTS,
+            max_reentrancy_timeout_slots: MAX_REENTRANCY_TIMEOUT_SLOTS,
+            oracle_change_delay: ORACLE_CHANGE_DELAY,
+            max_fee_basis_points: MAX_FEE_BASIS_POINTS,
+            min_transfer_fee_bps: MIN_LAUNCH_TRANSFER_FEE_BPS,
+            max_transfer_fee_bps: MAX_LAUNCH_TRANSFER_FEE_BPS,
+        })
+    }
+
+    /// Read-only: derive every PDA of a rift (authorities, vaults and oracle history, with bumps)
+    /// All are seeded by the rift account key, not the rift creation seeds, and are returned
+    /// even before the account at that address has been initialized
+    pub fn get_pdas(ctx: Context<GetPdas>) -> Result<RiftPdas> {
+        Ok(RiftPdas::derive(&ctx.accounts.rift.key(), ctx.program_id))
+    }
+
+    /// Read-only: alias of get_pdas for clients that still call get_authorities
+    pub fn get_authorities(ctx: Context<GetAuthorities>) -> Result<RiftPdas> {
+        Ok(RiftPdas::derive(&ctx.accounts.rift.key(), ctx.program_id))
+    }
+
+    /// Read-only: the full oracle price ring buffer, ordered oldest → newest
+    /// Reads the OracleHistory PDA once migrate_oracle_history has run (required then), otherwise
+    /// the inline 10-slot buffer - the same buffer average/median/TWAP reads use
+    /// Empty (never written) slots are included as zeroed entries at the front
+    pub fn get_oracle_history(ctx: Context<GetOracleHistory>) -> Result<OracleHistorySnapshot> {
+        let rift = &ctx.accounts.rift;
+        let oracle_history = rift.oracle_history_for(&ctx.accounts.oracle_history)?;
+        let (buffer, price_index) = rift.oracle_buffer(oracle_history);
+
+        // price_index points at the next slot to overwrite, i.e. the oldest sample
+        let prices = (0..buffer.len())
+            .map(|offset| buffer[(price_index + offset) % buffer.len()])
+            .collect();
+
+        Ok(OracleHistorySnapshot {
+            prices,
+            price_index: price_index as u16,
+            last_oracle_update: rift.last_oracle_update,
+            from_history_account: oracle_history.is_some(),
+        })
+    }
+
+    /// Distribute the entire fees_vault, wrap_fees_vault and withheld_vault balances in one call so
+    /// close_rift can succeed
+    /// Same callers, recipient owner/mint checks and protocol/partner/treasury split as the
+    /// distribute instructions and claim_wrap_fees; zeroes total_fees_collected once the fees
+    /// vaults are empty
+    pub fn drain_and_prepare_close(ctx: Context<DrainAndPrepareClose>) -> Result<()> {
+        let system_program_key = anchor_lang::solana_program::system_program::ID;
+
+        // **AUTHORIZATION**: Creator, partner, treasury, or program authority (same as distribution)
+        let program_authority =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        let partner_wallet = ctx
+            .accounts
+            .rift
+            .partner_wallet
+            .ok_or(ErrorCode::PartnerWalletNotSet)?;
+        let treasury_wallet = ctx
+            .accounts
+            .rift
+            .treasury_wallet
+            .ok_or(ErrorCode::TreasuryNotSet)?;
+        let payer_key = ctx.accounts.payer.key();
+        require!(
+            payer_key == ctx.accounts.rift.creator
+                || payer_key == partner_wallet
+                || payer_key == treasury_wallet
+                || payer_key == program_authority,
+            ErrorCode::Unauthorized
+        );
+
+        let rift_key = ctx.accounts.rift.key();
+        let vault_auth_bump = [ctx.bumps.vault_authority];
+        let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
+        let signer = &[vault_auth_seeds];
+
+        // Fees vault and wrap fees vault pay the same underlying recipients
+        let validate_underlying_recipients = |accounts: &DrainAndPrepareClose| -> Result<()> {
+            let underlying_mint_key = accounts.underlying_mint.key();
+            validate_fee_recipient(
+                &accounts.treasury_underlying_account,
+                &treasury_wallet,
+                &underlying_mint_key,
+                ErrorCode::InvalidTreasuryVault,
+            )?;
+            if let Some(partner_account) = &accounts.partner_underlying_account {
+                validate_fee_recipient(
+                    partner_account,
+                    &partner_wallet,
+                    &underlying_mint_key,
+                    ErrorCode::InvalidPartnerVault,
+                )?;
+            }
+            Ok(())
+        };
+
+        // ===== Fees vault (underlying tokens) =====
+        let mut fees_amount = 0u64;
+        let mut protocol_skim_paid = 0u64;
+        if ctx.accounts.rift.fees_vault != system_program_key {
+            let fees_vault = ctx
+                .accounts
+                .fees_vault
+                .as_ref()
+                .ok_or(ErrorCode::InvalidFeesVault)?;
+            fees_amount = fees_vault.amount;
+
+            if fees_amount > 0 {
+                validate_underlying_recipients(ctx.accounts)?;
+
+                let protocol_skim_amount = ctx.accounts.rift.protocol_fees_owed.min(fees_amount);
+                protocol_skim_paid = protocol_skim_amount;
+                transfer_protocol_skim(
+                    protocol_skim_amount,
+                    fees_vault.to_account_info(),
+                    ctx.accounts.underlying_mint.to_account_info(),
+                    ctx.accounts.underlying_mint.decimals,
+                    ctx.accounts.underlying_token_program.to_account_info(),
+                    ctx.accounts.vault_authority.to_account_info(),
+                    signer,
+                    ctx.accounts
+                        .protocol_treasury_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    &ctx.accounts.global_config.to_account_info(),
+                )?;
+
+                let (protocol_amount, partner_amount, treasury_amount) = transfer_fee_split(
+                    &ctx.accounts.rift,
+                    fees_amount
+                        .checked_sub(protocol_skim_amount)
+                        .ok_or(ErrorCode::MathOverflow)?,
+                    fees_vault.to_account_info(),
+                    ctx.accounts.underlying_mint.to_account_info(),
+                    ctx.accounts.underlying_mint.decimals,
+                    ctx.accounts.underlying_token_program.to_account_info(),
+                    ctx.accounts.vault_authority.to_account_info(),
+                    signer,
+                    ctx.accounts.treasury_underlying_account.to_account_info(),
+                    ctx.accounts
+                        .partner_underlying_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    ctx.accounts
+                        .protocol_underlying_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    &ctx.accounts.global_config.to_account_info(),
+                )?;
+
+                emit!(FeesDistributed {
+                    rift: rift_key,
+                    amount: fees_amount,
+                    protocol_skim_amount,
+                    protocol_amount,
+                    treasury_amount,
+                    partner_amount,
+                    distributor: payer_key,
+                    // Close preparation by the rift's own parties, not keeper work
+                    keeper: Pubkey::default(),
+                });
+            }
+
+            // Fee-on-transfer underlyings debit the source exactly; anything left means a bad transfer
+            if let Some(fees_vault) = ctx.accounts.fees_vault.as_mut() {
+                fees_vault.reload()?;
+                require!(fees_vault.amount == 0, ErrorCode::FeesVaultNotEmpty);
+            }
+        }
+
+        // ===== Wrap fees vault (underlying tokens) =====
+        let mut wrap_fees_amount = 0u64;
+        if let Some(wrap_fees_vault_key) = ctx.accounts.rift.wrap_fees_vault {
+            let wrap_fees_vault = ctx
+                .accounts
+                .wrap_fees_vault
+                .as_ref()
+                .ok_or(ErrorCode::InvalidFeesVault)?
+                .to_account_info();
+            wrap_fees_amount = read_vault_amount(
+                &wrap_fees_vault,
+                wrap_fees_vault_key,
+                ErrorCode::InvalidFeesVault,
+            )?;
+
+            if wrap_fees_amount > 0 {
+                validate_underlying_recipients(ctx.accounts)?;
+
+                // Skim not already paid from fees_vault comes out of the wrap fees first
+                let protocol_skim_owed = ctx
+                    .accounts
+                    .rift
+                    .protocol_fees_owed
+                    .checked_sub(protocol_skim_paid)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                let (protocol_skim_amount, protocol_amount, partner_amount, treasury_amount) =
+                    distribute_wrap_fees_vault(
+                        &ctx.accounts.rift,
+                        wrap_fees_amount,
+                        protocol_skim_owed,
+                        wrap_fees_vault.clone(),
+                        ctx.accounts.underlying_mint.to_account_info(),
+                        ctx.accounts.underlying_mint.decimals,
+                        ctx.accounts.underlying_token_program.to_account_info(),
+                        ctx.accounts.vault_authority.to_account_info(),
+                        signer,
+                        ctx.accounts
+                            .protocol_treasury_account
+                            .as_ref()
+                            .map(|account| account.to_account_info()),
+                        ctx.accounts.treasury_underlying_account.to_account_info(),
+                        ctx.accounts
+                            .partner_underlying_account
+                            .as_ref()
+                            .map(|account| account.to_account_info()),
+                        ctx.accounts
+                            .protocol_underlying_account
+                            .as_ref()
+                            .map(|account| account.to_account_info()),
+                        &ctx.accounts.global_config.to_account_info(),
+                    )?;
+
+                emit!(WrapFeesClaimed {
+                    rift: rift_key,
+                    amount: wrap_fees_amount
+                        .checked_sub(protocol_skim_amount)
+                        .ok_or(ErrorCode::MathOverflow)?,
+                    protocol_skim_amount,
+                    protocol_amount,
+                    partner_amount,
+                    treasury_amount,
+                    claimer: payer_key,
+                });
+            }
+
+            require!(
+                read_vault_amount(
+                    &wrap_fees_vault,
+                    wrap_fees_vault_key,
+                    ErrorCode::InvalidFeesVault
+                )? == 0,
+                ErrorCode::FeesVaultNotEmpty
+            );
+        }
+
+        // ===== Withheld vault (RIFT tokens, Token-2022 mints only) =====
+        let mut withheld_amount = 0u64;
+        if ctx.accounts.rift.withheld_vault != system_program_key && !ctx.accounts.rift.legacy_mint {
+            let withheld_vault = ctx
+                .accounts
+                .withheld_vault
+                .as_ref()
+                .ok_or(ErrorCode::InvalidWithheldVault)?;
+            withheld_amount = withheld_vault.amount;
+
+            if withheld_amount > 0 {
+                let token_2022_program = ctx
+                    .accounts
+                    .token_2022_program
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidProgramId)?;
+                let treasury_rift_account = ctx
+                    .accounts
+                    .treasury_rift_account
+                    .as_ref()
+                    .ok_or(ErrorCode::InvalidTreasuryVault)?;
+                let rift_mint_key = ctx.accounts.rift_mint.key();
+                validate_fee_recipient(
+                    treasury_rift_account,
+                    &treasury_wallet,
+                    &rift_mint_key,
+                    ErrorCode::InvalidTreasuryVault,
+                )?;
+                if let Some(partner_account) = &ctx.accounts.partner_rift_account {
+                    validate_fee_recipient(
+                        partner_account,
+                        &partner_wallet,
+                        &rift_mint_key,
+                        ErrorCode::InvalidPartnerVault,
+                    )?;
+                }
+
+                let rift_mint_data = ctx.accounts.rift_mint.try_borrow_data()?;
+                let rift_decimals = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(
+                    &rift_mint_data,
+                )
+                .map_err(|_| ErrorCode::InvalidMint)?
+                .base
+                .decimals;
+                drop(rift_mint_data);
+
+                let (protocol_amount, partner_amount, treasury_amount) = transfer_fee_split(
+                    &ctx.accounts.rift,
+                    withheld_amount,
+                    withheld_vault.to_account_info(),
+                    ctx.accounts.rift_mint.to_account_info(),
+                    rift_decimals,
+                    token_2022_program.to_account_info(),
+                    ctx.accounts.vault_authority.to_account_info(),
+                    signer,
+                    treasury_rift_account.to_account_info(),
+                    ctx.accounts
+                        .partner_rift_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    ctx.accounts
+                        .protocol_rift_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    &ctx.accounts.global_config.to_account_info(),
+                )?;
+
+                emit!(WithheldFeesDistributed {
+                    rift: rift_key,
+                    amount: withheld_amount,
+                    protocol_amount,
+                    treasury_amount,
+                    partner_amount,
+                    distributor: payer_key,
+                    // Close preparation by the rift's own parties, not keeper work
+                    keeper: Pubkey::default(),
+                });
+            }
+
+            if let Some(withheld_vault) = ctx.accounts.withheld_vault.as_mut() {
+                withheld_vault.reload()?;
+                require!(withheld_vault.amount == 0, ErrorCode::WithheldVaultNotEmpty);
+            }
+        }
+
+        // **FIX MEDIUM #4 (Audit)**: Fees vault is verified empty, so nothing remains to distribute
+        let rift = &mut ctx.accounts.rift;
+        rift.total_fees_collected = 0;
+        rift.protocol_fees_owed = 0;
+        rift.withheld_fees_distributed = rift
+            .withheld_fees_distributed
+            .checked_add(withheld_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        emit!(FeesDrainedForClose {
+            rift: rift_key,
+            fees_amount,
+            wrap_fees_amount,
+            withheld_amount,
+            distributor: payer_key,
+        });
+
+        msg!(
+            "✅ Drained {} fees, {} wrap fees and {} withheld RIFT - ready for close_rift",
+            fees_amount,
+            wrap_fees_amount,
+            withheld_amount
+        );
+
+        Ok(())
+    }
+
+    /// Creator: phase 1 of a two-phase close - block new wraps so holders can exit
+    /// Unwraps and fee distributions keep working until finalize_close, which is allowed
+    /// CLOSE_DELAY_SECONDS later
+    pub fn begin_close(ctx: Context<BeginClose>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::UnauthorizedClose
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(!rift.closing, ErrorCode::RiftClosing);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        rift.closing = true;
+        rift.close_started_at = timestamp;
+
+        msg!(
+            "✅ Rift entering close: {} RIFT outstanding, wraps disabled, closable after {}",
+            rift.total_rift_minted,
+            timestamp.saturating_add(CLOSE_DELAY_SECONDS)
+        );
+
+        emit!(RiftCloseStarted {
+            rift: rift.key(),
+            creator: rift.creator,
+            outstanding_rift: rift.total_rift_minted,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Creator: put the rift in unwrap-only wind-down - new wraps are rejected, unwraps continue
+    /// Unlike pause this keeps exits open, and unlike close it doesn't need zero supply
+    pub fn begin_wind_down(ctx: Context<BeginWindDown>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(!rift.wind_down, ErrorCode::RiftWindingDown);
+
+        rift.wind_down = true;
+
+        msg!(
+            "✅ Rift winding down: {} RIFT outstanding, wraps disabled",
+            rift.total_rift_minted
+        );
+
+        emit!(WindDownStarted {
+            rift: rift.key(),
+            creator: rift.creator,
+            outstanding_rift: rift.total_rift_minted,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Creator: phase 2 of a two-phase close - close_rift, but only after begin_close and its
+    /// CLOSE_DELAY_SECONDS notice period
+    pub fn finalize_close(ctx: Context<CloseRift>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        // **TWO-PHASE CLOSE**: Holders got the begin_close notice and had time to exit
+        let now = Clock::get()?.unix_timestamp;
+        require!(rift.closing, ErrorCode::RiftNotClosing);
+        require!(
+            now >= rift.close_started_at.saturating_add(CLOSE_DELAY_SECONDS),
+            ErrorCode::CloseDelayActive
+        );
+
+        emit!(RiftCloseFinalized {
+            rift: rift.key(),
+            creator: rift.creator,
+            timestamp: now,
+        });
+
+        close_rift(ctx)
+    }
+
+    /// Read-only: run close_rift's emptiness checks without closing
+    /// Returns a u16 bitmask of the failing checks (CLOSE_BLOCKED_*) as return data; 0 = closable
+    pub fn can_close(ctx: Context<CanClose>) -> Result<u16> {
+        let rift = &ctx.accounts.rift;
+        let system_program_key = anchor_lang::solana_program::system_program::ID;
+
+        let mut blocked = 0u16;
+        if !rift.closing
+            || Clock::get()?.unix_timestamp
+                < rift.close_started_at.saturating_add(CLOSE_DELAY_SECONDS)
+        {
+            blocked |= CLOSE_BLOCKED_CLOSE_DELAY;
+        }
+        if rift.total_rift_minted != 0 {
+            blocked |= CLOSE_BLOCKED_RIFT_SUPPLY;
+        }
+        if rift.total_underlying_wrapped != 0 {
+            blocked |= CLOSE_BLOCKED_UNDERLYING_WRAPPED;
+        }
+        if rift.total_fees_collected != 0 {
+            blocked |= CLOSE_BLOCKED_FEES_UNDISTRIBUTED;
+        }
+        if rift.vault != system_program_key
+            && read_vault_amount(&ctx.accounts.vault, rift.vault, ErrorCode::InvalidVault)?
+                > CLOSE_DUST_TOLERANCE
+        {
+            blocked |= CLOSE_BLOCKED_VAULT;
+        }
+        if rift.fees_vault != system_program_key
+            && read_vault_amount(
+                &ctx.accounts.fees_vault,
+                rift.fees_vault,
+                ErrorCode::InvalidFeesVault,
+            )? > CLOSE_DUST_TOLERANCE
+        {
+            blocked |= CLOSE_BLOCKED_FEES_VAULT;
+        }
+        if rift.withheld_vault != system_program_key
+            && read_vault_amount(
+                &ctx.accounts.withheld_vault,
+                rift.withheld_vault,
+                ErrorCode::InvalidWithheldVault,
+            )? > 0
+        {
+            blocked |= CLOSE_BLOCKED_WITHHELD_VAULT;
+        }
+        if let Some(wrap_fees_vault_key) = rift.wrap_fees_vault {
+            let wrap_fees_vault = ctx
+                .accounts
+                .wrap_fees_vault
+                .as_ref()
+                .ok_or(ErrorCode::InvalidFeesVault)?;
+            if read_vault_amount(wrap_fees_vault, wrap_fees_vault_key, ErrorCode::InvalidFeesVault)?
+                > 0
+            {
+                blocked |= CLOSE_BLOCKED_WRAP_FEES_VAULT;
+            }
+        }
+
+        msg!("📊 Close blockers: {:#08b}", blocked);
+
+        Ok(blocked)
+    }
+
+    /// Read-only: pre-validate a candidate underlying before create_rift
+    /// Returns the mint's Token-2022 extension types and the UNDERLYING_BLOCKED_* reasons creation
+    /// would fail as an `UnderlyingInspection` (return data). The RIFT + underlying fee
+    /// leakage cap depends on the chosen RIFT fee and is left to the caller (see transfer_fee_bps).
+    pub fn inspect_underlying(
+        ctx: Context<InspectUnderlying>,
+        allow_confidential: bool,
+        allow_nested: bool,
+    ) -> Result<UnderlyingInspection> {
+        use spl_token_2022::extension::confidential_transfer::ConfidentialTransferMint;
+        use spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+
+        let mint_info = ctx.accounts.underlying_mint.to_account_info();
+        let mut inspection = UnderlyingInspection::default();
+
+        if *mint_info.owner == spl_token_2022::ID {
+            let mint_data = mint_info.try_borrow_data()?;
+            let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)
+                .map_err(|_| ErrorCode::InvalidMint)?;
+            let extension_types = mint_state
+                .get_extension_types()
+                .map_err(|_| ErrorCode::InvalidMint)?;
+
+            // Same screening as create_rift's **FIX CRITICAL #31** block
+            for ext_type in extension_types.iter() {
+                inspection.extension_types.push(*ext_type as u16);
+                inspection.blocked |= match ext_type {
+                    ExtensionType::NonTransferable => UNDERLYING_BLOCKED_NON_TRANSFERABLE,
+                    ExtensionType::PermanentDelegate => UNDERLYING_BLOCKED_PERMANENT_DELEGATE,
+                    ExtensionType::MintCloseAuthority => UNDERLYING_BLOCKED_MINT_CLOSE_AUTHORITY,
+                    ExtensionType::TransferHook => UNDERLYING_BLOCKED_TRANSFER_HOOK,
+                    ExtensionType::MemoTransfer => UNDERLYING_BLOCKED_MEMO_TRANSFER,
+                    ExtensionType::DefaultAccountState => UNDERLYING_BLOCKED_DEFAULT_ACCOUNT_STATE,
+                    ExtensionType::ConfidentialTransferMint => {
+                        let ct_config = mint_state
+                            .get_extension::<ConfidentialTransferMint>()
+                            .map_err(|_| ErrorCode::InvalidMint)?;
+                        if !allow_confidential || bool::from(ct_config.auto_approve_new_accounts) {
+                            UNDERLYING_BLOCKED_CONFIDENTIAL_TRANSFER
+                        } else {
+                            0
+                        }
+                    }
+                    ExtensionType::ConfidentialTransferFeeConfig => {
+                        UNDERLYING_BLOCKED_CONFIDENTIAL_TRANSFER_FEE
+                    }
+                    ExtensionType::TransferFeeConfig => {
+                        let fee_config = mint_state
+                            .get_extension::<TransferFeeConfig>()
+                            .map_err(|_| ErrorCode::InvalidMint)?;
+                        inspection.transfer_fee_bps =
+                            u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points);
+                        if inspection.transfer_fee_bps > 100 {
+                            UNDERLYING_BLOCKED_TRANSFER_FEE
+                        } else {
+                            0
+                        }
+                    }
+                    ExtensionType::InterestBearingConfig => {
+                        inspection.interest_bearing = true;
+                        0
+                    }
+                    _ => 0,
+                };
+            }
+        } else if *mint_info.owner == anchor_spl::token::ID {
+            spl_token::state::Mint::unpack(&mint_info.try_borrow_data()?)
+                .map_err(|_| ErrorCode::InvalidMint)?;
+        } else {
+            return Err(ErrorCode::InvalidMint.into());
+        }
+
+        if GlobalConfig::check_mint_policy(
+            &ctx.accounts.global_config.to_account_info(),
+            &ctx.accounts.mint_policy.to_account_info(),
+        )
+        .is_err()
+        {
+            inspection.blocked |= UNDERLYING_BLOCKED_MINT_POLICY;
+        }
+        if !allow_nested
+            && RiftMintRegistry::is_registered(&ctx.accounts.underlying_registry.to_account_info())
+        {
+            inspection.blocked |= UNDERLYING_BLOCKED_NESTED_RIFT;
+        }
+        inspection.can_create = inspection.blocked == 0;
+
+        msg!(
+            "🔎 Underlying {}: {} extensions, blocked {:#013b}, creatable: {}",
+            mint_info.key(),
+            inspection.extension_types.len(),
+            inspection.blocked,
+            inspection.can_create
+        );
+
+        Ok(inspection)
+    }
+
+    /// Read-only: compare the live vault balance against the RIFT supply it must back
+    /// Writes a `SolvencyReport` (solvent = vault.amount >= total_rift_minted) to return data
+    pub fn check_solvency(ctx: Context<CheckSolvency>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+        let system_program_key = anchor_lang::solana_program::system_program::ID;
+
+        // Vaults never initialized hold nothing
+        let balance_of = |vault_info: &AccountInfo, stored_vault: Pubkey, error: ErrorCode| {
+            if stored_vault == system_program_key {
+                Ok(0)
+            } else {
+                read_vault_amount(vault_info, stored_vault, error)
+            }
+        };
+        let vault_amount = balance_of(&ctx.accounts.vault, rift.vault, ErrorCode::InvalidVault)?;
+        let fees_vault_amount = balance_of(
+            &ctx.accounts.fees_vault,
+            rift.fees_vault,
+            ErrorCode::InvalidFeesVault,
+        )?;
+        let withheld_vault_amount = balance_of(
+            &ctx.accounts.withheld_vault,
+            rift.withheld_vault,
+            ErrorCode::InvalidWithheldVault,
+        )?;
+
+        let report = SolvencyReport {
+            solvent: vault_amount >= rift.total_rift_minted,
+            shortfall: rift.total_rift_minted.saturating_sub(vault_amount),
+            surplus: vault_amount.saturating_sub(rift.total_rift_minted),
+        };
+
+        msg!("📊 Solvency check for rift: {}", rift.key());
+        msg!("   Vault: {} | RIFT supply: {}", vault_amount, rift.total_rift_minted);
+        msg!("   Fees vault: {} | Withheld vault: {}", fees_vault_amount, withheld_vault_amount);
+        msg!(
+            "   Solvent: {} (shortfall {}, surplus {})",
+            report.solvent,
+            report.shortfall,
+            report.surplus
+        );
+
+        let mut data = Vec::with_capacity(SolvencyReport::SIZE);
+        report.serialize(&mut data)?;
+        anchor_lang::solana_program::program::set_return_data(&data);
+
+        Ok(())
+    }
+
+    /// Creator: rebalance on the median of fresh oracle samples instead of the mean
+    pub fn set_use_median_oracle(ctx: Context<SetUseMedianOracle>, use_median: bool) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        rift.use_median_oracle = use_median;
+
+        msg!(
+            "✅ Rebalance oracle aggregation: {}",
+            if use_median { "median" } else { "mean" }
+        );
+
+        Ok(())
+    }
+
+    /// Admin function: Create the protocol config PDA (program authority only, once)
+    pub fn initialize_global_config(
+        ctx: Context<InitializeGlobalConfig>,
+        protocol_fee_recipient: Pubkey,
+        distribution_fee_bps: u16,
+    ) -> Result<()> {
+        let admin_pubkey = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        require!(
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
+        );
+        require!(
+            distribution_fee_bps <= MAX_DISTRIBUTION_FEE_BPS,
+            ErrorCode::InvalidProtocolFee
+        );
+
+        let config = &mut ctx.accounts.global_config;
+        config.protocol_fee_recipient = protocol_fee_recipient;
+        config.distribution_fee_bps = distribution_fee_bps;
+        config.bump = ctx.bumps.global_config;
+        config.apply_default_config();
+
+        msg!(
+            "✅ Global config initialized: protocol cut {} bps to {}",
+            distribution_fee_bps,
+            protocol_fee_recipient
+        );
+
+        Ok(())
+    }
+
+    /// Admin function: Rotate the protocol authorities stored in the config PDA
+    /// Requires BOTH current admin authorities (2-of-2, same as emergency withdraw)
+    pub fn update_global_authority(
+        ctx: Context<UpdateGlobalAuthority>,
+        new_program_authority: Pubkey,
+        new_admin_authority_2: Pubkey,
+        new_treasury_wallet: Pubkey,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.global_config;
+
+        require!(
+            ctx.accounts.admin_authority_1.key() == config.program_authority,
+            ErrorCode::UnauthorizedAdmin
+        );
+        require!(
+            ctx.accounts.admin_authority_2.key() == config.admin_authority_2,
+            ErrorCode::UnauthorizedAdmin
+        );
+        // Two distinct keys keep the 2-of-2 meaningful after rotation
+        require!(
+            new_program_authority != Pubkey::default()
+                && new_admin_authority_2 != Pubkey::default()
+                && new_treasury_wallet != Pubkey::default()
+                && new_program_authority != new_admin_authority_2,
+            ErrorCode::InvalidAuthority
+        );
+
+        let old_program_authority = config.program_authority;
+        let old_admin_authority_2 = config.admin_authority_2;
+        let old_treasury_wallet = config.treasury_wallet;
+        config.program_authority = new_program_authority;
+        config.admin_authority_2 = new_admin_authority_2;
+        config.treasury_wallet = new_treasury_wallet;
+
+        emit!(GlobalAuthorityUpdated {
+            old_program_authority,
+            new_program_authority,
+            old_admin_authority_2,
+            new_admin_authority_2,
+            old_treasury_wallet,
+            new_treasury_wallet,
+        });
+
+        msg!(
+            "✅ Global authorities rotated: program {} / admin2 {} / treasury {}",
+            new_program_authority,
+            new_admin_authority_2,
+            new_treasury_wallet
+        );
+
+        Ok(())
+    }
+
+    /// Admin function: Update the protocol cut of fee distributions (program authority only)
+    pub fn set_distribution_fee(
+        ctx: Context<SetDistributionFee>,
+        protocol_fee_recipient: Pubkey,
+        distribution_fee_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.program_authority.key() == ctx.accounts.global_config.program_authority,
+            ErrorCode::UnauthorizedAdmin
+        );
+        require!(
+            distribution_fee_bps <= MAX_DISTRIBUTION_FEE_BPS,
+            ErrorCode::InvalidProtocolFee
+        );
+
+        let config = &mut ctx.accounts.global_config;
+        let old_distribution_fee_bps = config.distribution_fee_bps;
+        config.protocol_fee_recipient = protocol_fee_recipient;
+        config.distribution_fee_bps = distribution_fee_bps;
+
+        emit!(DistributionFeeUpdated {
+            old_distribution_fee_bps,
+            new_distribution_fee_bps: distribution_fee_bps,
+            protocol_fee_recipient,
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Admin function: set the protocol skim of wrap/unwrap volume (program authority only)
+    /// The skim comes out of each wrap/unwrap fee before the partner/treasury split and is paid
+    /// to the global treasury on distribution; 0 disables it
+    pub fn set_protocol_fee_bps(ctx: Context<SetProtocolFee>, protocol_fee_bps: u16) -> Result<()> {
+        require!(
+            ctx.accounts.program_authority.key() == ctx.accounts.global_config.program_authority,
+            ErrorCode::UnauthorizedAdmin
+        );
+        require!(
+            protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            ErrorCode::InvalidProtocolFee
+        );
+
+        let config = &mut ctx.accounts.global_config;
+        let old_protocol_fee_bps = config.protocol_fee_bps;
+        config.protocol_fee_bps = protocol_fee_bps;
+
+        emit!(ProtocolFeeUpdated {
+            old_protocol_fee_bps,
+            new_protocol_fee_bps: protocol_fee_bps,
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        msg!(
+            "✅ Protocol fee: {} → {} bps",
+            old_protocol_fee_bps,
+            protocol_fee_bps
+        );
+
+        Ok(())
+    }
+
+    /// Admin function: choose how MintPolicy entries gate rift creation
+    /// MINT_POLICY_DENYLIST (0) = permissionless minus denied mints; MINT_POLICY_ALLOWLIST (1) = curated
+    pub fn set_mint_policy_mode(ctx: Context<SetMintPolicyMode>, mint_policy_mode: u8) -> Result<()> {
+        require!(
+            ctx.accounts.program_authority.key() == ctx.accounts.global_config.program_authority,
+            ErrorCode::UnauthorizedAdmin
+        );
+        require!(
+            mint_policy_mode == MINT_POLICY_DENYLIST || mint_policy_mode == MINT_POLICY_ALLOWLIST,
+            ErrorCode::InvalidMintPolicyMode
+        );
+
+        let config = &mut ctx.accounts.global_config;
+        let old_mint_policy_mode = config.mint_policy_mode;
+        config.mint_policy_mode = mint_policy_mode;
+
+        emit!(MintPolicyModeUpdated {
+            old_mint_policy_mode,
+            new_mint_policy_mode: mint_policy_mode,
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        msg!(
+            "✅ Mint policy mode: {} → {}",
+            old_mint_policy_mode,
+            mint_policy_mode
+        );
+
+        Ok(())
+    }
+
+    /// Admin function: allow or deny an underlying mint for new rifts
+    /// Existing rifts over the mint are unaffected
+    pub fn set_mint_policy(ctx: Context<SetMintPolicy>, allowed: bool) -> Result<()> {
+        let admin_pubkey =
+            GlobalConfig::load_authorities(&ctx.accounts.global_config.to_account_info())?
+                .program_authority;
+        require!(
+            ctx.accounts.program_authority.key() == admin_pubkey,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        let policy = &mut ctx.accounts.mint_policy;
+        policy.underlying_mint = ctx.accounts.underlying_mint.key();
+        policy.allowed = allowed;
+        policy.bump = ctx.bumps.mint_policy;
+
+        emit!(MintPolicyUpdated {
+            underlying_mint: ctx.accounts.underlying_mint.key(),
+            allowed,
+            authority: ctx.accounts.program_authority.key(),
+        });
+
+        msg!(
+            "✅ Underlying {} {}",
+            ctx.accounts.underlying_mint.key(),
+            if allowed { "allowed" } else { "denied" }
+        );
+
+        Ok(())
+    }
+
+    /// Creator: require a recent oracle sample for wrap/unwrap (max_oracle_age 60s-24h)
+    pub fn set_oracle_freshness_policy(
+        ctx: Context<SetOracleFreshnessPolicy>,
+        require_fresh_oracle: bool,
+        max_oracle_age: i64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            (MIN_ORACLE_FRESHNESS_AGE..=MAX_ORACLE_FRESHNESS_AGE).contains(&max_oracle_age),
+            ErrorCode::InvalidOracleFreshnessPolicy
+        );
+
+        rift.require_fresh_oracle = require_fresh_oracle;
+        rift.max_oracle_age = max_oracle_age;
+
+        msg!(
+            "✅ Oracle freshness policy: {} (max age {}s)",
+            if require_fresh_oracle { "required" } else { "disabled" },
+            max_oracle_age
+        );
+
+        Ok(())
+    }
+
+    /// Creator: retune oracle cadence without recreating the rift
+    /// `max_rebalance_interval` / `arbitrage_threshold_bps`: None keeps the current value
+    /// Bounds match creation: interval 300-86400s, rebalance 3600-604800s, threshold 10-5000 bps
+    pub fn set_oracle_update_interval(
+        ctx: Context<SetOracleUpdateInterval>,
+        oracle_update_interval: i64,
+        max_rebalance_interval: Option<i64>,
+        arbitrage_threshold_bps: Option<u16>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        let max_rebalance_interval = max_rebalance_interval.unwrap_or(rift.max_rebalance_interval);
+        let arbitrage_threshold_bps =
+            arbitrage_threshold_bps.unwrap_or(rift.arbitrage_threshold_bps);
+
+        require!(
+            (MIN_ORACLE_UPDATE_INTERVAL..=MAX_ORACLE_UPDATE_INTERVAL)
+                .contains(&oracle_update_interval),
+            ErrorCode::InvalidOracleParameters
+        );
+        require!(
+            (MIN_REBALANCE_INTERVAL..=MAX_REBALANCE_INTERVAL).contains(&max_rebalance_interval),
+            ErrorCode::InvalidOracleParameters
+        );
+        require!(
+            (MIN_ARBITRAGE_THRESHOLD_BPS..=MAX_ARBITRAGE_THRESHOLD_BPS)
+                .contains(&arbitrage_threshold_bps),
+            ErrorCode::InvalidOracleParameters
+        );
+
+        rift.oracle_update_interval = oracle_update_interval;
+        rift.max_rebalance_interval = max_rebalance_interval;
+        rift.arbitrage_threshold_bps = arbitrage_threshold_bps;
+
+        emit!(OracleParamsUpdated {
+            rift: rift.key(),
+            oracle_update_interval,
+            max_rebalance_interval,
+            arbitrage_threshold_bps,
+            creator: ctx.accounts.creator.key(),
+        });
+
+        msg!(
+            "✅ Oracle params: update every {}s, rebalance within {}s, arbitrage threshold {} bps",
+            oracle_update_interval,
+            max_rebalance_interval,
+            arbitrage_threshold_bps
+        );
+
+        Ok(())
+    }
+
+    /// Creator: revert unwraps that lose more than `max_unwrap_leakage_bps` to destination-side
+    /// transfer fees (deflationary underlyings). Off by default.
+    pub fn set_deflationary_protection(
+        ctx: Context<SetDeflationaryProtection>,
+        block_deflationary_loss: bool,
+        max_unwrap_leakage_bps: u16,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            max_unwrap_leakage_bps <= 10000,
+            ErrorCode::InvalidUnwrapLeakage
+        );
+
+        rift.block_deflationary_loss = block_deflationary_loss;
+        rift.max_unwrap_leakage_bps = max_unwrap_leakage_bps;
+
+        msg!(
+            "✅ Deflationary protection: {} (tolerance {} bps)",
+            if block_deflationary_loss { "blocking" } else { "off" },
+            max_unwrap_leakage_bps
+        );
+
+        Ok(())
+    }
+
+    /// Creator: cap a single unwrap's net payout at `max_unwrap_vault_fraction_bps` of the vault
+    /// (0 = disabled). Large holders split across transactions instead of draining the vault.
+    pub fn set_max_unwrap_fraction(
+        ctx: Context<SetMaxUnwrapFraction>,
+        max_unwrap_vault_fraction_bps: u16,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            max_unwrap_vault_fraction_bps <= 10000,
+            ErrorCode::InvalidUnwrapFraction
+        );
+
+        rift.max_unwrap_vault_fraction_bps = max_unwrap_vault_fraction_bps;
+
+        msg!(
+            "✅ Max single unwrap set to {} bps of the vault",
+            max_unwrap_vault_fraction_bps
+        );
+
+        Ok(())
+    }
+
+    /// Creator: set the minimum wrap/unwrap fee in underlying base units (0 = pure bps)
+    /// Only applies when the bps fee is nonzero; relevant for low-decimal underlyings where
+    /// small amounts would otherwise round to a zero fee
+    pub fn set_min_fee_units(ctx: Context<SetMinFeeUnits>, min_fee_units: u64) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            min_fee_units <= MAX_MIN_FEE_UNITS,
+            ErrorCode::InvalidMinFeeUnits
+        );
+
+        rift.min_fee_units = min_fee_units;
+
+        msg!("✅ Minimum fee set to {} base units", min_fee_units);
+
+        Ok(())
+    }
+
+    /// Creator: fees_vault balance at which opt-in unwraps distribute fees inline (0 = disabled)
+    pub fn set_auto_distribute_threshold(
+        ctx: Context<SetAutoDistributeThreshold>,
+        auto_distribute_threshold: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        rift.auto_distribute_threshold = auto_distribute_threshold;
+
+        msg!("✅ Auto-distribute threshold set to {}", auto_distribute_threshold);
+
+        Ok(())
+    }
+
+    /// Creator: underlying paid from fees_vault to whoever triggers a due rebalance (0 = disabled)
+    /// Each payout is further capped at MAX_REBALANCE_REWARD_BPS of the available fees_vault
+    pub fn set_rebalance_reward(
+        ctx: Context<SetRebalanceReward>,
+        rebalance_reward: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        rift.rebalance_reward = rebalance_reward;
+
+        msg!("✅ Rebalance reward set to {}", rebalance_reward);
+
+        Ok(())
+    }
+
+    /// Creator: reject Switchboard updates whose confidence is below this share of price
+    /// (0 = no floor, max MAX_MIN_CONFIDENCE_BPS so it stays under the 5% ceiling)
+    pub fn set_min_confidence_bps(
+        ctx: Context<SetMinConfidence>,
+        min_confidence_bps: u16,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            min_confidence_bps <= MAX_MIN_CONFIDENCE_BPS,
+            ErrorCode::InvalidMinConfidence
+        );
+
+        rift.min_confidence_bps = min_confidence_bps;
+
+        msg!("✅ Minimum oracle confidence set to {} bps", min_confidence_bps);
+
+        Ok(())
+    }
+
+    /// Creator: set the minimum slots between wraps by the same user (0 = disabled)
+    pub fn set_wrap_cooldown(ctx: Context<SetWrapCooldown>, wrap_cooldown_slots: u64) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        rift.wrap_cooldown_slots = wrap_cooldown_slots;
+
+        msg!("✅ Wrap cooldown set to {} slots", wrap_cooldown_slots);
+
+        Ok(())
+    }
+
+    /// Creator: set per-transaction wrap limits (0 = unbounded)
+    pub fn set_wrap_limits(
+        ctx: Context<SetWrapLimits>,
+        min_wrap_amount: u64,
+        max_wrap_amount: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            max_wrap_amount == 0 || min_wrap_amount <= max_wrap_amount,
+            ErrorCode::InvalidWrapLimits
+        );
+
+        rift.min_wrap_amount = min_wrap_amount;
+        rift.max_wrap_amount = max_wrap_amount;
+
+        msg!(
+            "✅ Wrap limits: min {} / max {} (0 = unbounded)",
+            min_wrap_amount,
+            max_wrap_amount
+        );
+
+        Ok(())
+    }
+
+    /// Creator: configure volume-tiered wrap/unwrap fees
+    /// Thresholds must be ascending (repeat the last tier to leave slots unused), each fee <= 100 bps
+    pub fn configure_fee_tiers(
+        ctx: Context<ConfigureFeeTiers>,
+        dynamic_fee_enabled: bool,
+        fee_tiers: [FeeTier; 4],
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(
+            ctx.accounts.creator.key() == rift.creator,
+            ErrorCode::Unauthorized
+        );
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(
+            fee_tiers
+                .windows(2)
+                .all(|pair| pair[0].volume_threshold <= pair[1].volume_threshold),
+            ErrorCode::InvalidFeeTiers
+        );
+        require!(
+            fee_tiers.iter().all(|tier| tier.fee_bps <= 100),
+            ErrorCode::InvalidFeeTiers
+        );
+
+        rift.dynamic_fee_enabled = dynamic_fee_enabled;
+        rift.fee_tiers = fee_tiers;
+
+        msg!(
+            "✅ Dynamic fees {}",
+            if dynamic_fee_enabled { "enabled" } else { "disabled" }
+        );
+        for tier in fee_tiers.iter() {
+            msg!("   ≥ {} volume → {} bps", tier.volume_threshold, tier.fee_bps);
+        }
+
+        Ok(())
+    }
+
+    /// Read-only: total withheld RIFT transfer fees across the token accounts in remaining_accounts
+    /// Lets operators judge whether a harvest batch is worth the transaction cost.
+    /// Accounts that aren't Token-2022 accounts of this rift's mint are skipped and counted.
+    pub fn sum_withheld_fees(ctx: Context<SumWithheldFees>) -> Result<WithheldFeesSummary> {
+        use spl_token_2022::extension::transfer_fee::TransferFeeAmount;
+
+        require!(!ctx.accounts.rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
+        let rift_mint = ctx.accounts.rift.rift_mint;
+        let mut summary = WithheldFeesSummary::default();
+
+        for account_info in ctx.remaining_accounts.iter() {
+            let withheld = if *account_info.owner == spl_token_2022::ID {
+                let account_data = account_info.try_borrow_data()?;
+                StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account_data)
+                    .ok()
+                    .filter(|token_account| token_account.base.mint == rift_mint)
+                    .map(|token_account| {
+                        token_account
+                            .get_extension::<TransferFeeAmount>()
+                            .map(|fee_amount| u64::from(fee_amount.withheld_amount))
+                            .unwrap_or(0)
+                    })
+            } else {
+                None
+            };
+
+            match withheld {
+                Some(amount) => {
+                    summary.total_withheld = summary
+                        .total_withheld
+                        .checked_add(amount)
+                        .ok_or(ErrorCode::MathOverflow)?;
+                    summary.accounts_counted += 1;
+                }
+                None => {
+                    summary.accounts_skipped += 1;
+                    msg!("⚠️ Skipping {} (not a token account of this rift's mint)", account_info.key);
+                }
+            }
+        }
+
+        msg!(
+            "📊 Withheld fees: {} across {} accounts ({} skipped)",
+            summary.total_withheld,
+            summary.accounts_counted,
+            summary.accounts_skipped
+        );
+
+        Ok(summary)
+    }
+
+    /// Permissionless: snapshot vault_backing_ratio from the live vault balance and RIFT supply
+    /// vault_backing_ratio = vault.amount * 1_000_000 / total_rift_minted (1_000_000 at 0 supply)
+    /// Kept apart from backing_ratio, which holds the oracle price used as fallback and arbitrage
+    /// baseline and must not be overwritable by anyone
+    pub fn refresh_backing_ratio(ctx: Context<RefreshBackingRatio>) -> Result<()> {
+        let vault_amount = ctx.accounts.vault.amount;
+        let rift = &mut ctx.accounts.rift;
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+        let old_backing_ratio = rift.vault_backing_ratio;
+        let new_backing_ratio = rift.compute_backing_ratio(vault_amount)?;
+        rift.vault_backing_ratio = new_backing_ratio;
+        rift.vault_backing_ratio_updated_at = timestamp;
+
+        emit!(BackingRatioUpdated {
+            rift: rift.key(),
+            old_backing_ratio,
+            new_backing_ratio,
+            vault_amount,
+            total_rift_minted: rift.total_rift_minted,
+            caller: ctx.accounts.caller.key(),
+            timestamp,
+        });
+
+        msg!(
+            "📊 Vault backing ratio refreshed: {} → {} (vault {}, supply {})",
+            old_backing_ratio,
+            new_backing_ratio,
+            vault_amount,
+            rift.total_rift_minted
+        );
+
+        Ok(())
+    }
+
+    /// Permissionless: refresh the arbitrage signal from the oracle price vs backing_ratio
+    /// arbitrage_opportunity_bps = |oracle - backing| * 10000 / backing; price_deviation holds the
+    /// raw |oracle - backing| (6 decimals). Uses the median when use_median_oracle is set.
+    pub fn update_arbitrage_metric(ctx: Context<UpdateArbitrageMetric>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+
+        // **ORACLE TRUST**: A long-dead oracle must not advertise a phantom opportunity
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(rift.is_oracle_trusted(current_time), ErrorCode::OraclePriceStale);
+
+        let oracle_history = rift.oracle_history_for(&ctx.accounts.oracle_history)?;
+        let oracle_price = if rift.use_median_oracle {
+            rift.get_median_oracle_price(oracle_history)?
+        } else {
+            rift.get_average_oracle_price(oracle_history)?
+        };
+        require!(oracle_price > 0, ErrorCode::InvalidOraclePrice);
+
+        let arbitrage_opportunity_bps = rift.calculate_price_deviation(oracle_price)?;
+        rift.arbitrage_opportunity_bps = arbitrage_opportunity_bps;
+        rift.price_deviation = oracle_price.abs_diff(rift.backing_ratio);
+
+        emit!(ArbitrageMetricUpdated {
+            rift: rift.key(),
+            oracle_price,
+            backing_ratio: rift.backing_ratio,
+            price_deviation: rift.price_deviation,
+            arbitrage_opportunity_bps,
+            caller: ctx.accounts.caller.key(),
+            timestamp: current_time,
+        });
+
+        msg!(
+            "📊 Arbitrage metric: oracle {} vs backing {} → {} bps",
+            oracle_price,
+            rift.backing_ratio,
+            arbitrage_opportunity_bps
+        );
+
+        Ok(())
+    }
+
+    /// Read-only: expose the launch transfer fee alongside the live transfer fee
+    /// Lets users see whether a rift's DEX trading fee has changed since creation
+    pub fn get_transfer_fee_disclosure(
+        ctx: Context<GetTransferFeeDisclosure>,
+    ) -> Result<TransferFeeDisclosure> {
+        let rift = &ctx.accounts.rift;
+
+        let disclosure = TransferFeeDisclosure {
+            initial_transfer_fee_bps: rift.initial_transfer_fee_bps,
+            current_transfer_fee_bps: rift.transfer_fee_bps,
+            changed_since_launch: rift.initial_transfer_fee_bps != rift.transfer_fee_bps,
+        };
+
+        msg!(
+            "📊 Transfer fee: launched at {} bps, now {} bps",
+            disclosure.initial_transfer_fee_bps,
+            disclosure.current_transfer_fee_bps
+        );
+
+        Ok(disclosure)
+    }
+
+    /// Read-only: live transfer fee straight from the RIFT mint's TransferFeeConfig
+    /// Returns older/newer fees and the epoch the newer fee takes effect (two-phase scheduling)
+    pub fn get_transfer_fee(ctx: Context<GetTransferFee>) -> Result<()> {
+        // **LEGACY MINT**: Classic SPL Token RIFT mints have no transfer fee
+        require!(!ctx.accounts.rift.legacy_mint, ErrorCode::LegacyMintUnsupported);
+
+        let rift_mint_data = ctx.accounts.rift_mint.try_borrow_data()?;
+        let mint_state =
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)?;
+        let fee_config = mint_state.get_extension::<TransferFeeConfig>()?;
+
+        let current_epoch = Clock::get()?.epoch;
+        let live_fee = LiveTransferFee {
+            older_transfer_fee_bps: u16::from(fee_config.older_transfer_fee.transfer_fee_basis_points),
+            older_maximum_fee: u64::from(fee_config.older_transfer_fee.maximum_fee),
+            newer_transfer_fee_bps: u16::from(fee_config.newer_transfer_fee.transfer_fee_basis_points),
+            newer_maximum_fee: u64::from(fee_config.newer_transfer_fee.maximum_fee),
+            newer_effective_epoch: u64::from(fee_config.newer_transfer_fee.epoch),
+            current_epoch,
+            active_transfer_fee_bps: u16::from(
+                fee_config
+                    .get_epoch_fee(current_epoch)
+                    .transfer_fee_basis_points,
+            ),
+        };
+        drop(rift_mint_data);
+
+        msg!(
+            "📊 Transfer fee: {} bps active, {} bps from epoch {}",
+            live_fee.active_transfer_fee_bps,
+            live_fee.newer_transfer_fee_bps,
+            live_fee.newer_effective_epoch
+        );
+
+        let mut data = Vec::with_capacity(LiveTransferFee::SIZE);
+        live_fee.serialize(&mut data)?;
+        anchor_lang::solana_program::program::set_return_data(&data);
+
+        Ok(())
+    }
+
+    /// Read-only: effective underlying-per-RIFT rate (6 decimals) a wrap or unwrap of `amount`
+    /// would execute at, after the wrap/unwrap fee and - with `include_rift_transfer_fee` - one
+    /// RIFT transfer (e.g. routing through a pool). `amount` is underlying for wraps, RIFT for
+    /// unwraps. Assumes no fee exemption; the underlying mint's transfer fee (if any) is applied
+    /// to the deposit (wraps) or the vault payout (unwraps).
+    /// Writes a PriceQuote (Borsh) to return data, with the oracle price for premium/discount.
+    pub fn quote_price(
+        ctx: Context<QuotePrice>,
+        wrap: bool,
+        amount: u64,
+        include_rift_transfer_fee: bool,
+    ) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+        require!(!rift.is_closed, ErrorCode::RiftClosed);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let clock = Clock::get()?;
+        let static_fee_bps = if wrap { rift.wrap_fee_bps } else { rift.unwrap_fee_bps };
+        let fee_bps = rift.effective_fee_bps(static_fee_bps, clock.unix_timestamp);
+
+        // RIFT transfer fee at the live epoch rate (legacy SPL Token mints have none)
+        let rift_transfer_fee = |rift_amount: u64| -> Result<u64> {
+            if !include_rift_transfer_fee || rift.legacy_mint {
+                return Ok(0);
+            }
+            let rift_mint_data = ctx.accounts.rift_mint.try_borrow_data()?;
+            let mint_state =
+                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&rift_mint_data)?;
+            let fee_config = mint_state.get_extension::<TransferFeeConfig>()?;
+            Ok(fee_config
+                .calculate_epoch_fee(clock.epoch, rift_amount)
+                .ok_or(ErrorCode::MathOverflow)?)
+        };
+
+        // Underlying transfer fee at the live epoch rate - always applied, since process_wrap
+        // charges the wrap fee on what the vault actually received (SPL Token mints have none)
+        let underlying_transfer_fee = |underlying_amount: u64| -> Result<u64> {
+            let underlying_mint_data = ctx.accounts.underlying_mint.try_borrow_data()?;
+            let mint_state =
+                StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&underlying_mint_data)?;
+            match mint_state.get_extension::<TransferFeeConfig>() {
+                Ok(fee_config) => Ok(fee_config
+                    .calculate_epoch_fee(clock.epoch, underlying_amount)
+                    .ok_or(ErrorCode::MathOverflow)?),
+                Err(_) => Ok(0),
+            }
+        };
+
+        let (underlying_amount, rift_amount, fee, transfer_fee, underlying_fee) = if wrap {
+            let underlying_fee = underlying_transfer_fee(amount)?;
+            let received = amount.checked_sub(underlying_fee).ok_or(ErrorCode::MathOverflow)?;
+            let fee = rift.charge_fee(received, u64::from(fee_bps))?;
+            let minted = received.checked_sub(fee).ok_or(ErrorCode::MathOverflow)?;
+            let transfer_fee = rift_transfer_fee(minted)?;
+            let rift_out = minted.checked_sub(transfer_fee).ok_or(ErrorCode::MathOverflow)?;
+            (amount, rift_out, fee, transfer_fee, underlying_fee)
+        } else {
+            let transfer_fee = rift_transfer_fee(amount)?;
+            let burned = amount.checked_sub(transfer_fee).ok_or(ErrorCode::MathOverflow)?;
+            let fee = rift.charge_fee(burned, u64::from(fee_bps))?;
+            let sent = burned.checked_sub(fee).ok_or(ErrorCode::MathOverflow)?;
+            let underlying_fee = underlying_transfer_fee(sent)?;
+            let underlying_out = sent.checked_sub(underlying_fee).ok_or(ErrorCode::MathOverflow)?;
+            (underlying_out, amount, fee, transfer_fee, underlying_fee)
+        };
+        require!(underlying_amount > 0 && rift_amount > 0, ErrorCode::InvalidAmount);
+
+        let effective_price = u128::from(underlying_amount)
+            .checked_mul(1_000_000)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(u128::from(rift_amount))
+            .ok_or(ErrorCode::MathOverflow)?;
+        let effective_price = u64::try_from(effective_price).map_err(|_| ErrorCode::MathOverflow)?;
+
+        // 0 when the rift has no usable oracle samples yet
+        let oracle_history = rift.oracle_history_for(&ctx.accounts.oracle_history)?;
+        let oracle_price = rift.get_average_oracle_price(oracle_history).unwrap_or(0);
+
+        let quote = PriceQuote {
+            wrap,
+            amount_in: amount,
+            amount_out: if wrap { rift_amount } else { underlying_amount },
+            fee_bps,
+            fee,
+            rift_transfer_fee: transfer_fee,
+            underlying_transfer_fee: underlying_fee,
+            effective_price,
+            oracle_price,
+        };
+
+        msg!(
+            "📊 {} quote: {} in → {} out, effective price {} (oracle {})",
+            if wrap { "Wrap" } else { "Unwrap" },
+            quote.amount_in,
+            quote.amount_out,
+            effective_price,
+            oracle_price
+        );
+
+        let mut data = Vec::with_capacity(PriceQuote::SIZE);
+        quote.serialize(&mut data)?;
+        anchor_lang::solana_program::program::set_return_data(&data);
+
+        Ok(())
+    }
+}
+
+/// Shared wrap logic for `wrap_tokens` and `wrap_tokens_exact_out`
+/// `amount` is the deposit (exact-in) or the deposit ceiling (exact-out, `exact_rift_out = Some(..)`)
+/// Returns the net RIFT minted to the user (after wrap fee and underlying transfer fee)
+fn process_wrap(
+    ctx: Context<WrapTokens>,
+    amount: u64,
+    min_rift_out: u64,
+    exact_rift_out: Option<u64>,
+    deadline_unix: i64,
+    create_rift_ata: bool,
+) -> Result<u64> {
+    // **CRITICAL FIX #2 + FIX HIGH #1 + FIX ISSUE #7**: Add reentrancy protection with auto-timeout
+    {
+        let rift = &mut ctx.accounts.rift;
+
+        // **FIX ISSUE #7**: Auto-clear stuck guard after timeout
+        if rift.reentrancy_guard {
+            let current_slot = Clock::get()?.slot;
+            if current_slot > rift.reentrancy_guard_slot + rift.reentrancy_timeout() {
+                msg!(
+                    "⚠️ Auto-clearing stuck reentrancy guard (set at slot {}, current {})",
+                    rift.reentrancy_guard_slot,
+                    current_slot
+                );
+                emit!(ReentrancyGuardAutoCleared {
+                    rift: rift.key(),
+                    set_at_slot: rift.reentrancy_guard_slot,
+                    cleared_at_slot: current_slot,
+                });
+                rift.reentrancy_guard = false;
+                rift.reentrancy_guard_slot = 0;
+            } else {
+                return Err(ErrorCode::ReentrancyDetected.into());
+            }
+        }
+
+        rift.reentrancy_guard = true;
+        rift.reentrancy_guard_slot = Clock::get()?.slot;
+    }
+
+    // Execute the actual function logic
+    let execution_result = (|| -> Result<u64> {
+        let rift = &mut ctx.accounts.rift;
+
+        // **FIX ISSUE #8**: Shared wrap preflight - closed, closing, paused, oracle, cooldown
+        let current_slot = Clock::get()?.slot;
+        rift.ensure_wrap_open(
+            ctx.accounts.user_activity.last_wrap_slot,
+            current_slot,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        // Basic validation
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        // **DEADLINE**: Prevent stale transactions from executing much later
+        require!(
+            deadline_unix == 0 || Clock::get()?.unix_timestamp <= deadline_unix,
+            ErrorCode::DeadlineExceeded
+        );
+
+        // **AUTO-ATA**: Opt-in creation of the user's canonical RIFT ATA (user pays rent)
+        if create_rift_ata && ctx.accounts.user_rift_tokens.data_is_empty() {
+            let expected_rift_ata =
+                anchor_spl::associated_token::get_associated_token_address_with_program_id(
+                    &ctx.accounts.user.key(),
+                    &rift.rift_mint,
+                    &rift.rift_token_program_id(),
+                );
+            require!(
+                ctx.accounts.user_rift_tokens.key() == expected_rift_ata,
+                ErrorCode::InvalidTokenAccount
+            );
+            let associated_token_program = ctx
+                .accounts
+                .associated_token_program
+                .as_ref()
+                .ok_or(ErrorCode::MissingAssociatedTokenProgram)?;
+
+            anchor_spl::associated_token::create(CpiContext::new(
+                associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: ctx.accounts.user.to_account_info(),
+                    associated_token: ctx.accounts.user_rift_tokens.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                    mint: ctx.accounts.rift_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.rift_token_program.to_account_info(),
+                },
+            ))?;
+            msg!("✅ Created RIFT ATA {} for first wrap", expected_rift_ata);
+        }
+
+        // **CRITICAL FIX #3**: Manual token account validation - MUST validate, not skip
+        // **FIX CRITICAL #27**: Validate accounts against their respective token programs
+        {
+            // Validate underlying token account (can be SPL Token or Token-2022)
+            require!(
+                *ctx.accounts.user_underlying.owner
+                    == ctx.accounts.underlying_token_program.key(),
+                ErrorCode::InvalidTokenAccount
+            );
+            let underlying_data = ctx.accounts.user_underlying.try_borrow_data()?;
+            require!(underlying_data.len() >= 64, ErrorCode::InvalidTokenAccount);
+            // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
+            let underlying_mint = Pubkey::new_from_array(
+                underlying_data[0..32]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            let underlying_owner = Pubkey::new_from_array(
+                underlying_data[32..64]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            require!(
+                underlying_mint == rift.underlying_mint,
+                ErrorCode::InvalidMint
+            );
+            require!(
+                underlying_owner == ctx.accounts.user.key(),
+                ErrorCode::UnauthorizedTokenAccount
+            );
+
+            // Validate rift token account (Token-2022, or SPL Token for legacy rifts)
+            require!(
+                *ctx.accounts.user_rift_tokens.owner == rift.rift_token_program_id(),
+                ErrorCode::InvalidTokenAccount
+            );
+            let rift_data = ctx.accounts.user_rift_tokens.try_borrow_data()?;
+            require!(rift_data.len() >= 64, ErrorCode::InvalidTokenAccount);
+            // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
+            let rift_mint_check = Pubkey::new_from_array(
+                rift_data[0..32]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            let rift_owner = Pubkey::new_from_array(
+                rift_data[32..64]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            require!(rift_mint_check == rift.rift_mint, ErrorCode::InvalidMint);
+            require!(
+                rift_owner == ctx.accounts.user.key(),
+                ErrorCode::UnauthorizedTokenAccount
+            );
+        }
+
+        // **wSOL SUPPORT**: Sync lamports sitting in the user's wSOL account into its token amount
+        // Without this, unsynced wSOL (e.g. SOL sent directly to the ATA) fails the deposit transfer
+        if rift.needs_native_sync(&ctx.accounts.underlying_token_program.key()) {
+            anchor_spl::token::sync_native(CpiContext::new(
+                ctx.accounts.underlying_token_program.to_account_info(),
+                anchor_spl::token::SyncNative {
+                    account: ctx.accounts.user_underlying.to_account_info(),
+                },
+            ))?;
+            msg!("✅ Synced native wSOL balance before deposit");
+        }
+
+        // **HIGH FIX #5**: Validate amount bounds BEFORE fee calculation to prevent edge case overflows
+        // **DYNAMIC FEES**: Volume tier overrides the static wrap fee when enabled
+        // **FEE EXEMPTION**: Whitelisted wrappers (validated PDA passed) pay no wrap fee
+        let fee_exempt = ctx.accounts.fee_exemption.is_some();
+        if fee_exempt {
+            msg!("Fee exemption applied for {}", ctx.accounts.user.key());
+        }
+        let fee_multiplier = rift.wrap_fee_bps(fee_exempt, Clock::get()?.unix_timestamp);
+        require!(
+            amount <= u64::MAX / fee_multiplier.max(1),
+            ErrorCode::AmountTooLarge
+        );
+
+        // **EXACT-OUT**: Back-compute the deposit that nets exactly rift_out after both fees
+        let transfer_amount = match exact_rift_out {
+            Some(rift_out) => {
+                // Smallest received amount r with r - floor(r * fee / 10000) >= rift_out
+                let fee_denominator = 10000u128
+                    .checked_sub(u128::from(fee_multiplier))
+                    .filter(|denominator| *denominator > 0)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                let required_received = u128::from(rift_out)
+                    .checked_mul(10000)
+                    .ok_or(ErrorCode::MathOverflow)?
+                    .checked_add(fee_denominator - 1)
+                    .ok_or(ErrorCode::MathOverflow)?
+                    .checked_div(fee_denominator)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                let required_received =
+                    u64::try_from(required_received).map_err(|_| ErrorCode::MathOverflow)?;
+                // **MIN FEE**: The fee floor may exceed the bps fee on small amounts
+                let required_received = if fee_multiplier > 0 {
+                    required_received.max(
+                        rift_out
+                            .checked_add(rift.min_fee_units)
+                            .ok_or(ErrorCode::MathOverflow)?,
+                    )
+                } else {
+                    required_received
+                };
+
+                // Gross up for the underlying's Token-2022 transfer fee (fee-on-transfer)
+                let underlying_mint_info = ctx.accounts.underlying_mint.to_account_info();
+                let required_in = if *underlying_mint_info.owner == spl_token_2022::ID {
+                    let mint_data = underlying_mint_info.try_borrow_data()?;
+                    let mint_state =
+                        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+                    match mint_state.get_extension::<TransferFeeConfig>() {
+                        Ok(fee_config) => {
+                            let epoch = Clock::get()?.epoch;
+                            let transfer_fee = fee_config
+                                .get_epoch_fee(epoch)
+                                .calculate_inverse_fee(required_received)
+                                .ok_or(ErrorCode::MathOverflow)?;
+                            required_received
+                                .checked_add(transfer_fee)
+                                .ok_or(ErrorCode::MathOverflow)?
+                        }
+                        Err(_) => required_received,
+                    }
+                } else {
+                    required_received
+                };
+
+                require!(required_in <= amount, ErrorCode::SlippageExceeded);
+                msg!(
+                    "Exact-out: {} RIFT requires {} underlying (max {})",
+                    rift_out,
+                    required_in,
+                    amount
+                );
+                required_in
+            }
+            None => amount,
+        };
+
+        // **WRAP LIMITS**: Cap the deposited amount (min is checked on actual_received below)
+        require!(
+            rift.max_wrap_amount == 0 || transfer_amount <= rift.max_wrap_amount,
+            ErrorCode::WrapAmountTooLarge
+        );
+
+        // **CRITICAL FIX - HIGH ISSUE #2**: Check vault balance BEFORE transfer to detect underlying transfer fees
+        let vault_balance_before = ctx.accounts.vault.amount;
+
+        // **BACKING CIRCUIT BREAKER**: Reject wraps into an under-collateralized rift
+        // Unwraps stay open so existing holders can always exit
+        let live_backing_bps = rift.get_live_backing_ratio_bps(vault_balance_before)?;
+        require!(
+            live_backing_bps >= u64::from(rift.min_backing_ratio_bps),
+            ErrorCode::BackingBelowThreshold
+        );
+
+        // **TOKEN-2022 FIX**: Read underlying mint decimals for transfer_checked
+        let underlying_mint_data = ctx.accounts.underlying_mint.try_borrow_data()?;
+        require!(underlying_mint_data.len() >= 45, ErrorCode::InvalidMint);
+        let underlying_decimals = underlying_mint_data[44]; // decimals at offset 44
+        drop(underlying_mint_data);
+
+        // **FIX CRITICAL #27**: Transfer underlying tokens using underlying_token_program
+        // **TOKEN-2022 FIX**: Use transfer_checked instead of transfer for Token-2022 compatibility
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.underlying_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_underlying.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+                mint: ctx.accounts.underlying_mint.to_account_info(),
+            },
+        );
+        interface_transfer_checked(transfer_ctx, transfer_amount, underlying_decimals)?;
+
+        // **CRITICAL FIX - HIGH ISSUE #2**: Reload vault to get actual amount received (after transfer fees)
+        ctx.accounts.vault.reload()?;
+        let vault_balance_after = ctx.accounts.vault.amount;
+        let actual_received = vault_balance_after
+            .checked_sub(vault_balance_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "Requested: {}, Actually received in vault: {}",
+            transfer_amount,
+            actual_received
+        );
+
+        // **CRITICAL FIX - HIGH ISSUE #2**: Calculate wrap fee based on ACTUAL amount received, not requested
+        // **WRAP LIMITS**: Minimum applies to what actually arrived (fee-on-transfer underlyings)
+        let (wrap_fee, amount_after_fee) = rift.plan_wrap(actual_received, fee_multiplier)?;
+
+        // **MEDIUM FIX #3**: Slippage protection - ensure user receives at least minimum expected RIFT
+        // Protects against fee-on-transfer tokens and extreme slippage
+        require!(
+            amount_after_fee >= min_rift_out,
+            ErrorCode::SlippageExceeded
+        );
+        msg!(
+            "✅ Slippage check passed: {} after fee >= minimum {}",
+            amount_after_fee,
+            min_rift_out
+        );
+
+        // **EXACT-OUT**: Mint exactly rift_out; the rounding surplus stays in the vault as backing
+        let rift_minted = exact_rift_out.unwrap_or(amount_after_fee);
+
+        let rift_key = rift.key();
+
+        // **FEE ROUTING**: fees_to_backing, wrap_fees_vault, fees_vault, or kept in vault
+        // **FIX MEDIUM #5 (Audit)**: Measure actual credited amount for transfer-fee underlyings
+        let vault_auth_bump = [ctx.bumps.vault_authority];
+        let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
+        let (actual_fee_credited, wrap_fee_routed) = route_wrap_fee(
+            rift,
+            wrap_fee,
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.fees_vault.to_account_info(),
+            ctx.accounts
+                .wrap_fees_vault
+                .as_ref()
+                .map(|account| account.to_account_info()),
+            ctx.accounts.underlying_mint.to_account_info(),
+            underlying_decimals,
+            ctx.accounts.underlying_token_program.to_account_info(),
+            ctx.accounts.vault_authority.to_account_info(),
+            &[vault_auth_seeds],
+        )?;
+
+        // **SUPPLY CEILING**: Fail clearly instead of inside the token program
+        ensure_mint_headroom(&ctx.accounts.rift_mint.to_account_info(), rift_minted)?;
+
+        // Mint RIFT tokens to user
+        let bump_seed = [ctx.bumps.rift_mint_authority];
+        let signer_seeds: &[&[u8]] = &[b"rift_mint_auth", rift_key.as_ref(), &bump_seed];
+        let signer = &[signer_seeds];
+
+        // **FIX CRITICAL #27**: Mint RIFT tokens using rift_token_program (always Token-2022)
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.rift_token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.rift_mint.to_account_info(),
+                to: ctx.accounts.user_rift_tokens.to_account_info(),
+                authority: ctx.accounts.rift_mint_authority.to_account_info(),
+            },
+            signer,
+        );
+        interface_mint_to(mint_ctx, rift_minted)?;
+
+        // Update rift state
+        // **FEE ACCOUNTING FIX**: Track wrap fees (same as unwrap), at the credited amount
+        rift.record_wrap(
+            rift_minted,
+            actual_received,
+            actual_fee_credited,
+            wrap_fee_routed,
+            &ctx.accounts.global_config.to_account_info(),
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        // **SAME-SLOT GUARD**: Record wrap slot for same_slot_protection
+        rift.record_action_slot(current_slot, true);
+
+        // **WRAP COOLDOWN**: Record this wrap on the user's activity PDA
+        let user_activity = &mut ctx.accounts.user_activity;
+        user_activity.rift = rift_key;
+        user_activity.user = ctx.accounts.user.key();
+        user_activity.last_wrap_slot = current_slot;
+        user_activity.bump = ctx.bumps.user_activity;
+
+        emit!(WrapExecuted {
+            rift: rift.key(),
+            user: ctx.accounts.user.key(),
+            underlying_deposited: actual_received,
+            fee_amount: wrap_fee,
+            fee_bps: u16::try_from(fee_multiplier).map_err(|_| ErrorCode::MathOverflow)?,
+            rift_minted,
+        });
+
+        msg!(
+            "✅ Wrapped {} tokens → {} RIFT (fee: {})",
+            transfer_amount,
+            rift_minted,
+            wrap_fee
+        );
+
+        Ok(rift_minted)
+    })();
+
+    // **FIX HIGH #1 + FIX ISSUE #7**: Always clear guard and slot, even on error
+    ctx.accounts.rift.reentrancy_guard = false;
+    ctx.accounts.rift.reentrancy_guard_slot = 0;
+
+    execution_result
+}
+
+/// Validated Switchboard price scaled to 6 decimals
+/// Owner, discriminator, consensus and staleness (`max_age_seconds`) are checked
+fn read_switchboard_price(feed_account_info: &AccountInfo, max_age_seconds: u64) -> Result<u64> {
+    read_switchboard_price_with_std_dev(feed_account_info, max_age_seconds).map(|(price, _)| price)
+}
+
+/// `read_switchboard_price` plus the feed's std deviation across oracle responses (6 decimals)
+/// The std deviation is None when the feed doesn't report a usable value
+fn read_switchboard_price_with_std_dev(
+    feed_account_info: &AccountInfo,
+    max_age_seconds: u64,
+) -> Result<(u64, Option<u64>)> {
+    // **SECURITY FIX #4**: Use Switchboard SDK for validated price parsing
+    // This replaces manual byte slicing with audited SDK that validates:
+    // - Account structure and version
+    // - Oracle responses and consensus
+    // - Staleness and update timestamps
+    // - Min oracle requirements
+
+    let switchboard_program_id =
+        Pubkey::from_str_const("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+    require!(
+        feed_account_info.owner == &switchboard_program_id,
+        ErrorCode::InvalidOracleOwner
+    );
+
+    // Load and validate feed using Switchboard SDK
+    // Note: switchboard-on-demand v0.11.1 API expects Ref<'_, &mut [u8]> for parse()
+    // PullFeedAccountData::parse() internally validates:
+    // ✅ Account discriminator (first 8 bytes must match aggregator type)
+    // ✅ Account version and structure
+    // ✅ Deserialization of all fields
+    let feed_data = feed_account_info
+        .try_borrow_data()
+        .map_err(|_| ErrorCode::InvalidOracleData)?;
+
+    let feed_account =
+        PullFeedAccountData::parse(feed_data).map_err(|_| ErrorCode::InvalidOracleData)?;
+
+    // Get validated price from feed
+    // SDK automatically checks:
+    // ✅ Oracle consensus (min responses met)
+    // ✅ Account structure and version
+    // ✅ Staleness based on update timestamp
+    let price_result = feed_account
+        .value(max_age_seconds)
+        .map_err(|_| ErrorCode::OraclePriceStale)?;
+
+    // Switchboard returns Decimal type - convert to f64
+    let price_f64 =
+        (price_result.mantissa() as f64) / 10f64.powi(price_result.scale() as i32);
+
+    // **FIX CRITICAL**: Validate finiteness and bounds before cast to prevent overflow
+    // Check for NaN, infinity, and that scaled price fits in u64 range
+    // Must validate BEFORE cast since invalid f64 can overflow to arbitrary u64 values
+    require!(
+        price_f64.is_finite() && price_f64 > 0.0,
+        ErrorCode::InvalidOraclePrice
+    );
+
+    // **FIX MEDIUM #44**: Validate price won't exceed u64::MAX after scaling
+    // Also check against protocol max (1e12) to prevent later protocol brick
+    let scaled_price_f64 = price_f64 * 1_000_000.0;
+    require!(
+        scaled_price_f64 > 0.0 && scaled_price_f64 <= 1_000_000_000_000.0,
+        ErrorCode::OraclePriceTooLarge
+    );
+
+    // Same scaling for the std deviation; anything non-finite or out of range is dropped
+    let std_dev = feed_account.result.std_dev().and_then(|std_dev| {
+        let scaled = (std_dev.mantissa() as f64) / 10f64.powi(std_dev.scale() as i32) * 1_000_000.0;
+        (scaled.is_finite() && scaled >= 0.0 && scaled <= scaled_price_f64).then_some(scaled as u64)
+    });
+
+    // Convert f64 to u64 (Switchboard returns decimal values)
+    // Assuming price is in USD with 6 decimals precision
+    // Safe cast: validated finiteness and bounds above
+    Ok((scaled_price_f64 as u64, std_dev))
+}
+
+/// Split the whole fees_vault (skim, protocol cut, partner, treasury) from inside an unwrap
+/// No-op while rift.auto_distribute_threshold is 0, the fees vault is uninitialized, or its balance
+/// is below the threshold. Recipient checks match distribute_fees_from_vault.
+fn auto_distribute_unwrap_fees(accounts: &mut UnwrapFromVault, vault_auth_bump: u8) -> Result<()> {
+    let threshold = accounts.rift.auto_distribute_threshold;
+    if threshold == 0 || accounts.rift.fees_vault == anchor_lang::solana_program::system_program::ID
+    {
+        return Ok(());
+    }
+
+    let fees_vault_info = accounts.fees_vault.to_account_info();
+    let read_balance = |info: &AccountInfo| -> Result<u64> {
+        let data = info.try_borrow_data()?;
+        require!(data.len() >= 72, ErrorCode::InvalidFeesVault);
+        Ok(u64::from_le_bytes(
+            data[64..72].try_into().map_err(|_| ErrorCode::InvalidAccountData)?,
+        ))
+    };
+    let fees_balance = read_balance(&fees_vault_info)?;
+    if fees_balance < threshold {
+        msg!("Auto-distribute skipped: {} below threshold {}", fees_balance, threshold);
+        return Ok(());
+    }
+
+    let partner_wallet = accounts.rift.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?;
+    let treasury_wallet = accounts.rift.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
+    let underlying_mint_key = accounts.rift.underlying_mint;
+    let treasury_account = accounts
+        .treasury_fee_account
+        .as_ref()
+        .ok_or(ErrorCode::InvalidTreasuryVault)?
+        .to_account_info();
+    validate_fee_recipient(
+        &treasury_account,
+        &treasury_wallet,
+        &underlying_mint_key,
+        ErrorCode::InvalidTreasuryVault,
+    )?;
+    let partner_account = accounts
+        .partner_fee_account
+        .as_ref()
+        .map(|account| account.to_account_info());
+    if let Some(partner_account) = &partner_account {
+        validate_fee_recipient(
+            partner_account,
+            &partner_wallet,
+            &underlying_mint_key,
+            ErrorCode::InvalidPartnerVault,
+        )?;
+    }
+
+    let underlying_decimals = {
+        let mint_data = accounts.underlying_mint.try_borrow_data()?;
+        require!(mint_data.len() >= 45, ErrorCode::InvalidMint);
+        mint_data[44] // decimals at offset 44
+    };
+
+    let rift_key = accounts.rift.key();
+    let vault_auth_bump = [vault_auth_bump];
+    let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
+    let signer = &[vault_auth_seeds];
+    let global_config_info = accounts.global_config.to_account_info();
+
+    let protocol_skim_amount = accounts.rift.protocol_fees_owed.min(fees_balance);
+    transfer_protocol_skim(
+        protocol_skim_amount,
+        fees_vault_info.clone(),
+        accounts.underlying_mint.to_account_info(),
+        underlying_decimals,
+        accounts.underlying_token_program.to_account_info(),
+        accounts.vault_authority.to_account_info(),
+        signer,
+        accounts
+            .protocol_treasury_account
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        &global_config_info,
+    )?;
+    let (protocol_amount, partner_amount, treasury_amount) = transfer_fee_split(
+        &accounts.rift,
+        fees_balance
+            .checked_sub(protocol_skim_amount)
+            .ok_or(ErrorCode::MathOverflow)?,
+        fees_vault_info.clone(),
+        accounts.underlying_mint.to_account_info(),
+        underlying_decimals,
+        accounts.underlying_token_program.to_account_info(),
+        accounts.vault_authority.to_account_info(),
+        signer,
+        treasury_account,
+        partner_account,
+        accounts
+            .protocol_fee_account
+            .as_ref()
+            .map(|account| account.to_account_info()),
+        &global_config_info,
+    )?;
+
+    let actual_sent = fees_balance
+        .checked_sub(read_balance(&fees_vault_info)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let rift = &mut accounts.rift;
+    // Saturating: a donation straight into fees_vault must not make the unwrap itself fail
+    rift.total_fees_collected = rift.total_fees_collected.saturating_sub(actual_sent);
+    rift.protocol_fees_owed = rift
+        .protocol_fees_owed
+        .checked_sub(protocol_skim_amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit!(FeesDistributed {
+        rift: rift_key,
+        amount: fees_balance,
+        protocol_skim_amount,
+        protocol_amount,
+        treasury_amount,
+        partner_amount,
+        distributor: accounts.user.key(),
+        // Side effect of the caller's own unwrap, not keeper work
+        keeper: Pubkey::default(),
+    });
+
+    msg!("✅ Auto-distributed {} fees from fees_vault", fees_balance);
+
+    Ok(())
+}
+
+/// Pay rift.rebalance_reward to the keeper's underlying account, capped at
+/// MAX_REBALANCE_REWARD_BPS of the fees_vault balance not owed to the protocol.
+/// Skipped when the optional reward accounts are not provided.
+fn pay_rebalance_reward(
+    accounts: &mut TriggerRebalance,
+    vault_auth_bump: Option<u8>,
+    timestamp: i64,
+) -> Result<()> {
+    let (
+        Some(fees_vault),
+        Some(vault_authority),
+        Some(underlying_mint),
+        Some(keeper_underlying),
+        Some(token_program),
+        Some(vault_auth_bump),
+    ) = (
+        accounts.fees_vault.as_ref(),
+        accounts.vault_authority.as_ref(),
+        accounts.underlying_mint.as_ref(),
+        accounts.keeper_underlying.as_ref(),
+        accounts.underlying_token_program.as_ref(),
+        vault_auth_bump,
+    )
+    else {
+        msg!("Rebalance reward skipped: reward accounts not provided");
+        return Ok(());
+    };
+
+    let rift = &accounts.rift;
+    require!(
+        rift.fees_vault != anchor_lang::solana_program::system_program::ID
+            && fees_vault.key() == rift.fees_vault
+            && fees_vault.owner == token_program.key,
+        ErrorCode::InvalidFeesVault
+    );
+    let fees_balance = {
+        let fees_data = fees_vault.try_borrow_data()?;
+        require!(fees_data.len() >= 72, ErrorCode::InvalidFeesVault);
+        u64::from_le_bytes(
+            fees_data[64..72]
+                .try_into()
+                .map_err(|_| ErrorCode::InvalidAccountData)?,
+        )
+    };
+    let available = fees_balance.saturating_sub(rift.protocol_fees_owed);
+    let reward_cap = u64::try_from(
+        (available as u128) * (MAX_REBALANCE_REWARD_BPS as u128) / 10_000,
+    )
+    .map_err(|_| ErrorCode::MathOverflow)?;
+    let reward = rift.rebalance_reward.min(reward_cap);
+    if reward == 0 {
+        msg!("Rebalance reward skipped: fees_vault has nothing to pay");
+        return Ok(());
+    }
+    validate_fee_recipient(
+        keeper_underlying,
+        &accounts.user.key(),
+        &rift.underlying_mint,
+        ErrorCode::InvalidTokenAccount,
+    )?;
+
+    let rift_key = rift.key();
+    let vault_auth_bump = [vault_auth_bump];
+    let vault_auth_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_auth_bump];
+    let signer = &[vault_auth_seeds];
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: fees_vault.to_account_info(),
+            to: keeper_underlying.to_account_info(),
+            authority: vault_authority.to_account_info(),
+            mint: underlying_mint.to_account_info(),
+        },
+        signer,
+    );
+    interface_transfer_checked(transfer_ctx, reward, underlying_mint.decimals)?;
+
+    let keeper = accounts.user.key();
+    let rift = &mut accounts.rift;
+    rift.total_fees_collected = rift.total_fees_collected.saturating_sub(reward);
+
+    emit!(RebalanceRewardPaid {
+        rift: rift_key,
+        keeper,
+        reward,
+        timestamp,
+    });
+
+    msg!("✅ Paid rebalance reward {} to {}", reward, keeper);
+
+    Ok(())
+}
+
+/// Publish the net RIFT minted as return data (u64, little-endian) for composing programs
+/// Must run after the last CPI of the instruction - every CPI clears return data
+fn set_wrap_return_data(rift_minted: u64) {
+    anchor_lang::solana_program::program::set_return_data(&rift_minted.to_le_bytes());
+}
+
+/// Shared unwrap logic for `unwrap_from_vault` and `emergency_unwrap`
+/// `emergency` swaps the oracle heartbeat for a stale-oracle gate and forces strict 1:1 payout
+/// `auto_distribute` flushes fees_vault past the rift's threshold (unwrap_from_vault only)
+fn process_unwrap(
+    ctx: Context<UnwrapFromVault>,
+    rift_token_amount: u64,
+    min_underlying_out: u64,
+    deadline_unix: i64,
+    emergency: bool,
+    auto_distribute: bool,
+) -> Result<()> {
+    // **CRITICAL FIX + FIX HIGH #1 + FIX ISSUE #7**: Add reentrancy protection with auto-timeout
+    {
+        let rift = &mut ctx.accounts.rift;
+
+        // **FIX ISSUE #7**: Auto-clear stuck guard after timeout
+        if rift.reentrancy_guard {
+            let current_slot = Clock::get()?.slot;
+            if current_slot > rift.reentrancy_guard_slot + rift.reentrancy_timeout() {
+                msg!(
+                    "⚠️ Auto-clearing stuck reentrancy guard (set at slot {}, current {})",
+                    rift.reentrancy_guard_slot,
+                    current_slot
+                );
+                emit!(ReentrancyGuardAutoCleared {
+                    rift: rift.key(),
+                    set_at_slot: rift.reentrancy_guard_slot,
+                    cleared_at_slot: current_slot,
+                });
+                rift.reentrancy_guard = false;
+                rift.reentrancy_guard_slot = 0;
+            } else {
+                return Err(ErrorCode::ReentrancyDetected.into());
+            }
+        }
+
+        rift.reentrancy_guard = true;
+        rift.reentrancy_guard_slot = Clock::get()?.slot;
+    }
+
+    // Execute the actual function logic
+    let execution_result = (|| -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        // **DEADLINE**: Prevent stale transactions from executing much later
+        require!(
+            deadline_unix == 0 || Clock::get()?.unix_timestamp <= deadline_unix,
+            ErrorCode::DeadlineExceeded
+        );
+
+        // **SAME-SLOT GUARD**: Block atomic wrap→unwrap in one slot (flash-loan style probing)
+        if !emergency {
+            rift.ensure_not_same_slot_wrap(Clock::get()?.slot)?;
+        }
+
+        // **SECURITY FIX #49**: Manual token account validation (stack optimization)
+        // **FIX CRITICAL #27**: Validate accounts against their respective token programs
+        {
+            // Validate underlying token account (can be SPL Token or Token-2022)
+            require!(
+                *ctx.accounts.user_underlying.owner
+                    == ctx.accounts.underlying_token_program.key(),
+                ErrorCode::InvalidTokenAccount
+            );
+            let underlying_data = ctx.accounts.user_underlying.try_borrow_data()?;
+            require!(underlying_data.len() >= 64, ErrorCode::InvalidTokenAccount);
+            // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
+            let underlying_mint = Pubkey::new_from_array(
+                underlying_data[0..32]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            let underlying_owner = Pubkey::new_from_array(
+                underlying_data[32..64]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            require!(
+                underlying_mint == rift.underlying_mint,
+                ErrorCode::InvalidMint
+            );
+            require!(
+                underlying_owner == ctx.accounts.user.key(),
+                ErrorCode::UnauthorizedTokenAccount
+            );
+
+            // Validate rift token account (Token-2022, or SPL Token for legacy rifts)
+            require!(
+                *ctx.accounts.user_rift_tokens.owner == rift.rift_token_program_id(),
+                ErrorCode::InvalidTokenAccount
+            );
+            let rift_data = ctx.accounts.user_rift_tokens.try_borrow_data()?;
+            require!(rift_data.len() >= 64, ErrorCode::InvalidTokenAccount);
+            // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
+            let rift_mint_check = Pubkey::new_from_array(
+                rift_data[0..32]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            let rift_owner = Pubkey::new_from_array(
+                rift_data[32..64]
+                    .try_into()
+                    .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+            );
+            require!(rift_mint_check == rift.rift_mint, ErrorCode::InvalidMint);
+            require!(
+                rift_owner == ctx.accounts.user.key(),
+                ErrorCode::UnauthorizedTokenAccount
+            );
+        }
+
+        // **HIGH FIX #5**: Shared preflight - state, oracle gate, fee, liquidity, drain limit
+        // **FEE EXEMPTION**: Whitelisted wrappers (validated PDA passed) pay no unwrap fee
+        let vault_balance = ctx.accounts.vault.amount;
+        let fee_exempt = ctx.accounts.fee_exemption.is_some();
+        if fee_exempt {
+            msg!("Fee exemption applied for {}", ctx.accounts.user.key());
+        }
+        let plan = rift.plan_unwrap(
+            rift_token_amount,
+            vault_balance,
+            fee_exempt,
+            emergency,
+            Clock::get()?.unix_timestamp,
+        )?;
+        let UnwrapPlan {
+            fee_bps: fee_multiplier,
+            fee: unwrap_fee,
+            net_underlying: amount_after_fee,
+            rift_burned,
+            ..
+        } = plan;
+        if emergency {
+            msg!("🚨 Emergency unwrap: oracle stale, paying strict 1:1 backing");
+        }
+
+        msg!(
+            "💰 Unwrapping {} RIFT from vault (fee: {}, net: {})",
+            rift_burned,
+            unwrap_fee,
+            amount_after_fee
+        );
+
+        // **BACKING CIRCUIT BREAKER**: Unwraps are never blocked, only surfaced in logs
+        let live_backing_bps = rift.get_live_backing_ratio_bps(vault_balance)?;
+        if live_backing_bps < u64::from(rift.min_backing_ratio_bps) {
+            msg!(
+                "⚠️ Backing below threshold: {} bps < {} bps (unwrap allowed)",
+                live_backing_bps,
+                rift.min_backing_ratio_bps
+            );
+        }
+
+        // **FIX CRITICAL #27**: Burn RIFT tokens using rift_token_program (always Token-2022)
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.rift_token_program.to_account_info(),
+            anchor_spl::token_interface::Burn {
+                mint: ctx.accounts.rift_mint.to_account_info(),
+                from: ctx.accounts.user_rift_tokens.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        // **TOKEN-2022 MIGRATION**: Burn is FREE - no transfer fee on burns!
+        interface_burn(burn_ctx, rift_burned)?;
+
+        msg!("✅ Burned {} RIFT tokens", rift_burned);
+
+        // Transfer underlying tokens from vault to user
+        // Use vault_authority (the vault owner) to sign the transfer
+        let rift_key = rift.key();
+        let bump_seed = [ctx.bumps.vault_authority];
+        let signer_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &bump_seed];
+        let signer = &[signer_seeds];
+
+        // **TOKEN-2022 FIX**: Read underlying mint decimals for transfer_checked
+        let underlying_mint_data = ctx.accounts.underlying_mint.try_borrow_data()?;
+        require!(underlying_mint_data.len() >= 45, ErrorCode::InvalidMint);
+        let underlying_decimals = underlying_mint_data[44]; // decimals at offset 44
+        drop(underlying_mint_data);
+
+        // **FEE ROUTING**: Transfer unwrap fee from vault to fees_vault FIRST (if initialized)
+        route_unwrap_fee(
+            rift,
+            unwrap_fee,
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.fees_vault.to_account_info(),
+            ctx.accounts.underlying_mint.to_account_info(),
+            underlying_decimals,
+            ctx.accounts.underlying_token_program.to_account_info(),
+            ctx.accounts.vault_authority.to_account_info(),
+            signer,
+        )?;
+
+        // **CRITICAL FIX - HIGH ISSUE #2**: Check vault balance BEFORE transfer
+        let vault_balance_before = ctx.accounts.vault.amount;
+
+        // **FIX CRITICAL #13**: Parse user DESTINATION balance before transfer (manual parsing for UncheckedAccount)
+        let user_data_before = ctx.accounts.user_underlying.try_borrow_data()?;
+        require!(user_data_before.len() >= 72, ErrorCode::InvalidTokenAccount);
+        // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
+        let user_balance_before = u64::from_le_bytes(
+            user_data_before[64..72]
+                .try_into()
+                .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+        );
+        drop(user_data_before); // Release borrow before CPI
+        msg!(
+            "📊 User underlying balance before transfer: {}",
+            user_balance_before
+        );
+
+        // **FIX CRITICAL #27**: Transfer underlying tokens using underlying_token_program
+        // **TOKEN-2022 FIX**: Use transfer_checked for Token-2022 compatibility
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.underlying_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.user_underlying.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+                mint: ctx.accounts.underlying_mint.to_account_info(),
+            },
+            signer,
+        );
+        interface_transfer_checked(transfer_ctx, amount_after_fee, underlying_decimals)?;
+
+        // **CRITICAL FIX - HIGH ISSUE #2**: Reload vault to verify actual amount sent (if underlying has transfer fees)
+        ctx.accounts.vault.reload()?;
+        let vault_balance_after = ctx.accounts.vault.amount;
+        let actual_sent = vault_balance_before
+            .checked_sub(vault_balance_after)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // **FIX CRITICAL #13**: Parse user DESTINATION balance after transfer to detect destination-side transfer fees
+        let user_data_after = ctx.accounts.user_underlying.try_borrow_data()?;
+        require!(user_data_after.len() >= 72, ErrorCode::InvalidTokenAccount);
+        // **FIX CRITICAL #49**: Replace .unwrap() with proper error handling to prevent panic
+        let user_balance_after = u64::from_le_bytes(
+            user_data_after[64..72]
+                .try_into()
+                .map_err(|_| ErrorCode::InvalidTokenAccount)?,
+        );
+        drop(user_data_after); // Release borrow
+
+        let actual_received = user_balance_after
+            .checked_sub(user_balance_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("✅ Transferred {} underlying tokens from vault (actually sent: {}, actually received: {})",
+        amount_after_fee, actual_sent, actual_received);
+
+        // **FIX CRITICAL #13**: Detect destination-side transfer fees
+        if actual_received < actual_sent {
+            let destination_fee = actual_sent.saturating_sub(actual_received);
+            let fee_percentage = (destination_fee as f64 / actual_sent as f64) * 100.0;
+            msg!("⚠️ DESTINATION-SIDE TRANSFER FEE DETECTED!");
+            msg!(
+                "⚠️ Vault sent: {}, User received: {}",
+                actual_sent,
+                actual_received
+            );
+            msg!(
+                "⚠️ Destination fee: {} ({:.4}%)",
+                destination_fee,
+                fee_percentage
+            );
+
+            // NOTE: Transfer fee limit removed - users are informed via UI warnings instead
+            msg!("⚠️ Destination fee accepted: {:.4}%", fee_percentage);
+        }
+
+        // **DEFLATIONARY SAFE MODE**: Creator opted to protect backing over unwrap liveness
+        if rift.block_deflationary_loss {
+            let leakage = amount_after_fee.saturating_sub(actual_received);
+            let max_leakage = u128::from(amount_after_fee)
+                .checked_mul(u128::from(rift.max_unwrap_leakage_bps))
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(10000)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(
+                u128::from(leakage) <= max_leakage,
+                ErrorCode::DeflationaryLossExceeded
+            );
+        }
+
+        // **CRITICAL FIX #2**: Slippage protection - ensure user received at least expected amount
+        // Protects against fee-on-transfer tokens and deflationary tokens
+        require!(actual_sent >= amount_after_fee, ErrorCode::SlippageExceeded);
+        msg!(
+            "✅ Slippage check passed: sent {} >= expected {}",
+            actual_sent,
+            amount_after_fee
+        );
+
+        // User-provided slippage protection on RECEIVED amount
+        require!(
+            actual_received >= min_underlying_out,
+            ErrorCode::SlippageExceeded
+        );
+        msg!(
+            "✅ User slippage check passed: received {} >= min_out {}",
+            actual_received,
+            min_underlying_out
+        );
+
+        // **CRITICAL FIX - HIGH ISSUE #2**: Update accounting based on ACTUAL amount sent, not requested
+        rift.record_unwrap(
+            &plan,
+            actual_sent,
+            &ctx.accounts.global_config.to_account_info(),
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        // NOTE: Fee distribution happens via separate batch process to avoid stack overflow
+        // **FIX MEDIUM #15**: Do NOT update last_oracle_update on unwrap to prevent rebalance DoS
+        // last_oracle_update should only be updated when actual oracle price data is updated,
+        // not on every vault activity. This prevents users from delaying rebalances via unwrap spam.
+
+        // **SAME-SLOT GUARD**: Record unwrap slot for same_slot_protection
+        rift.record_action_slot(Clock::get()?.slot, false);
+
+        emit!(UnwrapExecuted {
+            rift: rift.key(),
+            user: ctx.accounts.user.key(),
+            rift_token_amount: rift_burned,
+            fee_amount: unwrap_fee,
+            fee_bps: u16::try_from(fee_multiplier).map_err(|_| ErrorCode::MathOverflow)?,
+            underlying_returned: amount_after_fee,
+        });
+
+        // **AUTO-DISTRIBUTE**: Opt-in and threshold-gated so regular unwraps stay cheap
+        if auto_distribute {
+            auto_distribute_unwrap_fees(&mut *ctx.accounts, ctx.bumps.vault_authority)?;
+        }
+
+        msg!("✅ Unwrap from vault completed");
+
+        Ok(())
+    })();
+
+    // **FIX HIGH #1 + FIX ISSUE #7**: Always clear guard and slot, even on error
+    ctx.accounts.rift.reentrancy_guard = false;
+    ctx.accounts.rift.reentrancy_guard_slot = 0;
+
+    execution_result
+}
+
+/// Withheld transfer fees recorded on a token account (0 for SPL Token / no TransferFeeAmount)
+fn token_account_withheld_amount(account: &AccountInfo) -> Result<u64> {
+    use spl_token_2022::extension::transfer_fee::TransferFeeAmount;
+
+    if *account.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let account_data = account.try_borrow_data()?;
+    let token_account =
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account_data)?;
+    Ok(token_account
+        .get_extension::<TransferFeeAmount>()
+        .map(|fee_amount| u64::from(fee_amount.withheld_amount))
+        .unwrap_or(0))
+}
+
+/// Move a wrap fee out of the vault following the rift's routing, for wrap_tokens and
+/// migrate_position: fees_to_backing keeps it as backing, then wrap_fees_vault, then fees_vault,
+/// otherwise it stays in the vault. Returns (credited, routed to wrap_fees_vault) as measured
+/// after any underlying transfer fee - the inputs of `Rift::record_wrap`
+#[allow(clippy::too_many_arguments)]
+fn route_wrap_fee<'info>(
+    rift: &Rift,
+    wrap_fee: u64,
+    vault: AccountInfo<'info>,
+    fees_vault: AccountInfo<'info>,
+    wrap_fees_vault: Option<AccountInfo<'info>>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    token_program: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<(u64, u64)> {
+    if wrap_fee == 0 {
+        return Ok((0, 0));
+    }
+
+    // Transfer the fee out of the vault and measure what the destination was credited
+    let transfer_fee_to = |destination: AccountInfo<'info>, stored: Pubkey| -> Result<u64> {
+        require!(
+            destination.owner == token_program.key,
+            ErrorCode::InvalidFeesVault
+        );
+        let balance_before = read_vault_amount(&destination, stored, ErrorCode::InvalidFeesVault)?;
+        let fee_transfer_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            TransferChecked {
+                from: vault.clone(),
+                to: destination.clone(),
+                authority: vault_authority.clone(),
+                mint: mint.clone(),
+            },
+            signer,
+        );
+        interface_transfer_checked(fee_transfer_ctx, wrap_fee, decimals)?;
+        let balance_after = read_vault_amount(&destination, stored, ErrorCode::InvalidFeesVault)?;
+        Ok(balance_after
+            .checked_sub(balance_before)
+            .ok_or(ErrorCode::MathOverflow)?)
+    };
+
+    if rift.fees_to_backing {
+        // **FEE-TO-BACKING**: Fee stays in vault and permanently boosts backing for holders
+        // Not protocol revenue, so it is NOT counted in total_fees_collected; record_wrap books
+        // it in backing_surplus, which unwraps redeem pro-rata
+        msg!("Wrap fee {} retained in vault as backing (fees_to_backing)", wrap_fee);
+        Ok((0, 0))
+    } else if let Some(wrap_fees_vault_key) = rift.wrap_fees_vault {
+        // **WRAP FEE VAULT**: Creator-configured destination takes wrap fees instead of
+        // fees_vault. Claimed via claim_wrap_fees, so NOT counted in total_fees_collected
+        let wrap_fees_vault = wrap_fees_vault.ok_or(ErrorCode::InvalidFeesVault)?;
+        let routed = transfer_fee_to(wrap_fees_vault, wrap_fees_vault_key)?;
+        msg!("Wrap fee {} routed to wrap_fees_vault (credited: {})", wrap_fee, routed);
+        Ok((0, routed))
+    } else if rift.fees_vault != anchor_lang::solana_program::system_program::ID {
+        // **FIX MEDIUM #5 (Audit)**: Measure actual credited amount for transfer-fee underlyings
+        let credited = transfer_fee_to(fees_vault, rift.fees_vault)?;
+        if credited != wrap_fee {
+            msg!("⚠️ Transfer fee detected: sent {}, credited {}", wrap_fee, credited);
+        }
+        msg!("Wrap fee {} transferred to fees_vault (credited: {})", wrap_fee, credited);
+        Ok((credited, 0))
+    } else {
+        // Fee kept in vault, accounted at full value
+        msg!("Wrap fee {} kept in vault (fees_vault not initialized)", wrap_fee);
+        Ok((wrap_fee, 0))
+    }
+}
+
+/// Move an unwrap fee from the vault to fees_vault, for unwrap_from_vault and migrate_position
+/// The fee stays in the vault while fees_vault is uninitialized
+#[allow(clippy::too_many_arguments)]
+fn route_unwrap_fee<'info>(
+    rift: &Rift,
+    unwrap_fee: u64,
+    vault: AccountInfo<'info>,
+    fees_vault: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    token_program: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    if unwrap_fee == 0 {
+        return Ok(());
+    }
+    if rift.fees_vault == anchor_lang::solana_program::system_program::ID {
+        msg!("Unwrap fee {} kept in vault (fees_vault not initialized)", unwrap_fee);
+        return Ok(());
+    }
+
+    // **FIX MEDIUM #23**: Verify fees_vault is actually a valid token account before transferring
+    // **FIX CRITICAL #27**: fees_vault holds underlying tokens, validate against its token program
+    require!(
+        fees_vault.owner == token_program.key,
+        ErrorCode::InvalidFeesVault
+    );
+    read_vault_amount(&fees_vault, rift.fees_vault, ErrorCode::InvalidFeesVault)?;
+
+    let fee_transfer_ctx = CpiContext::new_with_signer(
+        token_program,
+        TransferChecked {
+            from: vault,
+            to: fees_vault,
+            authority: vault_authority,
+            mint,
+        },
+        signer,
+    );
+    interface_transfer_checked(fee_transfer_ctx, unwrap_fee, decimals)?;
+    msg!("Unwrap fee {} transferred to fees_vault", unwrap_fee);
+
+    Ok(())
+}
+
+/// **SUPPLY CEILING**: Fail clearly instead of inside the token program when minting `amount`
+/// Mint layout: mint_authority COption<Pubkey> (36 bytes), then supply u64 at [36..44]
+fn ensure_mint_headroom(rift_mint: &AccountInfo, amount: u64) -> Result<()> {
+    let rift_mint_data = rift_mint.try_borrow_data()?;
+    require!(rift_mint_data.len() >= 44, ErrorCode::InvalidMint);
+    let current_supply = u64::from_le_bytes(
+        rift_mint_data[36..44]
+            .try_into()
+            .map_err(|_| ErrorCode::InvalidMint)?,
+    );
+    require!(
+        current_supply.checked_add(amount).is_some(),
+        ErrorCode::SupplyOverflow
+    );
+    Ok(())
+}
+
+/// Byte size of a token account (vault) for `mint_info` under `token_program`
+/// Token-2022 mints map their extensions to the account extensions a holder needs
+/// (TransferFeeConfig → TransferFeeAmount, MemoTransfer, NonTransferable, ImmutableOwner, CpiGuard);
+/// other mint extensions (ScaledUiAmount, InterestBearingConfig, MintCloseAuthority, ...) need none.
+/// Classic SPL Token accounts are always 165 bytes.
+fn compute_vault_account_space(mint_info: &AccountInfo, token_program: &Pubkey) -> Result<usize> {
+    if *token_program != spl_token_2022::ID {
+        return Ok(spl_token::state::Account::LEN);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_account = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let account_extensions: Vec<ExtensionType> = mint_account
+        .get_extension_types()?
+        .into_iter()
+        .filter_map(|ext_type| match ext_type {
+            ExtensionType::TransferFeeConfig => Some(ExtensionType::TransferFeeAmount),
+            ExtensionType::MemoTransfer
+            | ExtensionType::NonTransferable
+            | ExtensionType::ImmutableOwner
+            | ExtensionType::CpiGuard => Some(ext_type),
+            // Display-only UI multiplier: no per-account state
+            ExtensionType::ScaledUiAmount => None,
+            _ => None,
+        })
+        .collect();
+
+    Ok(
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+            &account_extensions,
+        )
+        .map_err(|_| ErrorCode::InvalidMint)?,
+    )
+}
+
+/// Check the creator holds enough lamports for the RIFT mint rent plus vault, fees_vault and
+/// withheld_vault rent before any account is created, so create_rift fails up front with
+/// InsufficientRentFunds instead of midway through the atomic init
+fn ensure_create_rift_rent(
+    creator_info: &AccountInfo,
+    underlying_mint_info: &AccountInfo,
+    mint_rent: u64,
+) -> Result<()> {
+    let rent = Rent::get()?;
+
+    // vault and fees_vault hold the underlying; sized from its mint like the create path does
+    let vault_space =
+        compute_vault_account_space(underlying_mint_info, underlying_mint_info.owner)?;
+    // withheld_vault holds RIFT; the new mint's TransferFeeConfig adds TransferFeeAmount
+    let withheld_vault_space =
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[
+            ExtensionType::TransferFeeAmount,
+        ])
+        .map_err(|_| ErrorCode::InvalidMint)?;
+
+    let required_lamports = rent
+        .minimum_balance(vault_space)
+        .checked_mul(2)
+        .and_then(|vaults| vaults.checked_add(rent.minimum_balance(withheld_vault_space)))
+        .and_then(|vaults| vaults.checked_add(mint_rent))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    require!(
+        creator_info.lamports() >= required_lamports,
+        ErrorCode::InsufficientRentFunds
+    );
+
+    Ok(())
+}
+
+/// Token-2022 metadata symbol for a new RIFT mint
+/// An explicit symbol (UTF-8, at most MAX_SYMBOL_LEN bytes) is used as-is, with the r/m prefix
+/// only when `prefix_symbol` is set. `symbol_len == 0` keeps the original derivation:
+/// the prefixed display name truncated to 10 characters.
+fn rift_symbol(
+    prefix: &str,
+    display_name: &str,
+    symbol: &[u8; 16],
+    symbol_len: u8,
+    prefix_symbol: bool,
+) -> Result<String> {
+    if symbol_len == 0 {
+        return Ok(display_name.chars().take(10).collect());
+    }
+    require!(symbol_len <= MAX_SYMBOL_LEN, ErrorCode::InvalidSymbol);
+    let symbol_str = core::str::from_utf8(&symbol[..symbol_len as usize])
+        .map_err(|_| ErrorCode::InvalidSymbol)?;
+    Ok(if prefix_symbol {
+        format!("{}{}", prefix, symbol_str)
+    } else {
+        symbol_str.to_string()
+    })
+}
+
+/// Token-2022 additional_metadata written to a new RIFT mint: the wrapped mint and the decimals
+/// the RIFT mirrors from it
+fn rift_display_metadata(underlying_mint: &Pubkey, decimals: u8) -> Vec<(String, String)> {
+    vec![
+        ("underlying_mint".to_string(), underlying_mint.to_string()),
+        ("decimals".to_string(), decimals.to_string()),
+    ]
+}
+
+/// Whether a vault PDA already holds a token account usable as-is
+/// Ok(false) while the address is unallocated; an account owned by another program, or one with
+/// the wrong mint/authority, is rejected with `error` rather than silently reused
+fn existing_vault_matches(
+    vault: &AccountInfo,
+    token_program: &Pubkey,
+    expected_mint: &Pubkey,
+    expected_authority: &Pubkey,
+    error: ErrorCode,
+) -> Result<bool> {
+    if vault.data_is_empty() && vault.owner == &anchor_lang::solana_program::system_program::ID {
+        return Ok(false);
+    }
+    if vault.owner != token_program {
+        return Err(error.into());
+    }
+    let vault_data = vault.try_borrow_data()?;
+    let token_account = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&vault_data)
+        .map_err(|_| error)?;
+    if token_account.base.mint != *expected_mint || token_account.base.owner != *expected_authority {
+        return Err(error.into());
+    }
+    Ok(true)
+}
+
+/// End of the zero-fee launch window for a new rift (0 = no window)
+fn launch_fee_free_until(fee_free_seconds: u32) -> Result<i64> {
+    require!(
+        fee_free_seconds <= MAX_FEE_FREE_LAUNCH_SECONDS,
+        ErrorCode::InvalidFeeFreeWindow
+    );
+    if fee_free_seconds == 0 {
+        return Ok(0);
+    }
+    Clock::get()?
+        .unix_timestamp
+        .checked_add(i64::from(fee_free_seconds))
+        .ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// RIFT transfer fee plus the underlying's must stay within max_total_leakage_bps
+/// (None = DEFAULT_MAX_TOTAL_LEAKAGE_BPS, which admits every launch fee over a 1% underlying)
+fn check_combined_leakage(
+    transfer_fee_bps: u16,
+    underlying_fee_bps: u16,
+    max_total_leakage_bps: Option<u16>,
+) -> Result<()> {
+    let max_leakage_bps = max_total_leakage_bps.unwrap_or(DEFAULT_MAX_TOTAL_LEAKAGE_BPS);
+    let combined_fee_bps = u32::from(transfer_fee_bps) + u32::from(underlying_fee_bps);
+    require!(
+        combined_fee_bps <= u32::from(max_leakage_bps),
+        ErrorCode::ExcessiveCombinedFee
+    );
+    Ok(())
+}
+
+/// Append a validated sample to the rift's OracleHistory (no-op for rifts that haven't migrated)
+fn record_oracle_history(
+    rift: &Rift,
+    history: &mut Option<Account<OracleHistory>>,
+    price: u64,
+    confidence: u64,
+    timestamp: i64,
+) -> Result<()> {
+    if rift.oracle_history_for(history)?.is_none() {
+        return Ok(());
+    }
+    if let Some(history) = history.as_mut() {
+        history.push(PriceData {
+            price,
+            confidence,
+            timestamp,
+        });
+    }
+    Ok(())
+}
+
+/// Raw token amount of a rift vault read from account data
+/// The account must be the stored vault address and owned by SPL Token or Token-2022
+fn read_vault_amount(
+    vault_info: &AccountInfo,
+    stored_vault: Pubkey,
+    error: ErrorCode,
+) -> Result<u64> {
+    let token_owned =
+        *vault_info.owner == anchor_spl::token::ID || *vault_info.owner == spl_token_2022::ID;
+    if vault_info.key() != stored_vault || !token_owned {
+        return Err(error.into());
+    }
+    let vault_data = vault_info.try_borrow_data()?;
+    require!(vault_data.len() >= 72, ErrorCode::InvalidAccountData);
+    Ok(u64::from_le_bytes(
+        vault_data[64..72].try_into().map_err(|_| ErrorCode::InvalidAccountData)?,
+    ))
+}
+
+/// Reject rift names that would render badly as a token name: invalid UTF-8, empty after
+/// trimming whitespace, or containing control characters
+fn validate_rift_name(rift_name: &[u8; 32], name_len: u8) -> Result<()> {
+    require!(name_len <= 32, ErrorCode::NameTooLong);
+    let name = core::str::from_utf8(&rift_name[..name_len as usize])
+        .map_err(|_| ErrorCode::InvalidRiftName)?;
+    require!(
+        !name.trim().is_empty() && !name.chars().any(char::is_control),
+        ErrorCode::InvalidRiftName
+    );
+    Ok(())
+}
+
+/// Validate a fee distribution destination: token account of `expected_mint` owned by `expected_owner`
+fn validate_fee_recipient(
+    account: &AccountInfo,
+    expected_owner: &Pubkey,
+    expected_mint: &Pubkey,
+    error: ErrorCode,
+) -> Result<()> {
+    require!(
+        account.owner == &anchor_spl::token::ID || account.owner == &spl_token_2022::ID,
+        ErrorCode::InvalidProgramId
+    );
+    let account_data = account.try_borrow_data()?;
+    let token_account =
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account_data)
+            .map_err(|_| error)?;
+    if token_account.base.owner != *expected_owner || token_account.base.mint != *expected_mint {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Transfer `amount` out of a fee vault with the distribution split:
+/// protocol cut off the top, then partner_fee_bps to the partner and the remainder to treasury
+/// (one combined treasury transfer when partner == treasury).
+/// Partner/treasury accounts must already be validated; the protocol account is checked here.
+/// Returns (protocol_amount, partner_amount, treasury_amount)
+#[allow(clippy::too_many_arguments)]
+fn transfer_fee_split<'info>(
+    rift: &Rift,
+    amount: u64,
+    source: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    token_program: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    treasury_account: AccountInfo<'info>,
+    partner_account: Option<AccountInfo<'info>>,
+    protocol_fee_account: Option<AccountInfo<'info>>,
+    global_config: &AccountInfo<'info>,
+) -> Result<(u64, u64, u64)> {
+    let FeeSplit {
+        protocol_amount,
+        protocol_fee_recipient,
+        partner_amount,
+        treasury_amount,
+    } = rift.plan_fee_split(amount, global_config)?;
+
+    let transfer_share = |destination: AccountInfo<'info>, share: u64| -> Result<()> {
+        let transfer_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            TransferChecked {
+                from: source.clone(),
+                to: destination,
+                authority: vault_authority.clone(),
+                mint: mint.clone(),
+            },
+            signer,
+        );
+        interface_transfer_checked(transfer_ctx, share, decimals)
+    };
+
+    if protocol_amount > 0 {
+        let protocol_fee_account =
+            protocol_fee_account.ok_or(ErrorCode::MissingProtocolFeeAccount)?;
+        validate_fee_recipient(
+            &protocol_fee_account,
+            &protocol_fee_recipient,
+            mint.key,
+            ErrorCode::InvalidProtocolFeeAccount,
+        )?;
+        transfer_share(protocol_fee_account, protocol_amount)?;
+    }
+    if partner_amount > 0 {
+        transfer_share(
+            partner_account.ok_or(ErrorCode::MissingPartnerAccount)?,
+            partner_amount,
+        )?;
+    }
+    if treasury_amount > 0 {
+        transfer_share(treasury_account, treasury_amount)?;
+    }
+
+    msg!(
+        "Split {}: protocol {}, partner {}, treasury {}",
+        amount,
+        protocol_amount,
+        partner_amount,
+        treasury_amount
+    );
+
+    Ok((protocol_amount, partner_amount, treasury_amount))
+}
+
+/// Pay `amount` of accrued protocol skim out of a fees vault to the global treasury's token account
+/// (GlobalConfig.treasury_wallet, or the hardcoded treasury while the config is uninitialized).
+/// The caller decrements rift.protocol_fees_owed.
+#[allow(clippy::too_many_arguments)]
+fn transfer_protocol_skim<'info>(
+    amount: u64,
+    source: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    token_program: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    protocol_treasury_account: Option<AccountInfo<'info>>,
+    global_config: &AccountInfo<'info>,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let protocol_treasury_account =
+        protocol_treasury_account.ok_or(ErrorCode::MissingProtocolFeeAccount)?;
+    validate_fee_recipient(
+        &protocol_treasury_account,
+        &GlobalConfig::load_authorities(global_config)?.treasury_wallet,
+        mint.key,
+        ErrorCode::InvalidProtocolFeeAccount,
+    )?;
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program,
+        TransferChecked {
+            from: source,
+            to: protocol_treasury_account,
+            authority: vault_authority,
+            mint,
+        },
+        signer,
+    );
+    interface_transfer_checked(transfer_ctx, amount, decimals)?;
+    msg!("✅ Sent {} protocol skim to the global treasury", amount);
+    Ok(())
+}
+
+/// Pay out `balance` of a wrap fees vault the way claim_wrap_fees does: up to `protocol_skim_owed`
+/// of accrued protocol skim to the global treasury first, then transfer_fee_split on the rest.
+/// Partner/treasury accounts must already be validated; the caller decrements protocol_fees_owed.
+/// Returns (protocol_skim_amount, protocol_amount, partner_amount, treasury_amount)
+#[allow(clippy::too_many_arguments)]
+fn distribute_wrap_fees_vault<'info>(
+    rift: &Rift,
+    balance: u64,
+    protocol_skim_owed: u64,
+    wrap_fees_vault: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    token_program: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    protocol_treasury_account: Option<AccountInfo<'info>>,
+    treasury_account: AccountInfo<'info>,
+    partner_account: Option<AccountInfo<'info>>,
+    protocol_fee_account: Option<AccountInfo<'info>>,
+    global_config: &AccountInfo<'info>,
+) -> Result<(u64, u64, u64, u64)> {
+    let protocol_skim_amount = protocol_skim_owed.min(balance);
+    transfer_protocol_skim(
+        protocol_skim_amount,
+        wrap_fees_vault.clone(),
+        mint.clone(),
+        decimals,
+        token_program.clone(),
+        vault_authority.clone(),
+        signer,
+        protocol_treasury_account,
+        global_config,
+    )?;
+
+    let (protocol_amount, partner_amount, treasury_amount) = transfer_fee_split(
+        rift,
+        balance
+            .checked_sub(protocol_skim_amount)
+            .ok_or(ErrorCode::MathOverflow)?,
+        wrap_fees_vault,
+        mint,
+        decimals,
+        token_program,
+        vault_authority,
+        signer,
+        treasury_account,
+        partner_account,
+        protocol_fee_account,
+        global_config,
+    )?;
+
+    Ok((protocol_skim_amount, protocol_amount, partner_amount, treasury_amount))
+}
+
+// SIMPLIFIED ACCOUNT STRUCTS TO REDUCE STACK USAGE
+
+#[derive(Accounts)]
+#[instruction(vanity_seed: [u8; 32], seed_len: u8, partner_wallet: Option<Pubkey>, rift_name: [u8; 32], name_len: u8, transfer_fee_bps: u16)]
+pub struct CreateRiftWithVanityPDA<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// **CRITICAL SPACE FIX**: Use explicit Borsh size calculation
+    /// Option<Pubkey> = 33 bytes in Borsh (1 discriminant + 32 pubkey), not 32 from std::mem::size_of
+    /// 8 Option<Pubkey> fields in current struct
+    /// Correct size: 8 (discriminator) + 1399 (struct) = 1407 bytes (see RIFT_ACCOUNT_SIZE)
+    /// **FIX LOW #1 (Audit)**: Add constraint to prevent panic from invalid seed_len
+    /// **IDEMPOTENT CREATE**: init_if_needed so duplicate submissions fail with RiftAlreadyExists in the handler
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RIFT_ACCOUNT_SIZE,
+        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref(), &vanity_seed[..seed_len as usize]],
+        bump,
+        constraint = seed_len <= 32 @ ErrorCode::InvalidVanitySeedLength
+    )]
+    pub rift: Account<'info, Rift>,
+
+    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+
+    /// The PDA-derived mint account for vanity address
+    /// **TOKEN-2022**: Manually initialized with transfer fee extension (0.7% on DEX trades)
+    /// **SECURITY NOTE #8**: Using UncheckedAccount because Token-2022 extensions require manual initialization.
+    /// This account is created via invoke_signed with proper validation (lines 189-233).
+    /// RISK: If manual initialization code has bugs, could create invalid/exploitable mints.
+    /// MITIGATION: Thoroughly tested initialization sequence, PDA derivation enforced by seeds.
+    /// CHECK: Manually initialized with Token-2022 transfer fee extension in instruction handler
+    /// **FIX HIGH #4**: Changed from user-provided bump to auto-derived canonical bump
+    /// **FIX LOW #1 (Audit)**: seed_len already validated in rift account constraint
+    #[account(
+        mut,
+        seeds = [b"rift_mint", creator.key().as_ref(), underlying_mint.key().as_ref(), &vanity_seed[..seed_len as usize]],
+        bump,
+    )]
+    pub rift_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for rift mint authority
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// **ATOMIC INIT**: Vault token account (initialized during create_rift_with_vanity_pda)
+    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+    #[account(
+        mut,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// **ATOMIC INIT**: Fees vault token account (initialized during create_rift_with_vanity_pda)
+    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+    #[account(
+        mut,
+        seeds = [b"fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fees_vault: UncheckedAccount<'info>,
+
+    /// **ATOMIC INIT**: Withheld vault token account (initialized during create_rift_with_vanity_pda)
+    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub withheld_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for vault authority (controls all vault transfers)
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
+    #[account(
+        constraint = token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Validated in handler - must match underlying_mint.owner
+    pub underlying_token_program: UncheckedAccount<'info>,
+
+    /// Per-creator vanity rift counter (capped at max_rifts)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorRiftCount::SIZE,
+        seeds = [b"creator_count", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_rift_count: Account<'info, CreatorRiftCount>,
+
+    /// Registry entry marking rift_mint as a RIFT mint (checked when later rifts wrap it)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RiftMintRegistry::SIZE,
+        seeds = [b"rift_mint_registry", rift_mint.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_registry: Account<'info, RiftMintRegistry>,
+
+    /// Registry entry for the underlying - only exists when the underlying is itself a RIFT mint
+    /// CHECK: Address fixed by seeds; may be uninitialized (not a RIFT mint)
+    #[account(
+        seeds = [b"rift_mint_registry", underlying_mint.key().as_ref()],
+        bump
+    )]
+    pub underlying_registry: UncheckedAccount<'info>,
+
+    /// Protocol config PDA - its mint_policy_mode decides how mint_policy is applied
+    /// CHECK: Address fixed by seeds; may be uninitialized (denylist mode applies)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// Underlying allow/deny entry (validated in handler via GlobalConfig::check_mint_policy)
+    /// CHECK: Address fixed by seeds; may be uninitialized (no policy for this mint)
+    #[account(
+        seeds = [b"mint_policy", underlying_mint.key().as_ref()],
+        bump
+    )]
+    pub mint_policy: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(partner_wallet: Option<Pubkey>, rift_name: [u8; 32], name_len: u8, transfer_fee_bps: u16)]
+pub struct CreateRift<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// **CRITICAL SPACE FIX**: Use explicit Borsh size calculation
+    /// Option<Pubkey> = 33 bytes in Borsh (1 discriminant + 32 pubkey), not from std::mem::size_of
+    /// Correct size: 8 (discriminator) + 1399 (struct) = 1407 bytes (see RIFT_ACCOUNT_SIZE)
+    /// **IDEMPOTENT CREATE**: init_if_needed so duplicate submissions fail with RiftAlreadyExists in the handler
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RIFT_ACCOUNT_SIZE,
+        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump,
+    )]
+    pub rift: Account<'info, Rift>,
+
+    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Manually initialized as Token-2022 with transfer fee extension
+    #[account(
+        mut,
+        seeds = [b"rift_mint", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump
+    )]
+    pub rift_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for mint authority
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// **ATOMIC INIT**: Vault token account (initialized during create_rift)
+    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+    #[account(
+        mut,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// **ATOMIC INIT**: Fees vault token account (initialized during create_rift)
+    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+    #[account(
+        mut,
+        seeds = [b"fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fees_vault: UncheckedAccount<'info>,
+
+    /// **ATOMIC INIT**: Withheld vault token account (initialized during create_rift)
+    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub withheld_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for vault authority (controls all vault transfers)
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
+    #[account(
+        constraint = token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Validated in handler - must match underlying_mint.owner
+    pub underlying_token_program: UncheckedAccount<'info>,
+
+    /// Registry entry marking rift_mint as a RIFT mint (checked when later rifts wrap it)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RiftMintRegistry::SIZE,
+        seeds = [b"rift_mint_registry", rift_mint.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_registry: Account<'info, RiftMintRegistry>,
+
+    /// Registry entry for the underlying - only exists when the underlying is itself a RIFT mint
+    /// CHECK: Address fixed by seeds; may be uninitialized (not a RIFT mint)
+    #[account(
+        seeds = [b"rift_mint_registry", underlying_mint.key().as_ref()],
+        bump
+    )]
+    pub underlying_registry: UncheckedAccount<'info>,
+
+    /// Protocol config PDA - its mint_policy_mode decides how mint_policy is applied
+    /// CHECK: Address fixed by seeds; may be uninitialized (denylist mode applies)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// Underlying allow/deny entry (validated in handler via GlobalConfig::check_mint_policy)
+    /// CHECK: Address fixed by seeds; may be uninitialized (no policy for this mint)
+    #[account(
+        seeds = [b"mint_policy", underlying_mint.key().as_ref()],
+        bump
+    )]
+    pub mint_policy: UncheckedAccount<'info>,
+}
+
+/// Account struct for creating a rift with a classic SPL Token RIFT mint
+/// Same rift/mint/vault PDAs as create_rift; all init_if_needed so duplicates reach the handler
+#[derive(Accounts)]
+pub struct CreateRiftSpl<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RIFT_ACCOUNT_SIZE,
+        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump,
+    )]
+    pub rift: Box<Account<'info, Rift>>,
+
+    pub underlying_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Classic SPL Token RIFT mint mirroring the underlying decimals, no freeze authority
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [b"rift_mint", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        bump,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = rift_mint_authority,
+        mint::token_program = rift_token_program,
+    )]
+    pub rift_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Mint authority PDA - controls RIFT token minting
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// Backing vault for the underlying
+    #[account(
+        init_if_needed,
+        payer = creator,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = vault_authority,
+        token::token_program = underlying_token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Vault authority PDA - controls vault token transfers
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Program owning the underlying mint (SPL Token or Token-2022)
+    #[account(
+        constraint = underlying_token_program.key() == *underlying_mint.to_account_info().owner
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+
+    /// Classic SPL Token program (owns the legacy RIFT mint)
+    pub rift_token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Registry entry marking rift_mint as a RIFT mint (checked when later rifts wrap it)
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RiftMintRegistry::SIZE,
+        seeds = [b"rift_mint_registry", rift_mint.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_registry: Box<Account<'info, RiftMintRegistry>>,
+
+    /// Registry entry for the underlying - only exists when the underlying is itself a RIFT mint
+    /// CHECK: Address fixed by seeds; may be uninitialized (not a RIFT mint)
+    #[account(
+        seeds = [b"rift_mint_registry", underlying_mint.key().as_ref()],
+        bump
+    )]
+    pub underlying_registry: UncheckedAccount<'info>,
+
+    /// Protocol config PDA - its mint_policy_mode decides how mint_policy is applied
+    /// CHECK: Address fixed by seeds; may be uninitialized (denylist mode applies)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// Underlying allow/deny entry (validated in handler via GlobalConfig::check_mint_policy)
+    /// CHECK: Address fixed by seeds; may be uninitialized (no policy for this mint)
+    #[account(
+        seeds = [b"mint_policy", underlying_mint.key().as_ref()],
+        bump
+    )]
+    pub mint_policy: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Vault token account
+    #[account(
+        init,
+        payer = user,
+        token::mint = underlying_mint,
+        token::authority = vault_authority,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Mint validated by vault init constraint above (token::mint = underlying_mint)
+    #[account(constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint)]
+    pub underlying_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA - controls vault token transfers
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Mint authority PDA - controls RIFT token minting
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+            || token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeesVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Fees vault token account (holds collected wrap/unwrap fees)
+    /// **FIX CRITICAL #19**: Manual initialization with proper Token-2022 extension sizing
+    /// CHECK: Manually initialized in handler with proper space calculation based on token program
+    #[account(
+        mut,
+        seeds = [b"fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fees_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Mint validated by fees_vault init constraint
+    #[account(constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint)]
+    pub underlying_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA - controls fees vault transfers
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// **FIX CRITICAL #34**: Constrain token_program to SPL Token or Token-2022 only
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+            || token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// Account struct for creating the wrap fees vault (underlying tokens, owned by vault_authority)
+#[derive(Accounts)]
+pub struct InitializeWrapFeesVault<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Box<Account<'info, Rift>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"wrap_fees_vault", rift.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = vault_authority,
+        token::token_program = underlying_token_program,
+    )]
+    pub wrap_fees_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Vault authority PDA - owns the wrap fees vault
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = underlying_token_program.key() == *underlying_mint.to_account_info().owner
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account struct for paying out the wrap fees vault
+#[derive(Accounts)]
+pub struct ClaimWrapFees<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Box<Account<'info, Rift>>,
+
+    /// CHECK: Validated against rift.wrap_fees_vault in handler
+    #[account(
+        mut,
+        seeds = [b"wrap_fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub wrap_fees_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA - signs the payout
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Validated in handler - owner must be rift.treasury_wallet, mint must match
+    #[account(mut)]
+    pub treasury_underlying_account: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in handler - owner must be rift.partner_wallet, mint must match
+    #[account(mut)]
+    pub partner_underlying_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated in helper - owner must be GlobalConfig.protocol_fee_recipient
+    #[account(mut)]
+    pub protocol_underlying_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated against GlobalConfig.treasury_wallet when a protocol skim is owed
+    #[account(mut)]
+    pub protocol_treasury_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = underlying_token_program.key() == *underlying_mint.to_account_info().owner
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeWithheldVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Withheld vault token account (holds collected SPL Token-2022 withheld fees - RIFT tokens)
+    /// **FIX CRITICAL #20**: Manual initialization with proper Token-2022 extension sizing
+    /// CHECK: Manually initialized in handler with proper space for TransferFeeAmount extension
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub withheld_vault: UncheckedAccount<'info>,
+
+    /// CHECK: RIFT mint validated by withheld_vault init constraint
+    #[account(constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint)]
+    pub rift_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA - controls withheld vault transfers
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// **FIX CRITICAL #35**: Constrain token_program to Token-2022 only (RIFT mint is always Token-2022)
+    #[account(
+        constraint = token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WrapTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **SECURITY FIX #49**: User's underlying token account - validated manually in handler
+    /// CHECK: Token account validation performed manually to reduce stack usage
+    #[account(mut)]
+    pub user_underlying: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #49**: User's RIFT token account - validated manually in handler
+    /// CHECK: Token account validation performed manually to reduce stack usage
+    #[account(mut)]
+    pub user_rift_tokens: UncheckedAccount<'info>,
+
+    /// **CRITICAL FIX - HIGH ISSUE #3**: Vault account type must support .amount and .reload()
+    /// Changed from UncheckedAccount to InterfaceAccount<TokenAccount> to fix compilation error
+    #[account(
+        mut,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump,
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// **TOKEN-2022 FIX**: Underlying mint required for transfer_checked
+    /// CHECK: Validated against rift.underlying_mint
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #49**: Validate rift mint matches rift state
+    /// CHECK: Pubkey validated against rift.rift_mint; Token program validates it's a valid mint during CPI
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// Fees vault to collect wrap fees (underlying tokens)
+    /// CHECK: Optional - validated manually in handler. If not initialized (system_program::ID), fees stay in vault
+    #[account(
+        mut,
+        seeds = [b"fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fees_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA - signs transfers to fees_vault
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Wrap fee destination - required once rift.wrap_fees_vault is set
+    /// CHECK: Validated against rift.wrap_fees_vault in handler
+    #[account(
+        mut,
+        seeds = [b"wrap_fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub wrap_fees_vault: Option<UncheckedAccount<'info>>,
+
+    // **FIX CRITICAL #27**: Support different token programs for underlying and RIFT
+    // Underlying can be SPL Token or Token-2022
+    #[account(
+        constraint = underlying_token_program.key() == anchor_spl::token::ID
+            || underlying_token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+
+    // RIFT mint is Token-2022, or classic SPL Token for legacy rifts (fixed at creation)
+    /// **FIX CRITICAL #36**: Constrain rift_token_program to the rift's own mint program
+    /// Prevents malicious program from faking mint operations or using PDA signer to mint unauthorized tokens
+    #[account(
+        constraint = rift_token_program.key() == rift.rift_token_program_id()
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub rift_token_program: Interface<'info, TokenInterface>,
+
+    /// Per-user wrap activity for the optional cooldown
+    /// Created on the user's first wrap into this rift (one-time rent of ~0.0015 SOL for
+    /// UserRiftActivity::SIZE bytes, paid by the user)
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRiftActivity::SIZE,
+        seeds = [b"activity", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_activity: Box<Account<'info, UserRiftActivity>>,
+
+    /// Only needed when wrapping with create_rift_ata = true, or wrap_sol without a wSOL ATA
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional fee exemption for the signer (PDA ["fee_exempt", rift, user]); omit to pay normal fees
+    #[account(
+        seeds = [b"fee_exempt", rift.key().as_ref(), user.key().as_ref()],
+        bump = fee_exemption.bump,
+        constraint = fee_exemption.rift == rift.key()
+            && fee_exemption.wallet == user.key() @ ErrorCode::InvalidFeeExemption
+    )]
+    pub fee_exemption: Option<Box<Account<'info, FeeExemption>>>,
+
+    /// Protocol config PDA - if initialized, its protocol_fee_bps is skimmed from the fee
+    /// CHECK: Address fixed by seeds; may be uninitialized (no skim)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+// NOTE: underlying_mint validation removed to reduce stack size
+// Security is maintained via vault.mint == rift.underlying_mint constraint above
+
+/// Account struct for simple vault-based unwrap
+/// **SECURITY FIX #49**: Stack optimization - uses UncheckedAccount with manual validation
+#[derive(Accounts)]
+pub struct UnwrapFromVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **SECURITY FIX #49**: User's underlying token account - validated manually in handler
+    /// CHECK: Token account validation performed manually to reduce stack usage
+    #[account(mut)]
+    pub user_underlying: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #49**: User's RIFT token account - validated manually in handler
+    /// CHECK: Token account validation performed manually to reduce stack usage
+    #[account(mut)]
+    pub user_rift_tokens: UncheckedAccount<'info>,
+
+    /// **CRITICAL FIX - HIGH ISSUE #3**: Vault account type must support .amount and .reload()
+    /// Changed from UncheckedAccount to InterfaceAccount<TokenAccount> to fix compilation error
+    #[account(
+        mut,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump,
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// **TOKEN-2022 FIX**: Underlying mint required for transfer_checked
+    /// CHECK: Validated against rift.underlying_mint
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: UncheckedAccount<'info>,
+
+    /// Vault authority PDA (owns the vault, signs transfers from vault)
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Rift mint authority PDA (controls RIFT token minting/burning)
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #49**: RIFT mint (for burning)
+    /// CHECK: Pubkey validated against rift.rift_mint; Token program validates it's a valid mint during CPI
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: UncheckedAccount<'info>,
+
+    /// Fees vault to collect unwrap fees (underlying tokens)
+    /// CHECK: Optional - validated manually in handler. If not initialized (system_program::ID), fees stay in vault
+    #[account(
+        mut,
+        seeds = [b"fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fees_vault: UncheckedAccount<'info>,
+
+    // **FIX CRITICAL #27**: Support different token programs for underlying and RIFT
+    // Underlying can be SPL Token or Token-2022
+    #[account(
+        constraint = underlying_token_program.key() == anchor_spl::token::ID
+            || underlying_token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+
+    // RIFT mint is Token-2022, or classic SPL Token for legacy rifts (fixed at creation)
+    /// **FIX CRITICAL #37**: Constrain rift_token_program to the rift's own mint program
+    /// Prevents malicious program from faking burn operations and double-spending vault
+    #[account(
+        constraint = rift_token_program.key() == rift.rift_token_program_id()
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub rift_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional fee exemption for the signer (PDA ["fee_exempt", rift, user]); omit to pay normal fees
+    #[account(
+        seeds = [b"fee_exempt", rift.key().as_ref(), user.key().as_ref()],
+        bump = fee_exemption.bump,
+        constraint = fee_exemption.rift == rift.key()
+            && fee_exemption.wallet == user.key() @ ErrorCode::InvalidFeeExemption
+    )]
+    pub fee_exemption: Option<Box<Account<'info, FeeExemption>>>,
+
+    /// Only needed for unwrap_sol when the user has no wSOL ATA yet
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    /// Fee recipients for auto_distribute (only read once the threshold is reached)
+    /// CHECK: Validated in helper - owner must be rift.treasury_wallet, mint must match
+    #[account(mut)]
+    pub treasury_fee_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated in helper - owner must be rift.partner_wallet, mint must match
+    #[account(mut)]
+    pub partner_fee_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated in helper - owner must be GlobalConfig.protocol_fee_recipient
+    #[account(mut)]
+    pub protocol_fee_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated in helper - owner must be the global treasury wallet
+    #[account(mut)]
+    pub protocol_treasury_account: Option<UncheckedAccount<'info>>,
+
+    /// Protocol config PDA - if initialized, its protocol_fee_bps is skimmed from the fee
+    /// CHECK: Address fixed by seeds; may be uninitialized (no skim)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// Account struct for moving a rift's oracle samples to an OracleHistory PDA
+#[derive(Accounts)]
+#[instruction(capacity: u16)]
+pub struct MigrateOracleHistory<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = OracleHistory::space(capacity),
+        seeds = [b"oracle_history", rift.key().as_ref()],
+        bump
+    )]
+    pub oracle_history: Account<'info, OracleHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account struct for growing a launch-layout rift to the current layout
+#[derive(Accounts)]
+pub struct MigrateRiftAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Launch-layout Rift - owner, size and discriminator checked in handler
+    #[account(mut)]
+    pub rift: UncheckedAccount<'info>,
+
+    /// CHECK: Must equal the rift's stored rift_mint (checked in handler)
+    pub rift_mint: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account struct for growing an original-layout GlobalConfig to the current layout
+#[derive(Accounts)]
+pub struct MigrateGlobalConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Address fixed by seeds; owner and legacy size checked in handler
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account struct for backfilling the RIFT mint registry entry of an existing rift
+#[derive(Accounts)]
+pub struct RegisterRiftMint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub rift: Account<'info, Rift>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RiftMintRegistry::SIZE,
+        seeds = [b"rift_mint_registry", rift.rift_mint.as_ref()],
+        bump
+    )]
+    pub rift_mint_registry: Account<'info, RiftMintRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdminRepairVaultPointers<'info> {
+    pub program_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: Vault PDA; token account contents validated in the handler
+    #[account(
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Fees vault PDA; token account contents validated in the handler
+    #[account(
+        seeds = [b"fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fees_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Withheld vault PDA; token account contents validated in the handler
+    #[account(
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub withheld_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Vault authority PDA expected as owner of all three vaults
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Must match rift.underlying_mint; its owner is the vault token program
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Must match rift.rift_mint
+    #[account(
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: UncheckedAccount<'info>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminFixVaultConflict<'info> {
+    #[account(mut)]
+    pub program_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: Vault PDA that may have wrong owner
+    #[account(
+        mut,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Expected vault authority PDA
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// **SECURITY FIX #50**: Account struct for updating Switchboard oracle
+#[derive(Accounts)]
+pub struct UpdateSwitchboardOracle<'info> {
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **SECURITY FIX #50**: Authority authorized to update oracle prices (creator or governance)
+    pub oracle_authority: Signer<'info>,
+
+    /// **SECURITY FIX #50**: Switchboard aggregator feed - validated against rift.switchboard_feed_account
+    /// CHECK: Validated in instruction handler against stored pubkey and Switchboard program ownership
+    pub switchboard_feed: UncheckedAccount<'info>,
+
+    /// Oracle history PDA (required once the rift migrated to one, see migrate_oracle_history)
+    #[account(mut)]
+    pub oracle_history: Option<Account<'info, OracleHistory>>,
+}
+
+/// Account struct for updating oracle with manual price data (Jupiter API, etc.)
+#[derive(Accounts)]
+pub struct UpdateManualOracle<'info> {
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Authority authorized to update oracle prices (must be creator)
+    pub oracle_authority: Signer<'info>,
+
+    /// Bound Switchboard feed for the deviation cross-check (required when one is configured)
+    /// CHECK: Validated against rift.switchboard_feed_account and parsed via the Switchboard SDK
+    pub reference_feed: Option<UncheckedAccount<'info>>,
+
+    /// Oracle history PDA (required once the rift migrated to one, see migrate_oracle_history)
+    #[account(mut)]
+    pub oracle_history: Option<Account<'info, OracleHistory>>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerRebalance<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Keeper reward accounts - all optional; omit them to rebalance without claiming a reward
+    /// CHECK: Validated against rift.fees_vault in handler
+    #[account(
+        mut,
+        seeds = [b"fees_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fees_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Vault authority PDA - signs the reward transfer
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// CHECK: Validated in handler - owner must be the keeper, mint must be the underlying
+    #[account(mut)]
+    pub keeper_underlying: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        constraint = underlying_token_program.key() == anchor_spl::token::ID
+            || underlying_token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub underlying_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Oracle history PDA (required once the rift migrated to one, see migrate_oracle_history)
+    pub oracle_history: Option<Account<'info, OracleHistory>>,
+}
+
+/// Optimized fee distribution context - essential accounts only
+#[derive(Accounts)]
+/// **FIX CRITICAL #12**: CloseRift now requires ALL vaults to prevent fund loss
+pub struct CloseRift<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ ErrorCode::UnauthorizedClose
+    )]
+    pub rift: Account<'info, Rift>,
+
+    /// **FIX CRITICAL #27**: Make vault optional - may not be initialized if rift never used
+    /// CHECK: If initialized, validated against rift.vault. Manual check in handler.
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// **FIX CRITICAL #27**: Make fees_vault optional - may be system_program::ID if never initialized
+    /// CHECK: If initialized, validated by seeds and balance check in function
+    #[account(mut)]
+    pub fees_vault: UncheckedAccount<'info>,
+
+    /// **FIX CRITICAL #27**: Make withheld_vault optional - may be system_program::ID if never initialized
+    /// CHECK: If initialized, validated by seeds and balance check in function
+    #[account(mut)]
+    pub withheld_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that owns all rift vaults - signs close_account
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Wrap fees vault - required when rift.wrap_fees_vault is set
+    /// CHECK: Validated against rift.wrap_fees_vault and balance checked in handler
+    #[account(mut)]
+    pub wrap_fees_vault: Option<UncheckedAccount<'info>>,
+
+    /// SPL Token program (closes SPL underlying vaults)
+    pub token_program: Program<'info, Token>,
+
+    /// Token-2022 program (closes Token-2022 vaults)
+    pub token_2022_program: Program<'info, Token2022>,
+
+    /// Underlying mint - only needed when an underlying vault holds dust to forward or withheld
+    /// transfer fees to harvest
+    #[account(
+        mut,
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// RIFT mint - receives withheld transfer fees harvested from withheld_vault
+    /// CHECK: Address validated against rift.rift_mint; Token-2022 validates the harvest
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: UncheckedAccount<'info>,
+
+    /// Creator's underlying token account receiving vault dust - only needed when dust remains
+    #[account(
+        mut,
+        constraint = creator_underlying.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
+        constraint = creator_underlying.owner == creator.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub creator_underlying: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+}
+
+#[derive(Accounts)]
+pub struct AdminCloseRift<'info> {
+    #[account(mut)]
+    pub program_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = program_authority
+    )]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// Account struct for pausing/unpausing a rift
+#[derive(Accounts)]
+pub struct AdminPauseRift<'info> {
+    /// Program authority, or the rift's guardian for pause only (validated in handler)
+    pub program_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// Account struct for opening a paused rift for migration into another rift
+#[derive(Accounts)]
+pub struct MigrateRift<'info> {
+    pub program_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub source_rift: Box<Account<'info, Rift>>,
+
+    pub destination_rift: Box<Account<'info, Rift>>,
+
+    #[account(
+        constraint = source_vault.key() == source_rift.vault @ ErrorCode::InvalidVault
+    )]
+    pub source_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// Account struct for a holder redeeming source RIFT of a migrating rift
+#[derive(Accounts)]
+pub struct RedeemMigratedPosition<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub source_rift: Box<Account<'info, Rift>>,
+
+    #[account(mut)]
+    pub destination_rift: Box<Account<'info, Rift>>,
+
+    #[account(
+        constraint = underlying_mint.key() == source_rift.underlying_mint
+            && underlying_mint.key() == destination_rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_source_rift_tokens.mint == source_rift.rift_mint @ ErrorCode::InvalidMint,
+        constraint = user_source_rift_tokens.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_source_rift_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_destination_rift_tokens.mint == destination_rift.rift_mint
+            @ ErrorCode::InvalidMint,
+        constraint = user_destination_rift_tokens.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_destination_rift_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated against source_rift.rift_mint; token program validates during CPI
+    #[account(
+        mut,
+        constraint = source_rift_mint.key() == source_rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub source_rift_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against destination_rift.rift_mint; token program validates during CPI
+    #[account(
+        mut,
+        constraint = destination_rift_mint.key() == destination_rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub destination_rift_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = source_vault.key() == source_rift.vault @ ErrorCode::InvalidVault
+    )]
+    pub source_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = destination_vault.key() == destination_rift.vault @ ErrorCode::InvalidVault
+    )]
+    pub destination_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA that owns the source vault
+    #[account(
+        seeds = [b"vault_auth", source_rift.key().as_ref()],
+        bump
+    )]
+    pub source_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA mint authority of the destination rift
+    #[account(
+        seeds = [b"rift_mint_auth", destination_rift.key().as_ref()],
+        bump
+    )]
+    pub destination_mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = underlying_token_program.key() == anchor_spl::token::ID
+            || underlying_token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+
+    // Each rift burns/mints with its own mint program (Token-2022, or SPL Token for legacy rifts)
+    #[account(
+        constraint = source_rift_token_program.key() == source_rift.rift_token_program_id()
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub source_rift_token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        constraint = destination_rift_token_program.key()
+            == destination_rift.rift_token_program_id() @ ErrorCode::InvalidProgramId
+    )]
+    pub destination_rift_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Account struct for moving a user position between two rifts of the same underlying
+#[derive(Accounts)]
+pub struct MigratePosition<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub source_rift: Box<Account<'info, Rift>>,
+
+    #[account(mut)]
+    pub destination_rift: Box<Account<'info, Rift>>,
+
+    #[account(
+        constraint = underlying_mint.key() == source_rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_source_rift_tokens.mint == source_rift.rift_mint @ ErrorCode::InvalidMint,
+        constraint = user_source_rift_tokens.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub user_source_rift_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_destination_rift_tokens.mint == destination_rift.rift_mint @ ErrorCode::InvalidMint,
+        constraint = user_destination_rift_tokens.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub user_destination_rift_tokens: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated against source_rift.rift_mint; Token-2022 validates during burn
+    #[account(
+        mut,
+        constraint = source_rift_mint.key() == source_rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub source_rift_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against destination_rift.rift_mint; Token-2022 validates during mint
+    #[account(
+        mut,
+        constraint = destination_rift_mint.key() == destination_rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub destination_rift_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = source_vault.key() == source_rift.vault @ ErrorCode::InvalidVault
+    )]
+    pub source_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = destination_vault.key() == destination_rift.vault @ ErrorCode::InvalidVault
+    )]
+    pub destination_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Optional - only used if source fees_vault is initialized (validated in handler)
+    #[account(
+        mut,
+        seeds = [b"fees_vault", source_rift.key().as_ref()],
+        bump
+    )]
+    pub source_fees_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Optional - only used if destination fees_vault is initialized (validated in handler)
+    #[account(
+        mut,
+        seeds = [b"fees_vault", destination_rift.key().as_ref()],
+        bump
+    )]
+    pub destination_fees_vault: UncheckedAccount<'info>,
+
+    /// Destination wrap fee vault - required once destination_rift.wrap_fees_vault is set
+    /// CHECK: Validated against destination_rift.wrap_fees_vault in handler
+    #[account(
+        mut,
+        seeds = [b"wrap_fees_vault", destination_rift.key().as_ref()],
+        bump
+    )]
+    pub destination_wrap_fees_vault: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PDA that owns the source vault
+    #[account(
+        seeds = [b"vault_auth", source_rift.key().as_ref()],
+        bump
+    )]
+    pub source_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA that owns the destination vault
+    #[account(
+        seeds = [b"vault_auth", destination_rift.key().as_ref()],
+        bump
+    )]
+    pub destination_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA mint authority of the destination rift
+    #[account(
+        seeds = [b"rift_mint_auth", destination_rift.key().as_ref()],
+        bump
+    )]
+    pub destination_mint_authority: UncheckedAccount<'info>,
+
+    /// User's wrap activity on the destination rift - migrating in records a wrap
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserRiftActivity::SIZE,
+        seeds = [b"activity", destination_rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub destination_user_activity: Box<Account<'info, UserRiftActivity>>,
+
+    /// Optional fee exemption on the source rift (PDA ["fee_exempt", source_rift, user])
+    #[account(
+        seeds = [b"fee_exempt", source_rift.key().as_ref(), user.key().as_ref()],
+        bump = source_fee_exemption.bump,
+        constraint = source_fee_exemption.rift == source_rift.key()
+            && source_fee_exemption.wallet == user.key() @ ErrorCode::InvalidFeeExemption
+    )]
+    pub source_fee_exemption: Option<Box<Account<'info, FeeExemption>>>,
+
+    /// Optional fee exemption on the destination rift (PDA ["fee_exempt", destination_rift, user])
+    #[account(
+        seeds = [b"fee_exempt", destination_rift.key().as_ref(), user.key().as_ref()],
+        bump = destination_fee_exemption.bump,
+        constraint = destination_fee_exemption.rift == destination_rift.key()
+            && destination_fee_exemption.wallet == user.key() @ ErrorCode::InvalidFeeExemption
+    )]
+    pub destination_fee_exemption: Option<Box<Account<'info, FeeExemption>>>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        constraint = underlying_token_program.key() == anchor_spl::token::ID
+            || underlying_token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub underlying_token_program: Interface<'info, TokenInterface>,
+
+    // Each rift burns/mints with its own mint program (Token-2022, or SPL Token for legacy rifts)
+    #[account(
+        constraint = source_rift_token_program.key() == source_rift.rift_token_program_id()
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub source_rift_token_program: Interface<'info, TokenInterface>,
+
+    #[account(
+        constraint = destination_rift_token_program.key()
+            == destination_rift.rift_token_program_id() @ ErrorCode::InvalidProgramId
+    )]
+    pub destination_rift_token_program: Interface<'info, TokenInterface>,
+
+    /// Protocol config PDA - if initialized, its protocol_fee_bps is skimmed from the fee
+    /// CHECK: Address fixed by seeds; may be uninitialized (no skim)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// **FIX HIGH #1**: Account struct for resetting stuck reentrancy guard
+#[derive(Accounts)]
+pub struct AdminResetReentrancyGuard<'info> {
+    /// Program authority (only one authorized to reset guard)
+    pub program_authority: Signer<'info>,
+
+    /// Rift with potentially stuck reentrancy guard
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminEmergencyWithdrawVault<'info> {
+    /// **SECURITY FIX #3**: First admin authority (PROGRAM_AUTHORITY)
+    #[account(mut)]
+    pub admin_authority_1: Signer<'info>,
+
+    /// **SECURITY FIX #3**: Second admin authority (ADMIN_AUTHORITY_2)
+    #[account(mut)]
+    pub admin_authority_2: Signer<'info>,
+
+    /// **ACCOUNTING FIX**: Rift account to update accounting when withdrawing
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Vault holding the underlying tokens
+    /// CHECK: Admin can specify any vault to recover from
+    #[account(mut)]
+    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault authority PDA - will be verified against closed_rift_pubkey parameter
+    /// CHECK: Admin provides this, function verifies it matches expected PDA
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// **TOKEN-2022 FIX**: Underlying mint required for transfer_checked
+    /// CHECK: Validated against rift.underlying_mint
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: UncheckedAccount<'info>,
+
+    /// Admin's token account to receive withdrawn tokens
+    #[account(mut)]
+    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+            || token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminUpdateRiftMetadata<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The rift account
+    pub rift: Account<'info, Rift>,
+
+    /// The rift mint to create metadata for
+    /// **SECURITY FIX**: Constrain to rift.rift_mint and verify mint authority
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint,
+        constraint = rift_mint.mint_authority.is_some() @ ErrorCode::InvalidMintAuthority,
+        constraint = rift_mint.mint_authority.unwrap() == rift_mint_authority.key() @ ErrorCode::InvalidMintAuthority
+    )]
+    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
+    pub rift_mint: InterfaceAccount<'info, Mint>,
+
+    /// Rift mint authority PDA
+    /// CHECK: Verified by seeds constraint
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// Account struct for the creator-only metadata URI update
+#[derive(Accounts)]
+pub struct CreatorUpdateMetadataUri<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: InterfaceAccount<'info, Mint>,
+
+    /// Rift mint authority PDA (metadata update authority)
+    /// CHECK: Verified by seeds constraint
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Account struct for reclaiming surplus mint rent (creator or program authority)
+#[derive(Accounts)]
+pub struct ReclaimMintRentSurplus<'info> {
+    pub authority: Signer<'info>,
+
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: Rent destination, must be the rift creator
+    #[account(
+        mut,
+        constraint = creator.key() == rift.creator @ ErrorCode::Unauthorized
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: InterfaceAccount<'info, Mint>,
+
+    /// Rift mint authority PDA (authorizes WithdrawExcessLamports on the mint)
+    /// CHECK: Verified by seeds constraint
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+}
+
+/// Account struct for sweeping stray lamports out of a rift vault (program authority only)
+#[derive(Accounts)]
+pub struct SweepVaultLamports<'info> {
+    /// Program authority (validated in handler against GlobalConfig / PROGRAM_AUTHORITY)
+    pub program_authority: Signer<'info>,
+
+    pub rift: Account<'info, Rift>,
+
+    /// One of rift.vault / rift.fees_vault / rift.withheld_vault
+    /// CHECK: Validated in handler against the rift's vault pointers, must be Token-2022 owned
+    #[account(mut)]
+    pub source_vault: UncheckedAccount<'info>,
+
+    /// Vault authority PDA (owner of all three vaults, authorizes WithdrawExcessLamports)
+    /// CHECK: Verified by seeds constraint
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Lamport destination, must be the protocol treasury wallet
+    #[account(mut)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+/// Account struct for closing an orphaned rift vault (creator only)
+#[derive(Accounts)]
+pub struct CleanupStuckVault<'info> {
+    /// The creator who originally tried to create the rift (receives the rent)
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: Only used as a seed component for the expected rift PDA
+    pub underlying_mint: UncheckedAccount<'info>,
+
+    /// The expected rift account location (must be empty/non-existent)
+    /// CHECK: Derivation and emptiness verified in handler
+    pub expected_rift: UncheckedAccount<'info>,
+
+    /// The orphaned vault, fees_vault or withheld_vault PDA of the expected rift
+    /// CHECK: PDA, token program ownership and zero balance verified in handler
+    #[account(mut)]
+    pub stuck_vault: UncheckedAccount<'info>,
+
+    /// Vault authority PDA of the expected rift (owner of the stuck vault)
+    /// CHECK: Derivation verified in handler
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Token program owning the stuck vault (SPL Token or Token-2022)
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+            || token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CleanupStuckAccounts<'info> {
+    /// The creator who originally tried to create the rift
+    /// **SECURITY FIX**: Require creator signature to prevent griefing
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// The underlying mint that was used in the failed rift creation
+    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+
+    /// The stuck rift mint account that needs to be cleaned up
+    /// **FIX HIGH #8**: Use UncheckedAccount to support Token-2022 mint closing via close_account
+    /// **FIX CRITICAL #14**: Use correct PDA seeds matching create_rift (underlying_mint, creator)
+    /// We close this account using Token-2022's close_account instruction
+    #[account(
+        mut,
+        seeds = [b"rift_mint", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump
+    )]
+    pub stuck_rift_mint: UncheckedAccount<'info>,
+
+    /// The expected rift account location (should be empty/non-existent)
+    /// CHECK: We verify this account is empty to ensure it's truly stuck
+    #[account(
+        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump
+    )]
+    pub expected_rift: UncheckedAccount<'info>,
+
+    /// **FIX HIGH #8**: Add mint_authority PDA so we can sign close_account
+    /// Mint authority PDA - controls mint operations
+    /// CHECK: PDA verified by seeds
+    #[account(
+        seeds = [b"rift_mint_auth", expected_rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// The account that will pay for the transaction (can be anyone)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// **FIX HIGH #8**: Add Token-2022 program for close_account instruction
+    /// CHECK: Token-2022 program for closing mint account
+    #[account(address = spl_token_2022::ID)]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+/// **FIX CRITICAL #10**: Struct for cleaning up stuck VANITY rift accounts
+/// Vanity rifts use different PDA seeds than regular rifts
+#[derive(Accounts)]
+#[instruction(vanity_seed: [u8; 32], seed_len: u8)]
+pub struct CleanupStuckVanityAccounts<'info> {
+    /// The creator who originally tried to create the vanity rift
+    /// **SECURITY FIX**: Require creator signature to prevent griefing
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// The underlying mint that was used in the failed vanity rift creation
+    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+
+    /// The stuck VANITY rift mint account that needs to be cleaned up
+    /// **FIX CRITICAL #10**: Uses VANITY seeds (includes vanity_seed)
+    /// We close this account using Token-2022's close_account instruction
+    #[account(
+        mut,
+        seeds = [b"rift_mint", creator.key().as_ref(), underlying_mint.key().as_ref(), &vanity_seed[..seed_len as usize]],
+        bump
+    )]
+    pub stuck_rift_mint: UncheckedAccount<'info>,
+
+    /// The expected rift account location (should be empty/non-existent)
+    /// CHECK: We verify this account is empty to ensure it's truly stuck
+    /// **FIX CRITICAL #26**: Vanity rifts have DIFFERENT seeds than regular rifts!
+    #[account(
+        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref(), &vanity_seed[..seed_len as usize]],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump
+    )]
+    pub expected_rift: UncheckedAccount<'info>,
+
+    /// **FIX CRITICAL #10**: Mint authority PDA - same for vanity and non-vanity
+    /// Mint authority PDA - controls mint operations
+    /// CHECK: PDA verified by seeds
+    #[account(
+        seeds = [b"rift_mint_auth", expected_rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// The account that will pay for the transaction (can be anyone)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// **FIX CRITICAL #10**: Token-2022 program for close_account instruction
+    /// CHECK: Token-2022 program for closing vanity mint account
+    #[account(address = spl_token_2022::ID)]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeFeesFromVault<'info> {
+    /// Fee payer (anyone can call)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **FEE ROUTING UPDATE**: Fees vault holding collected wrap/unwrap fees (underlying tokens)
+    #[account(
+        mut,
+        seeds = [b"fees_vault", rift.key().as_ref()],
+        bump,
+        constraint = fees_vault.key() == rift.fees_vault @ ErrorCode::InvalidVault
+    )]
+    pub fees_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault authority PDA - signs transfers from fees_vault
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Underlying mint (to validate treasury and partner accounts)
+    /// CHECK: Validated in handler - key matches rift.underlying_mint, deserializes as a Mint
+    pub underlying_mint: UncheckedAccount<'info>,
+
+    /// Treasury wallet that owns the treasury_account
+    /// CHECK: Used to derive ATA
+    pub treasury_wallet: UncheckedAccount<'info>,
+
+    /// Treasury token account (ATA - must exist; create via ensure_distribution_atas)
+    /// CHECK: Validated in handler (validate_fee_recipient) - owner treasury_wallet, underlying
+    #[account(mut)]
+    pub treasury_account: UncheckedAccount<'info>,
+
+    /// Partner wallet that owns the partner_account (optional)
+    /// CHECK: Used to derive ATA. If a partner is configured in `rift.partner_wallet`,
+    /// this account MUST correspond to the same pubkey and its ATA must exist when
+    /// partner_amount > 0. The protocol assumes the partner ATA is pre-initialized
+    /// by either the partner or the admin flows.
+    pub partner_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Partner account (ATA - must exist). Initialize it ahead of time via
+    /// `ensure_distribution_atas` so the distribution path never creates accounts.
+    /// CHECK: Validated in handler (validate_fee_recipient) - owner partner_wallet, underlying mint
+    #[account(mut)]
+    pub partner_account: Option<UncheckedAccount<'info>>,
+
+    /// Protocol config PDA - if initialized, its distribution_fee_bps is taken before the split
+    /// CHECK: Address fixed by seeds; may be uninitialized (no protocol cut)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// Protocol fee token account (required when the protocol cut is non-zero)
+    /// CHECK: Validated in handler - owner must be GlobalConfig.protocol_fee_recipient, mint must match
+    #[account(mut)]
+    pub protocol_fee_account: Option<UncheckedAccount<'info>>,
+
+    /// Global treasury token account (required while rift.protocol_fees_owed is non-zero)
+    /// CHECK: Validated in handler - owner must be the global treasury wallet, mint must match
+    #[account(mut)]
+    pub protocol_treasury_account: Option<UncheckedAccount<'info>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+            || token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTreasuryWallet<'info> {
+    /// Rift creator (admin)
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+}
+
+// REMOVED: AdminWithdrawFeeCollector - obsolete struct for removed fee_collector program
+
+/// **TOKEN-2022**: Account struct for claiming withheld transfer fees (non-admin)
+/// Treasury wallet (per-rift) can call this
+#[derive(Accounts)]
+pub struct ClaimWithheldFees<'info> {
+    /// **PER-RIFT TREASURY FIX**: Treasury wallet must match rift.treasury_wallet
+    /// Authorization check is done in the function handler to use per-rift treasury
+    pub treasury_signer: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// RIFT mint (Token-2022 with transfer fee extension)
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: InterfaceAccount<'info, Mint>,
+
+    /// Withheld vault to receive withheld transfer fees (RIFT tokens)
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump,
+        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault,
+        constraint = withheld_vault.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Source account with withheld fees to claim
+    #[account(
+        mut,
+        constraint = source_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub source_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// **FIX MEDIUM #45**: Constrain token_program for defense-in-depth
+    /// Currently unused (handler uses hardcoded spl_token_2022::ID), but constraint
+    /// prevents future refactoring from introducing vulnerability
+    #[account(
+        constraint = token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// **TOKEN-2022**: Account struct for claiming withheld transfer fees with a multisig treasury
+/// Multisig signers are passed as remaining_accounts
+#[derive(Accounts)]
+pub struct ClaimWithheldFeesMultisig<'info> {
+    /// Fee payer submitting the multisig-signed transaction
+    pub payer: Signer<'info>,
+
+    /// CHECK: Must equal rift.treasury_wallet and be a Token-2022 multisig (checked in handler)
+    pub treasury_multisig: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// RIFT mint (Token-2022 with transfer fee extension)
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: InterfaceAccount<'info, Mint>,
+
+    /// Withheld vault to receive withheld transfer fees (RIFT tokens)
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump,
+        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault,
+        constraint = withheld_vault.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Source account with withheld fees to claim
+    #[account(
+        mut,
+        constraint = source_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub source_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// **TOKEN-2022**: Account struct for sweeping mint-level withheld fees
+/// Treasury wallet (per-rift withdraw authority) can call this
+#[derive(Accounts)]
+pub struct HarvestMintWithheld<'info> {
+    /// Must match rift.treasury_wallet (checked in handler)
+    pub treasury_signer: Signer<'info>,
+
+    pub rift: Account<'info, Rift>,
+
+    /// RIFT mint (Token-2022 with transfer fee extension)
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: InterfaceAccount<'info, Mint>,
+
+    /// Withheld vault to receive the mint-level withheld fees (RIFT tokens)
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump,
+        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault,
+        constraint = withheld_vault.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// **FEE MANAGEMENT**: Account struct for distributing withheld fees
+/// Splits withheld_vault RIFT tokens to partner and treasury accounts
+#[derive(Accounts)]
+pub struct DistributeWithheldVault<'info> {
+    /// Fee payer (creator or treasury_wallet)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Withheld vault holding collected transfer fees (RIFT tokens)
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump,
+        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault
+    )]
+    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault authority PDA - signs transfers from withheld_vault
+    /// CHECK: PDA validated by seeds
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// RIFT mint (to validate treasury and partner accounts)
+    /// CHECK: Manually validated in handler - owner must be Token-2022 program, deserializes as Mint, key matches rift.rift_mint
+    pub rift_mint: UncheckedAccount<'info>,
+
+    /// Treasury wallet that owns the treasury_account
+    /// CHECK: Used to derive ATA
+    pub treasury_wallet: UncheckedAccount<'info>,
+
+    /// Treasury token account (ATA - must exist; create via ensure_distribution_atas, holds RIFT tokens)
+    /// CHECK: Validated in handler (validate_fee_recipient) - owner treasury_wallet, RIFT mint
+    #[account(mut)]
+    pub treasury_account: UncheckedAccount<'info>,
+
+    /// Partner wallet that owns the partner_account (optional)
+    /// CHECK: Used to derive ATA. If a partner is configured in `rift.partner_wallet`,
+    /// this account MUST correspond to the same pubkey and its ATA must exist when
+    /// partner_amount > 0. The protocol assumes the partner ATA is pre-initialized
+    /// by either the partner or the admin flows.
+    pub partner_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Partner account (ATA - must exist). Initialize it ahead of time via
+    /// `ensure_distribution_atas` so the distribution path never creates accounts.
+    /// CHECK: Validated in handler (validate_fee_recipient) - owner partner_wallet, RIFT mint
+    #[account(mut)]
+    pub partner_account: Option<UncheckedAccount<'info>>,
+
+    /// Protocol config PDA - if initialized, its distribution_fee_bps is taken before the split
+    /// CHECK: Address fixed by seeds; may be uninitialized (no protocol cut)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// Protocol fee token account (required when the protocol cut is non-zero)
+    /// CHECK: Validated in handler - owner must be GlobalConfig.protocol_fee_recipient, mint must match
+    #[account(mut)]
+    pub protocol_fee_account: Option<UncheckedAccount<'info>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// **FIX CRITICAL #39**: Constrain token_program to Token-2022 only
+    /// Prevents malicious program from faking withheld vault distributions and draining funds
+    #[account(
+        constraint = token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// **TOKEN-2022**: Account struct for admin claiming withheld transfer fees
+#[derive(Accounts)]
+pub struct AdminClaimWithheldFees<'info> {
+    /// **WITHHELD AUTHORITY FIX**: Must be treasury_wallet (withdraw_withheld_authority)
+    /// The treasury_wallet is set as withdraw_withheld_authority during mint creation
+    pub treasury_signer: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// RIFT mint (Token-2022 with transfer fee extension)
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: InterfaceAccount<'info, Mint>,
+
+    /// **FEE ROUTING**: Withheld vault to receive withheld transfer fees (RIFT tokens)
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump,
+        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault,
+        constraint = withheld_vault.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Source account with withheld fees to claim
+    #[account(
+        mut,
+        constraint = source_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub source_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// **FIX MEDIUM #45**: Constrain token_program for defense-in-depth
+    /// Currently unused (handler uses hardcoded spl_token_2022::ID), but constraint
+    /// prevents future refactoring from introducing vulnerability
+    #[account(
+        constraint = token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// **TOKEN-2022**: Account struct for harvesting withheld fees directly to treasury
+#[derive(Accounts)]
+pub struct ClaimWithheldFeesDirect<'info> {
+    /// Must be treasury_wallet (withdraw_withheld_authority)
+    pub treasury_signer: Signer<'info>,
+
+    pub rift: Account<'info, Rift>,
+
+    /// RIFT mint (Token-2022 with transfer fee extension)
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: InterfaceAccount<'info, Mint>,
+
+    /// Treasury's RIFT ATA (validated in handler)
+    #[account(
+        mut,
+        constraint = treasury_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub treasury_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Source account with withheld fees to claim
+    #[account(
+        mut,
+        constraint = source_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub source_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Protocol config PDA - direct claims require a zero protocol cut
+    /// CHECK: Address fixed by seeds; may be uninitialized (no protocol cut)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// **TOKEN-2022**: Account struct for admin setting transfer fee
+#[derive(Accounts)]
+pub struct AdminSetTransferFee<'info> {
+    /// Must be PROGRAM_AUTHORITY (transfer_fee_config_authority)
+    pub program_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// RIFT mint (Token-2022 with transfer fee extension)
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: InterfaceAccount<'info, Mint>,
 
-    /// CHECK: Manually initialized as Token-2022 with transfer fee extension
+    /// **FIX MEDIUM #45**: Constrain token_program for defense-in-depth
+    /// Currently unused (handler uses hardcoded spl_token_2022::ID), but constraint
+    /// prevents future refactoring from introducing vulnerability
     #[account(
-        mut,
-        seeds = [b"rift_mint", underlying_mint.key().as_ref(), creator.key().as_ref()],
-        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
-        bump
+        constraint = token_program.key() == spl_token_2022::ID
+            @ ErrorCode::InvalidProgramId
     )]
-    pub rift_mint: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
 
-    /// CHECK: PDA for mint authority
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        seeds = [b"global_config"],
         bump
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+    pub global_config: UncheckedAccount<'info>,
+}
 
-    /// **ATOMIC INIT**: Vault token account (initialized during create_rift)
-    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+/// **FEE MANAGEMENT**: Account struct for admin withdrawing fees from fees_vault
+#[derive(Accounts)]
+pub struct AdminWithdrawFeesVault<'info> {
+    /// Must be PROGRAM_AUTHORITY
+    pub program_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Underlying mint (the original token being wrapped)
     #[account(
-        mut,
-        seeds = [b"vault", rift.key().as_ref()],
-        bump
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
     )]
-    pub vault: UncheckedAccount<'info>,
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
 
-    /// **ATOMIC INIT**: Fees vault token account (initialized during create_rift)
-    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+    /// Fees vault containing collected wrap/unwrap fees (underlying tokens)
     #[account(
         mut,
         seeds = [b"fees_vault", rift.key().as_ref()],
-        bump
+        bump,
+        constraint = fees_vault.key() == rift.fees_vault @ ErrorCode::InvalidVault,
+        constraint = fees_vault.mint == rift.underlying_mint @ ErrorCode::InvalidMint
     )]
-    pub fees_vault: UncheckedAccount<'info>,
+    pub fees_vault: InterfaceAccount<'info, TokenAccount>,
 
-    /// **ATOMIC INIT**: Withheld vault token account (initialized during create_rift)
-    /// CHECK: Manually initialized in handler with proper Token-2022 extension sizing
+    /// Treasury account to receive fees (underlying tokens)
     #[account(
         mut,
-        seeds = [b"withheld_vault", rift.key().as_ref()],
-        bump
+        constraint = treasury_account.mint == rift.underlying_mint @ ErrorCode::InvalidMint
     )]
-    pub withheld_vault: UncheckedAccount<'info>,
+    pub treasury_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: PDA for vault authority (controls all vault transfers)
+    /// CHECK: Vault authority PDA - signs transfers from fees_vault
     #[account(
         seeds = [b"vault_auth", rift.key().as_ref()],
         bump
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
+    /// **FIX HIGH #41**: Constrain token_program to SPL Token or Token-2022 only
+    /// Defense-in-depth: Even though admin-only, prevent admin error or compromised key from using malicious program
     #[account(
-        constraint = token_program.key() == anchor_spl::token_2022::ID
+        constraint = token_program.key() == anchor_spl::token::ID
+            || token_program.key() == spl_token_2022::ID
             @ ErrorCode::InvalidProgramId
     )]
     pub token_program: Interface<'info, TokenInterface>,
 
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-
-    /// CHECK: Validated in handler - must match underlying_mint.owner
-    pub underlying_token_program: UncheckedAccount<'info>,
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
 }
 
+/// **FEE MANAGEMENT**: Account struct for admin withdrawing withheld fees from withheld_vault
 #[derive(Accounts)]
-pub struct InitializeVault<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct AdminWithdrawWithheldVault<'info> {
+    /// Must be PROGRAM_AUTHORITY
+    pub program_authority: Signer<'info>,
 
     #[account(mut)]
     pub rift: Account<'info, Rift>,
 
-    /// Vault token account
+    /// RIFT mint (Token-2022 with transfer fee extension)
     #[account(
-        init,
-        payer = user,
-        token::mint = underlying_mint,
-        token::authority = vault_authority,
-        seeds = [b"vault", rift.key().as_ref()],
-        bump
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
-    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub rift_mint: InterfaceAccount<'info, Mint>,
 
-    /// CHECK: Mint validated by vault init constraint above (token::mint = underlying_mint)
-    #[account(constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint)]
-    pub underlying_mint: UncheckedAccount<'info>,
+    /// Withheld vault containing collected withheld transfer fees (RIFT tokens)
+    #[account(
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump,
+        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault,
+        constraint = withheld_vault.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: Vault authority PDA - controls vault token transfers
+    /// Treasury RIFT token account to receive fees (RIFT tokens)
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        mut,
+        constraint = treasury_rift_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
     )]
-    pub vault_authority: UncheckedAccount<'info>,
+    pub treasury_rift_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// CHECK: Mint authority PDA - controls RIFT token minting
+    /// CHECK: Vault authority PDA - signs transfers from withheld_vault
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        seeds = [b"vault_auth", rift.key().as_ref()],
         bump
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+    pub vault_authority: UncheckedAccount<'info>,
 
-    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
+    /// **FIX HIGH #42**: Constrain token_program to Token-2022 only (withheld_vault holds RIFT tokens)
+    /// Defense-in-depth: Even though admin-only, prevent admin error or compromised key from using malicious program
     #[account(
-        constraint = token_program.key() == anchor_spl::token::ID
-            || token_program.key() == anchor_spl::token_2022::ID
+        constraint = token_program.key() == spl_token_2022::ID
             @ ErrorCode::InvalidProgramId
     )]
     pub token_program: Interface<'info, TokenInterface>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
 }
 
+/// **SECURITY FIX #50**: Account struct for setting oracle addresses
 #[derive(Accounts)]
-pub struct InitializeFeesVault<'info> {
+pub struct SetOracleAccounts<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub creator: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
     pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for selecting the rift's oracle source
+#[derive(Accounts)]
+pub struct SetOracleSource<'info> {
+    pub creator: Signer<'info>,
 
-    /// Fees vault token account (holds collected wrap/unwrap fees)
-    /// **FIX CRITICAL #19**: Manual initialization with proper Token-2022 extension sizing
-    /// CHECK: Manually initialized in handler with proper space calculation based on token program
     #[account(
         mut,
-        seeds = [b"fees_vault", rift.key().as_ref()],
-        bump
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub fees_vault: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
+}
 
-    /// CHECK: Mint validated by fees_vault init constraint
-    #[account(constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint)]
-    pub underlying_mint: UncheckedAccount<'info>,
+/// **FIX ISSUE #5**: Account struct for proposing oracle change
+#[derive(Accounts)]
+pub struct ProposeOracleChange<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
 
-    /// CHECK: Vault authority PDA - controls fees vault transfers
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub vault_authority: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for proposing a creator transfer (current creator signs)
+#[derive(Accounts)]
+pub struct ProposeCreatorTransfer<'info> {
+    pub creator: Signer<'info>,
 
-    /// **FIX CRITICAL #34**: Constrain token_program to SPL Token or Token-2022 only
     #[account(
-        constraint = token_program.key() == anchor_spl::token::ID
-            || token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub token_program: Interface<'info, TokenInterface>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    pub rift: Account<'info, Rift>,
 }
 
+/// Account struct for accepting a creator transfer (pending creator signs)
 #[derive(Accounts)]
-pub struct InitializeWithheldVault<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct AcceptCreatorTransfer<'info> {
+    pub new_creator: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = rift.pending_creator == Some(new_creator.key()) @ ErrorCode::Unauthorized
+    )]
     pub rift: Account<'info, Rift>,
+}
+
+/// **FIX ISSUE #5 + FIX INFO #2 (Audit)**: Account struct for executing oracle change
+/// Only creator can execute to prevent griefing/front-running
+#[derive(Accounts)]
+pub struct ExecuteOracleChange<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
 
-    /// Withheld vault token account (holds collected SPL Token-2022 withheld fees - RIFT tokens)
-    /// **FIX CRITICAL #20**: Manual initialization with proper Token-2022 extension sizing
-    /// CHECK: Manually initialized in handler with proper space for TransferFeeAmount extension
     #[account(
         mut,
-        seeds = [b"withheld_vault", rift.key().as_ref()],
-        bump
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub withheld_vault: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
+}
 
-    /// CHECK: RIFT mint validated by withheld_vault init constraint
-    #[account(constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint)]
-    pub rift_mint: UncheckedAccount<'info>,
+/// **FIX ISSUE #5**: Account struct for cancelling oracle change
+#[derive(Accounts)]
+pub struct CancelOracleChange<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
 
-    /// CHECK: Vault authority PDA - controls withheld vault transfers
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub vault_authority: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for configuring the backing ratio circuit breaker
+#[derive(Accounts)]
+pub struct SetMinBackingRatio<'info> {
+    pub creator: Signer<'info>,
 
-    /// **FIX CRITICAL #35**: Constrain token_program to Token-2022 only (RIFT mint is always Token-2022)
     #[account(
-        constraint = token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub token_program: Interface<'info, TokenInterface>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+    pub rift: Account<'info, Rift>,
 }
 
+/// Read-only account struct for the full-exit preflight check
 #[derive(Accounts)]
-pub struct WrapTokens<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct CanFullyExit<'info> {
+    /// CHECK: Only used as the expected owner of user_rift_tokens
+    pub user: UncheckedAccount<'info>,
 
-    #[account(mut)]
     pub rift: Account<'info, Rift>,
 
-    /// **SECURITY FIX #49**: User's underlying token account - validated manually in handler
-    /// CHECK: Token account validation performed manually to reduce stack usage
-    #[account(mut)]
-    pub user_underlying: UncheckedAccount<'info>,
-
-    /// **SECURITY FIX #49**: User's RIFT token account - validated manually in handler
-    /// CHECK: Token account validation performed manually to reduce stack usage
-    #[account(mut)]
+    /// CHECK: Token account validation performed manually in handler
     pub user_rift_tokens: UncheckedAccount<'info>,
 
-    /// **CRITICAL FIX - HIGH ISSUE #3**: Vault account type must support .amount and .reload()
-    /// Changed from UncheckedAccount to InterfaceAccount<TokenAccount> to fix compilation error
     #[account(
-        mut,
         seeds = [b"vault", rift.key().as_ref()],
         bump,
         constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
 
-    /// **TOKEN-2022 FIX**: Underlying mint required for transfer_checked
-    /// CHECK: Validated against rift.underlying_mint
-    #[account(
-        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
-    )]
+    /// Optional fee exemption for `user` (PDA ["fee_exempt", rift, user]); omit if none
+    #[account(
+        seeds = [b"fee_exempt", rift.key().as_ref(), user.key().as_ref()],
+        bump = fee_exemption.bump,
+        constraint = fee_exemption.rift == rift.key()
+            && fee_exemption.wallet == user.key() @ ErrorCode::InvalidFeeExemption
+    )]
+    pub fee_exemption: Option<Box<Account<'info, FeeExemption>>>,
+}
+
+/// Read-only account struct for get_rift_state
+#[derive(Accounts)]
+pub struct GetRiftState<'info> {
+    pub rift: Account<'info, Rift>,
+}
+
+/// Read-only account struct for can_close (pass system_program for vaults never initialized)
+#[derive(Accounts)]
+pub struct CanClose<'info> {
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: Validated against rift.vault in handler (skipped when uninitialized)
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against rift.fees_vault in handler (skipped when uninitialized)
+    pub fees_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against rift.withheld_vault in handler (skipped when uninitialized)
+    pub withheld_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against rift.wrap_fees_vault in handler (required when set)
+    pub wrap_fees_vault: Option<UncheckedAccount<'info>>,
+}
+
+/// Read-only account struct for inspect_underlying (same policy PDAs as create_rift)
+#[derive(Accounts)]
+pub struct InspectUnderlying<'info> {
+    /// CHECK: Owner and layout validated in handler (SPL Token or Token-2022 mint)
     pub underlying_mint: UncheckedAccount<'info>,
 
-    /// **SECURITY FIX #49**: Validate rift mint matches rift state
-    /// CHECK: Pubkey validated against rift.rift_mint; Token program validates it's a valid mint during CPI
+    /// CHECK: Address fixed by seeds; may be uninitialized (denylist mode applies)
     #[account(
-        mut,
-        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+        seeds = [b"global_config"],
+        bump
     )]
-    pub rift_mint: UncheckedAccount<'info>,
+    pub global_config: UncheckedAccount<'info>,
 
-    /// CHECK: PDA
+    /// CHECK: Address fixed by seeds; may be uninitialized (no policy for this mint)
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        seeds = [b"mint_policy", underlying_mint.key().as_ref()],
         bump
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+    pub mint_policy: UncheckedAccount<'info>,
 
-    /// Fees vault to collect wrap fees (underlying tokens)
-    /// CHECK: Optional - validated manually in handler. If not initialized (system_program::ID), fees stay in vault
+    /// CHECK: Address fixed by seeds; may be uninitialized (not a RIFT mint)
     #[account(
-        mut,
-        seeds = [b"fees_vault", rift.key().as_ref()],
+        seeds = [b"rift_mint_registry", underlying_mint.key().as_ref()],
         bump
     )]
+    pub underlying_registry: UncheckedAccount<'info>,
+}
+
+/// Read-only account struct for check_solvency (pass system_program for vaults never initialized)
+#[derive(Accounts)]
+pub struct CheckSolvency<'info> {
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: Validated against rift.vault in handler (skipped when uninitialized)
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against rift.fees_vault in handler (skipped when uninitialized)
     pub fees_vault: UncheckedAccount<'info>,
 
-    /// CHECK: Vault authority PDA - signs transfers to fees_vault
+    /// CHECK: Validated against rift.withheld_vault in handler (skipped when uninitialized)
+    pub withheld_vault: UncheckedAccount<'info>,
+}
+
+/// Read-only account struct for quote_twap
+#[derive(Accounts)]
+pub struct QuoteTwap<'info> {
+    pub rift: Account<'info, Rift>,
+
+    /// Oracle history PDA (required once the rift migrated to one, see migrate_oracle_history)
+    pub oracle_history: Option<Account<'info, OracleHistory>>,
+}
+
+/// Account struct for updating the partner/treasury fee split
+#[derive(Accounts)]
+pub struct SetPartnerFeeBps<'info> {
+    /// Creator or program authority (validated in handler against GlobalConfig)
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
+        seeds = [b"global_config"],
         bump
     )]
-    pub vault_authority: UncheckedAccount<'info>,
+    pub global_config: UncheckedAccount<'info>,
+}
 
-    // **FIX CRITICAL #27**: Support different token programs for underlying and RIFT
-    // Underlying can be SPL Token or Token-2022
-    #[account(
-        constraint = underlying_token_program.key() == anchor_spl::token::ID
-            || underlying_token_program.key() == anchor_spl::token_2022::ID
-            @ ErrorCode::InvalidProgramId
-    )]
-    pub underlying_token_program: Interface<'info, TokenInterface>,
+/// Account struct for keeper-triggered daily snapshots
+#[derive(Accounts)]
+pub struct EmitDailySnapshot<'info> {
+    pub keeper: Signer<'info>,
 
-    // RIFT mint is always Token-2022 (enforced at creation)
-    /// **FIX CRITICAL #36**: Constrain rift_token_program to Token-2022 only
-    /// Prevents malicious program from faking mint operations or using PDA signer to mint unauthorized tokens
-    #[account(
-        constraint = rift_token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
-    )]
-    pub rift_token_program: Interface<'info, TokenInterface>,
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
 
-    pub system_program: Program<'info, System>,
+    /// Oracle history PDA (required once the rift migrated to one, see migrate_oracle_history)
+    pub oracle_history: Option<Account<'info, OracleHistory>>,
 }
 
-// NOTE: underlying_mint validation removed to reduce stack size
-// Security is maintained via vault.mint == rift.underlying_mint constraint above
+/// Account struct for configuring the oracle trust window
+#[derive(Accounts)]
+pub struct SetMaxOracleTrustAge<'info> {
+    pub creator: Signer<'info>,
 
-/// Account struct for simple vault-based unwrap
-/// **SECURITY FIX #49**: Stack optimization - uses UncheckedAccount with manual validation
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for pre-creating distribution ATAs (treasury + partner)
 #[derive(Accounts)]
-pub struct UnwrapFromVault<'info> {
+pub struct EnsureDistributionAtas<'info> {
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub payer: Signer<'info>,
 
-    #[account(mut)]
     pub rift: Account<'info, Rift>,
 
-    /// **SECURITY FIX #49**: User's underlying token account - validated manually in handler
-    /// CHECK: Token account validation performed manually to reduce stack usage
+    /// CHECK: Validated in handler - must be rift.underlying_mint or rift.rift_mint
+    pub mint: UncheckedAccount<'info>,
+
+    /// CHECK: Validated in handler against rift.treasury_wallet
+    pub treasury_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: ATA derivation validated in handler; created idempotently
     #[account(mut)]
-    pub user_underlying: UncheckedAccount<'info>,
+    pub treasury_ata: UncheckedAccount<'info>,
 
-    /// **SECURITY FIX #49**: User's RIFT token account - validated manually in handler
-    /// CHECK: Token account validation performed manually to reduce stack usage
+    /// CHECK: Validated in handler against rift.partner_wallet
+    pub partner_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: ATA derivation validated in handler; created idempotently
     #[account(mut)]
-    pub user_rift_tokens: UncheckedAccount<'info>,
+    pub partner_ata: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+            || token_program.key() == anchor_spl::token_2022::ID
+            @ ErrorCode::InvalidProgramId
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Account struct for switching the wrap fee model
+#[derive(Accounts)]
+pub struct SetFeesToBacking<'info> {
+    pub creator: Signer<'info>,
 
-    /// **CRITICAL FIX - HIGH ISSUE #3**: Vault account type must support .amount and .reload()
-    /// Changed from UncheckedAccount to InterfaceAccount<TokenAccount> to fix compilation error
     #[account(
         mut,
-        seeds = [b"vault", rift.key().as_ref()],
-        bump,
-        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for toggling same-slot wrap/unwrap protection
+#[derive(Accounts)]
+pub struct SetSameSlotProtection<'info> {
+    pub creator: Signer<'info>,
 
-    /// **TOKEN-2022 FIX**: Underlying mint required for transfer_checked
-    /// CHECK: Validated against rift.underlying_mint
     #[account(
-        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub underlying_mint: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
+}
 
-    /// Vault authority PDA (owns the vault, signs transfers from vault)
-    /// CHECK: PDA
+/// Account struct for draining every fee vault ahead of close_rift
+/// Vaults are optional only while uninitialized; recipient accounts are needed for non-zero shares
+#[derive(Accounts)]
+pub struct DrainAndPrepareClose<'info> {
+    /// Creator, partner, treasury, or program authority (validated in handler)
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: PDA validated by seeds - signs transfers from both fee vaults
     #[account(
         seeds = [b"vault_auth", rift.key().as_ref()],
         bump
     )]
     pub vault_authority: UncheckedAccount<'info>,
 
-    /// Rift mint authority PDA (controls RIFT token minting/burning)
-    /// CHECK: PDA
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+    pub underlying_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// **SECURITY FIX #49**: RIFT mint (for burning)
-    /// CHECK: Pubkey validated against rift.rift_mint; Token program validates it's a valid mint during CPI
+    /// CHECK: Must be the rift's mint; decimals parsed in handler
     #[account(
-        mut,
         constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
     pub rift_mint: UncheckedAccount<'info>,
 
-    /// Fees vault to collect unwrap fees (underlying tokens)
-    /// CHECK: Optional - validated manually in handler. If not initialized (system_program::ID), fees stay in vault
+    /// Fees vault (underlying tokens) - required once initialized
     #[account(
         mut,
         seeds = [b"fees_vault", rift.key().as_ref()],
-        bump
+        bump,
+        constraint = fees_vault.key() == rift.fees_vault @ ErrorCode::InvalidFeesVault
     )]
-    pub fees_vault: UncheckedAccount<'info>,
+    pub fees_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    // **FIX CRITICAL #27**: Support different token programs for underlying and RIFT
-    // Underlying can be SPL Token or Token-2022
+    /// Withheld vault (RIFT tokens) - required once initialized
     #[account(
-        constraint = underlying_token_program.key() == anchor_spl::token::ID
-            || underlying_token_program.key() == anchor_spl::token_2022::ID
-            @ ErrorCode::InvalidProgramId
+        mut,
+        seeds = [b"withheld_vault", rift.key().as_ref()],
+        bump,
+        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidWithheldVault
     )]
-    pub underlying_token_program: Interface<'info, TokenInterface>,
+    pub withheld_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    // RIFT mint is always Token-2022 (enforced at creation)
-    /// **FIX CRITICAL #37**: Constrain rift_token_program to Token-2022 only
-    /// Prevents malicious program from faking burn operations and double-spending vault
+    /// CHECK: Wrap fees vault (underlying tokens) - required once initialized, validated against
+    /// rift.wrap_fees_vault in handler
     #[account(
-        constraint = rift_token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
+        mut,
+        seeds = [b"wrap_fees_vault", rift.key().as_ref()],
+        bump
     )]
-    pub rift_token_program: Interface<'info, TokenInterface>,
+    pub wrap_fees_vault: Option<UncheckedAccount<'info>>,
 
-    pub system_program: Program<'info, System>,
-}
+    /// CHECK: Validated in handler - owner must be treasury_wallet, mint must be underlying_mint
+    #[account(mut)]
+    pub treasury_underlying_account: UncheckedAccount<'info>,
 
-#[derive(Accounts)]
-pub struct AdminFixVaultConflict<'info> {
+    /// CHECK: Validated in handler - owner must be partner_wallet, mint must be underlying_mint
     #[account(mut)]
-    pub program_authority: Signer<'info>,
+    pub partner_underlying_account: Option<UncheckedAccount<'info>>,
 
+    /// CHECK: Validated in handler - owner must be GlobalConfig.protocol_fee_recipient
     #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    pub protocol_underlying_account: Option<UncheckedAccount<'info>>,
 
-    /// CHECK: Vault PDA that may have wrong owner
+    /// CHECK: Validated in handler - owner must be treasury_wallet, mint must be rift_mint
+    #[account(mut)]
+    pub treasury_rift_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated in handler - owner must be partner_wallet, mint must be rift_mint
+    #[account(mut)]
+    pub partner_rift_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated in handler - owner must be GlobalConfig.protocol_fee_recipient
+    #[account(mut)]
+    pub protocol_rift_account: Option<UncheckedAccount<'info>>,
+
+    /// Global treasury underlying account (required while rift.protocol_fees_owed is non-zero)
+    /// CHECK: Validated in handler - owner must be the global treasury wallet
+    #[account(mut)]
+    pub protocol_treasury_account: Option<UncheckedAccount<'info>>,
+
+    /// Protocol config PDA - protocol cut and authorities (both optional while uninitialized)
+    /// CHECK: Address fixed by seeds; may be uninitialized (no protocol cut, constants apply)
     #[account(
-        mut,
-        seeds = [b"vault", rift.key().as_ref()],
+        seeds = [b"global_config"],
         bump
     )]
-    pub vault: UncheckedAccount<'info>,
+    pub global_config: UncheckedAccount<'info>,
 
-    /// CHECK: Expected vault authority PDA
+    /// Program owning the underlying mint (SPL Token or Token-2022)
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        constraint = underlying_token_program.key() == *underlying_mint.to_account_info().owner
+            @ ErrorCode::InvalidProgramId
     )]
-    pub vault_authority: UncheckedAccount<'info>,
-}
-
-/// **SECURITY FIX #50**: Account struct for updating Switchboard oracle
-#[derive(Accounts)]
-pub struct UpdateSwitchboardOracle<'info> {
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
-
-    /// **SECURITY FIX #50**: Authority authorized to update oracle prices (creator or governance)
-    pub oracle_authority: Signer<'info>,
+    pub underlying_token_program: Interface<'info, TokenInterface>,
 
-    /// **SECURITY FIX #50**: Switchboard aggregator feed - validated against rift.switchboard_feed_account
-    /// CHECK: Validated in instruction handler against stored pubkey and Switchboard program ownership
-    pub switchboard_feed: UncheckedAccount<'info>,
+    /// Token-2022 program (required when the withheld vault holds RIFT)
+    pub token_2022_program: Option<Program<'info, Token2022>>,
 }
 
-/// Account struct for updating oracle with manual price data (Jupiter API, etc.)
+/// Account struct for phase 1 of the two-phase close
 #[derive(Accounts)]
-pub struct UpdateManualOracle<'info> {
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+pub struct BeginClose<'info> {
+    pub creator: Signer<'info>,
 
-    /// Authority authorized to update oracle prices (must be creator)
-    pub oracle_authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = creator @ ErrorCode::UnauthorizedClose
+    )]
+    pub rift: Account<'info, Rift>,
 }
 
+/// Account struct for starting an unwrap-only wind-down
 #[derive(Accounts)]
-pub struct TriggerRebalance<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct BeginWindDown<'info> {
+    pub creator: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
     pub rift: Account<'info, Rift>,
 }
 
-/// Optimized fee distribution context - essential accounts only
+/// Account struct for switching oracle aggregation between mean and median
 #[derive(Accounts)]
-/// **FIX CRITICAL #12**: CloseRift now requires ALL vaults to prevent fund loss
-pub struct CloseRift<'info> {
-    #[account(mut)]
+pub struct SetUseMedianOracle<'info> {
     pub creator: Signer<'info>,
 
     #[account(
         mut,
-        close = creator,
-        has_one = creator @ ErrorCode::UnauthorizedClose
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
     pub rift: Account<'info, Rift>,
+}
 
-    /// **FIX CRITICAL #27**: Make vault optional - may not be initialized if rift never used
-    /// CHECK: If initialized, validated against rift.vault. Manual check in handler.
+/// Account struct for creating the protocol config PDA
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
     #[account(mut)]
-    pub vault: UncheckedAccount<'info>,
+    pub program_authority: Signer<'info>,
 
-    /// **FIX CRITICAL #27**: Make fees_vault optional - may be system_program::ID if never initialized
-    /// CHECK: If initialized, validated by seeds and balance check in function
-    #[account(mut)]
-    pub fees_vault: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = program_authority,
+        space = GlobalConfig::SIZE,
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
 
-    /// **FIX CRITICAL #27**: Make withheld_vault optional - may be system_program::ID if never initialized
-    /// CHECK: If initialized, validated by seeds and balance check in function
-    #[account(mut)]
-    pub withheld_vault: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Account struct for rotating the protocol authorities (2-of-2)
+#[derive(Accounts)]
+pub struct UpdateGlobalAuthority<'info> {
+    /// Current GlobalConfig.program_authority
+    pub admin_authority_1: Signer<'info>,
+
+    /// Current GlobalConfig.admin_authority_2
+    pub admin_authority_2: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
 }
 
+/// Account struct for updating the protocol distribution fee
 #[derive(Accounts)]
-pub struct AdminCloseRift<'info> {
-    #[account(mut)]
+pub struct SetDistributionFee<'info> {
     pub program_authority: Signer<'info>,
 
     #[account(
         mut,
-        close = program_authority
+        seeds = [b"global_config"],
+        bump = global_config.bump
     )]
-    pub rift: Account<'info, Rift>,
+    pub global_config: Account<'info, GlobalConfig>,
 }
 
-/// **FIX HIGH #1**: Account struct for resetting stuck reentrancy guard
+/// Account struct for setting the protocol skim of wrap/unwrap volume
 #[derive(Accounts)]
-pub struct AdminResetReentrancyGuard<'info> {
-    /// Program authority (only one authorized to reset guard)
+pub struct SetProtocolFee<'info> {
     pub program_authority: Signer<'info>,
 
-    /// Rift with potentially stuck reentrancy guard
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
 }
 
+/// Account struct for switching the mint policy mode
 #[derive(Accounts)]
-pub struct AdminEmergencyWithdrawVault<'info> {
-    /// **SECURITY FIX #3**: First admin authority (PROGRAM_AUTHORITY)
-    #[account(mut)]
-    pub admin_authority_1: Signer<'info>,
-
-    /// **SECURITY FIX #3**: Second admin authority (ADMIN_AUTHORITY_2)
-    #[account(mut)]
-    pub admin_authority_2: Signer<'info>,
+pub struct SetMintPolicyMode<'info> {
+    pub program_authority: Signer<'info>,
 
-    /// **ACCOUNTING FIX**: Rift account to update accounting when withdrawing
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
 
-    /// Vault holding the underlying tokens
-    /// CHECK: Admin can specify any vault to recover from
+/// Account struct for allowing/denying an underlying mint
+#[derive(Accounts)]
+pub struct SetMintPolicy<'info> {
+    /// Program authority (validated in handler against GlobalConfig / PROGRAM_AUTHORITY)
     #[account(mut)]
-    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub program_authority: Signer<'info>,
 
-    /// Vault authority PDA - will be verified against closed_rift_pubkey parameter
-    /// CHECK: Admin provides this, function verifies it matches expected PDA
-    pub vault_authority: UncheckedAccount<'info>,
+    /// CHECK: Only its key seeds the policy PDA (the mint may be of either token program)
+    pub underlying_mint: UncheckedAccount<'info>,
 
-    /// **TOKEN-2022 FIX**: Underlying mint required for transfer_checked
-    /// CHECK: Validated against rift.underlying_mint
     #[account(
-        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+        init_if_needed,
+        payer = program_authority,
+        space = MintPolicy::SIZE,
+        seeds = [b"mint_policy", underlying_mint.key().as_ref()],
+        bump
     )]
-    pub underlying_mint: UncheckedAccount<'info>,
-
-    /// Admin's token account to receive withdrawn tokens
-    #[account(mut)]
-    pub admin_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub mint_policy: Account<'info, MintPolicy>,
 
-    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
     #[account(
-        constraint = token_program.key() == anchor_spl::token::ID
-            || token_program.key() == anchor_spl::token_2022::ID
-            @ ErrorCode::InvalidProgramId
+        seeds = [b"global_config"],
+        bump
     )]
-    pub token_program: Interface<'info, TokenInterface>,
+    pub global_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
+/// Account struct for renouncing the RIFT mint's configuration authorities
 #[derive(Accounts)]
-pub struct AdminUpdateRiftMetadata<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
+pub struct RenounceMintAuthorities<'info> {
+    /// Program authority - also the mint's transfer_fee_config_authority (validated in handler)
+    pub program_authority: Signer<'info>,
 
-    /// The rift account
+    #[account(mut)]
     pub rift: Account<'info, Rift>,
 
-    /// The rift mint to create metadata for
-    /// **SECURITY FIX**: Constrain to rift.rift_mint and verify mint authority
+    /// CHECK: Must be the rift's mint; Token-2022 validates authorities during CPI
     #[account(
         mut,
-        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint,
-        constraint = rift_mint.mint_authority.is_some() @ ErrorCode::InvalidMintAuthority,
-        constraint = rift_mint.mint_authority.unwrap() == rift_mint_authority.key() @ ErrorCode::InvalidMintAuthority
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
-    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
-    pub rift_mint: InterfaceAccount<'info, Mint>,
+    pub rift_mint: UncheckedAccount<'info>,
 
-    /// Rift mint authority PDA
+    /// Rift mint authority PDA (current metadata update authority)
     /// CHECK: Verified by seeds constraint
     #[account(
         seeds = [b"rift_mint_auth", rift.key().as_ref()],
@@ -5160,525 +12871,597 @@ pub struct AdminUpdateRiftMetadata<'info> {
     )]
     pub rift_mint_authority: UncheckedAccount<'info>,
 
-    pub system_program: Program<'info, System>,
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
 }
 
+/// Account struct for assigning the pause-only guardian
 #[derive(Accounts)]
-pub struct CleanupStuckAccounts<'info> {
-    /// The creator who originally tried to create the rift
-    /// **SECURITY FIX**: Require creator signature to prevent griefing
-    #[account(mut)]
-    pub creator: Signer<'info>,
+pub struct SetGuardian<'info> {
+    /// Program authority (validated in handler against GlobalConfig / PROGRAM_AUTHORITY)
+    pub program_authority: Signer<'info>,
 
-    /// The underlying mint that was used in the failed rift creation
-    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
-    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
 
-    /// The stuck rift mint account that needs to be cleaned up
-    /// **FIX HIGH #8**: Use UncheckedAccount to support Token-2022 mint closing via close_account
-    /// **FIX CRITICAL #14**: Use correct PDA seeds matching create_rift (underlying_mint, creator)
-    /// We close this account using Token-2022's close_account instruction
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
     #[account(
-        mut,
-        seeds = [b"rift_mint", underlying_mint.key().as_ref(), creator.key().as_ref()],
-        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        seeds = [b"global_config"],
         bump
     )]
-    pub stuck_rift_mint: UncheckedAccount<'info>,
+    pub global_config: UncheckedAccount<'info>,
+}
 
-    /// The expected rift account location (should be empty/non-existent)
-    /// CHECK: We verify this account is empty to ensure it's truly stuck
-    #[account(
-        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
-        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
-        bump
-    )]
-    pub expected_rift: UncheckedAccount<'info>,
+/// Account struct for tuning the per-rift reentrancy guard timeout
+#[derive(Accounts)]
+pub struct SetReentrancyTimeout<'info> {
+    /// Program authority (validated in handler against GlobalConfig / PROGRAM_AUTHORITY)
+    pub program_authority: Signer<'info>,
 
-    /// **FIX HIGH #8**: Add mint_authority PDA so we can sign close_account
-    /// Mint authority PDA - controls mint operations
-    /// CHECK: PDA verified by seeds
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
     #[account(
-        seeds = [b"rift_mint_auth", expected_rift.key().as_ref()],
+        seeds = [b"global_config"],
         bump
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
-
-    /// The account that will pay for the transaction (can be anyone)
-    #[account(mut)]
-    pub payer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-
-    /// **FIX HIGH #8**: Add Token-2022 program for close_account instruction
-    /// CHECK: Token-2022 program for closing mint account
-    #[account(address = spl_token_2022::ID)]
-    pub token_program: UncheckedAccount<'info>,
+    pub global_config: UncheckedAccount<'info>,
 }
 
-/// **FIX CRITICAL #10**: Struct for cleaning up stuck VANITY rift accounts
-/// Vanity rifts use different PDA seeds than regular rifts
+/// Account struct for retuning oracle cadence parameters
 #[derive(Accounts)]
-#[instruction(vanity_seed: [u8; 32], seed_len: u8)]
-pub struct CleanupStuckVanityAccounts<'info> {
-    /// The creator who originally tried to create the vanity rift
-    /// **SECURITY FIX**: Require creator signature to prevent griefing
-    #[account(mut)]
+pub struct SetOracleUpdateInterval<'info> {
     pub creator: Signer<'info>,
 
-    /// The underlying mint that was used in the failed vanity rift creation
-    // **TOKEN-2022 MIGRATION**: Use InterfaceAccount for token types
-    pub underlying_mint: InterfaceAccount<'info, Mint>,
-
-    /// The stuck VANITY rift mint account that needs to be cleaned up
-    /// **FIX CRITICAL #10**: Uses VANITY seeds (includes vanity_seed)
-    /// We close this account using Token-2022's close_account instruction
     #[account(
         mut,
-        seeds = [b"rift_mint", creator.key().as_ref(), underlying_mint.key().as_ref(), &vanity_seed[..seed_len as usize]],
-        bump
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub stuck_rift_mint: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
+}
 
-    /// The expected rift account location (should be empty/non-existent)
-    /// CHECK: We verify this account is empty to ensure it's truly stuck
-    /// **FIX CRITICAL #26**: Vanity rifts have DIFFERENT seeds than regular rifts!
-    #[account(
-        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref(), &vanity_seed[..seed_len as usize]],
-        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
-        bump
-    )]
-    pub expected_rift: UncheckedAccount<'info>,
+/// Account struct for configuring the deflationary-underlying safe mode
+#[derive(Accounts)]
+pub struct SetDeflationaryProtection<'info> {
+    pub creator: Signer<'info>,
 
-    /// **FIX CRITICAL #10**: Mint authority PDA - same for vanity and non-vanity
-    /// Mint authority PDA - controls mint operations
-    /// CHECK: PDA verified by seeds
     #[account(
-        seeds = [b"rift_mint_auth", expected_rift.key().as_ref()],
-        bump
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
-
-    /// The account that will pay for the transaction (can be anyone)
-    #[account(mut)]
-    pub payer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
-
-    /// **FIX CRITICAL #10**: Token-2022 program for close_account instruction
-    /// CHECK: Token-2022 program for closing vanity mint account
-    #[account(address = spl_token_2022::ID)]
-    pub token_program: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
 }
 
+/// Account struct for configuring the minimum fee floor
 #[derive(Accounts)]
-pub struct DistributeFeesFromVault<'info> {
-    /// Fee payer (anyone can call)
-    #[account(mut)]
-    pub payer: Signer<'info>,
+pub struct SetMinFeeUnits<'info> {
+    pub creator: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
     pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for configuring the unwrap auto-distribute threshold
+#[derive(Accounts)]
+pub struct SetAutoDistributeThreshold<'info> {
+    pub creator: Signer<'info>,
 
-    /// **FEE ROUTING UPDATE**: Fees vault holding collected wrap/unwrap fees (underlying tokens)
     #[account(
         mut,
-        seeds = [b"fees_vault", rift.key().as_ref()],
-        bump,
-        constraint = fees_vault.key() == rift.fees_vault @ ErrorCode::InvalidVault
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub fees_vault: InterfaceAccount<'info, TokenAccount>,
+    pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for configuring the keeper rebalance reward
+#[derive(Accounts)]
+pub struct SetRebalanceReward<'info> {
+    pub creator: Signer<'info>,
 
-    /// Vault authority PDA - signs transfers from fees_vault
-    /// CHECK: PDA validated by seeds
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub vault_authority: UncheckedAccount<'info>,
-
-    /// Underlying mint (to validate treasury and partner accounts)
-    /// CHECK: Manually validated in handler - owner must be Token program, deserializes as Mint, key matches rift.underlying_mint
-    pub underlying_mint: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
+}
 
-    /// Treasury wallet that owns the treasury_account
-    /// CHECK: Used to derive ATA
-    pub treasury_wallet: UncheckedAccount<'info>,
+/// Account struct for configuring the single-unwrap vault fraction cap
+#[derive(Accounts)]
+pub struct SetMaxUnwrapFraction<'info> {
+    pub creator: Signer<'info>,
 
-    /// Treasury token account (ATA - auto-created if needed)
-    /// CHECK: Validated in handler - ATA derivation checked manually due to underlying_mint being UncheckedAccount
-    #[account(mut)]
-    pub treasury_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
 
-    /// Partner wallet that owns the partner_account (optional)
-    /// CHECK: Used to derive ATA. If a partner is configured in `rift.partner_wallet`,
-    /// this account MUST correspond to the same pubkey and its ATA must exist when
-    /// partner_amount > 0. The protocol assumes the partner ATA is pre-initialized
-    /// by either the partner or the admin flows.
-    pub partner_wallet: Option<UncheckedAccount<'info>>,
+/// Account struct for configuring the Switchboard confidence floor
+#[derive(Accounts)]
+pub struct SetMinConfidence<'info> {
+    pub creator: Signer<'info>,
 
-    /// Partner account (ATA - currently auto-created if needed). In practice,
-    /// for production deployments the ATA should be initialized ahead of time
-    /// via a dedicated admin/init instruction and `init_if_needed` can be removed
-    /// to avoid race conditions and unexpected payer charges.
-    /// CHECK: Validated in handler - ATA derivation checked manually due to underlying_mint being UncheckedAccount
-    #[account(mut)]
-    pub partner_account: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
 
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+/// Account struct for configuring the wrap/unwrap oracle freshness policy
+#[derive(Accounts)]
+pub struct SetOracleFreshnessPolicy<'info> {
+    pub creator: Signer<'info>,
 
-    // **CRITICAL FIX #1**: Constrain token_program to only accept SPL Token or Token-2022
     #[account(
-        constraint = token_program.key() == anchor_spl::token::ID
-            || token_program.key() == anchor_spl::token_2022::ID
-            @ ErrorCode::InvalidProgramId
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub token_program: Interface<'info, TokenInterface>,
+    pub rift: Account<'info, Rift>,
 }
 
+/// Account struct for configuring the per-user wrap cooldown
 #[derive(Accounts)]
-pub struct UpdateTreasuryWallet<'info> {
-    /// Rift creator (admin)
+pub struct SetWrapCooldown<'info> {
     pub creator: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
     pub rift: Account<'info, Rift>,
 }
 
-// REMOVED: AdminWithdrawFeeCollector - obsolete struct for removed fee_collector program
-
-/// **TOKEN-2022**: Account struct for claiming withheld transfer fees (non-admin)
-/// Treasury wallet (per-rift) can call this
+/// Account struct for configuring per-transaction wrap limits
 #[derive(Accounts)]
-pub struct ClaimWithheldFees<'info> {
-    /// **PER-RIFT TREASURY FIX**: Treasury wallet must match rift.treasury_wallet
-    /// Authorization check is done in the function handler to use per-rift treasury
-    pub treasury_signer: Signer<'info>,
-
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+pub struct SetWrapLimits<'info> {
+    pub creator: Signer<'info>,
 
-    /// RIFT mint (Token-2022 with transfer fee extension)
     #[account(
         mut,
-        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub rift_mint: InterfaceAccount<'info, Mint>,
+    pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for configuring volume-tiered fees
+#[derive(Accounts)]
+pub struct ConfigureFeeTiers<'info> {
+    pub creator: Signer<'info>,
 
-    /// Withheld vault to receive withheld transfer fees (RIFT tokens)
     #[account(
         mut,
-        seeds = [b"withheld_vault", rift.key().as_ref()],
-        bump,
-        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault,
-        constraint = withheld_vault.mint == rift.rift_mint @ ErrorCode::InvalidMint
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+    pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for whitelisting a fee-exempt wallet
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AddFeeExemption<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
 
-    /// Source account with withheld fees to claim
     #[account(
-        mut,
-        constraint = source_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub source_account: InterfaceAccount<'info, TokenAccount>,
+    pub rift: Account<'info, Rift>,
 
-    /// **FIX MEDIUM #45**: Constrain token_program for defense-in-depth
-    /// Currently unused (handler uses hardcoded spl_token_2022::ID), but constraint
-    /// prevents future refactoring from introducing vulnerability
     #[account(
-        constraint = token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
+        init,
+        payer = creator,
+        space = FeeExemption::SIZE,
+        seeds = [b"fee_exempt", rift.key().as_ref(), wallet.as_ref()],
+        bump
     )]
-    pub token_program: Interface<'info, TokenInterface>,
+    pub fee_exemption: Account<'info, FeeExemption>,
+
+    pub system_program: Program<'info, System>,
 }
 
-/// **FEE MANAGEMENT**: Account struct for distributing withheld fees
-/// Splits withheld_vault RIFT tokens to partner and treasury accounts
+/// Account struct for removing a fee exemption (rent returned to the creator)
 #[derive(Accounts)]
-pub struct DistributeWithheldVault<'info> {
-    /// Fee payer (creator or treasury_wallet)
+pub struct RemoveFeeExemption<'info> {
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub creator: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
     pub rift: Account<'info, Rift>,
 
-    /// Withheld vault holding collected transfer fees (RIFT tokens)
     #[account(
         mut,
-        seeds = [b"withheld_vault", rift.key().as_ref()],
-        bump,
-        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault
+        close = creator,
+        seeds = [b"fee_exempt", rift.key().as_ref(), fee_exemption.wallet.as_ref()],
+        bump = fee_exemption.bump,
+        constraint = fee_exemption.rift == rift.key() @ ErrorCode::InvalidFeeExemption
     )]
-    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+    pub fee_exemption: Account<'info, FeeExemption>,
+}
+
+/// Account struct for setting a creator's vanity rift limit
+#[derive(Accounts)]
+pub struct AdminSetCreatorRiftLimit<'info> {
+    /// Program authority (validated in handler against GlobalConfig / PROGRAM_AUTHORITY)
+    #[account(mut)]
+    pub program_authority: Signer<'info>,
+
+    /// CHECK: Only its key seeds the counter PDA
+    pub creator: UncheckedAccount<'info>,
 
-    /// Vault authority PDA - signs transfers from withheld_vault
-    /// CHECK: PDA validated by seeds
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
+        init_if_needed,
+        payer = program_authority,
+        space = CreatorRiftCount::SIZE,
+        seeds = [b"creator_count", creator.key().as_ref()],
         bump
     )]
-    pub vault_authority: UncheckedAccount<'info>,
-
-    /// RIFT mint (to validate treasury and partner accounts)
-    /// CHECK: Manually validated in handler - owner must be Token-2022 program, deserializes as Mint, key matches rift.rift_mint
-    pub rift_mint: UncheckedAccount<'info>,
+    pub creator_rift_count: Account<'info, CreatorRiftCount>,
 
-    /// Treasury wallet that owns the treasury_account
-    /// CHECK: Used to derive ATA
-    pub treasury_wallet: UncheckedAccount<'info>,
+    /// Protocol config PDA - if initialized, its authorities replace the hardcoded constants
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
+    #[account(
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: UncheckedAccount<'info>,
 
-    /// Treasury token account (ATA - auto-created if needed, holds RIFT tokens)
-    /// CHECK: Validated in handler - ATA derivation checked manually due to rift_mint being UncheckedAccount
-    #[account(mut)]
-    pub treasury_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// Partner wallet that owns the partner_account (optional)
-    /// CHECK: Used to derive ATA. If a partner is configured in `rift.partner_wallet`,
-    /// this account MUST correspond to the same pubkey and its ATA must exist when
-    /// partner_amount > 0. The protocol assumes the partner ATA is pre-initialized
-    /// by either the partner or the admin flows.
-    pub partner_wallet: Option<UncheckedAccount<'info>>,
+/// Account struct for the permissionless backing ratio refresh
+#[derive(Accounts)]
+pub struct RefreshBackingRatio<'info> {
+    pub caller: Signer<'info>,
 
-    /// Partner account (ATA - currently auto-created if needed). In practice,
-    /// for production deployments the ATA should be initialized ahead of time
-    /// via a dedicated admin/init instruction and `init_if_needed` can be removed
-    /// to avoid race conditions and unexpected payer charges.
-    /// CHECK: Validated in handler - ATA derivation checked manually due to rift_mint being UncheckedAccount
     #[account(mut)]
-    pub partner_account: Option<UncheckedAccount<'info>>,
-
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
+    pub rift: Account<'info, Rift>,
 
-    /// **FIX CRITICAL #39**: Constrain token_program to Token-2022 only
-    /// Prevents malicious program from faking withheld vault distributions and draining funds
     #[account(
-        constraint = token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
+        seeds = [b"vault", rift.key().as_ref()],
+        bump,
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault
     )]
-    pub token_program: Interface<'info, TokenInterface>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
 }
 
-/// **TOKEN-2022**: Account struct for admin claiming withheld transfer fees
+/// Account struct for the permissionless arbitrage metric refresh
 #[derive(Accounts)]
-pub struct AdminClaimWithheldFees<'info> {
-    /// **WITHHELD AUTHORITY FIX**: Must be treasury_wallet (withdraw_withheld_authority)
-    /// The treasury_wallet is set as withdraw_withheld_authority during mint creation
-    pub treasury_signer: Signer<'info>,
+pub struct UpdateArbitrageMetric<'info> {
+    pub caller: Signer<'info>,
 
     #[account(mut)]
     pub rift: Account<'info, Rift>,
 
-    /// RIFT mint (Token-2022 with transfer fee extension)
-    #[account(
-        mut,
-        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
-    )]
-    pub rift_mint: InterfaceAccount<'info, Mint>,
+    /// Oracle history PDA (required once the rift migrated to one, see migrate_oracle_history)
+    pub oracle_history: Option<Account<'info, OracleHistory>>,
+}
 
-    /// **FEE ROUTING**: Withheld vault to receive withheld transfer fees (RIFT tokens)
+/// Read-only account struct for get_protocol_authorities
+#[derive(Accounts)]
+pub struct GetProtocolAuthorities<'info> {
+    /// CHECK: Address fixed by seeds; may be uninitialized (constants apply)
     #[account(
-        mut,
-        seeds = [b"withheld_vault", rift.key().as_ref()],
-        bump,
-        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault,
-        constraint = withheld_vault.mint == rift.rift_mint @ ErrorCode::InvalidMint
+        seeds = [b"global_config"],
+        bump
     )]
-    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+    pub global_config: UncheckedAccount<'info>,
+}
 
-    /// Source account with withheld fees to claim
-    #[account(
-        mut,
-        constraint = source_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
-    )]
-    pub source_account: InterfaceAccount<'info, TokenAccount>,
+/// Read-only account struct for get_program_constants (no accounts needed)
+#[derive(Accounts)]
+pub struct GetProgramConstants {}
 
-    /// **FIX MEDIUM #45**: Constrain token_program for defense-in-depth
-    /// Currently unused (handler uses hardcoded spl_token_2022::ID), but constraint
-    /// prevents future refactoring from introducing vulnerability
-    #[account(
-        constraint = token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
-    )]
-    pub token_program: Interface<'info, TokenInterface>,
+/// Read-only account struct for get_authorities
+#[derive(Accounts)]
+pub struct GetAuthorities<'info> {
+    pub rift: Account<'info, Rift>,
 }
 
-/// **TOKEN-2022**: Account struct for admin setting transfer fee
+/// Read-only account struct for get_pdas
 #[derive(Accounts)]
-pub struct AdminSetTransferFee<'info> {
-    /// Must be PROGRAM_AUTHORITY (transfer_fee_config_authority)
-    pub program_authority: Signer<'info>,
+pub struct GetPdas<'info> {
+    pub rift: Account<'info, Rift>,
+}
 
-    #[account(mut)]
+/// Read-only account struct for get_oracle_history
+#[derive(Accounts)]
+pub struct GetOracleHistory<'info> {
     pub rift: Account<'info, Rift>,
 
-    /// RIFT mint (Token-2022 with transfer fee extension)
-    #[account(
-        mut,
-        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
-    )]
-    pub rift_mint: InterfaceAccount<'info, Mint>,
+    /// Oracle history PDA (required once the rift migrated to one, see migrate_oracle_history)
+    pub oracle_history: Option<Account<'info, OracleHistory>>,
+}
 
-    /// **FIX MEDIUM #45**: Constrain token_program for defense-in-depth
-    /// Currently unused (handler uses hardcoded spl_token_2022::ID), but constraint
-    /// prevents future refactoring from introducing vulnerability
-    #[account(
-        constraint = token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
-    )]
-    pub token_program: Interface<'info, TokenInterface>,
+/// Read-only account struct for sum_withheld_fees (token accounts passed as remaining_accounts)
+#[derive(Accounts)]
+pub struct SumWithheldFees<'info> {
+    pub rift: Account<'info, Rift>,
 }
 
-/// **FEE MANAGEMENT**: Account struct for admin withdrawing fees from fees_vault
+/// Read-only account struct for get_transfer_fee_disclosure
 #[derive(Accounts)]
-pub struct AdminWithdrawFeesVault<'info> {
-    /// Must be PROGRAM_AUTHORITY
-    pub program_authority: Signer<'info>,
+pub struct GetTransferFeeDisclosure<'info> {
+    pub rift: Account<'info, Rift>,
+}
 
-    #[account(mut)]
+/// Read-only account struct for get_transfer_fee
+#[derive(Accounts)]
+pub struct GetTransferFee<'info> {
     pub rift: Account<'info, Rift>,
 
-    /// Underlying mint (the original token being wrapped)
+    /// CHECK: Must be the rift's mint; TransferFeeConfig parsed in handler
     #[account(
-        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
-    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    pub rift_mint: UncheckedAccount<'info>,
+}
 
-    /// Fees vault containing collected wrap/unwrap fees (underlying tokens)
-    #[account(
-        mut,
-        seeds = [b"fees_vault", rift.key().as_ref()],
-        bump,
-        constraint = fees_vault.key() == rift.fees_vault @ ErrorCode::InvalidVault,
-        constraint = fees_vault.mint == rift.underlying_mint @ ErrorCode::InvalidMint
-    )]
-    pub fees_vault: InterfaceAccount<'info, TokenAccount>,
+/// Read-only account struct for quote_price
+#[derive(Accounts)]
+pub struct QuotePrice<'info> {
+    pub rift: Account<'info, Rift>,
 
-    /// Treasury account to receive fees (underlying tokens)
+    /// CHECK: Must be the rift's mint; TransferFeeConfig parsed in handler when requested
     #[account(
-        mut,
-        constraint = treasury_account.mint == rift.underlying_mint @ ErrorCode::InvalidMint
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
-    pub treasury_account: InterfaceAccount<'info, TokenAccount>,
+    pub rift_mint: UncheckedAccount<'info>,
 
-    /// CHECK: Vault authority PDA - signs transfers from fees_vault
+    /// CHECK: Must be the rift's underlying mint; TransferFeeConfig (if any) parsed in handler
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
     )]
-    pub vault_authority: UncheckedAccount<'info>,
+    pub underlying_mint: UncheckedAccount<'info>,
 
-    /// **FIX HIGH #41**: Constrain token_program to SPL Token or Token-2022 only
-    /// Defense-in-depth: Even though admin-only, prevent admin error or compromised key from using malicious program
-    #[account(
-        constraint = token_program.key() == anchor_spl::token::ID
-            || token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
-    )]
-    pub token_program: Interface<'info, TokenInterface>,
+    /// Oracle history PDA (required once the rift migrated to one, see migrate_oracle_history)
+    pub oracle_history: Option<Account<'info, OracleHistory>>,
 }
 
-/// **FEE MANAGEMENT**: Account struct for admin withdrawing withheld fees from withheld_vault
-#[derive(Accounts)]
-pub struct AdminWithdrawWithheldVault<'info> {
-    /// Must be PROGRAM_AUTHORITY
-    pub program_authority: Signer<'info>,
+/// Per-user activity for a rift (PDA ["activity", rift, user]) - wrap cooldown tracking
+#[account]
+pub struct UserRiftActivity {
+    pub rift: Pubkey,
+    pub user: Pubkey,
+    pub last_wrap_slot: u64,
+    pub bump: u8,
+}
 
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+impl UserRiftActivity {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 1;
+}
 
-    /// RIFT mint (Token-2022 with transfer fee extension)
-    #[account(
-        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
-    )]
-    pub rift_mint: InterfaceAccount<'info, Mint>,
+/// Allow/deny entry for an underlying mint (PDA ["mint_policy", underlying_mint])
+#[account]
+pub struct MintPolicy {
+    pub underlying_mint: Pubkey,
+    pub allowed: bool,
+    pub bump: u8,
+}
+
+impl MintPolicy {
+    pub const SIZE: usize = 8 + 32 + 1 + 1;
+}
+
+/// Configurable-length oracle ring buffer (PDA ["oracle_history", rift])
+/// Replaces the inline 10-slot oracle_prices for average/median/TWAP reads once migrated
+#[account]
+pub struct OracleHistory {
+    pub rift: Pubkey,
+    pub price_index: u16,       // Next slot to overwrite (the oldest sample once full)
+    pub prices: Vec<PriceData>, // Fixed length = capacity chosen at migration
+    pub bump: u8,
+}
+
+impl OracleHistory {
+    pub fn space(capacity: u16) -> usize {
+        8 + 32 + 2 + 4 + (8 + 8 + 8) * capacity as usize + 1
+    }
 
-    /// Withheld vault containing collected withheld transfer fees (RIFT tokens)
-    #[account(
-        mut,
-        seeds = [b"withheld_vault", rift.key().as_ref()],
-        bump,
-        constraint = withheld_vault.key() == rift.withheld_vault @ ErrorCode::InvalidVault,
-        constraint = withheld_vault.mint == rift.rift_mint @ ErrorCode::InvalidMint
-    )]
-    pub withheld_vault: InterfaceAccount<'info, TokenAccount>,
+    pub fn push(&mut self, price_data: PriceData) {
+        let capacity = self.prices.len();
+        if capacity == 0 {
+            return;
+        }
+        self.prices[self.price_index as usize % capacity] = price_data;
+        self.price_index = ((self.price_index as usize + 1) % capacity) as u16;
+    }
+}
 
-    /// Treasury RIFT token account to receive fees (RIFT tokens)
-    #[account(
-        mut,
-        constraint = treasury_rift_account.mint == rift.rift_mint @ ErrorCode::InvalidMint
-    )]
-    pub treasury_rift_account: InterfaceAccount<'info, TokenAccount>,
+/// Marks a RIFT mint (PDA ["rift_mint_registry", rift_mint]) - blocks unopted nested rifts
+#[account]
+pub struct RiftMintRegistry {
+    pub rift: Pubkey, // Rift that mints this RIFT token
+    pub bump: u8,
+}
 
-    /// CHECK: Vault authority PDA - signs transfers from withheld_vault
-    #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
-    )]
-    pub vault_authority: UncheckedAccount<'info>,
+impl RiftMintRegistry {
+    pub const SIZE: usize = 8 + 32 + 1;
 
-    /// **FIX HIGH #42**: Constrain token_program to Token-2022 only (withheld_vault holds RIFT tokens)
-    /// Defense-in-depth: Even though admin-only, prevent admin error or compromised key from using malicious program
-    #[account(
-        constraint = token_program.key() == spl_token_2022::ID
-            @ ErrorCode::InvalidProgramId
-    )]
-    pub token_program: Interface<'info, TokenInterface>,
+    /// True when the registry PDA for a mint has been created by this program
+    pub fn is_registered(registry_info: &AccountInfo) -> bool {
+        !registry_info.data_is_empty() && registry_info.owner == &crate::ID
+    }
 }
 
-/// **SECURITY FIX #50**: Account struct for setting oracle addresses
-#[derive(Accounts)]
-pub struct SetOracleAccounts<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+/// Vanity rifts created by a creator (PDA ["creator_count", creator]) - discovery spam cap
+#[account]
+pub struct CreatorRiftCount {
+    pub creator: Pubkey,
+    pub rift_count: u32, // Vanity rifts created so far (never decremented)
+    pub max_rifts: u32,  // Cap on rift_count, set by program authority
+    pub bump: u8,
+}
 
-    #[account(
-        mut,
-        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
-    )]
-    pub rift: Account<'info, Rift>,
+impl CreatorRiftCount {
+    pub const SIZE: usize = 8 + 32 + 4 + 4 + 1;
 }
 
-/// **FIX ISSUE #5**: Account struct for proposing oracle change
-#[derive(Accounts)]
-pub struct ProposeOracleChange<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+/// Fee-exempt wallet for a rift (PDA ["fee_exempt", rift, wallet]) - wraps/unwraps pay no fee
+#[account]
+pub struct FeeExemption {
+    pub rift: Pubkey,
+    pub wallet: Pubkey,
+    pub bump: u8,
+}
 
-    #[account(
-        mut,
-        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
-    )]
-    pub rift: Account<'info, Rift>,
+impl FeeExemption {
+    pub const SIZE: usize = 8 + 32 + 32 + 1;
 }
 
-/// **FIX ISSUE #5 + FIX INFO #2 (Audit)**: Account struct for executing oracle change
-/// Only creator can execute to prevent griefing/front-running
-#[derive(Accounts)]
-pub struct ExecuteOracleChange<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+/// Protocol-wide configuration singleton (PDA ["global_config"])
+#[account]
+pub struct GlobalConfig {
+    pub protocol_fee_recipient: Pubkey, // Wallet whose token accounts receive the protocol cut
+    pub distribution_fee_bps: u16,      // Protocol cut of each fee distribution (max 10%)
+    pub bump: u8,
+    pub program_authority: Pubkey, // Admin checks (replaces PROGRAM_AUTHORITY once initialized)
+    pub admin_authority_2: Pubkey, // Second 2-of-2 signer (replaces ADMIN_AUTHORITY_2)
+    pub treasury_wallet: Pubkey,   // Protocol treasury (replaces TREASURY_WALLET)
+    pub mint_policy_mode: u8,      // MINT_POLICY_DENYLIST (default) or MINT_POLICY_ALLOWLIST
+    pub protocol_fee_bps: u16,     // Skim of wrap/unwrap volume taken out of the fee (max 0.3%)
+}
 
-    #[account(
-        mut,
-        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
-    )]
-    pub rift: Account<'info, Rift>,
+impl GlobalConfig {
+    pub const SIZE: usize = 8 + 32 + 2 + 1 + 32 + 32 + 32 + 1 + 2;
+
+    /// Defaults for every field added after the original layout
+    /// Shared by initialize_global_config and migrate_global_config
+    pub fn apply_default_config(&mut self) {
+        // Seed authorities from the hardcoded constants; rotate later via update_global_authority
+        self.program_authority = Pubkey::from_str_const(PROGRAM_AUTHORITY);
+        self.admin_authority_2 = Pubkey::from_str_const(ADMIN_AUTHORITY_2);
+        self.treasury_wallet = Pubkey::from_str_const(TREASURY_WALLET);
+        // Permissionless by default; curated deployments switch via set_mint_policy_mode
+        self.mint_policy_mode = MINT_POLICY_DENYLIST;
+        // No protocol skim until set_protocol_fee_bps is called
+        self.protocol_fee_bps = 0;
+    }
+
+    /// Reject underlyings disallowed by the mint policy
+    /// Denylist (default, or config uninitialized): only a MintPolicy with allowed = false blocks.
+    /// Allowlist: a MintPolicy with allowed = true is required.
+    pub fn check_mint_policy(config_info: &AccountInfo, policy_info: &AccountInfo) -> Result<()> {
+        let mode = if config_info.data_is_empty() || config_info.owner != &crate::ID {
+            MINT_POLICY_DENYLIST
+        } else {
+            let config_data = config_info.try_borrow_data()?;
+            GlobalConfig::try_deserialize(&mut &config_data[..])?.mint_policy_mode
+        };
+        let allowed = if policy_info.data_is_empty() || policy_info.owner != &crate::ID {
+            None
+        } else {
+            let policy_data = policy_info.try_borrow_data()?;
+            Some(MintPolicy::try_deserialize(&mut &policy_data[..])?.allowed)
+        };
+        let permitted = if mode == MINT_POLICY_ALLOWLIST {
+            allowed == Some(true)
+        } else {
+            allowed != Some(false)
+        };
+        require!(permitted, ErrorCode::UnderlyingMintNotAllowed);
+        Ok(())
+    }
+
+    /// Protocol authorities for admin checks
+    /// Falls back to the hardcoded constants while the config PDA has not been initialized
+    pub fn load_authorities(config_info: &AccountInfo) -> Result<ProtocolAuthorities> {
+        let mut authorities = ProtocolAuthorities {
+            program_authority: Pubkey::from_str_const(PROGRAM_AUTHORITY),
+            admin_authority_2: Pubkey::from_str_const(ADMIN_AUTHORITY_2),
+            treasury_wallet: Pubkey::from_str_const(TREASURY_WALLET),
+            default_treasury: Pubkey::from_str_const(DEFAULT_TREASURY_WALLET),
+        };
+        if config_info.data_is_empty() || config_info.owner != &crate::ID {
+            return Ok(authorities);
+        }
+        let config_data = config_info.try_borrow_data()?;
+        let config = GlobalConfig::try_deserialize(&mut &config_data[..])?;
+        authorities.program_authority = config.program_authority;
+        authorities.admin_authority_2 = config.admin_authority_2;
+        authorities.treasury_wallet = config.treasury_wallet;
+        Ok(authorities)
+    }
+
+    /// Protocol cut of fee distributions in bps (0 while the config PDA has not been initialized)
+    pub fn load_distribution_fee_bps(config_info: &AccountInfo) -> Result<u16> {
+        if config_info.data_is_empty() || config_info.owner != &crate::ID {
+            return Ok(0);
+        }
+        let config_data = config_info.try_borrow_data()?;
+        Ok(GlobalConfig::try_deserialize(&mut &config_data[..])?.distribution_fee_bps)
+    }
+
+    /// Protocol skim of a wrap/unwrap: `volume` * protocol_fee_bps, capped at the fee it comes out of
+    /// Returns 0 while the config PDA has not been initialized
+    pub fn load_protocol_skim(config_info: &AccountInfo, volume: u64, fee: u64) -> Result<u64> {
+        if fee == 0 || config_info.data_is_empty() || config_info.owner != &crate::ID {
+            return Ok(0);
+        }
+        let config_data = config_info.try_borrow_data()?;
+        let config = GlobalConfig::try_deserialize(&mut &config_data[..])?;
+        let skim = u128::from(volume)
+            .checked_mul(u128::from(config.protocol_fee_bps))
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(u64::try_from(skim).map_err(|_| ErrorCode::MathOverflow)?.min(fee))
+    }
+
+    /// Protocol cut of a distribution and its recipient
+    /// Returns (0, default) while the config PDA has not been initialized
+    pub fn load_distribution_cut(config_info: &AccountInfo, amount: u64) -> Result<(u64, Pubkey)> {
+        if config_info.data_is_empty() || config_info.owner != &crate::ID {
+            return Ok((0, Pubkey::default()));
+        }
+        let config_data = config_info.try_borrow_data()?;
+        let config = GlobalConfig::try_deserialize(&mut &config_data[..])?;
+        let cut = u128::from(amount)
+            .checked_mul(u128::from(config.distribution_fee_bps))
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok((
+            u64::try_from(cut).map_err(|_| ErrorCode::MathOverflow)?,
+            config.protocol_fee_recipient,
+        ))
+    }
 }
 
-/// **FIX ISSUE #5**: Account struct for cancelling oracle change
-#[derive(Accounts)]
-pub struct CancelOracleChange<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+/// Amounts of one unwrap, computed by `Rift::plan_unwrap`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnwrapPlan {
+    pub fee_bps: u64,          // Unwrap fee rate applied
+    pub gross_underlying: u64, // Underlying leaving the vault (fee + payout)
+    pub fee: u64,              // Unwrap fee
+    pub net_underlying: u64,   // Underlying sent to the user
+    pub rift_burned: u64,      // RIFT burned (less than requested only on emergency unwraps)
+    pub backing_share: u64,    // Pro-rata share of backing_surplus included in gross_underlying
+}
 
-    #[account(
-        mut,
-        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
-    )]
-    pub rift: Account<'info, Rift>,
+/// Amounts of one fee distribution, computed by `Rift::plan_fee_split`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub protocol_amount: u64,           // Protocol cut (GlobalConfig.distribution_fee_bps)
+    pub protocol_fee_recipient: Pubkey, // Owner of the protocol fee token account
+    pub partner_amount: u64,            // Partner share (0 when partner == treasury)
+    pub treasury_amount: u64,           // Treasury share, including rounding remainder
 }
 
 #[account]
@@ -5721,7 +13504,7 @@ pub struct Rift {
     pub arbitrage_threshold_bps: u16,   // Threshold for arbitrage detection (basis points)
     pub last_oracle_update: i64,        // Last oracle price update
     // Advanced Metrics
-    pub total_volume_24h: u64,          // 24h trading volume
+    pub total_volume_24h: u64,          // Rolling 24h volume as of the last wrap/unwrap (see volume_buckets)
     pub price_deviation: u64,           // Current price deviation from backing
     pub arbitrage_opportunity_bps: u16, // Current arbitrage opportunity
     pub rebalance_count: u32,           // Total number of rebalances
@@ -5753,16 +13536,499 @@ pub struct Rift {
     pub oracle_change_pending: bool,
     pub pending_switchboard_account: Option<Pubkey>,
     pub oracle_change_timestamp: i64,
+
+    // Backing Ratio Circuit Breaker
+    pub min_backing_ratio_bps: u16, // Wraps rejected when vault backing falls below this (default 9500 = 95%)
+
+    // Token-2022 Transfer Fee Disclosure
+    pub initial_transfer_fee_bps: u16, // Transfer fee set at creation (immutable)
+    pub transfer_fee_bps: u16,         // Live transfer fee (updated by admin_set_transfer_fee)
+
+    // Keeper Metrics
+    pub last_snapshot_timestamp: i64, // Last DailySnapshot emission (rate limited to once per 24h)
+
+    // Oracle Trust Window
+    pub max_oracle_trust_age: i64, // Oracle older than this is ignored by rebalance logic (default 7 days)
+
+    // Fee Model
+    pub fees_to_backing: bool, // true = wrap fees stay in vault as extra backing (not counted as protocol fees)
+    pub backing_surplus: u64,  // Wrap fees retained under fees_to_backing, paid pro-rata on unwrap
+
+    // Interest-Bearing Underlying
+    pub interest_bearing: bool, // Underlying has InterestBearingConfig (interest is in its UI amount)
+
+    // Same-Slot Wrap/Unwrap Guard
+    pub last_action_slot: u64,      // Slot of the last wrap/unwrap on this rift
+    pub last_action_was_wrap: bool, // true if the last action in last_action_slot was a wrap
+    pub same_slot_protection: bool, // Opt-in: reject unwrap in the same slot as a wrap
+
+    // Two-Phase Close
+    pub closing: bool, // Set by begin_close - blocks new wraps, unwraps/distributions still allowed
+
+    // Pause
+    pub paused: bool, // Set by program authority - blocks wraps and unwraps
+
+    // Median Oracle
+    pub use_median_oracle: bool, // Rebalance on the median of fresh samples instead of the mean
+
+    // Oracle Freshness Heartbeat
+    pub require_fresh_oracle: bool, // Opt-in: wrap/unwrap require a recent oracle sample
+    pub max_oracle_age: i64,        // Max age (seconds) of the newest sample when required
+
+    // Per-User Wrap Cooldown
+    pub wrap_cooldown_slots: u64, // Min slots between wraps by the same user (0 = disabled)
+
+    // Per-Transaction Wrap Limits
+    pub min_wrap_amount: u64, // Min underlying actually received per wrap (0 = unbounded)
+    pub max_wrap_amount: u64, // Max requested underlying per wrap (0 = unbounded)
+
+    // Dynamic Volume-Tiered Fees
+    pub dynamic_fee_enabled: bool,  // Use fee_tiers (by total_volume_24h) instead of static wrap/unwrap fees
+    pub fee_tiers: [FeeTier; 4],    // Ascending volume thresholds
+
+    // Rolling 24h Volume Window
+    pub volume_buckets: [u64; 24], // Hourly volume, [23] = current hour, older hours to the left
+    pub volume_bucket_start: i64,  // Start (unix, hour-aligned) of the current bucket
+
+    // Legacy RIFT Mint
+    pub legacy_mint: bool, // RIFT mint is classic SPL Token (no transfer fee, no withheld vault)
+
+    // Reentrancy Guard Timeout
+    pub reentrancy_timeout_slots: u64, // Slots before a stuck guard auto-clears (150 - 432000)
+
+    // Guardian (pause-only role)
+    pub guardian: Option<Pubkey>, // May pause (never unpause/configure/withdraw); set by program authority
+
+    // Deflationary Underlying Safe Mode
+    pub block_deflationary_loss: bool, // Revert unwraps whose destination-side loss exceeds tolerance
+    pub max_unwrap_leakage_bps: u16,   // Tolerated (amount_after_fee - received) in bps
+
+    // Immutable Configuration
+    pub config_frozen: bool, // Metadata + transfer fee authorities renounced (irreversible)
+
+    // Confidential Transfer Opt-In
+    pub allow_confidential: bool, // Underlying has a non-enforced ConfidentialTransferMint (normal transfers only)
+
+    // Minimum Fee Floor
+    pub min_fee_units: u64, // Floor (base units) for nonzero bps wrap/unwrap fees; 0 = pure bps
+
+    // Oracle Source
+    pub oracle_source: OracleType, // Only this source's update instruction may push prices
+
+    // Fee Accrual Breakdown
+    pub wrap_fees_collected: u64,       // Lifetime wrap fees credited to the fees vault
+    pub unwrap_fees_collected: u64,     // Lifetime unwrap fees retained in the fees vault
+    pub withheld_fees_distributed: u64, // Lifetime withheld RIFT fees paid out
+
+    // Protocol Skim
+    pub protocol_fees_owed: u64, // Share of fees_vault skimmed for the global treasury
+
+    // Threshold Auto-Distribution
+    pub auto_distribute_threshold: u64, // fees_vault balance that opt-in unwraps flush (0 = off)
+
+    // Keeper Reward
+    pub rebalance_reward: u64, // Underlying paid from fees_vault per due rebalance
+
+    // Two-Step Creator Transfer
+    pub pending_creator: Option<Pubkey>, // Proposed creator; takes over once it signs the accept
+
+    // Oracle Confidence Floor
+    pub min_confidence_bps: u16, // Switchboard confidence floor; tighter reports are rejected
+
+    // Launch Fee-Free Window
+    pub fee_free_until: i64, // Launch grace window: wrap/unwrap fees are 0 before this
+
+    // Nested Rifts
+    pub allow_nested: bool, // Creator opted in to wrapping another rift's RIFT mint
+
+    // Oracle History
+    pub oracle_history: Option<Pubkey>, // OracleHistory PDA used for price reads once migrated
+
+    // Lifetime Flow
+    pub lifetime_wrapped: u128,   // Underlying deposited by wraps over the rift's life
+    pub lifetime_unwrapped: u128, // Underlying paid out by unwraps over the rift's life
+
+    // Wrap Fees Vault
+    pub wrap_fees_vault: Option<Pubkey>, // ["wrap_fees_vault", rift]; receives wrap fees if set
+
+    // Unwrap Drain Limit
+    pub max_unwrap_vault_fraction_bps: u16, // Largest single unwrap payout vs vault (0 = off)
+
+    // Wind-Down
+    pub wind_down: bool, // Unwrap-only: wraps rejected, holders can still exit
+
+    // Rift Migration
+    pub migration_destination: Option<Pubkey>, // Set by migrate_rift; holders redeem into this rift
+
+    // Vault Backing Snapshot
+    pub vault_backing_ratio: u64, // vault / supply (6 decimals), written by refresh_backing_ratio
+    pub vault_backing_ratio_updated_at: i64, // Last refresh_backing_ratio (0 = never)
+
+    // Close Delay
+    pub close_started_at: i64, // begin_close time; finalize_close waits CLOSE_DELAY_SECONDS
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PriceData {
+    pub price: u64,
+    pub confidence: u64,
+    pub timestamp: i64,
+}
+
+/// One step of the dynamic fee schedule: `fee_bps` applies once 24h volume >= `volume_threshold`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeTier {
+    pub volume_threshold: u64,
+    pub fee_bps: u16,
+}
+
+/// Compact, versioned rift summary returned by `get_rift_state` via return data
+/// Fields may only be appended; `version` is bumped whenever the layout changes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RiftSummary {
+    pub version: u8,
+    pub backing_ratio: u64,
+    pub total_rift_minted: u64,
+    pub total_underlying_wrapped: u64,
+    pub total_fees_collected: u64,
+    pub wrap_fee_bps: u16,
+    pub unwrap_fee_bps: u16,
+    pub is_closed: bool,
+    pub is_paused: bool,
+    // v2: lifetime fee accrual split by source
+    pub wrap_fees_collected: u64,
+    pub unwrap_fees_collected: u64,
+    pub withheld_fees_distributed: u64,
+    // v3: lifetime underlying flow
+    pub lifetime_wrapped: u128,
+    pub lifetime_unwrapped: u128,
+}
+
+impl RiftSummary {
+    pub const SIZE: usize = 1 + 8 + 8 + 8 + 8 + 2 + 2 + 1 + 1 + 8 + 8 + 8 + 16 + 16;
+}
+
+/// Candidate underlying report returned by `inspect_underlying` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UnderlyingInspection {
+    pub extension_types: Vec<u16>, // Token-2022 ExtensionType discriminants (empty for SPL Token)
+    pub blocked: u16,              // UNDERLYING_BLOCKED_* bits
+    pub transfer_fee_bps: u16,     // Underlying's newer transfer fee (0 without TransferFeeConfig)
+    pub interest_bearing: bool,    // Underlying has InterestBearingConfig (redeemed raw 1:1)
+    pub can_create: bool,          // blocked == 0
+}
+
+/// Backing invariant probe returned by `check_solvency` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct SolvencyReport {
+    pub solvent: bool,  // vault.amount >= total_rift_minted
+    pub shortfall: u64, // total_rift_minted - vault.amount when insolvent, else 0
+    pub surplus: u64,   // vault.amount - total_rift_minted when solvent, else 0
+}
+
+impl SolvencyReport {
+    pub const SIZE: usize = 1 + 8 + 8;
+}
+
+/// Protocol-level authorities, returned by `get_protocol_authorities` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ProtocolAuthorities {
+    pub program_authority: Pubkey,
+    pub admin_authority_2: Pubkey,
+    pub treasury_wallet: Pubkey,
+    pub default_treasury: Pubkey,
+}
+
+/// Launch vs live transfer fee, returned by `get_transfer_fee_disclosure` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TransferFeeDisclosure {
+    pub initial_transfer_fee_bps: u16,
+    pub current_transfer_fee_bps: u16,
+    pub changed_since_launch: bool,
+}
+
+impl TransferFeeDisclosure {
+    pub const SIZE: usize = 2 + 2 + 1;
+}
+
+/// Live mint transfer fee, returned by `get_transfer_fee` via return data
+/// `newer_*` applies from `newer_effective_epoch`; `older_*` applies before it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LiveTransferFee {
+    pub older_transfer_fee_bps: u16,
+    pub older_maximum_fee: u64,
+    pub newer_transfer_fee_bps: u16,
+    pub newer_maximum_fee: u64,
+    pub newer_effective_epoch: u64,
+    pub current_epoch: u64,
+    pub active_transfer_fee_bps: u16,
+}
+
+impl LiveTransferFee {
+    pub const SIZE: usize = 2 + 8 + 2 + 8 + 8 + 8 + 2;
+}
+
+/// Wrap/unwrap execution quote, returned by `quote_price` via return data
+/// `effective_price` and `oracle_price` are underlying per RIFT with 6 decimals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PriceQuote {
+    pub wrap: bool,
+    pub amount_in: u64,  // Underlying for wraps, RIFT for unwraps
+    pub amount_out: u64, // RIFT for wraps, underlying for unwraps
+    pub fee_bps: u16,
+    pub fee: u64, // Wrap/unwrap fee in underlying
+    pub rift_transfer_fee: u64,
+    pub underlying_transfer_fee: u64,
+    pub effective_price: u64,
+    pub oracle_price: u64,
+}
+
+impl PriceQuote {
+    pub const SIZE: usize = 1 + 8 + 8 + 2 + 8 + 8 + 8 + 8 + 8;
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
-pub struct PriceData {
-    pub price: u64,
-    pub confidence: u64,
-    pub timestamp: i64,
-}
+/// All derived PDAs of a rift, returned by `get_pdas` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RiftPdas {
+    pub vault_authority: Pubkey, // ["vault_auth", rift]
+    pub vault_authority_bump: u8,
+    pub rift_mint_authority: Pubkey, // ["rift_mint_auth", rift]
+    pub rift_mint_authority_bump: u8,
+    pub vault: Pubkey, // ["vault", rift]
+    pub vault_bump: u8,
+    pub fees_vault: Pubkey, // ["fees_vault", rift]
+    pub fees_vault_bump: u8,
+    pub withheld_vault: Pubkey, // ["withheld_vault", rift]
+    pub withheld_vault_bump: u8,
+    pub wrap_fees_vault: Pubkey, // ["wrap_fees_vault", rift]
+    pub wrap_fees_vault_bump: u8,
+    pub oracle_history: Pubkey, // ["oracle_history", rift]
+    pub oracle_history_bump: u8,
+}
+
+impl RiftPdas {
+    pub const SIZE: usize = (32 + 1) * 7;
+
+    /// Derive every PDA seeded by `rift` (the rift account key)
+    pub fn derive(rift: &Pubkey, program_id: &Pubkey) -> Self {
+        let derive =
+            |prefix: &[u8]| Pubkey::find_program_address(&[prefix, rift.as_ref()], program_id);
+
+        let (vault_authority, vault_authority_bump) = derive(b"vault_auth");
+        let (rift_mint_authority, rift_mint_authority_bump) = derive(b"rift_mint_auth");
+        let (vault, vault_bump) = derive(b"vault");
+        let (fees_vault, fees_vault_bump) = derive(b"fees_vault");
+        let (withheld_vault, withheld_vault_bump) = derive(b"withheld_vault");
+        let (wrap_fees_vault, wrap_fees_vault_bump) = derive(b"wrap_fees_vault");
+        let (oracle_history, oracle_history_bump) = derive(b"oracle_history");
+
+        RiftPdas {
+            vault_authority,
+            vault_authority_bump,
+            rift_mint_authority,
+            rift_mint_authority_bump,
+            vault,
+            vault_bump,
+            fees_vault,
+            fees_vault_bump,
+            withheld_vault,
+            withheld_vault_bump,
+            wrap_fees_vault,
+            wrap_fees_vault_bump,
+            oracle_history,
+            oracle_history_bump,
+        }
+    }
+}
+
+/// Oracle ring buffer in chronological order, returned by `get_oracle_history` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct OracleHistorySnapshot {
+    pub prices: Vec<PriceData>, // Oldest → newest (10 inline, or the OracleHistory capacity)
+    pub price_index: u16,       // Raw ring buffer write position
+    pub last_oracle_update: i64,
+    pub from_history_account: bool, // true = read from the OracleHistory PDA
+}
+
+impl OracleHistorySnapshot {
+    /// Largest snapshot: a full MAX_ORACLE_HISTORY_LEN buffer (fits in return data)
+    pub const MAX_SIZE: usize = 4 + (8 + 8 + 8) * MAX_ORACLE_HISTORY_LEN as usize + 2 + 8 + 1;
+}
+
+/// Withheld fee total over a set of RIFT token accounts, returned by `sum_withheld_fees` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct WithheldFeesSummary {
+    pub total_withheld: u64,
+    pub accounts_counted: u32,
+    pub accounts_skipped: u32, // Not Token-2022 accounts of rift.rift_mint
+}
+
+impl WithheldFeesSummary {
+    pub const SIZE: usize = 8 + 4 + 4;
+}
+
+/// Canonical program constants, returned by `get_program_constants` via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ProgramConstants {
+    pub rift_account_size: u64,
+    pub default_reentrancy_timeout_slots: u64, // Timeout a new rift starts with
+    pub min_reentrancy_timeout_slots: u64,     // Bounds for set_reentrancy_timeout
+    pub max_reentrancy_timeout_slots: u64,
+    pub oracle_change_delay: i64,    // Seconds between oracle change proposal and execution
+    pub max_fee_basis_points: u16,   // Token-2022 transfer fee ceiling (10000 = 100%)
+    pub min_transfer_fee_bps: u16,   // Allowed RIFT transfer fee band at creation
+    pub max_transfer_fee_bps: u16,
+}
+
+impl ProgramConstants {
+    pub const SIZE: usize = 8 + 8 + 8 + 8 + 8 + 2 + 2 + 2;
+}
+
+impl Rift {
+    /// Same-slot guard: when enabled, reject an unwrap in the slot of the rift's last wrap
+    /// Rift-wide on purpose - a per-user check is bypassed by moving the RIFT to another wallet
+    pub fn ensure_not_same_slot_wrap(&self, slot: u64) -> Result<()> {
+        if self.same_slot_protection {
+            require!(
+                !(self.last_action_was_wrap && slot == self.last_action_slot),
+                ErrorCode::SameSlotWrapUnwrap
+            );
+        }
+        Ok(())
+    }
+
+    /// Same-slot guard: record the slot and kind of the latest wrap/unwrap
+    pub fn record_action_slot(&mut self, slot: u64, was_wrap: bool) {
+        self.last_action_slot = slot;
+        self.last_action_was_wrap = was_wrap;
+    }
+
+    /// Defaults for every field added after the original launch layout
+    /// Shared by the create paths (which then apply their parameters) and migrate_rift_account
+    pub fn apply_default_config(&mut self) {
+        // Backing ratio circuit breaker (creator can adjust via set_min_backing_ratio)
+        self.min_backing_ratio_bps = DEFAULT_MIN_BACKING_RATIO_BPS;
+
+        // No transfer fee recorded until the create path sets the launch fee
+        self.initial_transfer_fee_bps = 0;
+        self.transfer_fee_bps = 0;
+
+        // No daily snapshot emitted yet
+        self.last_snapshot_timestamp = 0;
+
+        // Oracle trust window (creator can adjust via set_max_oracle_trust_age)
+        self.max_oracle_trust_age = DEFAULT_MAX_ORACLE_TRUST_AGE;
+
+        // Default fee model: extract wrap fees to fees_vault
+        self.fees_to_backing = false;
+        self.backing_surplus = 0;
+
+        // Interest-bearing underlying disclosure (set from the mint by the create paths)
+        self.interest_bearing = false;
+
+        // Same-slot guard disabled by default (creator opt-in)
+        self.last_action_slot = 0;
+        self.last_action_was_wrap = false;
+        self.same_slot_protection = false;
+
+        // Not closing
+        self.closing = false;
+
+        // Not paused
+        self.paused = false;
+
+        // Rebalance on the mean by default
+        self.use_median_oracle = false;
+
+        // Oracle freshness heartbeat disabled by default
+        self.require_fresh_oracle = false;
+        self.max_oracle_age = DEFAULT_ORACLE_FRESHNESS_AGE;
+
+        // Per-user wrap cooldown disabled by default
+        self.wrap_cooldown_slots = 0;
+
+        // No per-transaction wrap limits by default
+        self.min_wrap_amount = 0;
+        self.max_wrap_amount = 0;
+
+        // Static fees by default
+        self.dynamic_fee_enabled = false;
+        self.fee_tiers = [FeeTier::default(); 4];
+
+        // Rolling volume window starts on first wrap/unwrap
+        self.volume_buckets = [0; 24];
+        self.volume_bucket_start = 0;
+
+        // Token-2022 RIFT mint
+        self.legacy_mint = false;
+
+        // Reentrancy guard timeout (tunable by the program authority)
+        self.reentrancy_timeout_slots = REENTRANCY_TIMEOUT_SLOTS;
+
+        // No guardian until the program authority assigns one
+        self.guardian = None;
+
+        // Deflationary safe mode off (destination-side fees only logged)
+        self.block_deflationary_loss = false;
+        self.max_unwrap_leakage_bps = 0;
+
+        // Config stays mutable until authorities are renounced
+        self.config_frozen = false;
+
+        // Confidential transfer underlyings blocked unless the creator opts in
+        self.allow_confidential = false;
+
+        // Fee floor for low-decimal underlyings (bps fees on tiny amounts round to zero)
+        self.min_fee_units = DEFAULT_MIN_FEE_UNITS;
+
+        // Manual prices until a feed is bound and selected via set_oracle_source
+        self.oracle_source = OracleType::Manual;
+
+        // Fee accrual counters, skim owed and auto-distribution start empty / off
+        self.wrap_fees_collected = 0;
+        self.unwrap_fees_collected = 0;
+        self.withheld_fees_distributed = 0;
+        self.protocol_fees_owed = 0;
+        self.auto_distribute_threshold = 0;
+
+        // No keeper reward until the creator sets one
+        self.rebalance_reward = 0;
+
+        // No creator transfer in progress
+        self.pending_creator = None;
+
+        // Switchboard confidence floor (creator can adjust via set_min_confidence_bps)
+        self.min_confidence_bps = DEFAULT_MIN_CONFIDENCE_BPS;
+
+        // No launch fee-free window; nested wrapping requires an explicit opt-in
+        self.fee_free_until = 0;
+        self.allow_nested = false;
+
+        // Oracle samples stay inline until migrate_oracle_history
+        self.oracle_history = None;
+
+        // Lifetime flow counters
+        self.lifetime_wrapped = 0;
+        self.lifetime_unwrapped = 0;
+
+        // Wrap fees go to fees_vault until initialize_wrap_fees_vault
+        self.wrap_fees_vault = None;
+
+        // No single-unwrap drain limit
+        self.max_unwrap_vault_fraction_bps = 0;
+
+        // Not winding down or migrating
+        self.wind_down = false;
+        self.migration_destination = None;
+
+        // Fully backed until the first refresh_backing_ratio
+        self.vault_backing_ratio = 1_000_000;
+        self.vault_backing_ratio_updated_at = 0;
+
+        // Not closing
+        self.close_started_at = 0;
+    }
 
-impl Rift {
     pub fn add_price_data(&mut self, price: u64, confidence: u64, timestamp: i64) -> Result<()> {
         // **CRITICAL SECURITY FIX**: Validate timestamp bounds to prevent manipulation
         let current_time = Clock::get()?.unix_timestamp;
@@ -5783,7 +14049,11 @@ impl Rift {
         Ok(())
     }
 
-    pub fn should_trigger_rebalance(&self, current_time: i64) -> Result<bool> {
+    pub fn should_trigger_rebalance(
+        &self,
+        current_time: i64,
+        history: Option<&OracleHistory>,
+    ) -> Result<bool> {
         // **CRITICAL SECURITY FIX**: Validate current_time to prevent timestamp manipulation
         let actual_current_time = Clock::get()?.unix_timestamp;
         require!(
@@ -5802,7 +14072,7 @@ impl Rift {
             .total_rift_minted
             .checked_div(10) // 10% of total minted
             .unwrap_or(u64::MAX);
-        if self.total_volume_24h > volume_threshold {
+        if self.rolling_volume_24h(current_time) > volume_threshold {
             return Ok(true);
         }
 
@@ -5811,8 +14081,18 @@ impl Rift {
             return Ok(true);
         }
 
+        // **ORACLE TRUST**: Never act on a long-dead oracle
+        if !self.is_oracle_trusted(current_time) {
+            msg!("⚠️ Oracle older than trust age - ignoring price deviation");
+            return Ok(false);
+        }
+
         // Check if oracle indicates significant price deviation
-        let avg_price = self.get_average_oracle_price()?;
+        let avg_price = if self.use_median_oracle {
+            self.get_median_oracle_price(history)?
+        } else {
+            self.get_average_oracle_price(history)?
+        };
         let price_deviation = self.calculate_price_deviation(avg_price)?;
 
         // Trigger if deviation > 2%
@@ -5827,11 +14107,17 @@ impl Rift {
             ErrorCode::InvalidTimestamp
         );
 
-        // Allow manual rebalance if oracle interval has passed
-        Ok(current_time - self.last_oracle_update > self.oracle_update_interval)
+        // Allow manual rebalance if oracle interval has passed, at most once per interval
+        // (the oracle condition alone stays true until the next update)
+        Ok(current_time - self.last_oracle_update > self.oracle_update_interval
+            && current_time - self.last_rebalance > self.oracle_update_interval)
     }
 
-    pub fn trigger_automatic_rebalance(&mut self, current_time: i64) -> Result<()> {
+    pub fn trigger_automatic_rebalance(
+        &mut self,
+        current_time: i64,
+        history: Option<&OracleHistory>,
+    ) -> Result<()> {
         // **CRITICAL SECURITY FIX**: Validate current_time to prevent timestamp manipulation
         let actual_current_time = Clock::get()?.unix_timestamp;
         require!(
@@ -5839,7 +14125,23 @@ impl Rift {
             ErrorCode::InvalidTimestamp
         );
 
-        let avg_price = self.get_average_oracle_price()?;
+        // **ORACLE TRUST**: Skip oracle-based adjustments entirely when the oracle is too old
+        // Backing ratio stays untouched rather than being driven by an ancient price
+        if !self.is_oracle_trusted(current_time) {
+            msg!(
+                "⚠️ Oracle last updated at {} exceeds trust age {}s - skipping oracle-based rebalance",
+                self.last_oracle_update,
+                self.max_oracle_trust_age
+            );
+            return Ok(());
+        }
+
+        // **MEDIAN ORACLE**: Optionally use the median so one bad sample can't skew the result
+        let avg_price = if self.use_median_oracle {
+            self.get_median_oracle_price(history)?
+        } else {
+            self.get_average_oracle_price(history)?
+        };
 
         // **CRITICAL FIX**: Validate oracle price before updating backing ratio
         require!(avg_price > 0, ErrorCode::InvalidOraclePrice);
@@ -5866,18 +14168,86 @@ impl Rift {
         self.arbitrage_opportunity_bps = 0; // Reset after rebalance
         self.price_deviation = 0;
 
-        // **NEW FEATURE**: Reset volume counter after rebalance for volatility farming
-        self.total_volume_24h = 0; // Reset volume tracking
+        // NOTE: Volume is no longer reset here - it decays through the hourly volume buckets
 
         Ok(())
     }
 
     // **FIX CRITICAL #28**: Add allow_stale_fallback parameter to enable oracle recovery
-    pub fn get_average_oracle_price(&self) -> Result<u64> {
-        self.get_average_oracle_price_with_options(false)
+    pub fn get_average_oracle_price(&self, history: Option<&OracleHistory>) -> Result<u64> {
+        self.get_average_oracle_price_with_options(history, false)
+    }
+
+    /// Ring buffer and write position used for price reads
+    /// The OracleHistory companion once migrated, otherwise the inline oracle_prices
+    fn oracle_buffer<'a>(&'a self, history: Option<&'a OracleHistory>) -> (&'a [PriceData], usize) {
+        match history {
+            Some(history) => (&history.prices, history.price_index as usize),
+            None => (&self.oracle_prices, self.price_index as usize),
+        }
+    }
+
+    /// Validated OracleHistory for price reads: None until migrated, then required and bound
+    pub fn oracle_history_for<'a>(
+        &self,
+        history: &'a Option<Account<'_, OracleHistory>>,
+    ) -> Result<Option<&'a OracleHistory>> {
+        let Some(expected) = self.oracle_history else {
+            return Ok(None);
+        };
+        let history = history.as_ref().ok_or(ErrorCode::OracleHistoryRequired)?;
+        require!(history.key() == expected, ErrorCode::OracleHistoryMismatch);
+        Ok(Some(&**history))
+    }
+
+    /// Median of the fresh (non-zero, non-stale) samples in the oracle buffer
+    /// Sorted on a fixed stack array - no heap. Even counts average the two middle samples.
+    pub fn get_median_oracle_price(&self, history: Option<&OracleHistory>) -> Result<u64> {
+        // Same freshness window as get_average_oracle_price_with_options
+        const MAX_ORACLE_AGE: i64 = 3600; // 1 hour max age
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let mut samples = [0u64; MAX_ORACLE_HISTORY_LEN as usize];
+        let mut count = 0usize;
+        for price_data in self.oracle_buffer(history).0 {
+            if price_data.timestamp <= 0 || price_data.price == 0 {
+                continue;
+            }
+            let age = current_time
+                .checked_sub(price_data.timestamp)
+                .ok_or(ErrorCode::MathOverflow)?;
+            if age > MAX_ORACLE_AGE {
+                continue;
+            }
+            samples[count] = price_data.price;
+            count += 1;
+        }
+
+        require!(count > 0, ErrorCode::OraclePriceStale);
+
+        let valid = &mut samples[..count];
+        valid.sort_unstable();
+
+        let median = if count % 2 == 1 {
+            valid[count / 2]
+        } else {
+            let sum = u128::from(valid[count / 2 - 1])
+                .checked_add(u128::from(valid[count / 2]))
+                .ok_or(ErrorCode::MathOverflow)?;
+            u64::try_from(sum / 2).map_err(|_| ErrorCode::MathOverflow)?
+        };
+
+        require!(median > 0, ErrorCode::InvalidOraclePrice);
+        require!(median <= 1_000_000_000_000, ErrorCode::OraclePriceTooLarge);
+
+        Ok(median)
     }
 
-    pub fn get_average_oracle_price_with_options(&self, allow_stale_fallback: bool) -> Result<u64> {
+    pub fn get_average_oracle_price_with_options(
+        &self,
+        history: Option<&OracleHistory>,
+        allow_stale_fallback: bool,
+    ) -> Result<u64> {
         let mut total_price = 0u128; // **PRECISION FIX**: Use u128 for intermediate calculations
         let mut count = 0u64;
         let mut stale_count = 0u64;
@@ -5888,7 +14258,7 @@ impl Rift {
         const MIN_FRESH_SAMPLES: u64 = 1; // At least 1 fresh sample required
         let current_time = Clock::get()?.unix_timestamp;
 
-        for price_data in &self.oracle_prices {
+        for price_data in self.oracle_buffer(history).0 {
             if price_data.timestamp > 0 {
                 // **FIX MEDIUM #7**: Check oracle data staleness
                 let age = current_time
@@ -6036,45 +14406,656 @@ impl Rift {
             .rifts_tokens_distributed
             .checked_add(lp_staker_amount)
             .ok_or(ErrorCode::MathOverflow)?;
-        self.rifts_tokens_burned = self
-            .rifts_tokens_burned
-            .checked_add(burn_amount)
+        self.rifts_tokens_burned = self
+            .rifts_tokens_burned
+            .checked_add(burn_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// NOTE: This helper is currently unused by on-chain logic and kept only for
+/// off-chain analytics / future extensions. It can be safely removed if code
+/// size becomes a concern.
+pub fn get_pending_fees(&self) -> u64 {
+        // **SECURITY FIX**: Get total fees that haven't been distributed yet with proper error handling
+        let total_distributed = match self
+            .rifts_tokens_distributed
+            .checked_add(self.rifts_tokens_burned)
+        {
+            Some(total) => total,
+            None => return 0, // Overflow in distributed calculation - return 0 as safe fallback
+        };
+
+        self.total_fees_collected.saturating_sub(total_distributed)
+    }
+
+    pub fn get_oracle_countdown(&self, current_time: i64) -> i64 {
+        let next_oracle_time = self.last_oracle_update + self.oracle_update_interval;
+        (next_oracle_time - current_time).max(0)
+    }
+
+    pub fn get_rebalance_countdown(&self, current_time: i64) -> i64 {
+        let next_rebalance_time = self.last_rebalance + self.max_rebalance_interval;
+        (next_rebalance_time - current_time).max(0)
+    }
+
+    /// Time-weighted average price over the oracle ring buffer
+    /// Each sample is weighted by the time until the next sample (the newest until now).
+    /// Samples older than `oracle_update_interval * 10` are ignored; needs at least 2 valid samples.
+    pub fn get_twap_price(&self, history: Option<&OracleHistory>) -> Result<u64> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let max_age = self
+            .oracle_update_interval
+            .checked_mul(10)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Walk the ring buffer oldest → newest (price_index points at the oldest slot)
+        let (buffer, write_index) = self.oracle_buffer(history);
+        let buffer_len = buffer.len();
+        let mut samples = [PriceData::default(); MAX_ORACLE_HISTORY_LEN as usize];
+        let mut sample_count = 0usize;
+        for offset in 0..buffer_len {
+            let price_data = buffer[(write_index + offset) % buffer_len];
+            if price_data.timestamp <= 0 || price_data.price == 0 {
+                continue;
+            }
+            let age = current_time
+                .checked_sub(price_data.timestamp)
+                .ok_or(ErrorCode::MathOverflow)?;
+            if age > max_age {
+                continue;
+            }
+            samples[sample_count] = price_data;
+            sample_count += 1;
+        }
+
+        require!(sample_count >= 2, ErrorCode::InsufficientTwapSamples);
+
+        let mut weighted_total = 0u128;
+        let mut total_weight = 0u128;
+        let mut simple_total = 0u128;
+        for i in 0..sample_count {
+            let end_time = if i + 1 < sample_count {
+                samples[i + 1].timestamp
+            } else {
+                current_time
+            };
+            // Out-of-order samples (add_price_data accepts up to 5 min old) get zero weight
+            let weight = u128::try_from(end_time.saturating_sub(samples[i].timestamp).max(0))
+                .map_err(|_| ErrorCode::MathOverflow)?;
+
+            weighted_total = weighted_total
+                .checked_add(
+                    u128::from(samples[i].price)
+                        .checked_mul(weight)
+                        .ok_or(ErrorCode::MathOverflow)?,
+                )
+                .ok_or(ErrorCode::MathOverflow)?;
+            total_weight = total_weight
+                .checked_add(weight)
+                .ok_or(ErrorCode::MathOverflow)?;
+            simple_total = simple_total
+                .checked_add(u128::from(samples[i].price))
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // All samples share a timestamp: fall back to a simple average
+        let twap = if total_weight == 0 {
+            simple_total
+                .checked_div(sample_count as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            weighted_total
+                .checked_div(total_weight)
+                .ok_or(ErrorCode::MathOverflow)?
+        };
+
+        let twap_price = u64::try_from(twap).map_err(|_| ErrorCode::MathOverflow)?;
+        require!(twap_price > 0, ErrorCode::InvalidOraclePrice);
+        require!(
+            twap_price <= 1_000_000_000_000,
+            ErrorCode::OraclePriceTooLarge
+        );
+
+        Ok(twap_price)
+    }
+
+    /// Token program that owns the RIFT mint (classic SPL Token for legacy rifts)
+    pub fn rift_token_program_id(&self) -> Pubkey {
+        if self.legacy_mint {
+            anchor_spl::token::ID
+        } else {
+            spl_token_2022::ID
+        }
+    }
+
+    /// Shift the hourly volume buckets forward to `now`, zeroing hours that fell out of the window
+    fn roll_volume_window(&mut self, now: i64) {
+        let current_hour_start = now - now.rem_euclid(VOLUME_BUCKET_SECONDS);
+        if self.volume_bucket_start == 0 {
+            self.volume_bucket_start = current_hour_start;
+            return;
+        }
+        let elapsed_hours = (current_hour_start - self.volume_bucket_start) / VOLUME_BUCKET_SECONDS;
+        if elapsed_hours <= 0 {
+            return;
+        }
+        let bucket_count = self.volume_buckets.len();
+        if elapsed_hours as usize >= bucket_count {
+            self.volume_buckets = [0; 24];
+        } else {
+            let shift = elapsed_hours as usize;
+            self.volume_buckets.rotate_left(shift);
+            for bucket in self.volume_buckets[bucket_count - shift..].iter_mut() {
+                *bucket = 0;
+            }
+        }
+        self.volume_bucket_start = current_hour_start;
+    }
+
+    /// Add wrap/unwrap volume to the current hour and refresh the cached total_volume_24h
+    /// Saturates at u64::MAX: volume only drives fee tiers and rebalance triggers, so a
+    /// high-decimal underlying must not be able to make wraps/unwraps fail here
+    pub fn record_volume(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.roll_volume_window(now);
+        let current_bucket = self.volume_buckets.len() - 1;
+        self.volume_buckets[current_bucket] =
+            self.volume_buckets[current_bucket].saturating_add(amount);
+        self.total_volume_24h = self.rolling_volume_24h(now);
+        Ok(())
+    }
+
+    /// Volume over the last 24 hourly buckets as of `now` (read-only, ignores expired hours)
+    pub fn rolling_volume_24h(&self, now: i64) -> u64 {
+        if self.volume_bucket_start == 0 {
+            return 0;
+        }
+        let current_hour_start = now - now.rem_euclid(VOLUME_BUCKET_SECONDS);
+        let elapsed_hours =
+            ((current_hour_start - self.volume_bucket_start) / VOLUME_BUCKET_SECONDS).max(0) as usize;
+        let bucket_count = self.volume_buckets.len();
+        if elapsed_hours >= bucket_count {
+            return 0;
+        }
+        self.volume_buckets[elapsed_hours..]
+            .iter()
+            .fold(0u64, |total, bucket| total.saturating_add(*bucket))
+    }
+
+    /// Fee for the current 24h volume when dynamic fees are enabled, else `static_fee_bps`
+    /// Picks the highest tier whose threshold has been reached; below the first tier the static fee applies
+    /// Always 0 inside the launch grace window (`fee_free_until`)
+    pub fn effective_fee_bps(&self, static_fee_bps: u16, now: i64) -> u16 {
+        if now < self.fee_free_until {
+            return 0;
+        }
+        if !self.dynamic_fee_enabled {
+            return static_fee_bps;
+        }
+        let volume_24h = self.rolling_volume_24h(now);
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| volume_24h >= tier.volume_threshold)
+            .map(|tier| tier.fee_bps)
+            .unwrap_or(static_fee_bps)
+    }
+
+    /// Floor a nonzero bps fee at `min_fee_units`, never exceeding the charged amount
+    /// On low-decimal underlyings (0-2 decimals) `amount * bps / 10000` truncates to zero for
+    /// small amounts, which would let users wrap/unwrap fee-free in small chunks. Zero-bps
+    /// fees (exemptions, zero-fee tiers) are left untouched.
+    pub fn apply_min_fee(&self, fee: u64, fee_bps: u64, amount: u64) -> u64 {
+        if fee_bps == 0 {
+            return fee;
+        }
+        fee.max(self.min_fee_units).min(amount)
+    }
+
+    /// Wrap/unwrap fee on `amount` at `fee_bps`: amount * bps / 10000, floored by apply_min_fee
+    /// Shared by the execution paths and quote_price so quotes can't drift from real fees
+    pub fn charge_fee(&self, amount: u64, fee_bps: u64) -> Result<u64> {
+        let fee = amount
+            .checked_mul(fee_bps)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(self.apply_min_fee(fee, fee_bps, amount))
+    }
+
+    /// Emergency unwrap gate: newest oracle sample is older than max_rebalance_interval
+    /// A rift with no recorded sample has no oracle to go stale, so it never qualifies
+    pub fn is_oracle_stale_for_emergency(&self, current_time: i64) -> bool {
+        let newest_sample = self
+            .oracle_prices
+            .iter()
+            .map(|price_data| price_data.timestamp)
+            .max()
+            .unwrap_or(0);
+        newest_sample > 0
+            && current_time.saturating_sub(newest_sample) > self.max_rebalance_interval
+    }
+
+    /// When require_fresh_oracle is set, the newest oracle sample must be within max_oracle_age
+    pub fn ensure_oracle_fresh(&self, current_time: i64) -> Result<()> {
+        if !self.require_fresh_oracle {
+            return Ok(());
+        }
+        let newest_sample = self
+            .oracle_prices
+            .iter()
+            .map(|price_data| price_data.timestamp)
+            .max()
+            .unwrap_or(0);
+        require!(
+            newest_sample > 0 && current_time.saturating_sub(newest_sample) <= self.max_oracle_age,
+            ErrorCode::OracleStale
+        );
+        Ok(())
+    }
+
+    /// Shared unwrap preflight for unwrap_from_vault, emergency_unwrap, migrate_position and
+    /// can_fully_exit: rift state and oracle gate, fee rate (launch window, volume tiers,
+    /// exemption), payout, vault liquidity and the single-unwrap drain limit
+    /// `vault_balance` is the live backing vault balance before anything moves
+    pub fn plan_unwrap(
+        &self,
+        rift_token_amount: u64,
+        vault_balance: u64,
+        fee_exempt: bool,
+        emergency: bool,
+        now: i64,
+    ) -> Result<UnwrapPlan> {
+        require!(!self.is_closed, ErrorCode::RiftClosed);
+        require!(!self.paused, ErrorCode::RiftPaused);
+
+        // **ORACLE HEARTBEAT**: Optionally refuse to operate on an old price
+        // **EMERGENCY UNWRAP**: Inverted gate - only allowed once the oracle is confirmed stale
+        if emergency {
+            require!(
+                self.is_oracle_stale_for_emergency(now),
+                ErrorCode::OracleNotStale
+            );
+        } else {
+            self.ensure_oracle_fresh(now)?;
+        }
+
+        require!(rift_token_amount > 0, ErrorCode::InvalidAmount);
+
+        // **DYNAMIC FEES**: Volume tier overrides the static unwrap fee when enabled
+        // **FEE EXEMPTION**: Whitelisted wrappers pay no unwrap fee
+        // **EMERGENCY UNWRAP**: Static unwrap_fee_bps (no volume tiers)
+        let fee_bps = if fee_exempt {
+            0
+        } else if emergency {
+            u64::from(self.unwrap_fee_bps)
+        } else {
+            u64::from(self.effective_fee_bps(self.unwrap_fee_bps, now))
+        };
+        require!(
+            rift_token_amount <= u64::MAX / fee_bps.max(1),
+            ErrorCode::AmountTooLarge
+        );
+
+        // Raw 1:1 redemption - InterestBearingConfig interest lives in the UI amount, not the raw
+        // balance, so it reaches the holder without any share math
+        // **EMERGENCY UNWRAP**: Strict 1:1, capped by what the vault actually holds - only the RIFT
+        // actually paid out is burned, the rest stays with the holder
+        // **FEE-TO-BACKING**: Regular unwraps also redeem their pro-rata share of the retained
+        // wrap fees, so the last holder out leaves the vault empty
+        let (rift_burned, backing_share) = if emergency {
+            (rift_token_amount.min(vault_balance), 0)
+        } else {
+            (rift_token_amount, self.backing_share(rift_token_amount)?)
+        };
+        let gross_underlying = rift_burned
+            .checked_add(backing_share)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // **MIN FEE**: Floored at min_fee_units so low-decimal underlyings can't unwrap fee-free
+        let fee = self.charge_fee(gross_underlying, fee_bps)?;
+        let net_underlying = gross_underlying
+            .checked_sub(fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(net_underlying > 0, ErrorCode::InvalidAmount);
+
+        // **HIGH FIX #10**: Vault must cover the payout BEFORE the user's tokens are burned
+        // The fee leaves the vault first when fees_vault is initialized, so cover fee + payout
+        let required_vault_balance =
+            if self.fees_vault != anchor_lang::solana_program::system_program::ID {
+                gross_underlying
+            } else {
+                net_underlying
+            };
+        require!(
+            vault_balance >= required_vault_balance,
+            ErrorCode::InsufficientFunds
+        );
+
+        // **DRAIN LIMIT**: One unwrap can't take more than the configured share of the vault
+        // (not applied to emergency unwraps, which are already capped by the vault)
+        if self.max_unwrap_vault_fraction_bps > 0 && !emergency {
+            let max_payout = u128::from(vault_balance)
+                .checked_mul(u128::from(self.max_unwrap_vault_fraction_bps))
+                .ok_or(ErrorCode::MathOverflow)?
+                / 10000;
+            require!(
+                u128::from(net_underlying) <= max_payout,
+                ErrorCode::UnwrapExceedsVaultFraction
+            );
+        }
+
+        Ok(UnwrapPlan {
+            fee_bps,
+            gross_underlying,
+            fee,
+            net_underlying,
+            rift_burned,
+            backing_share,
+        })
+    }
+
+    /// Share of backing_surplus redeemed by `rift_token_amount` RIFT (floor, all of it for the
+    /// last holder)
+    pub fn backing_share(&self, rift_token_amount: u64) -> Result<u64> {
+        if self.backing_surplus == 0 || self.total_rift_minted == 0 {
+            return Ok(0);
+        }
+        let share = u128::from(self.backing_surplus)
+            .checked_mul(u128::from(rift_token_amount.min(self.total_rift_minted)))
+            .ok_or(ErrorCode::MathOverflow)?
+            / u128::from(self.total_rift_minted);
+        u64::try_from(share).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+
+    /// Shared wrap preflight for wrap_tokens and migrate_position: rift state, oracle gate and
+    /// the per-user wrap cooldown (`last_wrap_slot` 0 = the user never wrapped)
+    pub fn ensure_wrap_open(&self, last_wrap_slot: u64, current_slot: u64, now: i64) -> Result<()> {
+        require!(!self.is_closed, ErrorCode::RiftClosed);
+
+        // **TWO-PHASE CLOSE**: No new deposits once begin_close has been called
+        require!(!self.closing, ErrorCode::RiftClosing);
+
+        // **WIND-DOWN**: Creator stopped new deposits; unwraps keep working
+        require!(!self.wind_down, ErrorCode::RiftWindingDown);
+
+        // **PAUSE**: Program authority can halt wraps/unwraps (e.g. before a migration)
+        require!(!self.paused, ErrorCode::RiftPaused);
+
+        // **ORACLE HEARTBEAT**: Optionally refuse to operate on an old price
+        self.ensure_oracle_fresh(now)?;
+
+        // **WRAP COOLDOWN**: Per-user throttle against wrap spam (0 = disabled)
+        if self.wrap_cooldown_slots > 0 && last_wrap_slot > 0 {
+            require!(
+                current_slot.saturating_sub(last_wrap_slot) >= self.wrap_cooldown_slots,
+                ErrorCode::WrapCooldownActive
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Whether deposits must sync_native the user's account first: the underlying is the
+    /// canonical wSOL mint under classic SPL Token, where lamports can sit unsynced
+    pub fn needs_native_sync(&self, underlying_token_program: &Pubkey) -> bool {
+        self.underlying_mint == spl_token::native_mint::ID
+            && *underlying_token_program == anchor_spl::token::ID
+    }
+
+    /// Wrap fee rate: 0 for exempt wallets, otherwise launch window and volume tiers apply
+    pub fn wrap_fee_bps(&self, fee_exempt: bool, now: i64) -> u64 {
+        if fee_exempt {
+            0
+        } else {
+            u64::from(self.effective_fee_bps(self.wrap_fee_bps, now))
+        }
+    }
+
+    /// Wrap fee on the underlying that actually reached the vault, as (fee, RIFT to mint)
+    /// Enforces min_wrap_amount on the received amount (fee-on-transfer underlyings)
+    pub fn plan_wrap(&self, actual_received: u64, fee_bps: u64) -> Result<(u64, u64)> {
+        // **WRAP LIMITS**: Minimum applies to what actually arrived
+        require!(
+            actual_received >= self.min_wrap_amount,
+            ErrorCode::WrapAmountTooSmall
+        );
+        require!(
+            actual_received <= u64::MAX / fee_bps.max(1),
+            ErrorCode::AmountTooLarge
+        );
+
+        // **MIN FEE**: Floored at min_fee_units so low-decimal underlyings can't wrap fee-free
+        let wrap_fee = self.charge_fee(actual_received, fee_bps)?;
+        let amount_after_fee = actual_received
+            .checked_sub(wrap_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(amount_after_fee > 0, ErrorCode::InvalidAmount);
+
+        Ok((wrap_fee, amount_after_fee))
+    }
+
+    /// Book a wrap: backing and supply, lifetime flow, wrap fee revenue and protocol skim
+    /// `fee_credited` reached fees_vault (or stayed in the vault), `fee_routed` reached
+    /// wrap_fees_vault - both count as wrap revenue, only the former as total_fees_collected
+    pub fn record_wrap(
+        &mut self,
+        rift_minted: u64,
+        actual_received: u64,
+        fee_credited: u64,
+        fee_routed: u64,
+        global_config: &AccountInfo,
+        now: i64,
+    ) -> Result<()> {
+        self.total_underlying_wrapped = self
+            .total_underlying_wrapped
+            .checked_add(rift_minted)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_rift_minted = self
+            .total_rift_minted
+            .checked_add(rift_minted)
+            .ok_or(ErrorCode::MathOverflow)?;
+        // **LIFETIME STATS**: u128 so 9+ decimal, max-supply underlyings can't overflow over time
+        self.lifetime_wrapped = self
+            .lifetime_wrapped
+            .checked_add(u128::from(actual_received))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // **FEE-TO-BACKING**: The fee stayed in the vault - book it as backing owed to holders
+        if self.fees_to_backing {
+            let retained = actual_received
+                .checked_sub(rift_minted)
+                .ok_or(ErrorCode::MathOverflow)?;
+            self.total_underlying_wrapped = self
+                .total_underlying_wrapped
+                .checked_add(retained)
+                .ok_or(ErrorCode::MathOverflow)?;
+            self.backing_surplus = self
+                .backing_surplus
+                .checked_add(retained)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        self.total_fees_collected = self
+            .total_fees_collected
+            .checked_add(fee_credited)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let wrap_revenue = fee_credited
+            .checked_add(fee_routed)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if wrap_revenue > 0 {
+            self.wrap_fees_collected = self
+                .wrap_fees_collected
+                .checked_add(wrap_revenue)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            // **PROTOCOL SKIM**: Earmark the global treasury's share of the credited fee
+            // (routed wrap fees pay theirs out of wrap_fees_vault in claim_wrap_fees)
+            let protocol_skim =
+                GlobalConfig::load_protocol_skim(global_config, actual_received, wrap_revenue)?;
+            self.protocol_fees_owed = self
+                .protocol_fees_owed
+                .checked_add(protocol_skim)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // Update rolling 24h volume
+        self.record_volume(rift_minted, now)
+    }
+
+    /// Book an unwrap planned by `plan_unwrap`; `underlying_sent` is what actually left the vault
+    /// for the payout (below the plan's net amount only for fee-on-transfer underlyings)
+    pub fn record_unwrap(
+        &mut self,
+        plan: &UnwrapPlan,
+        underlying_sent: u64,
+        global_config: &AccountInfo,
+        now: i64,
+    ) -> Result<()> {
+        self.total_underlying_wrapped = self
+            .total_underlying_wrapped
+            .checked_sub(underlying_sent)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_rift_minted = self
+            .total_rift_minted
+            .checked_sub(plan.rift_burned)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.backing_surplus = self
+            .backing_surplus
+            .checked_sub(plan.backing_share)
+            .ok_or(ErrorCode::MathOverflow)?;
+        // Lifetime statistic - saturates rather than blocking unwraps on huge-supply tokens
+        self.total_burned = self.total_burned.saturating_add(plan.rift_burned);
+        self.lifetime_unwrapped = self
+            .lifetime_unwrapped
+            .checked_add(u128::from(underlying_sent))
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_fees_collected = self
+            .total_fees_collected
+            .checked_add(plan.fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.unwrap_fees_collected = self
+            .unwrap_fees_collected
+            .checked_add(plan.fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        // **PROTOCOL SKIM**: Only fees that actually reached fees_vault can be skimmed
+        if self.fees_vault != anchor_lang::solana_program::system_program::ID {
+            let protocol_skim =
+                GlobalConfig::load_protocol_skim(global_config, plan.gross_underlying, plan.fee)?;
+            self.protocol_fees_owed = self
+                .protocol_fees_owed
+                .checked_add(protocol_skim)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // Update rolling 24h volume
+        self.record_volume(plan.net_underlying, now)
+    }
+
+    /// Reject if another guarded operation (wrap/unwrap/rebalance) is in progress
+    /// Auto-clears a stuck guard after the rift's reentrancy timeout like the guarded instructions
+    pub fn ensure_not_reentrant(&mut self, current_slot: u64) -> Result<()> {
+        if self.reentrancy_guard {
+            if current_slot > self.reentrancy_guard_slot + self.reentrancy_timeout() {
+                msg!(
+                    "⚠️ Auto-clearing stuck reentrancy guard (set at slot {}, current {})",
+                    self.reentrancy_guard_slot,
+                    current_slot
+                );
+                self.reentrancy_guard = false;
+                self.reentrancy_guard_slot = 0;
+            } else {
+                return Err(ErrorCode::ReentrancyDetected.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Slots after which a stuck reentrancy guard auto-clears (0 = pre-upgrade rift, use default)
+    pub fn reentrancy_timeout(&self) -> u64 {
+        if self.reentrancy_timeout_slots == 0 {
+            REENTRANCY_TIMEOUT_SLOTS
+        } else {
+            self.reentrancy_timeout_slots
+        }
+    }
+
+    /// Whether the oracle is recent enough to drive backing/arbitrage adjustments
+    pub fn is_oracle_trusted(&self, current_time: i64) -> bool {
+        current_time.saturating_sub(self.last_oracle_update) <= self.max_oracle_trust_age
+    }
+
+    /// Split a fee distribution into (partner_amount, treasury_amount) using partner_fee_bps
+    /// Treasury receives any rounding remainder so nothing is lost to truncation
+    pub fn split_partner_treasury(&self, amount: u64) -> Result<(u64, u64)> {
+        let partner_amount = u128::from(amount)
+            .checked_mul(u128::from(self.partner_fee_bps.min(10000)))
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let partner_amount = u64::try_from(partner_amount).map_err(|_| ErrorCode::MathOverflow)?;
+        let treasury_amount = amount
+            .checked_sub(partner_amount)
             .ok_or(ErrorCode::MathOverflow)?;
-
-        Ok(())
+        Ok((partner_amount, treasury_amount))
     }
 
-    /// NOTE: This helper is currently unused by on-chain logic and kept only for
-/// off-chain analytics / future extensions. It can be safely removed if code
-/// size becomes a concern.
-pub fn get_pending_fees(&self) -> u64 {
-        // **SECURITY FIX**: Get total fees that haven't been distributed yet with proper error handling
-        let total_distributed = match self
-            .rifts_tokens_distributed
-            .checked_add(self.rifts_tokens_burned)
-        {
-            Some(total) => total,
-            None => return 0, // Overflow in distributed calculation - return 0 as safe fallback
-        };
+    /// Distribution split of `amount`: protocol cut off the top, then split_partner_treasury on
+    /// the remainder - or all of it to treasury (one transfer) when partner == treasury
+    pub fn plan_fee_split(&self, amount: u64, global_config: &AccountInfo) -> Result<FeeSplit> {
+        let partner_wallet = self.partner_wallet.ok_or(ErrorCode::PartnerWalletNotSet)?;
+        let treasury_wallet = self.treasury_wallet.ok_or(ErrorCode::TreasuryNotSet)?;
 
-        if self.total_fees_collected > total_distributed {
-            match self.total_fees_collected.checked_sub(total_distributed) {
-                Some(pending) => pending,
-                None => 0, // Underflow should not happen given the check above, but return 0 as safe fallback
-            }
+        let (protocol_amount, protocol_fee_recipient) =
+            GlobalConfig::load_distribution_cut(global_config, amount)?;
+        let split_amount = amount
+            .checked_sub(protocol_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let (partner_amount, treasury_amount) = if partner_wallet == treasury_wallet {
+            (0, split_amount)
         } else {
-            0
-        }
+            self.split_partner_treasury(split_amount)?
+        };
+
+        Ok(FeeSplit {
+            protocol_amount,
+            protocol_fee_recipient,
+            partner_amount,
+            treasury_amount,
+        })
     }
 
-    pub fn get_oracle_countdown(&self, current_time: i64) -> i64 {
-        let next_oracle_time = self.last_oracle_update + self.oracle_update_interval;
-        (next_oracle_time - current_time).max(0)
+    /// Live backing of RIFT supply by the main vault, in basis points (10000 = fully backed)
+    /// Returns 10000 when nothing has been minted yet
+    pub fn get_live_backing_ratio_bps(&self, vault_amount: u64) -> Result<u64> {
+        if self.total_rift_minted == 0 {
+            return Ok(10000);
+        }
+
+        let backing_bps = u128::from(vault_amount)
+            .checked_mul(10000)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(u128::from(self.total_rift_minted))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(u64::try_from(backing_bps).unwrap_or(u64::MAX))
     }
 
-    pub fn get_rebalance_countdown(&self, current_time: i64) -> i64 {
-        let next_rebalance_time = self.last_rebalance + self.max_rebalance_interval;
-        (next_rebalance_time - current_time).max(0)
+    /// Vault-backed ratio at backing_ratio precision (1_000_000 = 100%)
+    /// Zero supply is fully backed by definition
+    pub fn compute_backing_ratio(&self, vault_amount: u64) -> Result<u64> {
+        if self.total_rift_minted == 0 {
+            return Ok(1_000_000);
+        }
+
+        let ratio = u128::from(vault_amount)
+            .checked_mul(1_000_000)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(u128::from(self.total_rift_minted))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(u64::try_from(ratio).unwrap_or(u64::MAX))
     }
 }
 
@@ -6090,6 +15071,7 @@ pub struct RiftCreated {
 pub struct RiftClosed {
     pub rift: Pubkey,
     pub creator: Pubkey,
+    pub reclaimed_lamports: u64, // Rift + vault rent returned to creator
 }
 
 #[event]
@@ -6106,6 +15088,14 @@ pub struct StuckAccountCleaned {
     pub underlying_mint: Pubkey,
 }
 
+#[event]
+pub struct StuckVaultCleaned {
+    pub creator: Pubkey,
+    pub stuck_vault: Pubkey,
+    pub expected_rift: Pubkey,
+    pub rent_returned: u64,
+}
+
 /// **FIX HIGH #1**: Event emitted when reentrancy guard is reset by admin
 #[event]
 pub struct ReentrancyGuardReset {
@@ -6113,6 +15103,44 @@ pub struct ReentrancyGuardReset {
     pub authority: Pubkey,
 }
 
+#[event]
+pub struct RiftPauseUpdated {
+    pub rift: Pubkey,
+    pub paused: bool,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct PositionMigrated {
+    pub source_rift: Pubkey,
+    pub destination_rift: Pubkey,
+    pub user: Pubkey,
+    pub source_rift_burned: u64,
+    pub unwrap_fee: u64,
+    pub underlying_received: u64,
+    pub wrap_fee: u64,
+    pub destination_rift_minted: u64,
+}
+
+#[event]
+pub struct RiftMigrated {
+    pub source_rift: Pubkey,
+    pub destination_rift: Pubkey,
+    pub source_rift_supply: u64,
+    pub source_vault_balance: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct MigratedPositionRedeemed {
+    pub source_rift: Pubkey,
+    pub destination_rift: Pubkey,
+    pub user: Pubkey,
+    pub source_rift_burned: u64,
+    pub underlying_moved: u64,
+    pub destination_rift_minted: u64,
+}
+
 /// **FIX ISSUE #5**: Event emitted when oracle change is proposed
 #[event]
 pub struct OracleChangeProposed {
@@ -6150,12 +15178,23 @@ pub struct TokensWrapped {
     pub rift_tokens_minted: u64,
 }
 
+#[event]
+pub struct WrapExecuted {
+    pub rift: Pubkey,
+    pub user: Pubkey,
+    pub underlying_deposited: u64,
+    pub fee_amount: u64,
+    pub fee_bps: u16, // Effective rate charged (0 during the launch window or for exempt wrappers)
+    pub rift_minted: u64,
+}
+
 #[event]
 pub struct UnwrapExecuted {
     pub rift: Pubkey,
     pub user: Pubkey,
     pub rift_token_amount: u64,
     pub fee_amount: u64,
+    pub fee_bps: u16, // Effective rate charged (0 during the launch window or for exempt wrappers)
     pub underlying_returned: u64,
 }
 
@@ -6173,13 +15212,13 @@ pub enum ErrorCode {
     #[msg("Invalid account data")] 
     InvalidAccountData,
 
-    #[msg("Invalid partner fee (max 5%)")]
+    #[msg("Invalid partner fee split (max 10000 basis points)")]
     InvalidPartnerFee,
     #[msg("Invalid trading fee (max 1%)")]
     InvalidTradingFee,
     #[msg("Invalid transfer fee (must be 0.7%-1% = 70-100 basis points)")]
     InvalidTransferFee,
-    #[msg("Rift name must end with '_RIFT' or 'RIFT'")]
+    #[msg("Rift name must be non-empty UTF-8 without control characters")]
     InvalidRiftName,
     #[msg("Rift name too long (max 32 chars)")]
     NameTooLong,
@@ -6199,7 +15238,7 @@ pub enum ErrorCode {
     VaultNotEmpty,
     #[msg("Invalid stuck account - does not match expected PDA")]
     InvalidStuckAccount,
-    #[msg("Rift already exists - not a stuck account")]
+    #[msg("Rift already exists (duplicate creation, or not a stuck account)")]
     RiftAlreadyExists,
     #[msg("Math overflow")]
     MathOverflow,
@@ -6357,13 +15396,149 @@ pub enum ErrorCode {
     InvalidRift,
     #[msg("Invalid vanity seed length - seed_len exceeds vanity_seed array bounds")]
     InvalidVanitySeedLength,
+    #[msg("Vault backing below minimum threshold - wrapping halted (unwraps still allowed)")]
+    BackingBelowThreshold,
+    #[msg("Invalid backing threshold - must be between 5000 and 10000 basis points")]
+    InvalidBackingThreshold,
+    #[msg("Oracle change proposed too recently - wait at least 1 hour between proposals")]
+    OracleProposalTooFrequent,
+    #[msg("Insufficient oracle samples for TWAP - at least 2 fresh samples required")]
+    InsufficientTwapSamples,
+    #[msg("Daily snapshot already emitted - wait 24 hours between snapshots")]
+    SnapshotTooFrequent,
+    #[msg("Transaction deadline exceeded")]
+    DeadlineExceeded,
+    #[msg("Unwrap rejected: wrap occurred in the same slot")]
+    SameSlotWrapUnwrap,
+    #[msg("Rift is closing - new wraps are disabled")]
+    RiftClosing,
+    #[msg("Rift is not closing - call begin_close first")]
+    RiftNotClosing,
+    #[msg("Rift is paused")]
+    RiftPaused,
+    #[msg("Rift must be paused for this operation")]
+    RiftNotPaused,
+    #[msg("RIFT transfer fee plus underlying transfer fee exceeds max_total_leakage_bps")]
+    ExcessiveCombinedFee,
+    #[msg("Invalid protocol fee (max 1000 basis points)")]
+    InvalidProtocolFee,
+    #[msg("Protocol fee account required when a protocol fee is configured")]
+    MissingProtocolFeeAccount,
+    #[msg("Invalid protocol fee account (wrong owner or mint)")]
+    InvalidProtocolFeeAccount,
+    #[msg("Oracle price is older than max_oracle_age")]
+    OracleStale,
+    #[msg("Invalid oracle freshness policy (max age must be 60-86400 seconds)")]
+    InvalidOracleFreshnessPolicy,
+    #[msg("Wrap cooldown active - wait before wrapping again")]
+    WrapCooldownActive,
+    #[msg("Associated token program required to create the RIFT token account")]
+    MissingAssociatedTokenProgram,
+    #[msg("Wrap amount below the rift minimum")]
+    WrapAmountTooSmall,
+    #[msg("Wrap amount above the rift maximum")]
+    WrapAmountTooLarge,
+    #[msg("Invalid wrap limits (min must not exceed max)")]
+    InvalidWrapLimits,
+    #[msg("Invalid fee tiers (thresholds must ascend, each fee max 100 basis points)")]
+    InvalidFeeTiers,
+    #[msg("Minting would overflow the RIFT mint supply")]
+    SupplyOverflow,
+    #[msg("Fee exemption does not belong to this rift and signer")]
+    InvalidFeeExemption,
+    #[msg("Metadata URI exceeds 200 characters")]
+    MetadataUriTooLong,
+    #[msg("Vault holds dust: pass underlying_mint and creator_underlying to forward it")]
+    CloseDustAccountRequired,
+    #[msg("RIFT mint holds no lamports above its rent-exempt minimum")]
+    NoMintRentSurplus,
+    #[msg("Not supported for rifts with a classic SPL Token RIFT mint")]
+    LegacyMintUnsupported,
+    #[msg("Emergency unwrap requires the oracle to be stale beyond max_rebalance_interval")]
+    OracleNotStale,
+    #[msg("Invalid protocol authority (must be non-default, admin keys must differ)")]
+    InvalidAuthority,
+    #[msg("Rift underlying is not the native wSOL mint")]
+    NotNativeSolRift,
+    #[msg("Reentrancy timeout must be between 150 and 432000 slots")]
+    InvalidReentrancyTimeout,
+    #[msg("Lamport sweep requires a Token-2022 vault (WithdrawExcessLamports)")]
+    LamportSweepUnsupported,
+    #[msg("Vault holds no lamports above its rent-exempt minimum")]
+    NoLamportSurplus,
+    #[msg("Unwrap leakage tolerance cannot exceed 10000 bps")]
+    InvalidUnwrapLeakage,
+    #[msg("Underlying transfer lost more than the rift's tolerated unwrap leakage")]
+    DeflationaryLossExceeded,
+    #[msg("Maximum transfer fee must be nonzero")]
+    InvalidMaxTransferFee,
+    #[msg("Manual oracle price deviates from the reference feed beyond arbitrage_threshold_bps")]
+    ManualOracleDeviatesFromReference,
+    #[msg("Rift configuration is frozen (mint authorities renounced)")]
+    ConfigFrozen,
+    #[msg("Minimum fee units exceeds the allowed maximum")]
+    InvalidMinFeeUnits,
+    #[msg("Creator has reached their vanity rift limit")]
+    CreatorRiftLimitReached,
+    #[msg("Underlying mint is not allowed by the protocol mint policy")]
+    UnderlyingMintNotAllowed,
+    #[msg("Invalid mint policy mode (0 = denylist, 1 = allowlist)")]
+    InvalidMintPolicyMode,
+    #[msg("Direct withheld claim requires no partner share and no protocol cut")]
+    DirectClaimNotAllowed,
+    #[msg("Invalid symbol (must be valid UTF-8, at most 16 bytes)")]
+    InvalidSymbol,
+    #[msg("Oracle update does not match the rift's configured oracle source")]
+    OracleSourceMismatch,
+    #[msg("Oracle source is not supported by this program")]
+    OracleSourceUnsupported,
+    #[msg("New creator must be a non-default key different from the current creator")]
+    InvalidNewCreator,
+    #[msg("No creator transfer is pending")]
+    NoCreatorTransferPending,
+    #[msg("Oracle confidence is tighter than the rift's min_confidence_bps floor")]
+    OracleConfidenceTooTight,
+    #[msg("Minimum confidence must be at most 500 bps")]
+    InvalidMinConfidence,
+    #[msg("Fee-free launch window must be at most 72 hours")]
+    InvalidFeeFreeWindow,
+    #[msg("Underlying is a RIFT mint; nested rifts require allow_nested")]
+    NestedRiftNotAllowed,
+    #[msg("Oracle history length must be between 2 and 32 samples")]
+    InvalidOracleHistoryLength,
+    #[msg("Rift reads prices from an oracle history account that was not provided")]
+    OracleHistoryRequired,
+    #[msg("Oracle history account does not match rift.oracle_history")]
+    OracleHistoryMismatch,
+    #[msg("Withheld vault did not receive the mint's full withheld amount")]
+    MintWithheldHarvestMismatch,
+    #[msg("Max unwrap vault fraction must be at most 10000 bps")]
+    InvalidUnwrapFraction,
+    #[msg("Unwrap exceeds the maximum share of the vault allowed in one transaction")]
+    UnwrapExceedsVaultFraction,
+    #[msg("Rift is winding down - new wraps are disabled")]
+    RiftWindingDown,
+    #[msg("Creator cannot fund rent for the RIFT mint and all three vaults")]
+    InsufficientRentFunds,
+    #[msg("Account already uses the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Rift is already migrating to another rift")]
+    RiftAlreadyMigrating,
+    #[msg("Rift is not migrating to this destination")]
+    RiftNotMigrating,
+    #[msg("Close delay has not elapsed since begin_close")]
+    CloseDelayActive,
+    #[msg("Vault holds withheld transfer fees: pass its mint so they can be harvested")]
+    CloseMintAccountRequired,
 }
 
 /// **SECURITY FIX #50**: Oracle type enum for event emission
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+/// Also stored as `Rift.oracle_source` to pin which update instruction is accepted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OracleType {
     Switchboard,
     Manual, // Manual price updates (Jupiter API, etc.)
+    Pyth,   // Reserved - no Pyth update path yet
 }
 
 // Events
@@ -6382,12 +15557,14 @@ pub struct WithheldFeesClaimed {
     pub destination: Pubkey, // Withheld vault where fees are sent
     pub source_account: Pubkey,
     pub claimer: Pubkey,
+    pub keeper: Pubkey, // Signer that performed the harvest (keeper reward attribution)
 }
 
 #[event]
 pub struct TransferFeeUpdated {
     pub rift: Pubkey,
     pub new_fee_bps: u16,
+    pub max_transfer_fee: u64,
     pub authority: Pubkey,
 }
 
@@ -6411,7 +15588,308 @@ pub struct WithheldVaultWithdrawn {
 pub struct WithheldFeesDistributed {
     pub rift: Pubkey,
     pub amount: u64,
+    pub protocol_amount: u64,
+    pub treasury_amount: u64,
+    pub partner_amount: u64,
+    pub distributor: Pubkey,
+    pub keeper: Pubkey, // Distributing signer for keeper rewards (default = not a keeper call)
+}
+
+#[event]
+pub struct TwapQuoted {
+    pub rift: Pubkey,
+    pub twap_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartnerFeeUpdated {
+    pub rift: Pubkey,
+    pub old_partner_fee_bps: u16,
+    pub new_partner_fee_bps: u16,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct DailySnapshot {
+    pub rift: Pubkey,
+    pub total_underlying_wrapped: u64,
+    pub total_rift_minted: u64,
+    pub backing_ratio: u64,
+    pub total_fees_collected: u64,
+    pub avg_oracle_price: u64,
+    pub keeper: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesDistributed {
+    pub rift: Pubkey,
+    pub amount: u64,
+    pub protocol_skim_amount: u64, // Wrap/unwrap skim paid to the global treasury
+    pub protocol_amount: u64,
     pub treasury_amount: u64,
     pub partner_amount: u64,
     pub distributor: Pubkey,
+    pub keeper: Pubkey, // Distributing signer for keeper rewards (default = not a keeper call)
+}
+
+#[event]
+pub struct RiftCloseStarted {
+    pub rift: Pubkey,
+    pub creator: Pubkey,
+    pub outstanding_rift: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RiftCloseFinalized {
+    pub rift: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionFeeUpdated {
+    pub old_distribution_fee_bps: u16,
+    pub new_distribution_fee_bps: u16,
+    pub protocol_fee_recipient: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct FeeExemptionAdded {
+    pub rift: Pubkey,
+    pub wallet: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct FeeExemptionRemoved {
+    pub rift: Pubkey,
+    pub wallet: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct RebalanceTriggered {
+    pub rift: Pubkey,
+    pub backing_ratio: u64,
+    pub keeper: Pubkey, // Signer that triggered the rebalance (keeper reward attribution)
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintRentReclaimed {
+    pub rift: Pubkey,
+    pub rift_mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct GlobalAuthorityUpdated {
+    pub old_program_authority: Pubkey,
+    pub new_program_authority: Pubkey,
+    pub old_admin_authority_2: Pubkey,
+    pub new_admin_authority_2: Pubkey,
+    pub old_treasury_wallet: Pubkey,
+    pub new_treasury_wallet: Pubkey,
+}
+
+#[event]
+pub struct ReentrancyTimeoutUpdated {
+    pub rift: Pubkey,
+    pub old_timeout_slots: u64,
+    pub new_timeout_slots: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct BackingRatioUpdated {
+    pub rift: Pubkey,
+    pub old_backing_ratio: u64,
+    pub new_backing_ratio: u64,
+    pub vault_amount: u64,
+    pub total_rift_minted: u64,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub rift: Pubkey,
+    pub old_guardian: Option<Pubkey>,
+    pub new_guardian: Option<Pubkey>,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct LamportsSwept {
+    pub rift: Pubkey,
+    pub source: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct MintAuthoritiesRenounced {
+    pub rift: Pubkey,
+    pub rift_mint: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct OracleParamsUpdated {
+    pub rift: Pubkey,
+    pub oracle_update_interval: i64,
+    pub max_rebalance_interval: i64,
+    pub arbitrage_threshold_bps: u16,
+    pub creator: Pubkey,
+}
+
+/// Manual oracle cumulative drift baseline initialized (first manual update)
+/// The baseline never rolls over: later updates are capped at 30% drift from base_price
+#[event]
+pub struct OracleDriftBaselineSet {
+    pub rift: Pubkey,
+    pub base_price: u64,
+    pub window_start: i64,
+}
+
+#[event]
+pub struct CreatorRiftLimitUpdated {
+    pub creator: Pubkey,
+    pub old_max_rifts: u32,
+    pub new_max_rifts: u32,
+    pub rift_count: u32,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct MintPolicyModeUpdated {
+    pub old_mint_policy_mode: u8,
+    pub new_mint_policy_mode: u8,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct MintPolicyUpdated {
+    pub underlying_mint: Pubkey,
+    pub allowed: bool,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct FeesDrainedForClose {
+    pub rift: Pubkey,
+    pub fees_amount: u64,
+    pub wrap_fees_amount: u64,
+    pub withheld_amount: u64,
+    pub distributor: Pubkey,
+}
+
+/// Withheld fees harvested straight to treasury (claim + distribute in one step)
+#[event]
+pub struct WithheldFeesClaimedDirect {
+    pub rift: Pubkey,
+    pub source_account: Pubkey,
+    pub treasury_account: Pubkey,
+    pub amount: u64,
+    pub claimer: Pubkey,
+}
+
+#[event]
+pub struct OracleSourceUpdated {
+    pub rift: Pubkey,
+    pub old_source: OracleType,
+    pub new_source: OracleType,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct WrapAndTransferExecuted {
+    pub rift: Pubkey,
+    pub user: Pubkey,
+    pub destination: Pubkey,
+    pub rift_minted: u64,
+    pub destination_received: u64, // After the RIFT transfer fee withheld on the destination
+}
+
+#[event]
+pub struct ProtocolFeeUpdated {
+    pub old_protocol_fee_bps: u16,
+    pub new_protocol_fee_bps: u16,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct RebalanceRewardPaid {
+    pub rift: Pubkey,
+    pub keeper: Pubkey,
+    pub reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbitrageMetricUpdated {
+    pub rift: Pubkey,
+    pub oracle_price: u64,
+    pub backing_ratio: u64,
+    pub price_deviation: u64,
+    pub arbitrage_opportunity_bps: u16,
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorTransferProposed {
+    pub rift: Pubkey,
+    pub current_creator: Pubkey,
+    pub pending_creator: Pubkey,
+}
+
+#[event]
+pub struct CreatorTransferAccepted {
+    pub rift: Pubkey,
+    pub old_creator: Pubkey,
+    pub new_creator: Pubkey,
+}
+
+#[event]
+pub struct WrapFeesClaimed {
+    pub rift: Pubkey,
+    pub amount: u64,
+    pub protocol_skim_amount: u64,
+    pub protocol_amount: u64,
+    pub partner_amount: u64,
+    pub treasury_amount: u64,
+    pub claimer: Pubkey,
+}
+
+/// Transfer fees withheld at the mint swept into withheld_vault
+#[event]
+pub struct MintWithheldFeesHarvested {
+    pub rift: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub claimer: Pubkey,
+}
+
+/// A stuck reentrancy guard was cleared by the timeout path (an earlier operation failed mid-way)
+#[event]
+pub struct ReentrancyGuardAutoCleared {
+    pub rift: Pubkey,
+    pub set_at_slot: u64,
+    pub cleared_at_slot: u64,
+}
+
+#[event]
+pub struct WindDownStarted {
+    pub rift: Pubkey,
+    pub creator: Pubkey,
+    pub outstanding_rift: u64,
+    pub timestamp: i64,
 }
@@ -0,0 +1,80 @@
+use super::*;
+
+const WRAP_AMOUNT: u64 = 1_000_000;
+
+/// First-time wrapper: funded underlying account, RIFT ATA not created yet
+fn first_time_user(env: &Env, rift: &TestRift) -> TestUser {
+    let user = rift.new_user(env, WRAP_AMOUNT);
+    env.set_account(user.rift_tokens, system_program::ID, 0, &[]);
+    rift.prepare_wrap(env, &user);
+    user
+}
+
+fn wrap_creating_ata(
+    env: &Env,
+    rift: &TestRift,
+    user: &TestUser,
+    associated_token_program: Option<Pubkey>,
+) -> ProgramResult {
+    env.call(
+        accounts::WrapTokens {
+            associated_token_program,
+            ..rift.wrap_accounts(user)
+        },
+        instruction::WrapTokens {
+            create_rift_ata: true,
+            ..TestRift::wrap_args(WRAP_AMOUNT)
+        },
+    )
+}
+
+#[test]
+fn missing_rift_ata_is_rejected_without_opt_in() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let user = first_time_user(&env, &rift);
+
+        assert_error(
+            env.call(
+                accounts::WrapTokens {
+                    associated_token_program: Some(spl_associated_token_account::ID),
+                    ..rift.wrap_accounts(&user)
+                },
+                TestRift::wrap_args(WRAP_AMOUNT),
+            ),
+            ErrorCode::InvalidTokenAccount,
+        );
+    });
+}
+
+#[test]
+fn auto_create_rejects_non_canonical_account() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let mut user = first_time_user(&env, &rift);
+        user.rift_tokens = Pubkey::new_unique();
+
+        assert_error(
+            wrap_creating_ata(&env, &rift, &user, Some(spl_associated_token_account::ID)),
+            ErrorCode::InvalidTokenAccount,
+        );
+        // Nothing was created at the bogus address
+        assert_eq!(env.owner(&user.rift_tokens), system_program::ID);
+    });
+}
+
+#[test]
+fn auto_create_requires_associated_token_program() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let user = first_time_user(&env, &rift);
+
+        assert_error(
+            wrap_creating_ata(&env, &rift, &user, None),
+            ErrorCode::MissingAssociatedTokenProgram,
+        );
+    });
+}
@@ -0,0 +1,337 @@
+use super::*;
+
+fn begin_close(env: &Env, rift: &TestRift) -> ProgramResult {
+    env.call(
+        accounts::BeginClose {
+            creator: rift.creator,
+            rift: rift.rift,
+        },
+        instruction::BeginClose {},
+    )
+}
+
+fn close_accounts(rift: &TestRift) -> accounts::CloseRift {
+    accounts::CloseRift {
+        creator: rift.creator,
+        rift: rift.rift,
+        vault: rift.vault,
+        fees_vault: rift.fees_vault,
+        withheld_vault: rift.withheld_vault,
+        vault_authority: rift.vault_authority,
+        wrap_fees_vault: None,
+        token_program: spl_token::ID,
+        token_2022_program: spl_token_2022::ID,
+        underlying_mint: None,
+        rift_mint: rift.rift_mint,
+        creator_underlying: None,
+    }
+}
+
+fn finalize_close(env: &Env, rift: &TestRift) -> ProgramResult {
+    env.call(close_accounts(rift), instruction::FinalizeClose {})
+}
+
+fn can_close(env: &Env, rift: &TestRift) -> u16 {
+    env.view(
+        accounts::CanClose {
+            rift: rift.rift,
+            vault: rift.vault,
+            fees_vault: rift.fees_vault,
+            withheld_vault: rift.withheld_vault,
+            wrap_fees_vault: None,
+        },
+        rifts_protocol::can_close,
+    )
+    .unwrap()
+}
+
+/// Rift whose vaults were never initialized: closing it needs no token program CPIs
+fn rift_without_vaults(env: &Env) -> TestRift {
+    let rift = TestRift::new(env);
+    rift.update(env, |state| {
+        state.vault = system_program::ID;
+        state.fees_vault = system_program::ID;
+        state.withheld_vault = system_program::ID;
+    });
+    rift
+}
+
+#[test]
+fn begin_close_blocks_wraps_but_not_unwraps() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let user = rift.new_user(&env, 1_000_000);
+        rift.prepare_wrap(&env, &user);
+        rift.update(&env, |state| state.total_rift_minted = 1_000_000);
+
+        begin_close(&env, &rift).unwrap();
+
+        let state = rift.state(&env);
+        assert!(state.closing);
+        assert_eq!(state.close_started_at, clock().unix_timestamp);
+        assert_error(
+            env.call(rift.wrap_accounts(&user), TestRift::wrap_args(1_000_000)),
+            ErrorCode::RiftClosing,
+        );
+        // Holders can still exit until finalize_close
+        let plan = state
+            .plan_unwrap(1_000_000, 1_000_000, false, false, clock().unix_timestamp)
+            .unwrap();
+        assert_eq!(plan.rift_burned, 1_000_000);
+    });
+}
+
+#[test]
+fn begin_close_is_creator_only_and_once() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        assert_error(
+            env.call(
+                accounts::BeginClose {
+                    creator: env.wallet(USER_LAMPORTS),
+                    rift: rift.rift,
+                },
+                instruction::BeginClose {},
+            ),
+            ErrorCode::UnauthorizedClose,
+        );
+
+        begin_close(&env, &rift).unwrap();
+        assert_error(begin_close(&env, &rift), ErrorCode::RiftClosing);
+    });
+}
+
+#[test]
+fn finalize_close_requires_begin_close() {
+    run(|| {
+        let env = Env::new();
+        let rift = rift_without_vaults(&env);
+
+        assert_error(finalize_close(&env, &rift), ErrorCode::RiftNotClosing);
+    });
+}
+
+#[test]
+fn finalize_close_waits_for_close_delay() {
+    run(|| {
+        let env = Env::new();
+        let rift = rift_without_vaults(&env);
+        begin_close(&env, &rift).unwrap();
+
+        warp_seconds(CLOSE_DELAY_SECONDS - 1);
+        assert_error(finalize_close(&env, &rift), ErrorCode::CloseDelayActive);
+        assert!(rift.state(&env).closing);
+    });
+}
+
+#[test]
+fn finalize_close_rejects_outstanding_rift() {
+    run(|| {
+        let env = Env::new();
+        let rift = rift_without_vaults(&env);
+        rift.update(&env, |state| state.total_rift_minted = 1);
+        begin_close(&env, &rift).unwrap();
+        warp_seconds(CLOSE_DELAY_SECONDS);
+
+        assert_error(finalize_close(&env, &rift), ErrorCode::VaultNotEmpty);
+    });
+}
+
+#[test]
+fn two_phase_close_reclaims_rent() {
+    run(|| {
+        let env = Env::new();
+        let rift = rift_without_vaults(&env);
+        let rift_rent = env.lamports(&rift.rift);
+        let creator_lamports = env.lamports(&rift.creator);
+
+        begin_close(&env, &rift).unwrap();
+        warp_seconds(CLOSE_DELAY_SECONDS);
+        finalize_close(&env, &rift).unwrap();
+
+        assert_eq!(env.lamports(&rift.rift), 0);
+        assert_eq!(env.owner(&rift.rift), system_program::ID);
+        assert_eq!(env.lamports(&rift.creator), creator_lamports + rift_rent);
+    });
+}
+
+#[test]
+fn close_rift_is_single_shot() {
+    run(|| {
+        let env = Env::new();
+        let rift = rift_without_vaults(&env);
+        let rift_rent = env.lamports(&rift.rift);
+        let creator_lamports = env.lamports(&rift.creator);
+
+        // No begin_close and no notice period
+        env.call(close_accounts(&rift), instruction::CloseRift {})
+            .unwrap();
+
+        assert_eq!(env.lamports(&rift.rift), 0);
+        assert_eq!(env.lamports(&rift.creator), creator_lamports + rift_rent);
+    });
+}
+
+/// Rift past its close notice with `dust` underlying stranded in the (otherwise empty) vault
+fn wound_down_rift_with_dust(env: &Env, dust: u64) -> TestRift {
+    let rift = TestRift::new(env);
+    mint_to(
+        env,
+        &rift.underlying_mint,
+        &rift.vault,
+        &rift.underlying_mint_authority,
+        dust,
+    );
+    begin_close(env, &rift).unwrap();
+    warp_seconds(CLOSE_DELAY_SECONDS);
+    rift
+}
+
+#[test]
+fn vault_dust_within_tolerance_counts_as_empty() {
+    run(|| {
+        let env = Env::new();
+        let rift = wound_down_rift_with_dust(&env, CLOSE_DUST_TOLERANCE);
+
+        assert_eq!(can_close(&env, &rift), 0);
+    });
+}
+
+#[test]
+fn vault_dust_above_tolerance_blocks_close() {
+    run(|| {
+        let env = Env::new();
+        let rift = wound_down_rift_with_dust(&env, CLOSE_DUST_TOLERANCE + 1);
+
+        assert_eq!(can_close(&env, &rift), CLOSE_BLOCKED_VAULT);
+        assert_error(finalize_close(&env, &rift), ErrorCode::VaultNotEmpty);
+    });
+}
+
+#[test]
+fn dust_tolerance_does_not_relax_rift_supply() {
+    run(|| {
+        let env = Env::new();
+        let rift = wound_down_rift_with_dust(&env, 1);
+        rift.update(&env, |state| state.total_rift_minted = 1);
+
+        assert_eq!(can_close(&env, &rift), CLOSE_BLOCKED_RIFT_SUPPLY);
+        assert_error(finalize_close(&env, &rift), ErrorCode::VaultNotEmpty);
+    });
+}
+
+#[test]
+fn closing_with_dust_requires_a_destination_for_it() {
+    run(|| {
+        let env = Env::new();
+        let rift = wound_down_rift_with_dust(&env, 3);
+
+        // Without creator_underlying the dust has nowhere to go, so the close is refused
+        // rather than leaving the vault unclosable
+        assert_error(
+            finalize_close(&env, &rift),
+            ErrorCode::CloseDustAccountRequired,
+        );
+        assert!(rift.state(&env).closing);
+        assert_eq!(token_balance(&env, &rift.vault), 3);
+    });
+}
+
+/// Rift whose wrap fees go to an initialized wrap_fees_vault holding `balance`
+fn rift_with_wrap_fees(env: &Env, balance: u64) -> (TestRift, Pubkey) {
+    let rift = TestRift::new(env);
+    let wrap_fees_vault = create_token_account(
+        env,
+        rift.underlying_program,
+        pda(&[b"wrap_fees_vault", rift.rift.as_ref()]),
+        rift.underlying_mint,
+        rift.vault_authority,
+    );
+    if balance > 0 {
+        mint_to(
+            env,
+            &rift.underlying_mint,
+            &wrap_fees_vault,
+            &rift.underlying_mint_authority,
+            balance,
+        );
+    }
+    rift.update(env, |state| state.wrap_fees_vault = Some(wrap_fees_vault));
+    (rift, wrap_fees_vault)
+}
+
+fn drain_and_prepare_close(
+    env: &Env,
+    rift: &TestRift,
+    wrap_fees_vault: Option<Pubkey>,
+    treasury_underlying_account: Pubkey,
+) -> ProgramResult {
+    env.call(
+        accounts::DrainAndPrepareClose {
+            payer: rift.creator,
+            rift: rift.rift,
+            vault_authority: rift.vault_authority,
+            underlying_mint: rift.underlying_mint,
+            rift_mint: rift.rift_mint,
+            fees_vault: Some(rift.fees_vault),
+            withheld_vault: Some(rift.withheld_vault),
+            wrap_fees_vault,
+            treasury_underlying_account,
+            partner_underlying_account: None,
+            protocol_underlying_account: None,
+            treasury_rift_account: None,
+            partner_rift_account: None,
+            protocol_rift_account: None,
+            protocol_treasury_account: None,
+            global_config: pda(&[b"global_config"]),
+            underlying_token_program: rift.underlying_program,
+            token_2022_program: Some(spl_token_2022::ID),
+        },
+        instruction::DrainAndPrepareClose {},
+    )
+}
+
+#[test]
+fn drain_requires_the_initialized_wrap_fees_vault() {
+    run(|| {
+        let env = Env::new();
+        let (rift, _) = rift_with_wrap_fees(&env, 1_000);
+
+        assert_error(
+            drain_and_prepare_close(&env, &rift, None, Pubkey::new_unique()),
+            ErrorCode::InvalidFeesVault,
+        );
+    });
+}
+
+#[test]
+fn drain_pays_wrap_fees_to_the_validated_recipients() {
+    run(|| {
+        let env = Env::new();
+        let (rift, wrap_fees_vault) = rift_with_wrap_fees(&env, 1_000);
+        let stranger = rift.new_user(&env, 0);
+
+        // The wrap fee payout goes through the same recipient checks as the fees vault
+        assert_error(
+            drain_and_prepare_close(&env, &rift, Some(wrap_fees_vault), stranger.underlying),
+            ErrorCode::InvalidTreasuryVault,
+        );
+        assert_eq!(token_balance(&env, &wrap_fees_vault), 1_000);
+    });
+}
+
+#[test]
+fn drain_accepts_an_empty_wrap_fees_vault() {
+    run(|| {
+        let env = Env::new();
+        let (rift, wrap_fees_vault) = rift_with_wrap_fees(&env, 0);
+        rift.update(&env, |state| state.total_fees_collected = 7);
+
+        drain_and_prepare_close(&env, &rift, Some(wrap_fees_vault), Pubkey::new_unique()).unwrap();
+
+        assert_eq!(rift.state(&env).total_fees_collected, 0);
+    });
+}
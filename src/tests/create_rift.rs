@@ -0,0 +1,100 @@
+use super::*;
+
+/// create_rift for a fresh underlying; the handler runs up to its first CPI, so only its
+/// rejections are observable
+fn create_rift(env: &Env, options: &RiftOptions) -> ProgramResult {
+    let (underlying_mint, _) = create_underlying_mint(env, options);
+    let creator = env.wallet(USER_LAMPORTS);
+    let accounts = create_rift_accounts(creator, underlying_mint, options.underlying_program);
+    prepare_create_rift(env, &accounts);
+    env.call(accounts, create_rift_args(options))
+}
+
+fn fee_underlying(underlying_fee_bps: u16, max_total_leakage_bps: Option<u16>) -> RiftOptions {
+    RiftOptions {
+        underlying_program: spl_token_2022::ID,
+        underlying_transfer_fee_bps: Some(underlying_fee_bps),
+        transfer_fee_bps: MAX_LAUNCH_TRANSFER_FEE_BPS,
+        max_total_leakage_bps,
+        ..RiftOptions::default()
+    }
+}
+
+#[test]
+fn combined_fee_above_cap_is_rejected() {
+    run(|| {
+        let env = Env::new();
+
+        assert_error(
+            create_rift(&env, &fee_underlying(100, Some(199))),
+            ErrorCode::ExcessiveCombinedFee,
+        );
+        assert_error(
+            create_rift(&env, &fee_underlying(1, Some(MAX_LAUNCH_TRANSFER_FEE_BPS))),
+            ErrorCode::ExcessiveCombinedFee,
+        );
+    });
+}
+
+#[test]
+fn default_cap_preserves_existing_behavior() {
+    // Every launch fee combined with any accepted (<= 1%) underlying fee
+    for transfer_fee_bps in [MIN_LAUNCH_TRANSFER_FEE_BPS, MAX_LAUNCH_TRANSFER_FEE_BPS] {
+        check_combined_leakage(transfer_fee_bps, 100, None).unwrap();
+    }
+    assert_eq!(
+        check_combined_leakage(MAX_LAUNCH_TRANSFER_FEE_BPS, 101, None).unwrap_err(),
+        ErrorCode::ExcessiveCombinedFee.into()
+    );
+}
+
+#[test]
+fn combined_fee_at_cap_is_accepted() {
+    check_combined_leakage(MAX_LAUNCH_TRANSFER_FEE_BPS, 50, Some(150)).unwrap();
+    check_combined_leakage(
+        MAX_LAUNCH_TRANSFER_FEE_BPS,
+        0,
+        Some(MAX_LAUNCH_TRANSFER_FEE_BPS),
+    )
+    .unwrap();
+    assert_eq!(
+        check_combined_leakage(MAX_LAUNCH_TRANSFER_FEE_BPS, 51, Some(150)).unwrap_err(),
+        ErrorCode::ExcessiveCombinedFee.into()
+    );
+}
+
+#[test]
+fn underlying_fee_over_one_percent_is_rejected_first() {
+    run(|| {
+        let env = Env::new();
+
+        assert_error(
+            create_rift(&env, &fee_underlying(101, Some(u16::MAX))),
+            ErrorCode::ExcessiveTransferFee,
+        );
+    });
+}
+
+#[test]
+fn duplicate_create_is_rift_already_exists() {
+    run(|| {
+        let env = Env::new();
+        // State left behind by the first, successful submission
+        let rift = TestRift::new(&env);
+        let accounts =
+            create_rift_accounts(rift.creator, rift.underlying_mint, rift.underlying_program);
+        allocate_program_account(
+            &env,
+            accounts.rift_mint_registry,
+            RiftMintRegistry::SIZE,
+            RiftMintRegistry::DISCRIMINATOR,
+        );
+        let before = env.data(&rift.rift);
+
+        assert_error(
+            env.call(accounts, create_rift_args(&RiftOptions::default())),
+            ErrorCode::RiftAlreadyExists,
+        );
+        assert_eq!(env.data(&rift.rift), before);
+    });
+}
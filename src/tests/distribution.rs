@@ -0,0 +1,117 @@
+use super::*;
+
+fn plan_fee_split(env: &Env, rift: &TestRift, amount: u64) -> FeeSplit {
+    let global_config = env.account(&pda(&[b"global_config"]));
+    rift.state(env)
+        .plan_fee_split(amount, &global_config)
+        .unwrap()
+}
+
+#[test]
+fn partner_equal_to_treasury_gets_one_combined_share() {
+    run(|| {
+        let env = Env::new();
+        let treasury = Pubkey::from_str_const(DEFAULT_TREASURY_WALLET);
+        let rift = TestRift::with_options(
+            &env,
+            RiftOptions {
+                partner_wallet: Some(treasury),
+                ..RiftOptions::default()
+            },
+        );
+
+        let split = plan_fee_split(&env, &rift, 1_001);
+        assert_eq!(split.protocol_amount, 0);
+        assert_eq!(split.partner_amount, 0);
+        assert_eq!(split.treasury_amount, 1_001);
+    });
+}
+
+#[test]
+fn distinct_partner_and_treasury_split_by_partner_fee_bps() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        // Treasury keeps the rounding remainder
+        let split = plan_fee_split(&env, &rift, 1_001);
+        assert_eq!(split.partner_amount, 500);
+        assert_eq!(split.treasury_amount, 501);
+    });
+}
+
+fn set_distribution_fee(env: &Env, authority: Pubkey, distribution_fee_bps: u16) -> ProgramResult {
+    env.call(
+        accounts::SetDistributionFee {
+            program_authority: authority,
+            global_config: pda(&[b"global_config"]),
+        },
+        instruction::SetDistributionFee {
+            protocol_fee_recipient: Pubkey::new_unique(),
+            distribution_fee_bps,
+        },
+    )
+}
+
+#[test]
+fn protocol_cut_is_taken_before_the_split() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let recipient = Pubkey::new_unique();
+        init_global_config(&env, recipient, 1_000);
+
+        // 10% off the top, then 50/50 on the remaining 9_000 (not 50/50 of 10_000 minus 10%)
+        let split = plan_fee_split(&env, &rift, 10_001);
+        assert_eq!(split.protocol_amount, 1_000);
+        assert_eq!(split.protocol_fee_recipient, recipient);
+        assert_eq!(split.partner_amount, 4_500);
+        assert_eq!(split.treasury_amount, 4_501);
+        assert_eq!(
+            split.protocol_amount + split.partner_amount + split.treasury_amount,
+            10_001
+        );
+    });
+}
+
+#[test]
+fn protocol_cut_applies_when_partner_is_treasury() {
+    run(|| {
+        let env = Env::new();
+        let treasury = Pubkey::from_str_const(DEFAULT_TREASURY_WALLET);
+        let rift = TestRift::with_options(
+            &env,
+            RiftOptions {
+                partner_wallet: Some(treasury),
+                ..RiftOptions::default()
+            },
+        );
+        init_global_config(&env, Pubkey::new_unique(), 250);
+
+        let split = plan_fee_split(&env, &rift, 10_000);
+        assert_eq!(split.protocol_amount, 250);
+        assert_eq!(split.partner_amount, 0);
+        assert_eq!(split.treasury_amount, 9_750);
+    });
+}
+
+#[test]
+fn distribution_fee_is_capped_at_ten_percent() {
+    run(|| {
+        let env = Env::new();
+        init_global_config(&env, Pubkey::new_unique(), 0);
+
+        assert_error(
+            set_distribution_fee(&env, program_authority(), MAX_DISTRIBUTION_FEE_BPS + 1),
+            ErrorCode::InvalidProtocolFee,
+        );
+        assert_error(
+            set_distribution_fee(&env, Pubkey::new_unique(), 100),
+            ErrorCode::UnauthorizedAdmin,
+        );
+
+        set_distribution_fee(&env, program_authority(), MAX_DISTRIBUTION_FEE_BPS).unwrap();
+        let config: GlobalConfig = env.load(&pda(&[b"global_config"]));
+        assert_eq!(config.distribution_fee_bps, MAX_DISTRIBUTION_FEE_BPS);
+    });
+}
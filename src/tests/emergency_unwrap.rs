@@ -0,0 +1,74 @@
+use super::*;
+
+const REBALANCE_INTERVAL: i64 = 24 * 60 * 60;
+
+/// Rift whose newest oracle sample is `age` seconds old (None = never sampled)
+fn rift_with_oracle_age(env: &Env, age: Option<i64>) -> Rift {
+    warp_seconds(7 * REBALANCE_INTERVAL);
+    let rift = TestRift::new(env);
+    rift.update(env, |state| {
+        state.max_rebalance_interval = REBALANCE_INTERVAL
+    });
+    match age {
+        Some(age) => rift.set_oracle_samples(env, &[(1_000_000, clock().unix_timestamp - age)]),
+        None => rift.set_oracle_samples(env, &[]),
+    }
+    rift.state(env)
+}
+
+fn plan_emergency(state: &Rift) -> Result<UnwrapPlan> {
+    state.plan_unwrap(1_000, 1_000, false, true, clock().unix_timestamp)
+}
+
+#[test]
+fn emergency_unwrap_opens_once_the_oracle_is_stale() {
+    run(|| {
+        let env = Env::new();
+        let state = rift_with_oracle_age(&env, Some(REBALANCE_INTERVAL + 1));
+
+        assert!(state.is_oracle_stale_for_emergency(clock().unix_timestamp));
+        assert_eq!(plan_emergency(&state).unwrap().rift_burned, 1_000);
+    });
+}
+
+#[test]
+fn emergency_unwrap_is_closed_while_the_oracle_is_healthy() {
+    run(|| {
+        let env = Env::new();
+        let state = rift_with_oracle_age(&env, Some(REBALANCE_INTERVAL));
+
+        assert!(!state.is_oracle_stale_for_emergency(clock().unix_timestamp));
+        assert_eq!(
+            plan_emergency(&state).unwrap_err(),
+            ErrorCode::OracleNotStale.into()
+        );
+    });
+}
+
+#[test]
+fn never_sampled_oracle_is_not_stale() {
+    run(|| {
+        let env = Env::new();
+        let state = rift_with_oracle_age(&env, None);
+
+        assert!(!state.is_oracle_stale_for_emergency(clock().unix_timestamp));
+        assert_eq!(
+            plan_emergency(&state).unwrap_err(),
+            ErrorCode::OracleNotStale.into()
+        );
+    });
+}
+
+#[test]
+fn emergency_unwrap_respects_pause() {
+    run(|| {
+        let env = Env::new();
+        let mut state = rift_with_oracle_age(&env, Some(REBALANCE_INTERVAL + 1));
+        state.paused = true;
+
+        assert_eq!(
+            plan_emergency(&state).unwrap_err(),
+            ErrorCode::RiftPaused.into()
+        );
+    });
+}
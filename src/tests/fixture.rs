@@ -0,0 +1,433 @@
+// Rift fixtures: a rift in its post-create_rift state plus funded users with token accounts
+// create_rift itself CPIs into Token-2022 and System, so the fixture writes the state the
+// handler would leave behind (same field values, then apply_default_config)
+
+use super::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+pub const UNDERLYING_DECIMALS: u8 = 6;
+pub const USER_LAMPORTS: u64 = 100_000_000_000;
+
+#[derive(Clone, Copy)]
+pub struct RiftOptions {
+    pub underlying_program: Pubkey,
+    pub underlying_decimals: u8,
+    pub underlying_transfer_fee_bps: Option<u16>,
+    pub transfer_fee_bps: u16,
+    pub max_total_leakage_bps: Option<u16>,
+    pub partner_wallet: Option<Pubkey>,
+}
+
+impl Default for RiftOptions {
+    fn default() -> Self {
+        RiftOptions {
+            underlying_program: spl_token::ID,
+            underlying_decimals: UNDERLYING_DECIMALS,
+            underlying_transfer_fee_bps: None,
+            transfer_fee_bps: RIFT_TRANSFER_FEE_BPS,
+            max_total_leakage_bps: None,
+            partner_wallet: None,
+        }
+    }
+}
+
+pub struct TestRift {
+    pub rift: Pubkey,
+    pub creator: Pubkey,
+    pub underlying_mint: Pubkey,
+    pub underlying_program: Pubkey,
+    pub underlying_mint_authority: Pubkey,
+    pub rift_mint: Pubkey,
+    pub rift_mint_authority: Pubkey,
+    pub vault: Pubkey,
+    pub fees_vault: Pubkey,
+    pub withheld_vault: Pubkey,
+    pub vault_authority: Pubkey,
+}
+
+pub struct TestUser {
+    pub wallet: Pubkey,
+    pub underlying: Pubkey,
+    pub rift_tokens: Pubkey,
+}
+
+pub fn create_rift_args(options: &RiftOptions) -> instruction::CreateRift {
+    let mut rift_name = [0u8; 32];
+    rift_name[..4].copy_from_slice(b"TEST");
+    instruction::CreateRift {
+        partner_wallet: options.partner_wallet,
+        rift_name,
+        name_len: 4,
+        transfer_fee_bps: options.transfer_fee_bps,
+        prefix_type: 0,
+        max_total_leakage_bps: options.max_total_leakage_bps,
+        max_transfer_fee: None,
+        allow_confidential: false,
+        symbol: [0u8; 16],
+        symbol_len: 0,
+        prefix_symbol: false,
+        fee_free_seconds: 0,
+        allow_nested: false,
+    }
+}
+
+pub fn create_rift_accounts(
+    creator: Pubkey,
+    underlying_mint: Pubkey,
+    underlying_program: Pubkey,
+) -> accounts::CreateRift {
+    let rift = pda(&[b"rift", underlying_mint.as_ref(), creator.as_ref()]);
+    let rift_mint = pda(&[b"rift_mint", underlying_mint.as_ref(), creator.as_ref()]);
+    accounts::CreateRift {
+        creator,
+        rift,
+        underlying_mint,
+        rift_mint,
+        rift_mint_authority: pda(&[b"rift_mint_auth", rift.as_ref()]),
+        vault: pda(&[b"vault", rift.as_ref()]),
+        fees_vault: pda(&[b"fees_vault", rift.as_ref()]),
+        withheld_vault: pda(&[b"withheld_vault", rift.as_ref()]),
+        vault_authority: pda(&[b"vault_auth", rift.as_ref()]),
+        token_program: spl_token_2022::ID,
+        system_program: system_program::ID,
+        rent: anchor_lang::solana_program::sysvar::rent::ID,
+        underlying_token_program: underlying_program,
+        rift_mint_registry: pda(&[b"rift_mint_registry", rift_mint.as_ref()]),
+        underlying_registry: pda(&[b"rift_mint_registry", underlying_mint.as_ref()]),
+        global_config: pda(&[b"global_config"]),
+        mint_policy: pda(&[b"mint_policy", underlying_mint.as_ref()]),
+    }
+}
+
+/// Pre-create the init_if_needed accounts of create_rift (empty, program-owned) so the
+/// instruction reaches its handler without the System program; the handler's validation
+/// runs before its first CPI
+pub fn prepare_create_rift(env: &Env, accounts: &accounts::CreateRift) {
+    allocate_program_account(env, accounts.rift, RIFT_ACCOUNT_SIZE, Rift::DISCRIMINATOR);
+    allocate_program_account(
+        env,
+        accounts.rift_mint_registry,
+        RiftMintRegistry::SIZE,
+        RiftMintRegistry::DISCRIMINATOR,
+    );
+}
+
+/// Underlying mint for a new rift; the mint authority is kept so tests can fund users
+pub fn create_underlying_mint(env: &Env, options: &RiftOptions) -> (Pubkey, Pubkey) {
+    let authority = env.wallet(USER_LAMPORTS);
+    let mint = create_mint(
+        env,
+        options.underlying_program,
+        Pubkey::new_unique(),
+        authority,
+        options.underlying_decimals,
+        options.underlying_transfer_fee_bps,
+    );
+    (mint, authority)
+}
+
+impl TestRift {
+    pub fn new(env: &Env) -> Self {
+        Self::with_options(env, RiftOptions::default())
+    }
+
+    pub fn with_options(env: &Env, options: RiftOptions) -> Self {
+        let (underlying_mint, underlying_mint_authority) = create_underlying_mint(env, &options);
+        Self::with_underlying(env, options, underlying_mint, underlying_mint_authority)
+    }
+
+    /// Rift over the canonical wSOL mint (classic SPL Token, 9 decimals)
+    /// The native mint has no authority: fund users with lamports and sync_native instead
+    pub fn wsol(env: &Env) -> Self {
+        let options = RiftOptions {
+            underlying_decimals: 9,
+            ..RiftOptions::default()
+        };
+        let native_mint = create_native_mint(env);
+        Self::with_underlying(env, options, native_mint, Pubkey::default())
+    }
+
+    pub fn with_underlying(
+        env: &Env,
+        options: RiftOptions,
+        underlying_mint: Pubkey,
+        underlying_mint_authority: Pubkey,
+    ) -> Self {
+        let creator = env.wallet(USER_LAMPORTS);
+        let accounts = create_rift_accounts(creator, underlying_mint, options.underlying_program);
+        let test_rift = TestRift {
+            rift: accounts.rift,
+            creator,
+            underlying_mint,
+            underlying_program: options.underlying_program,
+            underlying_mint_authority,
+            rift_mint: accounts.rift_mint,
+            rift_mint_authority: accounts.rift_mint_authority,
+            vault: accounts.vault,
+            fees_vault: accounts.fees_vault,
+            withheld_vault: accounts.withheld_vault,
+            vault_authority: accounts.vault_authority,
+        };
+
+        create_mint(
+            env,
+            spl_token_2022::ID,
+            test_rift.rift_mint,
+            test_rift.rift_mint_authority,
+            options.underlying_decimals,
+            Some(options.transfer_fee_bps),
+        );
+        for vault in [test_rift.vault, test_rift.fees_vault] {
+            create_token_account(
+                env,
+                options.underlying_program,
+                vault,
+                underlying_mint,
+                test_rift.vault_authority,
+            );
+        }
+        create_token_account(
+            env,
+            spl_token_2022::ID,
+            test_rift.withheld_vault,
+            test_rift.rift_mint,
+            test_rift.vault_authority,
+        );
+
+        let rift = test_rift.initial_state(&options);
+        allocate_program_account(env, test_rift.rift, RIFT_ACCOUNT_SIZE, Rift::DISCRIMINATOR);
+        env.store(&test_rift.rift, &rift);
+        test_rift
+    }
+
+    /// Rift fields as left by create_rift
+    fn initial_state(&self, options: &RiftOptions) -> Rift {
+        let now = clock().unix_timestamp;
+        let mut rift = zeroed_rift();
+        rift.name[..4].copy_from_slice(b"TEST");
+        rift.creator = self.creator;
+        rift.underlying_mint = self.underlying_mint;
+        rift.rift_mint = self.rift_mint;
+        rift.vault = self.vault;
+        rift.fees_vault = self.fees_vault;
+        rift.withheld_vault = self.withheld_vault;
+        rift.partner_wallet = Some(options.partner_wallet.unwrap_or(self.creator));
+        rift.partner_fee_bps = 5000;
+        rift.treasury_wallet = Some(Pubkey::from_str_const(DEFAULT_TREASURY_WALLET));
+        rift.wrap_fee_bps = 30;
+        rift.unwrap_fee_bps = 30;
+        rift.backing_ratio = 1_000_000;
+        rift.last_rebalance = now;
+        rift.created_at = now;
+        rift.oracle_update_interval = 30 * 60;
+        rift.max_rebalance_interval = 24 * 60 * 60;
+        rift.arbitrage_threshold_bps = 200;
+        rift.last_oracle_update = now;
+        rift.apply_default_config();
+        rift.initial_transfer_fee_bps = options.transfer_fee_bps;
+        rift.transfer_fee_bps = options.transfer_fee_bps;
+        rift
+    }
+
+    pub fn state(&self, env: &Env) -> Rift {
+        env.load(&self.rift)
+    }
+
+    pub fn update(&self, env: &Env, modify: impl FnOnce(&mut Rift)) {
+        env.update(&self.rift, modify);
+    }
+
+    /// Replace the inline oracle samples with `(price, timestamp)` pairs, newest last
+    pub fn set_oracle_samples(&self, env: &Env, samples: &[(u64, i64)]) {
+        self.update(env, |state| {
+            state.oracle_prices = [PriceData::default(); 10];
+            for (slot, (price, timestamp)) in samples.iter().enumerate() {
+                state.oracle_prices[slot] = PriceData {
+                    price: *price,
+                    confidence: 0,
+                    timestamp: *timestamp,
+                };
+                state.last_oracle_update = *timestamp;
+            }
+            state.price_index = (samples.len() % 10) as u8;
+        });
+    }
+
+    /// trigger_rebalance without the keeper reward accounts
+    pub fn trigger_rebalance(&self, env: &Env) -> ProgramResult {
+        env.call(
+            accounts::TriggerRebalance {
+                user: Pubkey::new_unique(),
+                rift: self.rift,
+                fees_vault: None,
+                vault_authority: None,
+                underlying_mint: None,
+                keeper_underlying: None,
+                underlying_token_program: None,
+                oracle_history: None,
+            },
+            instruction::TriggerRebalance {},
+        )
+    }
+
+    /// Wallet with `underlying_amount` underlying and an empty RIFT ATA
+    pub fn new_user(&self, env: &Env, underlying_amount: u64) -> TestUser {
+        let wallet = env.wallet(USER_LAMPORTS);
+        let underlying = create_token_account(
+            env,
+            self.underlying_program,
+            get_associated_token_address_with_program_id(
+                &wallet,
+                &self.underlying_mint,
+                &self.underlying_program,
+            ),
+            self.underlying_mint,
+            wallet,
+        );
+        if underlying_amount > 0 {
+            mint_to(
+                env,
+                &self.underlying_mint,
+                &underlying,
+                &self.underlying_mint_authority,
+                underlying_amount,
+            );
+        }
+        let rift_tokens = create_token_account(
+            env,
+            spl_token_2022::ID,
+            self.rift_ata(&wallet),
+            self.rift_mint,
+            wallet,
+        );
+        TestUser {
+            wallet,
+            underlying,
+            rift_tokens,
+        }
+    }
+
+    pub fn rift_ata(&self, wallet: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(wallet, &self.rift_mint, &spl_token_2022::ID)
+    }
+
+    pub fn activity(&self, user: &Pubkey) -> Pubkey {
+        pda(&[b"activity", self.rift.as_ref(), user.as_ref()])
+    }
+
+    pub fn wrap_accounts(&self, user: &TestUser) -> accounts::WrapTokens {
+        accounts::WrapTokens {
+            user: user.wallet,
+            rift: self.rift,
+            user_underlying: user.underlying,
+            user_rift_tokens: user.rift_tokens,
+            vault: self.vault,
+            underlying_mint: self.underlying_mint,
+            rift_mint: self.rift_mint,
+            rift_mint_authority: self.rift_mint_authority,
+            fees_vault: self.fees_vault,
+            vault_authority: self.vault_authority,
+            wrap_fees_vault: None,
+            underlying_token_program: self.underlying_program,
+            rift_token_program: spl_token_2022::ID,
+            user_activity: self.activity(&user.wallet),
+            associated_token_program: None,
+            system_program: system_program::ID,
+            fee_exemption: None,
+            global_config: pda(&[b"global_config"]),
+        }
+    }
+
+    pub fn wrap_args(amount: u64) -> instruction::WrapTokens {
+        instruction::WrapTokens {
+            amount,
+            min_rift_out: 0,
+            deadline_unix: 0,
+            create_rift_ata: false,
+        }
+    }
+
+    pub fn unwrap_accounts(&self, user: &TestUser) -> accounts::UnwrapFromVault {
+        accounts::UnwrapFromVault {
+            user: user.wallet,
+            rift: self.rift,
+            user_underlying: user.underlying,
+            user_rift_tokens: user.rift_tokens,
+            vault: self.vault,
+            underlying_mint: self.underlying_mint,
+            vault_authority: self.vault_authority,
+            rift_mint_authority: self.rift_mint_authority,
+            rift_mint: self.rift_mint,
+            fees_vault: self.fees_vault,
+            underlying_token_program: self.underlying_program,
+            rift_token_program: spl_token_2022::ID,
+            system_program: system_program::ID,
+            fee_exemption: None,
+            associated_token_program: None,
+            treasury_fee_account: None,
+            partner_fee_account: None,
+            protocol_fee_account: None,
+            protocol_treasury_account: None,
+            global_config: pda(&[b"global_config"]),
+        }
+    }
+
+    pub fn unwrap_args(rift_token_amount: u64) -> instruction::UnwrapFromVault {
+        instruction::UnwrapFromVault {
+            rift_token_amount,
+            min_underlying_out: 0,
+            deadline_unix: 0,
+            auto_distribute: false,
+        }
+    }
+
+    /// Pre-create the user's activity PDA (init_if_needed in wrap_tokens)
+    pub fn prepare_wrap(&self, env: &Env, user: &TestUser) {
+        allocate_program_account(
+            env,
+            self.activity(&user.wallet),
+            UserRiftActivity::SIZE,
+            UserRiftActivity::DISCRIMINATOR,
+        );
+    }
+}
+
+/// GlobalConfig PDA as left by initialize_global_config
+pub fn init_global_config(
+    env: &Env,
+    protocol_fee_recipient: Pubkey,
+    distribution_fee_bps: u16,
+) -> Pubkey {
+    let (key, bump) = Pubkey::find_program_address(&[b"global_config"], &crate::ID);
+    let mut config = GlobalConfig {
+        protocol_fee_recipient,
+        distribution_fee_bps,
+        bump,
+        program_authority: Pubkey::default(),
+        admin_authority_2: Pubkey::default(),
+        treasury_wallet: Pubkey::default(),
+        mint_policy_mode: 0,
+        protocol_fee_bps: 0,
+    };
+    config.apply_default_config();
+    allocate_program_account(env, key, GlobalConfig::SIZE, GlobalConfig::DISCRIMINATOR);
+    env.store(&key, &config);
+    key
+}
+
+/// A Rift with every field zeroed (None / false / first variant)
+pub fn zeroed_rift() -> Rift {
+    Rift::try_deserialize_unchecked(&mut &[0u8; RIFT_ACCOUNT_SIZE][..]).unwrap()
+}
+
+/// Empty program-owned Anchor account: discriminator followed by zeroes
+pub fn allocate_program_account(env: &Env, key: Pubkey, space: usize, discriminator: &[u8]) {
+    let mut data = vec![0u8; space];
+    data[..discriminator.len()].copy_from_slice(discriminator);
+    env.set_account(
+        key,
+        crate::ID,
+        Rent::default().minimum_balance(space),
+        &data,
+    );
+}
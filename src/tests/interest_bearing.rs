@@ -0,0 +1,235 @@
+use super::*;
+use spl_token_2022::extension::interest_bearing_mint::{self, InterestBearingConfig};
+
+const RATE_BPS: i16 = 500;
+const ONE_YEAR: i64 = 365 * 24 * 60 * 60;
+const FEE_BPS: u64 = 30;
+
+/// Token-2022 underlying accruing RATE_BPS a year through InterestBearingConfig
+fn create_interest_bearing_mint(env: &Env) -> (Pubkey, Pubkey) {
+    let authority = env.wallet(USER_LAMPORTS);
+    let mint = Pubkey::new_unique();
+    let space = ExtensionType::try_calculate_account_len::<Mint2022State>(&[
+        ExtensionType::InterestBearingConfig,
+    ])
+    .unwrap();
+    env.set_account(
+        mint,
+        spl_token_2022::ID,
+        Rent::default().minimum_balance(space),
+        &vec![0; space],
+    );
+    env.process(
+        &interest_bearing_mint::instruction::initialize(
+            &spl_token_2022::ID,
+            &mint,
+            Some(authority),
+            RATE_BPS,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    env.process(
+        &spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint,
+            &authority,
+            None,
+            UNDERLYING_DECIMALS,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    (mint, authority)
+}
+
+/// Rift over an interest-bearing underlying, flagged the way create_rift flags it
+fn interest_bearing_rift(env: &Env) -> TestRift {
+    let options = RiftOptions {
+        underlying_program: spl_token_2022::ID,
+        ..RiftOptions::default()
+    };
+    let (mint, authority) = create_interest_bearing_mint(env);
+    let rift = TestRift::with_underlying(env, options, mint, authority);
+    rift.update(env, |state| state.interest_bearing = true);
+    rift
+}
+
+/// What `amount` raw underlying displays as right now (principal plus accrued interest)
+fn ui_amount(env: &Env, mint: &Pubkey, amount: u64) -> f64 {
+    let data = env.data(mint);
+    let mint_state = StateWithExtensions::<Mint2022State>::unpack(&data).unwrap();
+    mint_state
+        .get_extension::<InterestBearingConfig>()
+        .unwrap()
+        .amount_to_ui_amount(amount, UNDERLYING_DECIMALS, clock().unix_timestamp)
+        .unwrap()
+        .parse()
+        .unwrap()
+}
+
+/// wrap_tokens: deposit into the vault, extract the fee, book the wrap and mint the RIFT
+/// Returns the RIFT minted
+fn wrap(env: &Env, rift: &TestRift, user: &TestUser, amount: u64) -> u64 {
+    transfer_tokens(env, &user.underlying, &rift.vault, &user.wallet, amount);
+    let mut state = rift.state(env);
+    let (wrap_fee, rift_minted) = state.plan_wrap(amount, FEE_BPS).unwrap();
+    transfer_tokens(
+        env,
+        &rift.vault,
+        &rift.fees_vault,
+        &rift.vault_authority,
+        wrap_fee,
+    );
+    state
+        .record_wrap(
+            rift_minted,
+            amount,
+            wrap_fee,
+            0,
+            &env.account(&pda(&[b"global_config"])),
+            clock().unix_timestamp,
+        )
+        .unwrap();
+    env.store(&rift.rift, &state);
+    mint_to(
+        env,
+        &rift.rift_mint,
+        &user.rift_tokens,
+        &rift.rift_mint_authority,
+        rift_minted,
+    );
+    rift_minted
+}
+
+/// unwrap_from_vault for `rift_amount` RIFT: plan against the live vault, pay out, book it
+fn unwrap(env: &Env, rift: &TestRift, user: &TestUser, rift_amount: u64) -> UnwrapPlan {
+    let mut state = rift.state(env);
+    let plan = state
+        .plan_unwrap(
+            rift_amount,
+            token_balance(env, &rift.vault),
+            false,
+            false,
+            clock().unix_timestamp,
+        )
+        .unwrap();
+    transfer_tokens(
+        env,
+        &rift.vault,
+        &rift.fees_vault,
+        &rift.vault_authority,
+        plan.fee,
+    );
+    transfer_tokens(
+        env,
+        &rift.vault,
+        &user.underlying,
+        &rift.vault_authority,
+        plan.net_underlying,
+    );
+    state
+        .record_unwrap(
+            &plan,
+            plan.net_underlying,
+            &env.account(&pda(&[b"global_config"])),
+            clock().unix_timestamp,
+        )
+        .unwrap();
+    env.store(&rift.rift, &state);
+    plan
+}
+
+#[test]
+fn inspect_underlying_detects_interest_bearing() {
+    run(|| {
+        let env = Env::new();
+        let (mint, _) = create_interest_bearing_mint(&env);
+
+        let inspection = env
+            .view(
+                accounts::InspectUnderlying {
+                    underlying_mint: mint,
+                    global_config: pda(&[b"global_config"]),
+                    mint_policy: pda(&[b"mint_policy", mint.as_ref()]),
+                    underlying_registry: pda(&[b"rift_mint_registry", mint.as_ref()]),
+                },
+                |ctx| rifts_protocol::inspect_underlying(ctx, false, false),
+            )
+            .unwrap();
+
+        assert!(inspection.interest_bearing);
+        assert_eq!(
+            inspection.extension_types,
+            vec![ExtensionType::InterestBearingConfig as u16]
+        );
+        assert_eq!(inspection.blocked, 0);
+        assert!(inspection.can_create);
+    });
+}
+
+#[test]
+fn interest_accrues_in_ui_amount_and_redeems_raw_one_to_one() {
+    run(|| {
+        let env = Env::new();
+        let rift = interest_bearing_rift(&env);
+        let deposit = 1_000_000;
+        let user = rift.new_user(&env, deposit);
+
+        let rift_minted = wrap(&env, &rift, &user, deposit);
+        let vault_raw = token_balance(&env, &rift.vault);
+        let vault_ui_at_wrap = ui_amount(&env, &rift.underlying_mint, vault_raw);
+
+        warp_seconds(ONE_YEAR);
+
+        // Interest shows up in the UI amount; the raw vault balance does not move
+        assert_eq!(token_balance(&env, &rift.vault), vault_raw);
+        let vault_ui_after_year = ui_amount(&env, &rift.underlying_mint, vault_raw);
+        assert!(vault_ui_after_year > vault_ui_at_wrap * 1.05);
+        assert!(vault_ui_after_year < vault_ui_at_wrap * 1.06);
+
+        let plan = unwrap(&env, &rift, &user, rift_minted);
+
+        // Raw 1:1: every RIFT burned redeems one raw underlying unit, interest included
+        assert_eq!(plan.rift_burned, rift_minted);
+        assert_eq!(plan.gross_underlying, rift_minted);
+        assert_eq!(plan.net_underlying, rift_minted - plan.fee);
+        assert_eq!(token_balance(&env, &rift.vault), 0);
+        let payout_ui = ui_amount(&env, &rift.underlying_mint, plan.net_underlying);
+        assert!(payout_ui > plan.net_underlying as f64 / 1e6 * 1.05);
+
+        assert_eq!(rift.state(&env).total_rift_minted, 0);
+    });
+}
+
+#[test]
+fn accrued_interest_is_shared_pro_rata() {
+    run(|| {
+        let env = Env::new();
+        let rift = interest_bearing_rift(&env);
+        let small = rift.new_user(&env, 1_000_000);
+        let large = rift.new_user(&env, 3_000_000);
+
+        let small_minted = wrap(&env, &rift, &small, 1_000_000);
+        let large_minted = wrap(&env, &rift, &large, 3_000_000);
+        let small_ui_at_wrap = ui_amount(&env, &rift.underlying_mint, small_minted);
+        let large_ui_at_wrap = ui_amount(&env, &rift.underlying_mint, large_minted);
+
+        warp_seconds(ONE_YEAR);
+
+        let small_plan = unwrap(&env, &rift, &small, small_minted);
+        let large_plan = unwrap(&env, &rift, &large, large_minted);
+
+        // Each holder's redemption grew by the same rate as their share of the vault
+        let small_growth =
+            ui_amount(&env, &rift.underlying_mint, small_plan.gross_underlying) / small_ui_at_wrap;
+        let large_growth =
+            ui_amount(&env, &rift.underlying_mint, large_plan.gross_underlying) / large_ui_at_wrap;
+        assert!(small_growth > 1.05);
+        assert!((small_growth - large_growth).abs() < 1e-6);
+
+        // Together they drained exactly the backing: nothing accrued is stranded in the vault
+        assert_eq!(token_balance(&env, &rift.vault), 0);
+        assert_eq!(rift.state(&env).total_rift_minted, 0);
+    });
+}
@@ -0,0 +1,124 @@
+use super::*;
+use anchor_lang::{Discriminator, Event};
+
+/// Decode an event the way indexers read it from the `Program data:` log emit! writes
+fn decode_logged<T: Event + Discriminator + AnchorDeserialize>(event: &T) -> T {
+    let data = event.data();
+    assert_eq!(&data[..T::DISCRIMINATOR.len()], T::DISCRIMINATOR);
+    T::deserialize(&mut &data[T::DISCRIMINATOR.len()..]).unwrap()
+}
+
+#[test]
+fn any_keeper_can_trigger_rebalance() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let keeper = env.wallet(USER_LAMPORTS);
+        warp_seconds(86_400);
+        let now = clock().unix_timestamp;
+        rift.set_oracle_samples(&env, &[(1_000_000, now - 3_000), (1_020_000, now - 1_801)]);
+
+        env.call(
+            accounts::TriggerRebalance {
+                user: keeper,
+                rift: rift.rift,
+                fees_vault: None,
+                vault_authority: None,
+                underlying_mint: None,
+                keeper_underlying: None,
+                underlying_token_program: None,
+                oracle_history: None,
+            },
+            instruction::TriggerRebalance {},
+        )
+        .unwrap();
+
+        let state = rift.state(&env);
+        assert_eq!(state.rebalance_count, 1);
+        assert_eq!(state.last_rebalance, now);
+        assert!(!state.reentrancy_guard);
+    });
+}
+
+#[test]
+fn rebalance_event_carries_keeper() {
+    let keeper = Pubkey::new_unique();
+
+    let event = decode_logged(&RebalanceTriggered {
+        rift: Pubkey::new_unique(),
+        backing_ratio: 1_000_000,
+        keeper,
+        timestamp: 1_700_000_000,
+    });
+
+    assert_eq!(event.keeper, keeper);
+    assert_eq!(event.backing_ratio, 1_000_000);
+    assert_eq!(event.timestamp, 1_700_000_000);
+}
+
+#[test]
+fn distribution_events_carry_keeper_alongside_distributor() {
+    let keeper = Pubkey::new_unique();
+
+    let fees = decode_logged(&FeesDistributed {
+        rift: Pubkey::new_unique(),
+        amount: 1_000,
+        protocol_skim_amount: 10,
+        protocol_amount: 0,
+        treasury_amount: 495,
+        partner_amount: 495,
+        distributor: keeper,
+        keeper,
+    });
+    assert_eq!(fees.keeper, keeper);
+    assert_eq!(fees.partner_amount, 495);
+
+    let withheld = decode_logged(&WithheldFeesDistributed {
+        rift: Pubkey::new_unique(),
+        amount: 1_000,
+        protocol_amount: 0,
+        treasury_amount: 500,
+        partner_amount: 500,
+        distributor: keeper,
+        keeper,
+    });
+    assert_eq!(withheld.keeper, keeper);
+    assert_eq!(withheld.partner_amount, 500);
+}
+
+#[test]
+fn non_keeper_distribution_leaves_keeper_unset() {
+    let creator = Pubkey::new_unique();
+
+    // drain_and_prepare_close / auto-distribution on unwrap: a distributor, but no keeper
+    let fees = decode_logged(&FeesDistributed {
+        rift: Pubkey::new_unique(),
+        amount: 1_000,
+        protocol_skim_amount: 0,
+        protocol_amount: 0,
+        treasury_amount: 500,
+        partner_amount: 500,
+        distributor: creator,
+        keeper: Pubkey::default(),
+    });
+    assert_eq!(fees.distributor, creator);
+    assert_eq!(fees.keeper, Pubkey::default());
+}
+
+#[test]
+fn harvest_event_separates_keeper_from_claimer() {
+    let treasury_multisig = Pubkey::new_unique();
+    let keeper = Pubkey::new_unique();
+
+    // claim_withheld_fees_multisig: the multisig claims, the fee payer did the work
+    let event = decode_logged(&WithheldFeesClaimed {
+        rift: Pubkey::new_unique(),
+        destination: Pubkey::new_unique(),
+        source_account: Pubkey::new_unique(),
+        claimer: treasury_multisig,
+        keeper,
+    });
+
+    assert_eq!(event.claimer, treasury_multisig);
+    assert_eq!(event.keeper, keeper);
+}
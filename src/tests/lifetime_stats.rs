@@ -0,0 +1,82 @@
+use super::*;
+
+/// The largest wrap a max-supply underlying allows: its entire u64 supply
+const MAX_SUPPLY: u64 = u64::MAX;
+
+fn full_exit(amount: u64) -> UnwrapPlan {
+    UnwrapPlan {
+        fee_bps: 0,
+        gross_underlying: amount,
+        fee: 0,
+        net_underlying: amount,
+        rift_burned: amount,
+        backing_share: 0,
+    }
+}
+
+fn record_wrap(env: &Env, state: &mut Rift, amount: u64) -> Result<()> {
+    let global_config = env.account(&pda(&[b"global_config"]));
+    state.record_wrap(amount, amount, 0, 0, &global_config, clock().unix_timestamp)
+}
+
+fn record_unwrap(env: &Env, state: &mut Rift, amount: u64) -> Result<()> {
+    let global_config = env.account(&pda(&[b"global_config"]));
+    state.record_unwrap(
+        &full_exit(amount),
+        amount,
+        &global_config,
+        clock().unix_timestamp,
+    )
+}
+
+#[test]
+fn lifetime_flow_outgrows_u64_while_live_balance_stays_bounded() {
+    run(|| {
+        let env = Env::new();
+        let mut state = TestRift::new(&env).state(&env);
+
+        record_wrap(&env, &mut state, MAX_SUPPLY).unwrap();
+        record_unwrap(&env, &mut state, MAX_SUPPLY).unwrap();
+        record_wrap(&env, &mut state, MAX_SUPPLY).unwrap();
+
+        assert_eq!(state.lifetime_wrapped, 2 * u128::from(MAX_SUPPLY));
+        assert_eq!(state.lifetime_unwrapped, u128::from(MAX_SUPPLY));
+        assert_eq!(state.total_underlying_wrapped, MAX_SUPPLY);
+        assert_eq!(state.total_rift_minted, MAX_SUPPLY);
+    });
+}
+
+#[test]
+fn live_balance_past_u64_is_math_overflow() {
+    run(|| {
+        let env = Env::new();
+        let mut state = TestRift::new(&env).state(&env);
+        record_wrap(&env, &mut state, MAX_SUPPLY).unwrap();
+
+        assert_eq!(
+            record_wrap(&env, &mut state, 1).unwrap_err(),
+            ErrorCode::MathOverflow.into()
+        );
+    });
+}
+
+#[test]
+fn volume_and_burn_statistics_saturate_instead_of_failing() {
+    run(|| {
+        let env = Env::new();
+        let mut state = TestRift::new(&env).state(&env);
+
+        // Two full-supply round trips in the same hour
+        for _ in 0..2 {
+            record_wrap(&env, &mut state, MAX_SUPPLY).unwrap();
+            record_unwrap(&env, &mut state, MAX_SUPPLY).unwrap();
+        }
+
+        assert_eq!(state.total_volume_24h, u64::MAX);
+        assert_eq!(state.rolling_volume_24h(clock().unix_timestamp), u64::MAX);
+        assert_eq!(state.total_burned, u64::MAX);
+        assert_eq!(state.lifetime_wrapped, 2 * u128::from(MAX_SUPPLY));
+        assert_eq!(state.lifetime_unwrapped, 2 * u128::from(MAX_SUPPLY));
+        assert_eq!(state.total_underlying_wrapped, 0);
+    });
+}
@@ -0,0 +1,110 @@
+use super::*;
+use spl_token_2022::extension::metadata_pointer;
+use spl_token_metadata_interface::instruction as metadata_instruction;
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
+
+/// RIFT mint carrying its own metadata, set up with the instruction sequence create_rift CPIs:
+/// metadata pointer, initialize_mint2, metadata initialize, then one update_field per pair
+fn create_rift_mint_with_metadata(env: &Env, underlying_mint: &Pubkey, decimals: u8) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let space = ExtensionType::try_calculate_account_len::<Mint2022State>(&[
+        ExtensionType::MetadataPointer,
+    ])
+    .unwrap();
+    env.set_account(
+        mint,
+        spl_token_2022::ID,
+        Rent::default().minimum_balance(space),
+        &vec![0; space],
+    );
+    let mut instructions = vec![
+        metadata_pointer::instruction::initialize(
+            &spl_token_2022::ID,
+            &mint,
+            Some(authority),
+            Some(mint),
+        )
+        .unwrap(),
+        spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint,
+            &authority,
+            None,
+            decimals,
+        )
+        .unwrap(),
+        metadata_instruction::initialize(
+            &spl_token_2022::ID,
+            &mint,
+            &authority,
+            &mint,
+            &authority,
+            "rTEST".to_string(),
+            "rTEST".to_string(),
+            String::new(),
+        ),
+    ];
+    for (key, value) in rift_display_metadata(underlying_mint, decimals) {
+        instructions.push(metadata_instruction::update_field(
+            &spl_token_2022::ID,
+            &mint,
+            &authority,
+            Field::Key(key),
+            value,
+        ));
+    }
+    for instruction in instructions {
+        env.process(&instruction).unwrap();
+    }
+    mint
+}
+
+fn read_metadata(env: &Env, mint: &Pubkey) -> TokenMetadata {
+    let data = env.data(mint);
+    StateWithExtensions::<Mint2022State>::unpack(&data)
+        .unwrap()
+        .get_variable_len_extension::<TokenMetadata>()
+        .unwrap()
+}
+
+#[test]
+fn display_metadata_names_underlying_and_decimals() {
+    let underlying_mint = Pubkey::new_unique();
+
+    assert_eq!(
+        rift_display_metadata(&underlying_mint, 9),
+        vec![
+            ("underlying_mint".to_string(), underlying_mint.to_string()),
+            ("decimals".to_string(), "9".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn display_metadata_reads_back_from_rift_mint() {
+    run(|| {
+        let env = Env::new();
+        let options = RiftOptions {
+            underlying_decimals: 9,
+            ..RiftOptions::default()
+        };
+        let (underlying_mint, _) = create_underlying_mint(&env, &options);
+
+        let rift_mint = create_rift_mint_with_metadata(&env, &underlying_mint, 9);
+
+        let metadata = read_metadata(&env, &rift_mint);
+        let field = |key: &str| {
+            metadata
+                .additional_metadata
+                .iter()
+                .find(|(field_key, _)| field_key == key)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(field("underlying_mint"), Some(underlying_mint.to_string()));
+        let decimals: u8 = field("decimals").unwrap().parse().unwrap();
+        assert_eq!(decimals, 9);
+        // The advertised precision is the one the RIFT mint actually has
+        assert_eq!(env.data(&rift_mint)[44], decimals);
+    });
+}
@@ -0,0 +1,231 @@
+use super::*;
+
+const POSITION: u64 = 1_000_000;
+
+/// Source rift holding a POSITION-sized position for a new user, plus a destination rift over
+/// the same underlying where the user holds an empty RIFT account
+fn setup(env: &Env) -> (TestRift, TestRift, TestUser, Pubkey) {
+    let source = TestRift::new(env);
+    let destination = TestRift::with_underlying(
+        env,
+        RiftOptions::default(),
+        source.underlying_mint,
+        source.underlying_mint_authority,
+    );
+    let user = source.new_user(env, 0);
+    open_position(env, &source, &user, POSITION);
+    let destination_rift_tokens = create_token_account(
+        env,
+        spl_token_2022::ID,
+        destination.rift_ata(&user.wallet),
+        destination.rift_mint,
+        user.wallet,
+    );
+    destination.prepare_wrap(env, &user);
+    (source, destination, user, destination_rift_tokens)
+}
+
+/// Fully backed position of `amount` RIFT (vault, supply and user balance in step)
+fn open_position(env: &Env, rift: &TestRift, user: &TestUser, amount: u64) {
+    mint_to(
+        env,
+        &rift.underlying_mint,
+        &rift.vault,
+        &rift.underlying_mint_authority,
+        amount,
+    );
+    mint_to(
+        env,
+        &rift.rift_mint,
+        &user.rift_tokens,
+        &rift.rift_mint_authority,
+        amount,
+    );
+    rift.update(env, |state| {
+        state.total_rift_minted = amount;
+        state.total_underlying_wrapped = amount;
+    });
+}
+
+fn migrate_position(
+    env: &Env,
+    source: &TestRift,
+    destination: &TestRift,
+    user: &TestUser,
+    destination_rift_tokens: Pubkey,
+) -> ProgramResult {
+    env.call(
+        accounts::MigratePosition {
+            user: user.wallet,
+            source_rift: source.rift,
+            destination_rift: destination.rift,
+            underlying_mint: source.underlying_mint,
+            user_source_rift_tokens: user.rift_tokens,
+            user_destination_rift_tokens: destination_rift_tokens,
+            source_rift_mint: source.rift_mint,
+            destination_rift_mint: destination.rift_mint,
+            source_vault: source.vault,
+            destination_vault: destination.vault,
+            source_fees_vault: source.fees_vault,
+            destination_fees_vault: destination.fees_vault,
+            destination_wrap_fees_vault: None,
+            source_vault_authority: source.vault_authority,
+            destination_vault_authority: destination.vault_authority,
+            destination_mint_authority: destination.rift_mint_authority,
+            destination_user_activity: destination.activity(&user.wallet),
+            source_fee_exemption: None,
+            destination_fee_exemption: None,
+            system_program: system_program::ID,
+            underlying_token_program: source.underlying_program,
+            source_rift_token_program: spl_token_2022::ID,
+            destination_rift_token_program: spl_token_2022::ID,
+            global_config: pda(&[b"global_config"]),
+        },
+        instruction::MigratePosition {
+            amount: POSITION,
+            min_destination_out: 0,
+        },
+    )
+}
+
+#[test]
+fn different_underlying_is_rejected() {
+    run(|| {
+        let env = Env::new();
+        let (source, _, user, _) = setup(&env);
+        let other = TestRift::new(&env);
+        let other_rift_tokens = create_token_account(
+            &env,
+            spl_token_2022::ID,
+            other.rift_ata(&user.wallet),
+            other.rift_mint,
+            user.wallet,
+        );
+        other.prepare_wrap(&env, &user);
+
+        assert_error(
+            migrate_position(&env, &source, &other, &user, other_rift_tokens),
+            ErrorCode::InvalidMint,
+        );
+    });
+}
+
+#[test]
+fn closing_destination_is_rejected() {
+    run(|| {
+        let env = Env::new();
+        let (source, destination, user, destination_rift_tokens) = setup(&env);
+        destination.update(&env, |state| state.closing = true);
+
+        assert_error(
+            migrate_position(&env, &source, &destination, &user, destination_rift_tokens),
+            ErrorCode::RiftClosing,
+        );
+        // Rolled back: neither rift is left holding the guard
+        assert!(!source.state(&env).reentrancy_guard);
+        assert!(!destination.state(&env).reentrancy_guard);
+    });
+}
+
+#[test]
+fn paused_source_is_rejected() {
+    run(|| {
+        let env = Env::new();
+        let (source, destination, user, destination_rift_tokens) = setup(&env);
+        source.update(&env, |state| state.paused = true);
+
+        assert_error(
+            migrate_position(&env, &source, &destination, &user, destination_rift_tokens),
+            ErrorCode::RiftPaused,
+        );
+    });
+}
+
+#[test]
+fn migration_moves_backing_between_rifts() {
+    run(|| {
+        let env = Env::new();
+        let (source, destination, _, _) = setup(&env);
+        let now = clock().unix_timestamp;
+        let global_config = env.account(&pda(&[b"global_config"]));
+
+        // migrate_position's sequence: source unwrap fee, vault to vault, destination wrap fee
+        let mut source_state = source.state(&env);
+        let plan = source_state
+            .plan_unwrap(
+                POSITION,
+                token_balance(&env, &source.vault),
+                false,
+                false,
+                now,
+            )
+            .unwrap();
+        transfer_tokens(
+            &env,
+            &source.vault,
+            &source.fees_vault,
+            &source.vault_authority,
+            plan.fee,
+        );
+        transfer_tokens(
+            &env,
+            &source.vault,
+            &destination.vault,
+            &source.vault_authority,
+            plan.net_underlying,
+        );
+        let mut destination_state = destination.state(&env);
+        let wrap_fee_bps = destination_state.wrap_fee_bps(false, now);
+        let (wrap_fee, destination_minted) = destination_state
+            .plan_wrap(plan.net_underlying, wrap_fee_bps)
+            .unwrap();
+        transfer_tokens(
+            &env,
+            &destination.vault,
+            &destination.fees_vault,
+            &destination.vault_authority,
+            wrap_fee,
+        );
+        source_state
+            .record_unwrap(&plan, plan.net_underlying, &global_config, now)
+            .unwrap();
+        destination_state
+            .record_wrap(
+                destination_minted,
+                plan.net_underlying,
+                wrap_fee,
+                0,
+                &global_config,
+                now,
+            )
+            .unwrap();
+
+        // Both fees charged once, and each vault still backs exactly its rift's supply
+        assert_eq!(plan.rift_burned, POSITION);
+        assert_eq!(destination_minted, POSITION - plan.fee - wrap_fee);
+        assert_eq!(source_state.total_rift_minted, 0);
+        assert_eq!(token_balance(&env, &source.vault), 0);
+        assert_eq!(destination_state.total_rift_minted, destination_minted);
+        assert_eq!(token_balance(&env, &destination.vault), destination_minted);
+        assert_eq!(token_balance(&env, &source.fees_vault), plan.fee);
+        assert_eq!(token_balance(&env, &destination.fees_vault), wrap_fee);
+    });
+}
+
+#[test]
+fn source_wrapped_this_slot_is_rejected_by_same_slot_guard() {
+    run(|| {
+        let env = Env::new();
+        let (source, destination, user, destination_rift_tokens) = setup(&env);
+        // Another wallet wrapped into the protected source rift earlier in this slot
+        source.update(&env, |state| {
+            state.same_slot_protection = true;
+            state.record_action_slot(clock().slot, true);
+        });
+
+        assert_error(
+            migrate_position(&env, &source, &destination, &user, destination_rift_tokens),
+            ErrorCode::SameSlotWrapUnwrap,
+        );
+    });
+}
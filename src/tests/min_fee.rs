@@ -0,0 +1,120 @@
+use super::*;
+
+const FEE_BPS: u64 = 30;
+
+/// Rift over a 0-decimal underlying (e.g. a gaming token) accepting any wrap size
+fn zero_decimal_rift(env: &Env, min_fee_units: u64) -> TestRift {
+    let rift = TestRift::with_options(
+        env,
+        RiftOptions {
+            underlying_decimals: 0,
+            ..RiftOptions::default()
+        },
+    );
+    rift.update(env, |state| {
+        state.min_fee_units = min_fee_units;
+        state.min_wrap_amount = 0;
+        state.unwrap_fee_bps = FEE_BPS as u16;
+    });
+    rift
+}
+
+fn set_min_fee_units(
+    env: &Env,
+    rift: &TestRift,
+    creator: Pubkey,
+    min_fee_units: u64,
+) -> ProgramResult {
+    env.call(
+        accounts::SetMinFeeUnits {
+            creator,
+            rift: rift.rift,
+        },
+        instruction::SetMinFeeUnits { min_fee_units },
+    )
+}
+
+#[test]
+fn small_amount_fee_is_floored_at_min_fee_units() {
+    run(|| {
+        let env = Env::new();
+        let state = zero_decimal_rift(&env, 1).state(&env);
+
+        // 100 * 30 / 10000 truncates to zero
+        assert_eq!(state.charge_fee(100, FEE_BPS).unwrap(), 1);
+        assert_eq!(state.plan_wrap(100, FEE_BPS).unwrap(), (1, 99));
+        let plan = state
+            .plan_unwrap(100, 100, false, false, clock().unix_timestamp)
+            .unwrap();
+        assert_eq!(plan.fee, 1);
+        assert_eq!(plan.net_underlying, 99);
+    });
+}
+
+#[test]
+fn bps_fee_above_the_floor_is_unchanged() {
+    run(|| {
+        let env = Env::new();
+        let state = zero_decimal_rift(&env, 1).state(&env);
+
+        assert_eq!(state.charge_fee(10_000, FEE_BPS).unwrap(), 30);
+    });
+}
+
+#[test]
+fn zero_min_fee_units_keeps_pure_bps() {
+    run(|| {
+        let env = Env::new();
+        let state = zero_decimal_rift(&env, 0).state(&env);
+
+        assert_eq!(state.charge_fee(100, FEE_BPS).unwrap(), 0);
+        assert_eq!(state.plan_wrap(100, FEE_BPS).unwrap(), (0, 100));
+    });
+}
+
+#[test]
+fn zero_bps_fee_is_not_floored() {
+    run(|| {
+        let env = Env::new();
+        let state = zero_decimal_rift(&env, 5).state(&env);
+
+        // Exemptions and zero-fee tiers stay free
+        assert_eq!(state.charge_fee(100, 0).unwrap(), 0);
+    });
+}
+
+#[test]
+fn fee_floor_never_exceeds_the_amount() {
+    run(|| {
+        let env = Env::new();
+        let state = zero_decimal_rift(&env, 5).state(&env);
+
+        assert_eq!(state.charge_fee(3, FEE_BPS).unwrap(), 3);
+        // Nothing would be left to mint
+        assert_eq!(
+            state.plan_wrap(3, FEE_BPS).unwrap_err(),
+            ErrorCode::InvalidAmount.into()
+        );
+    });
+}
+
+#[test]
+fn min_fee_units_is_creator_set_and_capped() {
+    run(|| {
+        let env = Env::new();
+        let rift = zero_decimal_rift(&env, 1);
+
+        set_min_fee_units(&env, &rift, rift.creator, 10).unwrap();
+        assert_eq!(rift.state(&env).min_fee_units, 10);
+
+        assert_error(
+            set_min_fee_units(&env, &rift, rift.creator, MAX_MIN_FEE_UNITS + 1),
+            ErrorCode::InvalidMinFeeUnits,
+        );
+        assert_error(
+            set_min_fee_units(&env, &rift, env.wallet(USER_LAMPORTS), 0),
+            ErrorCode::Unauthorized,
+        );
+        assert_eq!(rift.state(&env).min_fee_units, 10);
+    });
+}
@@ -0,0 +1,568 @@
+// In-process test harness
+// Instructions run through the program entrypoint against an in-memory ledger. Off-chain the
+// Solana SDK leaves `invoke_signed` unimplemented and discards Anchor events and return data, so:
+// - handlers are driven up to their first CPI; token movements are covered through the shared
+//   planning/accounting helpers they call (plan_wrap, plan_unwrap, record_wrap, ...)
+// - token state is set up directly with the SPL processors (see `dispatch`)
+// - tests assert on account state, and views are called as handlers (see `Env::view`)
+// Sysvars come from thread-local state, so every test runs in its own thread (see `run`)
+
+use super::*;
+use anchor_lang::solana_program::{
+    clock::Clock,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    program_option::COption,
+};
+use anchor_lang::{Bumps, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::spl_associated_token_account;
+use solana_sysvar::epoch_schedule::EpochSchedule;
+use solana_sysvar::program_stubs::{self, SyscallStubs};
+use spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Once;
+
+const TEST_STACK_SIZE: usize = 64 * 1024 * 1024;
+const START_SLOT: u64 = 1_000;
+const START_TIME: i64 = 1_700_000_000;
+
+thread_local! {
+    static CLOCK: RefCell<Clock> = const {
+        RefCell::new(Clock {
+            slot: START_SLOT,
+            epoch_start_timestamp: START_TIME,
+            epoch: 1,
+            leader_schedule_epoch: 1,
+            unix_timestamp: START_TIME,
+        })
+    };
+    static CALL_STACK: RefCell<Vec<Pubkey>> = const { RefCell::new(Vec::new()) };
+}
+
+struct TestStubs;
+
+impl SyscallStubs for TestStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = CLOCK.with(|clock| clock.borrow().clone());
+        unsafe { *(var_addr as *mut Clock) = clock };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut EpochSchedule) = EpochSchedule::default() };
+        0
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        CALL_STACK.with(|stack| stack.borrow().len() as u64)
+    }
+}
+
+/// Native processors for top-level setup instructions (mints, token accounts, mint_to)
+fn dispatch(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if *program_id == spl_token_2022::ID {
+        spl_token_2022::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_token::ID {
+        spl_token::processor::Processor::process(program_id, accounts, data)
+    } else if *program_id == spl_associated_token_account::ID {
+        spl_associated_token_account::processor::process_instruction(program_id, accounts, data)
+    } else {
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+/// Run a test body on a thread with a large stack (debug builds of the account structs are big)
+pub fn run(test: impl FnOnce() + Send + 'static) {
+    static STUBS: Once = Once::new();
+    STUBS.call_once(|| {
+        program_stubs::set_syscall_stubs(Box::new(TestStubs));
+    });
+    std::thread::Builder::new()
+        .stack_size(TEST_STACK_SIZE)
+        .spawn(test)
+        .unwrap()
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+}
+
+/// Account storage laid out like the runtime's serialized input, so `realloc` and `assign`
+/// behave as on-chain: the original data length sits right before the key, and the data
+/// length right before the data, followed by MAX_PERMITTED_DATA_INCREASE bytes of headroom
+#[repr(C)]
+struct KeySlot {
+    original_data_len: u32,
+    key: Pubkey,
+}
+
+fn new_account_info(
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: &[u8],
+    executable: bool,
+) -> AccountInfo<'static> {
+    let key_slot = Box::leak(Box::new(KeySlot {
+        original_data_len: data.len() as u32,
+        key,
+    }));
+    let words = (8 + data.len() + MAX_PERMITTED_DATA_INCREASE).div_ceil(8);
+    let buffer = Vec::leak(vec![0u64; words]);
+    let bytes =
+        unsafe { std::slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, buffer.len() * 8) };
+    let (len_prefix, rest) = bytes.split_at_mut(8);
+    len_prefix.copy_from_slice(&(data.len() as u64).to_le_bytes());
+    rest[..data.len()].copy_from_slice(data);
+    AccountInfo::new(
+        &key_slot.key,
+        false,
+        false,
+        Box::leak(Box::new(lamports)),
+        &mut rest[..data.len()],
+        Box::leak(Box::new(owner)),
+        executable,
+        0,
+    )
+}
+
+struct AccountSnapshot {
+    lamports: u64,
+    data: Vec<u8>,
+    owner: Pubkey,
+}
+
+/// An in-memory ledger; failed instructions are rolled back like a failed transaction
+pub struct Env {
+    accounts: RefCell<HashMap<Pubkey, AccountInfo<'static>>>,
+}
+
+impl Env {
+    pub fn new() -> Self {
+        let env = Env {
+            accounts: RefCell::new(HashMap::new()),
+        };
+        let native_loader = Pubkey::from_str_const("NativeLoader1111111111111111111111111111111");
+        let bpf_loader = anchor_lang::solana_program::bpf_loader_upgradeable::ID;
+        env.add_program(system_program::ID, native_loader);
+        env.add_program(spl_token::ID, bpf_loader);
+        env.add_program(spl_token_2022::ID, bpf_loader);
+        env.add_program(spl_associated_token_account::ID, bpf_loader);
+        env.add_program(crate::ID, bpf_loader);
+
+        let rent = Rent::default();
+        let mut rent_data = Vec::with_capacity(17);
+        rent_data.extend_from_slice(&rent.lamports_per_byte_year.to_le_bytes());
+        rent_data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+        rent_data.push(rent.burn_percent);
+        env.set_account(
+            anchor_lang::solana_program::sysvar::rent::ID,
+            anchor_lang::solana_program::sysvar::ID,
+            1,
+            &rent_data,
+        );
+        env
+    }
+
+    fn add_program(&self, program_id: Pubkey, loader: Pubkey) {
+        let info = new_account_info(program_id, loader, 1, &[], true);
+        self.accounts.borrow_mut().insert(program_id, info);
+    }
+
+    /// Create or overwrite an account
+    pub fn set_account(&self, key: Pubkey, owner: Pubkey, lamports: u64, data: &[u8]) {
+        let info = new_account_info(key, owner, lamports, data, false);
+        self.accounts.borrow_mut().insert(key, info);
+    }
+
+    /// Create a system-owned wallet holding `lamports`
+    pub fn wallet(&self, lamports: u64) -> Pubkey {
+        let key = Pubkey::new_unique();
+        self.set_account(key, system_program::ID, lamports, &[]);
+        key
+    }
+
+    /// Unknown keys resolve to an empty system account, as on a real cluster
+    pub fn account(&self, key: &Pubkey) -> AccountInfo<'static> {
+        self.accounts
+            .borrow_mut()
+            .entry(*key)
+            .or_insert_with(|| new_account_info(*key, system_program::ID, 0, &[], false))
+            .clone()
+    }
+
+    pub fn lamports(&self, key: &Pubkey) -> u64 {
+        self.account(key).lamports()
+    }
+
+    /// Credit lamports to an existing account (e.g. SOL sent straight to a wSOL account)
+    pub fn airdrop(&self, key: &Pubkey, lamports: u64) {
+        **self.account(key).lamports.borrow_mut() += lamports;
+    }
+
+    pub fn owner(&self, key: &Pubkey) -> Pubkey {
+        *self.account(key).owner
+    }
+
+    pub fn data(&self, key: &Pubkey) -> Vec<u8> {
+        self.account(key).data.borrow().to_vec()
+    }
+
+    /// Rewrite an account's data in place (same length)
+    pub fn write_data(&self, key: &Pubkey, data: &[u8]) {
+        self.account(key).data.borrow_mut().copy_from_slice(data);
+    }
+
+    fn snapshot(&self) -> Vec<(AccountInfo<'static>, AccountSnapshot)> {
+        self.accounts
+            .borrow()
+            .values()
+            .map(|info| {
+                let snapshot = AccountSnapshot {
+                    lamports: info.lamports(),
+                    data: info.data.borrow().to_vec(),
+                    owner: *info.owner,
+                };
+                (info.clone(), snapshot)
+            })
+            .collect()
+    }
+
+    fn restore(snapshot: Vec<(AccountInfo<'static>, AccountSnapshot)>) {
+        for (info, saved) in snapshot {
+            **info.lamports.borrow_mut() = saved.lamports;
+            info.resize(saved.data.len()).unwrap();
+            info.data.borrow_mut().copy_from_slice(&saved.data);
+            info.assign(&saved.owner);
+        }
+    }
+
+    fn account_infos(&self, metas: &[AccountMeta]) -> &'static [AccountInfo<'static>] {
+        let infos = metas
+            .iter()
+            .map(|meta| {
+                let mut info = self.account(&meta.pubkey);
+                info.is_signer = meta.is_signer;
+                info.is_writable = meta.is_writable;
+                info
+            })
+            .collect();
+        Vec::leak(infos)
+    }
+
+    /// Execute one top-level instruction; account changes are reverted if it fails
+    pub fn process(&self, instruction: &Instruction) -> ProgramResult {
+        let infos = self.account_infos(&instruction.accounts);
+        let snapshot = self.snapshot();
+
+        CALL_STACK.with(|stack| stack.borrow_mut().push(instruction.program_id));
+        let result = if instruction.program_id == crate::ID {
+            crate::entry(&instruction.program_id, infos, &instruction.data)
+        } else {
+            dispatch(&instruction.program_id, infos, &instruction.data)
+        };
+        CALL_STACK.with(|stack| stack.borrow_mut().pop());
+
+        if result.is_err() {
+            Self::restore(snapshot);
+        }
+        result
+    }
+
+    /// Execute a rift program instruction built from its Anchor accounts and args
+    pub fn call(&self, accounts: impl ToAccountMetas, args: impl InstructionData) -> ProgramResult {
+        self.process(&Instruction {
+            program_id: crate::ID,
+            accounts: accounts.to_account_metas(None),
+            data: args.data(),
+        })
+    }
+
+    /// Run a view handler (`-> Result<T>`) and return its value
+    /// Off-chain `set_return_data` is a no-op, so the handler is called directly with a Context
+    /// built the way the entrypoint builds it (account validation included)
+    pub fn view<A, T>(
+        &self,
+        accounts: impl ToAccountMetas,
+        handler: impl FnOnce(Context<A>) -> Result<T>,
+    ) -> Result<T>
+    where
+        A: Accounts<'static, A::Bumps> + Bumps,
+        A::Bumps: Default,
+    {
+        let mut infos = self.account_infos(&accounts.to_account_metas(None));
+        let mut bumps = A::Bumps::default();
+        let mut accounts = A::try_accounts(
+            &crate::ID,
+            &mut infos,
+            &[],
+            &mut bumps,
+            &mut Default::default(),
+        )?;
+        CALL_STACK.with(|stack| stack.borrow_mut().push(crate::ID));
+        let result = handler(Context::new(&crate::ID, &mut accounts, infos, bumps));
+        CALL_STACK.with(|stack| stack.borrow_mut().pop());
+        result
+    }
+
+    pub fn load<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        T::try_deserialize(&mut &self.data(key)[..]).unwrap()
+    }
+
+    pub fn store<T: AccountSerialize>(&self, key: &Pubkey, account: &T) {
+        let mut data = self.data(key);
+        account.try_serialize(&mut &mut data[..]).unwrap();
+        self.write_data(key, &data);
+    }
+
+    /// Load, modify and store an Anchor account
+    pub fn update<T: AccountSerialize + AccountDeserialize>(
+        &self,
+        key: &Pubkey,
+        modify: impl FnOnce(&mut T),
+    ) {
+        let mut account = self.load::<T>(key);
+        modify(&mut account);
+        self.store(key, &account);
+    }
+}
+
+pub fn clock() -> Clock {
+    CLOCK.with(|clock| clock.borrow().clone())
+}
+
+/// Advance the cluster clock (400ms slots)
+pub fn warp_seconds(seconds: i64) {
+    CLOCK.with(|clock| {
+        let mut clock = clock.borrow_mut();
+        clock.unix_timestamp += seconds;
+        clock.slot += (seconds.max(0) as u64 * 5).div_ceil(2);
+    });
+}
+
+pub fn warp_slots(slots: u64) {
+    CLOCK.with(|clock| {
+        let mut clock = clock.borrow_mut();
+        clock.slot += slots;
+        clock.unix_timestamp += (slots * 2 / 5) as i64;
+    });
+}
+
+pub fn assert_error(result: ProgramResult, expected: impl Into<u32>) {
+    assert_eq!(result, Err(ProgramError::Custom(expected.into())));
+}
+
+pub fn program_authority() -> Pubkey {
+    Pubkey::from_str_const(PROGRAM_AUTHORITY)
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
+
+// ---------------------------------------------------------------------------
+// Token fixtures
+// ---------------------------------------------------------------------------
+
+pub const RIFT_TRANSFER_FEE_BPS: u16 = 70;
+
+/// Create an initialized mint; `transfer_fee_bps` adds a Token-2022 TransferFeeConfig
+pub fn create_mint(
+    env: &Env,
+    token_program: Pubkey,
+    key: Pubkey,
+    authority: Pubkey,
+    decimals: u8,
+    transfer_fee_bps: Option<u16>,
+) -> Pubkey {
+    let extensions: Vec<ExtensionType> = transfer_fee_bps
+        .map(|_| vec![ExtensionType::TransferFeeConfig])
+        .unwrap_or_default();
+    let space = if token_program == spl_token_2022::ID {
+        ExtensionType::try_calculate_account_len::<Mint2022State>(&extensions).unwrap()
+    } else {
+        spl_token::state::Mint::LEN
+    };
+    env.set_account(
+        key,
+        token_program,
+        Rent::default().minimum_balance(space),
+        &vec![0; space],
+    );
+    if let Some(bps) = transfer_fee_bps {
+        env.process(
+            &initialize_transfer_fee_config(
+                &token_program,
+                &key,
+                Some(&authority),
+                Some(&authority),
+                bps,
+                u64::MAX,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    }
+    let initialize_mint = if token_program == spl_token_2022::ID {
+        spl_token_2022::instruction::initialize_mint2(
+            &token_program,
+            &key,
+            &authority,
+            None,
+            decimals,
+        )
+    } else {
+        spl_token::instruction::initialize_mint2(&token_program, &key, &authority, None, decimals)
+    };
+    env.process(&initialize_mint.unwrap()).unwrap();
+    key
+}
+
+/// Create an initialized token account at `key` (sized for the mint's extensions)
+pub fn create_token_account(
+    env: &Env,
+    token_program: Pubkey,
+    key: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+) -> Pubkey {
+    let space = if token_program == spl_token_2022::ID {
+        let mint_data = env.data(&mint);
+        let mint_state = StateWithExtensions::<Mint2022State>::unpack(&mint_data).unwrap();
+        let account_extensions = ExtensionType::get_required_init_account_extensions(
+            &mint_state.get_extension_types().unwrap(),
+        );
+        ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+            &account_extensions,
+        )
+        .unwrap()
+    } else {
+        spl_token::state::Account::LEN
+    };
+    env.set_account(
+        key,
+        token_program,
+        Rent::default().minimum_balance(space),
+        &vec![0; space],
+    );
+    let initialize_account = if token_program == spl_token_2022::ID {
+        spl_token_2022::instruction::initialize_account3(&token_program, &key, &mint, &owner)
+    } else {
+        spl_token::instruction::initialize_account3(&token_program, &key, &mint, &owner)
+    };
+    env.process(&initialize_account.unwrap()).unwrap();
+    key
+}
+
+/// The canonical wSOL mint, as it exists on every cluster
+pub fn create_native_mint(env: &Env) -> Pubkey {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::None,
+        supply: 0,
+        decimals: 9,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    let native_mint = spl_token::native_mint::ID;
+    env.set_account(
+        native_mint,
+        spl_token::ID,
+        Rent::default().minimum_balance(data.len()),
+        &data,
+    );
+    native_mint
+}
+
+pub fn mint_to(env: &Env, mint: &Pubkey, destination: &Pubkey, authority: &Pubkey, amount: u64) {
+    let token_program = env.owner(mint);
+    let mut instruction = spl_token_2022::instruction::mint_to(
+        &token_program,
+        mint,
+        destination,
+        authority,
+        &[],
+        amount,
+    )
+    .unwrap();
+    instruction.program_id = token_program;
+    env.process(&instruction).unwrap();
+}
+
+/// Token transfer between two accounts of the same mint (stands in for the program's CPIs)
+pub fn transfer_tokens(
+    env: &Env,
+    source: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+) {
+    let token_program = env.owner(source);
+    let source_data = env.data(source);
+    let mint = Pubkey::try_from(&source_data[..32]).unwrap();
+    let decimals = env.data(&mint)[44];
+    let mut instruction = spl_token_2022::instruction::transfer_checked(
+        &token_program,
+        source,
+        &mint,
+        destination,
+        authority,
+        &[],
+        amount,
+        decimals,
+    )
+    .unwrap();
+    instruction.program_id = token_program;
+    env.process(&instruction).unwrap();
+}
+
+pub fn token_balance(env: &Env, account: &Pubkey) -> u64 {
+    let data = env.data(account);
+    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+        .unwrap()
+        .base
+        .amount
+}
+
+pub fn mint_supply(env: &Env, mint: &Pubkey) -> u64 {
+    let data = env.data(mint);
+    StateWithExtensions::<Mint2022State>::unpack(&data)
+        .unwrap()
+        .base
+        .supply
+}
+
+mod fixture;
+pub use fixture::*;
+
+mod auto_ata;
+mod close;
+mod create_rift;
+mod distribution;
+mod emergency_unwrap;
+mod interest_bearing;
+mod keeper_events;
+mod lifetime_stats;
+mod metadata;
+mod migrate_position;
+mod min_fee;
+mod oracle_median;
+mod oracle_proposal;
+mod oracle_reentrancy;
+mod oracle_trust;
+mod pdas;
+mod program_constants;
+mod same_slot;
+mod scaled_ui_amount;
+mod snapshot;
+mod supply;
+mod transfer_fee;
+mod wind_down;
+mod wrap_fees;
+mod wsol;
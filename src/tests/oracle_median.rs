@@ -0,0 +1,110 @@
+use super::*;
+
+const OUTLIER: u64 = 9_000_000;
+
+/// `(price, seconds ago)` samples as fresh-enough oracle updates
+fn set_samples(env: &Env, rift: &TestRift, samples: &[(u64, i64)]) {
+    let now = clock().unix_timestamp;
+    let samples: Vec<(u64, i64)> = samples
+        .iter()
+        .map(|(price, age)| (*price, now - age))
+        .collect();
+    rift.set_oracle_samples(env, &samples);
+}
+
+#[test]
+fn median_of_odd_sample_count_ignores_outlier() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        set_samples(
+            &env,
+            &rift,
+            &[(1_000_000, 30), (OUTLIER, 20), (1_020_000, 10)],
+        );
+
+        let median = rift.state(&env).get_median_oracle_price(None).unwrap();
+        assert_eq!(median, 1_020_000);
+    });
+}
+
+#[test]
+fn median_of_even_sample_count_averages_middle_pair() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        set_samples(
+            &env,
+            &rift,
+            &[
+                (1_030_000, 40),
+                (1_000_000, 30),
+                (OUTLIER, 20),
+                (1_010_000, 10),
+            ],
+        );
+
+        let median = rift.state(&env).get_median_oracle_price(None).unwrap();
+        assert_eq!(median, 1_020_000);
+    });
+}
+
+#[test]
+fn median_skips_stale_and_empty_samples() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        // The stale outlier drops out, leaving an even count of fresh samples
+        set_samples(
+            &env,
+            &rift,
+            &[(OUTLIER, 3_601), (1_000_000, 30), (0, 20), (1_010_000, 10)],
+        );
+
+        let median = rift.state(&env).get_median_oracle_price(None).unwrap();
+        assert_eq!(median, 1_005_000);
+    });
+}
+
+#[test]
+fn median_with_all_samples_stale_is_rejected() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        set_samples(&env, &rift, &[(1_000_000, 3_601), (1_010_000, 7_200)]);
+
+        assert_eq!(
+            rift.state(&env).get_median_oracle_price(None).unwrap_err(),
+            ErrorCode::OraclePriceStale.into()
+        );
+    });
+}
+
+#[test]
+fn rebalance_uses_median_when_enabled() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        env.call(
+            accounts::SetUseMedianOracle {
+                creator: rift.creator,
+                rift: rift.rift,
+            },
+            instruction::SetUseMedianOracle { use_median: true },
+        )
+        .unwrap();
+        assert!(rift.state(&env).use_median_oracle);
+
+        warp_seconds(86_400);
+        set_samples(
+            &env,
+            &rift,
+            &[(1_000_000, 3_000), (OUTLIER, 2_500), (1_020_000, 1_801)],
+        );
+        rift.trigger_rebalance(&env).unwrap();
+
+        let state = rift.state(&env);
+        assert_eq!(state.backing_ratio, 1_020_000);
+        assert_eq!(state.rebalance_count, 1);
+    });
+}
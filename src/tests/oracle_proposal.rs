@@ -0,0 +1,70 @@
+use super::*;
+
+fn propose(env: &Env, rift: &TestRift) -> ProgramResult {
+    env.call(
+        accounts::ProposeOracleChange {
+            creator: rift.creator,
+            rift: rift.rift,
+        },
+        instruction::ProposeOracleChange {
+            switchboard_account: Some(Pubkey::new_unique()),
+        },
+    )
+}
+
+#[test]
+fn second_proposal_within_cooldown_is_rejected() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        propose(&env, &rift).unwrap();
+        let first_proposal = rift.state(&env).oracle_change_timestamp;
+
+        warp_seconds(60);
+        assert_error(propose(&env, &rift), ErrorCode::OracleProposalTooFrequent);
+
+        // The pending change and its timelock start are untouched by the rejected proposal
+        let state = rift.state(&env);
+        assert!(state.oracle_change_pending);
+        assert_eq!(state.oracle_change_timestamp, first_proposal);
+    });
+}
+
+#[test]
+fn proposal_allowed_after_cooldown() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        propose(&env, &rift).unwrap();
+        warp_seconds(ORACLE_PROPOSAL_COOLDOWN);
+        propose(&env, &rift).unwrap();
+
+        assert_eq!(
+            rift.state(&env).oracle_change_timestamp,
+            clock().unix_timestamp
+        );
+    });
+}
+
+#[test]
+fn cancel_does_not_reset_cooldown() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        propose(&env, &rift).unwrap();
+        env.call(
+            accounts::CancelOracleChange {
+                creator: rift.creator,
+                rift: rift.rift,
+            },
+            instruction::CancelOracleChange {},
+        )
+        .unwrap();
+
+        warp_seconds(60);
+        assert_error(propose(&env, &rift), ErrorCode::OracleProposalTooFrequent);
+    });
+}
@@ -0,0 +1,74 @@
+use super::*;
+
+const ORACLE_PRICE: u64 = 1_000_000;
+
+fn update_manual_oracle(env: &Env, rift: &TestRift, price: u64) -> ProgramResult {
+    env.call(
+        accounts::UpdateManualOracle {
+            rift: rift.rift,
+            oracle_authority: rift.creator,
+            reference_feed: None,
+            oracle_history: None,
+        },
+        instruction::UpdateManualOracle {
+            price,
+            confidence: 0,
+        },
+    )
+}
+
+/// Rift as seen from inside a rebalance/wrap/unwrap: guard taken in the current slot
+fn hold_guard(env: &Env, rift: &TestRift) {
+    rift.update(env, |state| {
+        state.reentrancy_guard = true;
+        state.reentrancy_guard_slot = clock().slot;
+    });
+}
+
+#[test]
+fn oracle_update_rejected_while_guard_held() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        rift.set_oracle_samples(&env, &[(ORACLE_PRICE, clock().unix_timestamp)]);
+        hold_guard(&env, &rift);
+
+        assert_error(
+            update_manual_oracle(&env, &rift, ORACLE_PRICE),
+            ErrorCode::ReentrancyDetected,
+        );
+
+        let state = rift.state(&env);
+        assert_eq!(state.last_manual_oracle_update, 0);
+        assert_eq!(state.price_index, 1);
+    });
+}
+
+#[test]
+fn rebalance_rejected_while_guard_held() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        hold_guard(&env, &rift);
+
+        assert_error(rift.trigger_rebalance(&env), ErrorCode::ReentrancyDetected);
+    });
+}
+
+#[test]
+fn oracle_update_clears_stuck_guard_after_timeout() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        rift.set_oracle_samples(&env, &[(ORACLE_PRICE, clock().unix_timestamp)]);
+        hold_guard(&env, &rift);
+
+        warp_slots(rift.state(&env).reentrancy_timeout() + 1);
+        update_manual_oracle(&env, &rift, ORACLE_PRICE).unwrap();
+
+        let state = rift.state(&env);
+        assert!(!state.reentrancy_guard);
+        assert_eq!(state.reentrancy_guard_slot, 0);
+        assert_eq!(state.last_manual_oracle_update, clock().unix_timestamp);
+    });
+}
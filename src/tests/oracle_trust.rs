@@ -0,0 +1,93 @@
+use super::*;
+
+const ORACLE_PRICE: u64 = 1_250_000;
+const ONE_DAY: i64 = 86_400;
+
+fn set_max_oracle_trust_age(
+    env: &Env,
+    rift: &TestRift,
+    max_oracle_trust_age: i64,
+) -> ProgramResult {
+    env.call(
+        accounts::SetMaxOracleTrustAge {
+            creator: rift.creator,
+            rift: rift.rift,
+        },
+        instruction::SetMaxOracleTrustAge {
+            max_oracle_trust_age,
+        },
+    )
+}
+
+#[test]
+fn rebalance_ignores_oracle_older_than_trust_age() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        warp_seconds(DEFAULT_MAX_ORACLE_TRUST_AGE * 2);
+        let now = clock().unix_timestamp;
+        rift.set_oracle_samples(
+            &env,
+            &[(ORACLE_PRICE, now - DEFAULT_MAX_ORACLE_TRUST_AGE - 1)],
+        );
+
+        rift.trigger_rebalance(&env).unwrap();
+
+        let state = rift.state(&env);
+        assert_eq!(state.backing_ratio, 1_000_000);
+        assert_eq!(state.rebalance_count, 0);
+        assert!(!state.reentrancy_guard);
+    });
+}
+
+#[test]
+fn rebalance_uses_oracle_within_trust_age() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        warp_seconds(ONE_DAY);
+        let now = clock().unix_timestamp;
+        rift.set_oracle_samples(&env, &[(ORACLE_PRICE, now - 1_801)]);
+
+        rift.trigger_rebalance(&env).unwrap();
+
+        let state = rift.state(&env);
+        assert_eq!(state.backing_ratio, ORACLE_PRICE);
+        assert_eq!(state.rebalance_count, 1);
+        assert_eq!(state.last_rebalance, now);
+    });
+}
+
+#[test]
+fn trust_age_is_bounded() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        assert_error(
+            set_max_oracle_trust_age(&env, &rift, MIN_ORACLE_TRUST_AGE - 1),
+            ErrorCode::InvalidOracleParameters,
+        );
+        assert_error(
+            set_max_oracle_trust_age(&env, &rift, MAX_ORACLE_TRUST_AGE + 1),
+            ErrorCode::InvalidOracleParameters,
+        );
+
+        set_max_oracle_trust_age(&env, &rift, MIN_ORACLE_TRUST_AGE).unwrap();
+        assert_eq!(rift.state(&env).max_oracle_trust_age, MIN_ORACLE_TRUST_AGE);
+    });
+}
+
+#[test]
+fn closed_rift_rejects_trust_age_change() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        rift.update(&env, |state| state.is_closed = true);
+
+        assert_error(
+            set_max_oracle_trust_age(&env, &rift, MIN_ORACLE_TRUST_AGE),
+            ErrorCode::RiftClosed,
+        );
+    });
+}
@@ -0,0 +1,103 @@
+use super::*;
+
+fn get_pdas(env: &Env, rift: &TestRift) -> RiftPdas {
+    env.view(
+        accounts::GetPdas { rift: rift.rift },
+        rifts_protocol::get_pdas,
+    )
+    .unwrap()
+}
+
+#[test]
+fn pdas_match_find_program_address() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        let pdas = get_pdas(&env, &rift);
+
+        let derive =
+            |prefix: &[u8]| Pubkey::find_program_address(&[prefix, rift.rift.as_ref()], &crate::ID);
+        let cases = [
+            (
+                b"vault_auth".as_slice(),
+                pdas.vault_authority,
+                pdas.vault_authority_bump,
+            ),
+            (
+                b"rift_mint_auth".as_slice(),
+                pdas.rift_mint_authority,
+                pdas.rift_mint_authority_bump,
+            ),
+            (b"vault".as_slice(), pdas.vault, pdas.vault_bump),
+            (
+                b"fees_vault".as_slice(),
+                pdas.fees_vault,
+                pdas.fees_vault_bump,
+            ),
+            (
+                b"withheld_vault".as_slice(),
+                pdas.withheld_vault,
+                pdas.withheld_vault_bump,
+            ),
+            (
+                b"wrap_fees_vault".as_slice(),
+                pdas.wrap_fees_vault,
+                pdas.wrap_fees_vault_bump,
+            ),
+            (
+                b"oracle_history".as_slice(),
+                pdas.oracle_history,
+                pdas.oracle_history_bump,
+            ),
+        ];
+        for (prefix, key, bump) in cases {
+            assert_eq!((key, bump), derive(prefix));
+        }
+    });
+}
+
+#[test]
+fn pdas_match_the_accounts_create_rift_uses() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        let pdas = get_pdas(&env, &rift);
+
+        // Seeded by the rift account key, not the (underlying, creator) creation seeds
+        assert_eq!(pdas.vault_authority, rift.vault_authority);
+        assert_eq!(pdas.rift_mint_authority, rift.rift_mint_authority);
+        assert_eq!(pdas.vault, rift.vault);
+        assert_eq!(pdas.fees_vault, rift.fees_vault);
+        assert_eq!(pdas.withheld_vault, rift.withheld_vault);
+        assert_ne!(
+            pdas.rift_mint_authority,
+            pda(&[
+                b"rift_mint_auth",
+                rift.underlying_mint.as_ref(),
+                rift.creator.as_ref(),
+            ])
+        );
+    });
+}
+
+#[test]
+fn get_authorities_is_an_alias_of_get_pdas() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        let authorities = env
+            .view(
+                accounts::GetAuthorities { rift: rift.rift },
+                rifts_protocol::get_authorities,
+            )
+            .unwrap();
+
+        assert_eq!(
+            authorities.try_to_vec().unwrap(),
+            get_pdas(&env, &rift).try_to_vec().unwrap()
+        );
+    });
+}
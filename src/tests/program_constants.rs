@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn program_constants_expose_reentrancy_timeout_bounds() {
+    run(|| {
+        let env = Env::new();
+
+        let constants = env
+            .view(
+                accounts::GetProgramConstants {},
+                rifts_protocol::get_program_constants,
+            )
+            .unwrap();
+
+        assert_eq!(constants.rift_account_size, RIFT_ACCOUNT_SIZE as u64);
+        assert_eq!(
+            constants.default_reentrancy_timeout_slots,
+            REENTRANCY_TIMEOUT_SLOTS
+        );
+        assert_eq!(
+            constants.min_reentrancy_timeout_slots,
+            MIN_REENTRANCY_TIMEOUT_SLOTS
+        );
+        assert_eq!(
+            constants.max_reentrancy_timeout_slots,
+            MAX_REENTRANCY_TIMEOUT_SLOTS
+        );
+        assert_eq!(
+            constants.try_to_vec().unwrap().len(),
+            ProgramConstants::SIZE
+        );
+    });
+}
@@ -0,0 +1,89 @@
+use super::*;
+
+const AMOUNT: u64 = 1_000_000;
+
+fn set_same_slot_protection(env: &Env, rift: &TestRift, enabled: bool) {
+    env.call(
+        accounts::SetSameSlotProtection {
+            creator: rift.creator,
+            rift: rift.rift,
+        },
+        instruction::SetSameSlotProtection { enabled },
+    )
+    .unwrap();
+}
+
+/// Protected rift where wallet A just wrapped, and wallet B holding RIFT it received from A
+fn wrapped_this_slot(env: &Env) -> (TestRift, TestUser) {
+    let rift = TestRift::new(env);
+    set_same_slot_protection(env, &rift, true);
+    let wallet_b = rift.new_user(env, 0);
+    mint_to(
+        env,
+        &rift.rift_mint,
+        &wallet_b.rift_tokens,
+        &rift.rift_mint_authority,
+        AMOUNT,
+    );
+    // What process_wrap records for wallet A's wrap
+    rift.update(env, |state| {
+        state.total_rift_minted = AMOUNT;
+        state.record_action_slot(clock().slot, true);
+    });
+    (rift, wallet_b)
+}
+
+#[test]
+fn unwrap_by_another_wallet_in_the_wrap_slot_is_rejected() {
+    run(|| {
+        let env = Env::new();
+        let (rift, wallet_b) = wrapped_this_slot(&env);
+
+        assert_error(
+            env.call(
+                rift.unwrap_accounts(&wallet_b),
+                TestRift::unwrap_args(AMOUNT),
+            ),
+            ErrorCode::SameSlotWrapUnwrap,
+        );
+    });
+}
+
+#[test]
+fn unwrap_in_a_later_slot_passes_the_guard() {
+    run(|| {
+        let env = Env::new();
+        let (rift, _) = wrapped_this_slot(&env);
+
+        warp_slots(1);
+        rift.state(&env)
+            .ensure_not_same_slot_wrap(clock().slot)
+            .unwrap();
+    });
+}
+
+#[test]
+fn guard_is_inactive_unless_enabled() {
+    run(|| {
+        let env = Env::new();
+        let (rift, _) = wrapped_this_slot(&env);
+        set_same_slot_protection(&env, &rift, false);
+
+        rift.state(&env)
+            .ensure_not_same_slot_wrap(clock().slot)
+            .unwrap();
+    });
+}
+
+#[test]
+fn unwrap_after_unwrap_in_the_same_slot_is_allowed() {
+    run(|| {
+        let env = Env::new();
+        let (rift, _) = wrapped_this_slot(&env);
+        rift.update(&env, |state| state.record_action_slot(clock().slot, false));
+
+        rift.state(&env)
+            .ensure_not_same_slot_wrap(clock().slot)
+            .unwrap();
+    });
+}
@@ -0,0 +1,155 @@
+use super::*;
+use spl_token_2022::extension::scaled_ui_amount::{self, ScaledUiAmountConfig};
+
+const MULTIPLIER: f64 = 2.0;
+const WRAP_AMOUNT: u64 = 1_000_000;
+const FEE_BPS: u64 = 30;
+
+/// Token-2022 underlying whose balances display at MULTIPLIER x their raw amount
+fn create_scaled_mint(env: &Env) -> (Pubkey, Pubkey) {
+    let authority = env.wallet(USER_LAMPORTS);
+    let mint = Pubkey::new_unique();
+    let space =
+        ExtensionType::try_calculate_account_len::<Mint2022State>(&[ExtensionType::ScaledUiAmount])
+            .unwrap();
+    env.set_account(
+        mint,
+        spl_token_2022::ID,
+        Rent::default().minimum_balance(space),
+        &vec![0; space],
+    );
+    env.process(
+        &scaled_ui_amount::instruction::initialize(
+            &spl_token_2022::ID,
+            &mint,
+            Some(authority),
+            MULTIPLIER,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    env.process(
+        &spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint,
+            &authority,
+            None,
+            UNDERLYING_DECIMALS,
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    (mint, authority)
+}
+
+fn scaled_rift(env: &Env) -> TestRift {
+    let options = RiftOptions {
+        underlying_program: spl_token_2022::ID,
+        ..RiftOptions::default()
+    };
+    let (mint, authority) = create_scaled_mint(env);
+    TestRift::with_underlying(env, options, mint, authority)
+}
+
+fn ui_amount(env: &Env, mint: &Pubkey, amount: u64) -> String {
+    let data = env.data(mint);
+    let mint_state = StateWithExtensions::<Mint2022State>::unpack(&data).unwrap();
+    mint_state
+        .get_extension::<ScaledUiAmountConfig>()
+        .unwrap()
+        .amount_to_ui_amount(amount, UNDERLYING_DECIMALS, clock().unix_timestamp)
+        .unwrap()
+}
+
+#[test]
+fn inspect_underlying_accepts_scaled_ui_amount() {
+    run(|| {
+        let env = Env::new();
+        let (mint, _) = create_scaled_mint(&env);
+
+        let inspection = env
+            .view(
+                accounts::InspectUnderlying {
+                    underlying_mint: mint,
+                    global_config: pda(&[b"global_config"]),
+                    mint_policy: pda(&[b"mint_policy", mint.as_ref()]),
+                    underlying_registry: pda(&[b"rift_mint_registry", mint.as_ref()]),
+                },
+                |ctx| rifts_protocol::inspect_underlying(ctx, false, false),
+            )
+            .unwrap();
+
+        assert_eq!(
+            inspection.extension_types,
+            vec![ExtensionType::ScaledUiAmount as u16]
+        );
+        assert!(!inspection.interest_bearing);
+        assert_eq!(inspection.blocked, 0);
+        assert!(inspection.can_create);
+    });
+}
+
+#[test]
+fn vault_sizing_matches_token_program_for_scaled_mint() {
+    run(|| {
+        let env = Env::new();
+        let rift = scaled_rift(&env);
+
+        // The fixture sizes the vault with the token program's own required account extensions
+        let space =
+            compute_vault_account_space(&env.account(&rift.underlying_mint), &spl_token_2022::ID)
+                .unwrap();
+        assert_eq!(space, env.data(&rift.vault).len());
+    });
+}
+
+#[test]
+fn wrap_scaled_underlying_mirrors_raw_amounts() {
+    run(|| {
+        let env = Env::new();
+        let rift = scaled_rift(&env);
+        let user = rift.new_user(&env, WRAP_AMOUNT);
+
+        // wrap_tokens: deposit, fee out of the vault, book the wrap, mint the RIFT
+        transfer_tokens(
+            &env,
+            &user.underlying,
+            &rift.vault,
+            &user.wallet,
+            WRAP_AMOUNT,
+        );
+        let mut state = rift.state(&env);
+        let (wrap_fee, rift_minted) = state.plan_wrap(WRAP_AMOUNT, FEE_BPS).unwrap();
+        transfer_tokens(
+            &env,
+            &rift.vault,
+            &rift.fees_vault,
+            &rift.vault_authority,
+            wrap_fee,
+        );
+        state
+            .record_wrap(
+                rift_minted,
+                WRAP_AMOUNT,
+                wrap_fee,
+                0,
+                &env.account(&pda(&[b"global_config"])),
+                clock().unix_timestamp,
+            )
+            .unwrap();
+        env.store(&rift.rift, &state);
+        mint_to(
+            &env,
+            &rift.rift_mint,
+            &user.rift_tokens,
+            &rift.rift_mint_authority,
+            rift_minted,
+        );
+
+        assert_eq!(rift_minted, WRAP_AMOUNT - WRAP_AMOUNT * FEE_BPS / 10_000);
+        assert_eq!(token_balance(&env, &rift.vault), rift_minted);
+        assert_eq!(token_balance(&env, &user.rift_tokens), rift_minted);
+        // Only the underlying's display is scaled; RIFT carries the raw amount
+        assert_eq!(ui_amount(&env, &rift.underlying_mint, rift_minted), "1.994");
+    });
+}
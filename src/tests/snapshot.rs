@@ -0,0 +1,63 @@
+use super::*;
+
+fn emit_daily_snapshot(env: &Env, rift: &TestRift) -> ProgramResult {
+    env.call(
+        accounts::EmitDailySnapshot {
+            keeper: Pubkey::new_unique(),
+            rift: rift.rift,
+            oracle_history: None,
+        },
+        instruction::EmitDailySnapshot {},
+    )
+}
+
+#[test]
+fn snapshot_records_timestamp_without_oracle_data() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        // No oracle samples yet: the average is reported as 0 rather than failing the snapshot
+        emit_daily_snapshot(&env, &rift).unwrap();
+        assert_eq!(
+            rift.state(&env).last_snapshot_timestamp,
+            clock().unix_timestamp
+        );
+    });
+}
+
+#[test]
+fn snapshot_cannot_fire_twice_within_a_day() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        emit_daily_snapshot(&env, &rift).unwrap();
+        let first_snapshot = clock().unix_timestamp;
+
+        warp_seconds(DAILY_SNAPSHOT_INTERVAL - 1);
+        assert_error(
+            emit_daily_snapshot(&env, &rift),
+            ErrorCode::SnapshotTooFrequent,
+        );
+        assert_eq!(rift.state(&env).last_snapshot_timestamp, first_snapshot);
+
+        warp_seconds(1);
+        emit_daily_snapshot(&env, &rift).unwrap();
+        assert_eq!(
+            rift.state(&env).last_snapshot_timestamp,
+            clock().unix_timestamp
+        );
+    });
+}
+
+#[test]
+fn closed_rift_rejects_snapshot() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        rift.update(&env, |state| state.is_closed = true);
+
+        assert_error(emit_daily_snapshot(&env, &rift), ErrorCode::RiftClosed);
+    });
+}
@@ -0,0 +1,59 @@
+use super::*;
+
+const HEADROOM: u64 = 1_000;
+
+/// Rift whose RIFT supply sits HEADROOM below the u64 ceiling
+fn rift_near_supply_ceiling(env: &Env) -> TestRift {
+    let rift = TestRift::new(env);
+    let holder = rift.new_user(env, 0);
+    mint_to(
+        env,
+        &rift.rift_mint,
+        &holder.rift_tokens,
+        &rift.rift_mint_authority,
+        u64::MAX - HEADROOM,
+    );
+    rift
+}
+
+#[test]
+fn mint_up_to_supply_ceiling_is_allowed() {
+    run(|| {
+        let env = Env::new();
+        let rift = rift_near_supply_ceiling(&env);
+
+        ensure_mint_headroom(&env.account(&rift.rift_mint), HEADROOM).unwrap();
+    });
+}
+
+#[test]
+fn mint_past_supply_ceiling_is_supply_overflow() {
+    run(|| {
+        let env = Env::new();
+        let rift = rift_near_supply_ceiling(&env);
+        let user = rift.new_user(&env, 0);
+
+        assert_eq!(
+            ensure_mint_headroom(&env.account(&rift.rift_mint), HEADROOM + 1).unwrap_err(),
+            ErrorCode::SupplyOverflow.into()
+        );
+
+        // What the mint CPI would have failed with instead: a bare token-program overflow
+        let result = env.process(
+            &spl_token_2022::instruction::mint_to(
+                &spl_token_2022::ID,
+                &rift.rift_mint,
+                &user.rift_tokens,
+                &rift.rift_mint_authority,
+                &[],
+                HEADROOM + 1,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            result,
+            Err(spl_token_2022::error::TokenError::Overflow.into())
+        );
+        assert_eq!(mint_supply(&env, &rift.rift_mint), u64::MAX - HEADROOM);
+    });
+}
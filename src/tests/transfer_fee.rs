@@ -0,0 +1,85 @@
+use super::*;
+use spl_token_2022::extension::transfer_fee::instruction::set_transfer_fee;
+
+fn disclosure(env: &Env, rift: &TestRift) -> TransferFeeDisclosure {
+    env.view(
+        accounts::GetTransferFeeDisclosure { rift: rift.rift },
+        rifts_protocol::get_transfer_fee_disclosure,
+    )
+    .unwrap()
+}
+
+fn admin_set_transfer_fee(env: &Env, rift: &TestRift, new_fee_bps: u16) -> ProgramResult {
+    env.call(
+        accounts::AdminSetTransferFee {
+            program_authority: program_authority(),
+            rift: rift.rift,
+            rift_mint: rift.rift_mint,
+            token_program: spl_token_2022::ID,
+            global_config: pda(&[b"global_config"]),
+        },
+        instruction::AdminSetTransferFee {
+            new_fee_bps,
+            max_transfer_fee: None,
+        },
+    )
+}
+
+#[test]
+fn fresh_rift_discloses_launch_fee() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        let disclosure = disclosure(&env, &rift);
+        assert_eq!(disclosure.initial_transfer_fee_bps, RIFT_TRANSFER_FEE_BPS);
+        assert_eq!(disclosure.current_transfer_fee_bps, RIFT_TRANSFER_FEE_BPS);
+        assert!(!disclosure.changed_since_launch);
+    });
+}
+
+#[test]
+fn initial_fee_persists_after_fee_change() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        // admin_set_transfer_fee: Token-2022 set_transfer_fee on the RIFT mint, then the rift
+        // tracks the live fee (the CPI is applied directly - CPIs can't run in-process)
+        env.process(
+            &set_transfer_fee(
+                &spl_token_2022::ID,
+                &rift.rift_mint,
+                &rift.rift_mint_authority,
+                &[],
+                100,
+                u64::MAX,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        rift.update(&env, |state| state.transfer_fee_bps = 100);
+
+        let disclosure = disclosure(&env, &rift);
+        assert_eq!(disclosure.initial_transfer_fee_bps, RIFT_TRANSFER_FEE_BPS);
+        assert_eq!(disclosure.current_transfer_fee_bps, 100);
+        assert!(disclosure.changed_since_launch);
+    });
+}
+
+#[test]
+fn rejected_fee_change_keeps_both_fees() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        assert_error(
+            admin_set_transfer_fee(&env, &rift, 201),
+            ErrorCode::InvalidTransferFee,
+        );
+
+        let state = rift.state(&env);
+        assert_eq!(state.initial_transfer_fee_bps, RIFT_TRANSFER_FEE_BPS);
+        assert_eq!(state.transfer_fee_bps, RIFT_TRANSFER_FEE_BPS);
+    });
+}
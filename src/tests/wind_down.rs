@@ -0,0 +1,57 @@
+use super::*;
+
+fn begin_wind_down(env: &Env, rift: &TestRift, creator: Pubkey) -> ProgramResult {
+    env.call(
+        accounts::BeginWindDown {
+            creator,
+            rift: rift.rift,
+        },
+        instruction::BeginWindDown {},
+    )
+}
+
+#[test]
+fn wind_down_blocks_wraps_but_not_unwraps() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let user = rift.new_user(&env, 1_000_000);
+        rift.prepare_wrap(&env, &user);
+        rift.update(&env, |state| state.total_rift_minted = 1_000_000);
+
+        begin_wind_down(&env, &rift, rift.creator).unwrap();
+
+        let state = rift.state(&env);
+        assert!(state.wind_down);
+        // Wind-down is not a close: no close notice starts
+        assert!(!state.closing);
+        assert_eq!(state.close_started_at, 0);
+        assert_error(
+            env.call(rift.wrap_accounts(&user), TestRift::wrap_args(1_000_000)),
+            ErrorCode::RiftWindingDown,
+        );
+        let plan = state
+            .plan_unwrap(1_000_000, 1_000_000, false, false, clock().unix_timestamp)
+            .unwrap();
+        assert_eq!(plan.rift_burned, 1_000_000);
+    });
+}
+
+#[test]
+fn wind_down_is_creator_only_and_once() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let stranger = env.wallet(USER_LAMPORTS);
+
+        assert_error(
+            begin_wind_down(&env, &rift, stranger),
+            ErrorCode::Unauthorized,
+        );
+        begin_wind_down(&env, &rift, rift.creator).unwrap();
+        assert_error(
+            begin_wind_down(&env, &rift, rift.creator),
+            ErrorCode::RiftWindingDown,
+        );
+    });
+}
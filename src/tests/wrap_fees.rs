@@ -0,0 +1,189 @@
+use super::*;
+
+const WRAP_AMOUNT: u64 = 1_000_000;
+const WRAP_FEE_BPS: u64 = 30;
+
+fn set_fees_to_backing(env: &Env, rift: &TestRift, fees_to_backing: bool) {
+    env.call(
+        accounts::SetFeesToBacking {
+            creator: rift.creator,
+            rift: rift.rift,
+        },
+        instruction::SetFeesToBacking { fees_to_backing },
+    )
+    .unwrap();
+}
+
+/// wrap_tokens after the deposit landed in the vault: plan the fee, route it, book the wrap
+/// Returns the wrap fee
+fn wrap(env: &Env, rift: &TestRift) -> u64 {
+    mint_to(
+        env,
+        &rift.underlying_mint,
+        &rift.vault,
+        &rift.underlying_mint_authority,
+        WRAP_AMOUNT,
+    );
+
+    let mut state = rift.state(env);
+    let (wrap_fee, rift_minted) = state.plan_wrap(WRAP_AMOUNT, WRAP_FEE_BPS).unwrap();
+    let (fee_credited, fee_routed) = if state.fees_to_backing {
+        route_wrap_fee(
+            &state,
+            wrap_fee,
+            env.account(&rift.vault),
+            env.account(&rift.fees_vault),
+            None,
+            env.account(&rift.underlying_mint),
+            UNDERLYING_DECIMALS,
+            env.account(&rift.underlying_program),
+            env.account(&rift.vault_authority),
+            &[],
+        )
+        .unwrap()
+    } else {
+        // The vault → fees_vault transfer route_wrap_fee makes via CPI
+        transfer_tokens(
+            env,
+            &rift.vault,
+            &rift.fees_vault,
+            &rift.vault_authority,
+            wrap_fee,
+        );
+        (wrap_fee, 0)
+    };
+    state
+        .record_wrap(
+            rift_minted,
+            WRAP_AMOUNT,
+            fee_credited,
+            fee_routed,
+            &env.account(&pda(&[b"global_config"])),
+            clock().unix_timestamp,
+        )
+        .unwrap();
+    env.store(&rift.rift, &state);
+    wrap_fee
+}
+
+#[test]
+fn extracted_wrap_fee_is_protocol_revenue() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+
+        let wrap_fee = wrap(&env, &rift);
+
+        let state = rift.state(&env);
+        assert_eq!(token_balance(&env, &rift.fees_vault), wrap_fee);
+        assert_eq!(token_balance(&env, &rift.vault), WRAP_AMOUNT - wrap_fee);
+        assert_eq!(state.total_fees_collected, wrap_fee);
+        assert_eq!(state.wrap_fees_collected, wrap_fee);
+        // Vault holds exactly the RIFT supply
+        assert_eq!(
+            state.total_underlying_wrapped,
+            token_balance(&env, &rift.vault)
+        );
+        let vault_balance = token_balance(&env, &rift.vault);
+        assert_eq!(
+            state.compute_backing_ratio(vault_balance).unwrap(),
+            1_000_000
+        );
+    });
+}
+
+#[test]
+fn fee_to_backing_keeps_wrap_fee_in_vault() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        set_fees_to_backing(&env, &rift, true);
+        assert!(rift.state(&env).fees_to_backing);
+
+        let wrap_fee = wrap(&env, &rift);
+
+        let state = rift.state(&env);
+        assert_eq!(token_balance(&env, &rift.fees_vault), 0);
+        assert_eq!(token_balance(&env, &rift.vault), WRAP_AMOUNT);
+        assert_eq!(state.total_fees_collected, 0);
+        assert_eq!(state.wrap_fees_collected, 0);
+        // RIFT is minted for the deposit minus the fee; the retained fee is surplus backing
+        assert_eq!(state.total_underlying_wrapped, WRAP_AMOUNT);
+        assert_eq!(state.total_rift_minted, WRAP_AMOUNT - wrap_fee);
+        assert_eq!(state.backing_surplus, wrap_fee);
+        let backing_ratio = state.compute_backing_ratio(WRAP_AMOUNT).unwrap();
+        assert!(backing_ratio > 1_000_000);
+    });
+}
+
+#[test]
+fn fee_to_backing_surplus_is_paid_out_so_the_rift_can_close() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        set_fees_to_backing(&env, &rift, true);
+        let wrap_fee = wrap(&env, &rift);
+        let user = rift.new_user(&env, 0);
+
+        // Every holder exits: unwrap the whole RIFT supply fee-exempt, so fees_vault stays empty
+        let mut state = rift.state(&env);
+        let vault_balance = token_balance(&env, &rift.vault);
+        let plan = state
+            .plan_unwrap(
+                state.total_rift_minted,
+                vault_balance,
+                true,
+                false,
+                clock().unix_timestamp,
+            )
+            .unwrap();
+        assert_eq!(plan.backing_share, wrap_fee);
+        assert_eq!(plan.net_underlying, WRAP_AMOUNT);
+        transfer_tokens(
+            &env,
+            &rift.vault,
+            &user.underlying,
+            &rift.vault_authority,
+            plan.net_underlying,
+        );
+        state
+            .record_unwrap(
+                &plan,
+                plan.net_underlying,
+                &env.account(&pda(&[b"global_config"])),
+                clock().unix_timestamp,
+            )
+            .unwrap();
+        env.store(&rift.rift, &state);
+
+        assert_eq!(token_balance(&env, &rift.vault), 0);
+        assert_eq!(token_balance(&env, &user.underlying), WRAP_AMOUNT);
+        let state = rift.state(&env);
+        assert_eq!(state.backing_surplus, 0);
+        assert_eq!(state.total_underlying_wrapped, 0);
+        assert_eq!(state.total_rift_minted, 0);
+
+        env.call(
+            accounts::BeginClose {
+                creator: rift.creator,
+                rift: rift.rift,
+            },
+            instruction::BeginClose {},
+        )
+        .unwrap();
+        warp_seconds(CLOSE_DELAY_SECONDS);
+        let close_blockers = env
+            .view(
+                accounts::CanClose {
+                    rift: rift.rift,
+                    vault: rift.vault,
+                    fees_vault: rift.fees_vault,
+                    withheld_vault: rift.withheld_vault,
+                    wrap_fees_vault: None,
+                },
+                rifts_protocol::can_close,
+            )
+            .unwrap();
+        assert_eq!(close_blockers, 0);
+    });
+}
@@ -0,0 +1,181 @@
+use super::*;
+
+const DEPOSIT_LAMPORTS: u64 = 2_000_000_000;
+
+/// Deposit leg of wrap_tokens: the user's underlying moved into the vault
+fn deposit(env: &Env, rift: &TestRift, user: &TestUser, amount: u64) -> ProgramResult {
+    env.process(
+        &spl_token::instruction::transfer_checked(
+            &spl_token::ID,
+            &user.underlying,
+            &rift.underlying_mint,
+            &rift.vault,
+            &user.wallet,
+            &[],
+            amount,
+            9,
+        )
+        .unwrap(),
+    )
+}
+
+#[test]
+fn native_sync_only_for_classic_wsol() {
+    run(|| {
+        let env = Env::new();
+        let wsol_rift = TestRift::wsol(&env).state(&env);
+        assert!(wsol_rift.needs_native_sync(&spl_token::ID));
+        assert!(!wsol_rift.needs_native_sync(&spl_token_2022::ID));
+
+        let other_rift = TestRift::new(&env).state(&env);
+        assert!(!other_rift.needs_native_sync(&spl_token::ID));
+    });
+}
+
+#[test]
+fn unsynced_wsol_deposit_is_handled_by_sync_native() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::wsol(&env);
+        let user = rift.new_user(&env, 0);
+
+        // SOL sent straight to the wSOL account: lamports present, token amount not synced
+        env.airdrop(&user.underlying, DEPOSIT_LAMPORTS);
+        assert_eq!(token_balance(&env, &user.underlying), 0);
+        assert_eq!(
+            deposit(&env, &rift, &user, DEPOSIT_LAMPORTS),
+            Err(ProgramError::Custom(
+                spl_token::error::TokenError::InsufficientFunds as u32
+            ))
+        );
+
+        // wrap_tokens syncs before the deposit transfer for this rift
+        assert!(rift.state(&env).needs_native_sync(&spl_token::ID));
+        env.process(
+            &spl_token::instruction::sync_native(&spl_token::ID, &user.underlying).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(token_balance(&env, &user.underlying), DEPOSIT_LAMPORTS);
+
+        deposit(&env, &rift, &user, DEPOSIT_LAMPORTS).unwrap();
+        assert_eq!(token_balance(&env, &rift.vault), DEPOSIT_LAMPORTS);
+    });
+}
+
+fn wrap_sol(env: &Env, accounts: accounts::WrapTokens) -> ProgramResult {
+    env.call(
+        accounts,
+        instruction::WrapSol {
+            lamports: DEPOSIT_LAMPORTS,
+            min_rift_out: 0,
+            deadline_unix: 0,
+            create_rift_ata: false,
+        },
+    )
+}
+
+/// First-time SOL wrapper: lamports only, no wSOL ATA yet
+fn sol_only_user(env: &Env, rift: &TestRift) -> TestUser {
+    let user = rift.new_user(env, 0);
+    env.set_account(user.underlying, system_program::ID, 0, &[]);
+    env.airdrop(&user.wallet, DEPOSIT_LAMPORTS);
+    rift.prepare_wrap(env, &user);
+    user
+}
+
+#[test]
+fn wrap_sol_rejects_non_wsol_rift() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::new(&env);
+        let user = rift.new_user(&env, 0);
+        rift.prepare_wrap(&env, &user);
+        let lamports = env.lamports(&user.wallet);
+
+        assert_error(
+            wrap_sol(&env, rift.wrap_accounts(&user)),
+            ErrorCode::NotNativeSolRift,
+        );
+        assert_eq!(env.lamports(&user.wallet), lamports);
+    });
+}
+
+#[test]
+fn wrap_sol_requires_the_canonical_wsol_ata() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::wsol(&env);
+        let mut user = sol_only_user(&env, &rift);
+        user.underlying = Pubkey::new_unique();
+
+        assert_error(
+            wrap_sol(&env, rift.wrap_accounts(&user)),
+            ErrorCode::InvalidTokenAccount,
+        );
+        // No temporary account was created at the bogus address
+        assert_eq!(env.owner(&user.underlying), system_program::ID);
+        assert_eq!(env.lamports(&user.underlying), 0);
+    });
+}
+
+#[test]
+fn failed_wrap_sol_leaves_no_temporary_account() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::wsol(&env);
+        let user = sol_only_user(&env, &rift);
+        let lamports = env.lamports(&user.wallet);
+
+        // Creating the temporary wSOL ATA needs the associated token program
+        assert_error(
+            wrap_sol(&env, rift.wrap_accounts(&user)),
+            ErrorCode::MissingAssociatedTokenProgram,
+        );
+        assert_eq!(env.owner(&user.underlying), system_program::ID);
+        assert_eq!(env.lamports(&user.underlying), 0);
+        assert_eq!(env.lamports(&user.wallet), lamports);
+    });
+}
+
+#[test]
+fn temporary_wsol_account_is_closed_back_to_sol() {
+    run(|| {
+        let env = Env::new();
+        let rift = TestRift::wsol(&env);
+        let user = sol_only_user(&env, &rift);
+        let wallet_lamports = env.lamports(&user.wallet);
+
+        // The CPI sequence wrap_sol runs around the wrap: create ATA, fund, sync, deposit, close
+        create_token_account(
+            &env,
+            spl_token::ID,
+            user.underlying,
+            spl_token::native_mint::ID,
+            user.wallet,
+        );
+        let ata_rent = env.lamports(&user.underlying);
+        env.airdrop(&user.underlying, DEPOSIT_LAMPORTS);
+        env.process(
+            &spl_token::instruction::sync_native(&spl_token::ID, &user.underlying).unwrap(),
+        )
+        .unwrap();
+        deposit(&env, &rift, &user, DEPOSIT_LAMPORTS).unwrap();
+        assert_eq!(token_balance(&env, &rift.vault), DEPOSIT_LAMPORTS);
+
+        env.process(
+            &spl_token::instruction::close_account(
+                &spl_token::ID,
+                &user.underlying,
+                &user.wallet,
+                &user.wallet,
+                &[],
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        // The wrapped SOL stays in the vault; the account's rent comes back to the user
+        assert_eq!(env.lamports(&user.underlying), 0);
+        assert_eq!(env.lamports(&user.wallet), wallet_lamports + ata_rent);
+    });
+}